@@ -1,58 +1,88 @@
-// Benchmark for recurrence calculations
-// Measures performance of fortnightly and quarterly occurrence generation
-
-use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+// Benchmark for recurrence expansion and range queries
+// Measures a month view over a calendar with years of recurring and single events,
+// with and without the occurrence cache
+
+use chrono::{DateTime, Duration, Local, TimeZone};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rust_calendar::models::event::Event;
+use rust_calendar::models::recurrence::{Frequency, RecurrenceRule};
+use rust_calendar::services::database::Database;
+use rust_calendar::services::event::EventService;
+
+const RULES: &[&str] = &[
+    "FREQ=DAILY",
+    "FREQ=WEEKLY;BYDAY=MO,WE,FR",
+    "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU",
+    "FREQ=MONTHLY;BYMONTHDAY=15",
+    "FREQ=YEARLY",
+];
+
+fn series_start() -> DateTime<Local> {
+    Local.with_ymd_and_hms(2016, 1, 4, 9, 0, 0).unwrap()
+}
 
-// Mock types for demonstration (will use actual types)
-#[derive(Debug, Clone, Copy)]
-enum Frequency {
-    Fortnightly,
-    Quarterly,
+fn viewed_month() -> (DateTime<Local>, DateTime<Local>) {
+    (
+        Local.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap(),
+        Local.with_ymd_and_hms(2026, 3, 31, 23, 59, 59).unwrap(),
+    )
 }
 
-// Mock calculation function
-fn calculate_occurrences(freq: Frequency, start_year: i32, count: usize) -> Vec<(i32, u32, u32)> {
-    let mut occurrences = Vec::with_capacity(count);
-    let mut year = start_year;
-    let mut month = 1u32;
-    let day = 1u32;
-
-    for _ in 0..count {
-        occurrences.push((year, month, day));
-
-        match freq {
-            Frequency::Fortnightly => {
-                // Simplified: just increment month for demo
-                month += 1;
-                if month > 12 {
-                    month = 1;
-                    year += 1;
-                }
-            }
-            Frequency::Quarterly => {
-                month += 3;
-                if month > 12 {
-                    month -= 12;
-                    year += 1;
-                }
-            }
-        }
+/// A database with `series` recurring events starting in 2016 and `singles`
+/// one-off events spread across the same ten years.
+fn seeded_database(series: usize, singles: usize) -> Database {
+    let db = Database::new(":memory:").unwrap();
+    db.initialize_schema().unwrap();
+    let service = EventService::new(db.connection());
+
+    for i in 0..series {
+        let start = series_start() + Duration::hours((i % 8) as i64);
+        let mut event =
+            Event::new(format!("Series {}", i), start, start + Duration::hours(1)).unwrap();
+        event.recurrence_rule = Some(RULES[i % RULES.len()].to_string());
+        service.create(event).unwrap();
+    }
+    for i in 0..singles {
+        let start = series_start() + Duration::hours(17 * i as i64);
+        let event = Event::new(format!("Event {}", i), start, start + Duration::hours(1)).unwrap();
+        service.create(event).unwrap();
     }
 
-    occurrences
+    db
 }
 
-fn bench_fortnightly_occurrences(c: &mut Criterion) {
-    let mut group = c.benchmark_group("fortnightly_occurrences");
+fn clear_occurrence_cache(db: &Database) {
+    db.connection()
+        .execute_batch("DELETE FROM event_occurrences; DELETE FROM event_occurrence_windows;")
+        .unwrap();
+}
 
-    for count in [10, 100, 1000].iter() {
-        group.bench_with_input(BenchmarkId::from_parameter(count), count, |b, &count| {
+fn bench_month_expansion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("expand_month");
+    let (start, end) = viewed_month();
+
+    for series in [50, 200] {
+        let db = seeded_database(series, 2000);
+        let service = EventService::new(db.connection());
+
+        group.bench_with_input(BenchmarkId::new("uncached", series), &series, |b, _| {
+            b.iter_batched(
+                || clear_occurrence_cache(&db),
+                |_| {
+                    service
+                        .expand_recurring_events(black_box(start), black_box(end))
+                        .unwrap()
+                },
+                BatchSize::SmallInput,
+            );
+        });
+
+        service.expand_recurring_events(start, end).unwrap();
+        group.bench_with_input(BenchmarkId::new("cached", series), &series, |b, _| {
             b.iter(|| {
-                calculate_occurrences(
-                    black_box(Frequency::Fortnightly),
-                    black_box(2025),
-                    black_box(count),
-                )
+                service
+                    .expand_recurring_events(black_box(start), black_box(end))
+                    .unwrap()
             });
         });
     }
@@ -60,17 +90,18 @@ fn bench_fortnightly_occurrences(c: &mut Criterion) {
     group.finish();
 }
 
-fn bench_quarterly_occurrences(c: &mut Criterion) {
-    let mut group = c.benchmark_group("quarterly_occurrences");
+fn bench_range_query(c: &mut Criterion) {
+    let mut group = c.benchmark_group("find_by_date_range");
+    let (start, end) = viewed_month();
 
-    for count in [10, 100, 400].iter() {
-        group.bench_with_input(BenchmarkId::from_parameter(count), count, |b, &count| {
+    for singles in [1000, 5000] {
+        let db = seeded_database(20, singles);
+        let service = EventService::new(db.connection());
+        group.bench_with_input(BenchmarkId::from_parameter(singles), &singles, |b, _| {
             b.iter(|| {
-                calculate_occurrences(
-                    black_box(Frequency::Quarterly),
-                    black_box(2025),
-                    black_box(count),
-                )
+                service
+                    .find_by_date_range(black_box(start), black_box(end))
+                    .unwrap()
             });
         });
     }
@@ -78,38 +109,31 @@ fn bench_quarterly_occurrences(c: &mut Criterion) {
     group.finish();
 }
 
-fn bench_comparison(c: &mut Criterion) {
-    let mut group = c.benchmark_group("frequency_comparison");
-
-    let count = 100;
+fn bench_rule_occurrences(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rule_occurrences");
+    let (start, end) = viewed_month();
 
-    group.bench_function("fortnightly_100", |b| {
-        b.iter(|| {
-            calculate_occurrences(
-                black_box(Frequency::Fortnightly),
-                black_box(2025),
-                black_box(count),
-            )
-        });
-    });
-
-    group.bench_function("quarterly_100", |b| {
-        b.iter(|| {
-            calculate_occurrences(
-                black_box(Frequency::Quarterly),
-                black_box(2025),
-                black_box(count),
-            )
+    for frequency in [Frequency::Fortnightly, Frequency::Quarterly] {
+        let rule = RecurrenceRule::new(frequency);
+        group.bench_function(format!("{:?}", frequency), |b| {
+            b.iter(|| {
+                rule.generate_occurrences(
+                    black_box(series_start()),
+                    black_box(start),
+                    black_box(end),
+                    &[],
+                )
+            });
         });
-    });
+    }
 
     group.finish();
 }
 
 criterion_group!(
     benches,
-    bench_fortnightly_occurrences,
-    bench_quarterly_occurrences,
-    bench_comparison
+    bench_month_expansion,
+    bench_range_query,
+    bench_rule_occurrences
 );
 criterion_main!(benches);
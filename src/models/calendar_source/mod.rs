@@ -3,8 +3,12 @@
 use serde::{Deserialize, Serialize};
 
 pub const GOOGLE_ICS_SOURCE_TYPE: &str = "google_ics";
+/// Source subscribed from an account's calendar list and synced only through the API.
+pub const GOOGLE_API_SOURCE_TYPE: &str = "google_api";
 pub const SYNC_CAPABILITY_READ_ONLY: &str = "read_only";
 pub const SYNC_CAPABILITY_READ_WRITE: &str = "read_write";
+/// `last_sync_status` of a source whose last run was paused by the mass-deletion guard.
+pub const SYNC_STATUS_NEEDS_CONFIRMATION: &str = "needs_confirmation";
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CalendarSource {
@@ -22,6 +26,12 @@ pub struct CalendarSource {
     pub sync_capability: String,
     pub api_sync_token: Option<String>,
     pub last_push_at: Option<String>,
+    /// Google account whose tokens are used for API sync of this source.
+    pub google_account_id: Option<i64>,
+    /// Calendar ID from the account's calendar list; unset for pasted ICS URLs.
+    pub remote_calendar_id: Option<String>,
+    /// Background colour reported by Google (`#rrggbb`).
+    pub color: Option<String>,
 }
 
 impl CalendarSource {
@@ -31,12 +41,33 @@ impl CalendarSource {
             return Err("Calendar source name cannot be empty".to_string());
         }
 
-        if self.source_type != GOOGLE_ICS_SOURCE_TYPE {
-            return Err("Calendar source type must be 'google_ics'".to_string());
-        }
-
-        if !Self::is_valid_google_ics_url(&self.ics_url) {
-            return Err("Calendar source URL must be a valid Google Calendar ICS URL".to_string());
+        match self.source_type.as_str() {
+            GOOGLE_ICS_SOURCE_TYPE => {
+                if !Self::is_valid_google_ics_url(&self.ics_url) {
+                    return Err(
+                        "Calendar source URL must be a valid Google Calendar ICS URL".to_string(),
+                    );
+                }
+            }
+            GOOGLE_API_SOURCE_TYPE => {
+                if self
+                    .remote_calendar_id
+                    .as_deref()
+                    .map(str::trim)
+                    .unwrap_or_default()
+                    .is_empty()
+                {
+                    return Err("Subscribed calendar is missing its Google calendar ID".to_string());
+                }
+                if self.google_account_id.is_none() {
+                    return Err(
+                        "Subscribed calendar must be linked to a Google account".to_string()
+                    );
+                }
+            }
+            _ => {
+                return Err("Calendar source type must be 'google_ics' or 'google_api'".to_string());
+            }
         }
 
         if self.poll_interval_minutes <= 0 {
@@ -71,7 +102,24 @@ impl CalendarSource {
     }
 
     pub fn google_calendar_id(&self) -> Option<String> {
-        Self::extract_google_calendar_id(&self.ics_url)
+        self.remote_calendar_id
+            .clone()
+            .filter(|id| !id.trim().is_empty())
+            .or_else(|| Self::extract_google_calendar_id(&self.ics_url))
+    }
+
+    /// True when the source is synced through the Calendar API rather than its ICS feed.
+    pub fn uses_google_api(&self) -> bool {
+        self.source_type == GOOGLE_API_SOURCE_TYPE
+            || self.sync_capability == SYNC_CAPABILITY_READ_WRITE
+    }
+
+    /// Maps a calendarList `accessRole` to the matching sync capability.
+    pub fn sync_capability_for_access_role(access_role: &str) -> &'static str {
+        match access_role {
+            "owner" | "writer" => SYNC_CAPABILITY_READ_WRITE,
+            _ => SYNC_CAPABILITY_READ_ONLY,
+        }
     }
 
     pub fn extract_google_calendar_id(url: &str) -> Option<String> {
@@ -108,13 +156,19 @@ impl Default for CalendarSource {
             sync_capability: SYNC_CAPABILITY_READ_ONLY.to_string(),
             api_sync_token: None,
             last_push_at: None,
+            google_account_id: None,
+            remote_calendar_id: None,
+            color: None,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{CalendarSource, GOOGLE_ICS_SOURCE_TYPE, SYNC_CAPABILITY_READ_ONLY};
+    use super::{
+        CalendarSource, GOOGLE_API_SOURCE_TYPE, GOOGLE_ICS_SOURCE_TYPE, SYNC_CAPABILITY_READ_ONLY,
+        SYNC_CAPABILITY_READ_WRITE,
+    };
 
     fn valid_source() -> CalendarSource {
         CalendarSource {
@@ -132,6 +186,9 @@ mod tests {
             sync_capability: SYNC_CAPABILITY_READ_ONLY.to_string(),
             api_sync_token: None,
             last_push_at: None,
+            google_account_id: None,
+            remote_calendar_id: None,
+            color: None,
         }
     }
 
@@ -228,4 +285,43 @@ mod tests {
 
         assert_eq!(calendar_id.as_deref(), Some("test@gmail.com"));
     }
+
+    #[test]
+    fn test_validate_api_source_without_url() {
+        let source = CalendarSource {
+            source_type: GOOGLE_API_SOURCE_TYPE.to_string(),
+            ics_url: String::new(),
+            remote_calendar_id: Some("team@group.calendar.google.com".to_string()),
+            google_account_id: Some(1),
+            ..valid_source()
+        };
+        assert!(source.validate().is_ok());
+        assert!(source.uses_google_api());
+        assert_eq!(
+            source.google_calendar_id().as_deref(),
+            Some("team@group.calendar.google.com")
+        );
+
+        let unlinked = CalendarSource {
+            google_account_id: None,
+            ..source
+        };
+        assert!(unlinked.validate().is_err());
+    }
+
+    #[test]
+    fn test_sync_capability_for_access_role() {
+        for role in ["owner", "writer"] {
+            assert_eq!(
+                CalendarSource::sync_capability_for_access_role(role),
+                SYNC_CAPABILITY_READ_WRITE
+            );
+        }
+        for role in ["reader", "freeBusyReader"] {
+            assert_eq!(
+                CalendarSource::sync_capability_for_access_role(role),
+                SYNC_CAPABILITY_READ_ONLY
+            );
+        }
+    }
 }
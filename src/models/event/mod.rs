@@ -2,9 +2,10 @@
 // Calendar event model with iCalendar compatibility
 
 use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
 
 /// Calendar event with iCalendar (RFC 5545) compatibility
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Event {
     pub id: Option<i64>,
     pub title: String,
@@ -19,6 +20,10 @@ pub struct Event {
     pub recurrence_exceptions: Option<Vec<DateTime<Local>>>, // Exception dates
     pub created_at: Option<DateTime<Local>>,
     pub updated_at: Option<DateTime<Local>>,
+    /// iCalendar properties and sub-components the model has no field for,
+    /// kept verbatim (unfolded, one line each) so exports can re-emit them
+    #[serde(default)]
+    pub ics_raw_properties: Option<String>,
 }
 
 impl Event {
@@ -73,6 +78,7 @@ impl Event {
             recurrence_exceptions: None,
             created_at: None,
             updated_at: None,
+            ics_raw_properties: None,
         })
     }
 
@@ -230,6 +236,7 @@ impl EventBuilder {
             recurrence_exceptions: None,
             created_at: None,
             updated_at: None,
+            ics_raw_properties: None,
         };
 
         event.validate()?;
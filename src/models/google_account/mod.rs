@@ -2,10 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
+/// A connected Google identity with its own OAuth tokens.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GoogleAccount {
     pub id: i64,
-    pub oauth_client_id: Option<String>,
     pub account_email: Option<String>,
     pub access_token: Option<String>,
     pub refresh_token: Option<String>,
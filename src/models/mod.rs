@@ -9,6 +9,7 @@ pub mod google_account;
 pub mod outbound_sync_operation;
 pub mod recurrence;
 pub mod reminder;
+pub mod saved_search;
 pub mod settings;
 pub mod sync_conflict;
 pub mod template;
@@ -0,0 +1,33 @@
+// Saved search model
+// A named event query shown in the sidebar and usable as a view filter
+
+/// A named search in the query language of `services::event::queries`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedSearch {
+    pub id: Option<i64>,
+    pub name: String,
+    pub query: String,
+}
+
+impl SavedSearch {
+    pub fn new(name: impl Into<String>, query: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            name: name.into(),
+            query: query.into(),
+        }
+    }
+
+    /// Validate the saved search
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Saved search name cannot be empty".to_string());
+        }
+
+        if self.query.trim().is_empty() {
+            return Err("Saved search query cannot be empty".to_string());
+        }
+
+        Ok(())
+    }
+}
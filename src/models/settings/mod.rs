@@ -29,6 +29,30 @@ pub struct Settings {
     pub sync_startup_delay_minutes: i64,
     pub minimize_to_tray: bool,
     pub show_countdown_cards: bool,
+    /// Pause incoming sync when it would delete at least this percentage of a
+    /// source's mapped events (0 disables the check).
+    pub sync_mass_delete_percent: i64,
+    /// Pause incoming sync when it would delete at least this many events
+    /// (0 disables the check).
+    pub sync_mass_delete_count: i64,
+    /// How often the background backup runs: "off", "hourly", "daily" or "weekly".
+    pub backup_interval: String,
+    /// Folder for scheduled backups; `None` uses the default app data folder.
+    pub backup_directory: Option<String>,
+    /// Grandfather-father-son retention: how many of the newest hourly,
+    /// daily, weekly and monthly backups to keep.
+    pub backup_keep_hourly: i64,
+    pub backup_keep_daily: i64,
+    pub backup_keep_weekly: i64,
+    pub backup_keep_monthly: i64,
+    /// Encrypt backup files with the passphrase behind `backup_passphrase_ref`.
+    pub backup_encrypt: bool,
+    /// Secret-store reference to the backup passphrase.
+    pub backup_passphrase_ref: Option<String>,
+    /// Days deleted events stay in the trash before being purged.
+    pub trash_retention_days: i64,
+    /// TrueType font embedded in PDF exports; `None` uses the bundled fonts.
+    pub pdf_font_path: Option<String>,
 }
 
 impl Default for Settings {
@@ -58,6 +82,18 @@ impl Default for Settings {
             sync_startup_delay_minutes: 15,
             minimize_to_tray: false,
             show_countdown_cards: true,
+            sync_mass_delete_percent: 50,
+            sync_mass_delete_count: 25,
+            backup_interval: "daily".to_string(),
+            backup_directory: None,
+            backup_keep_hourly: 24,
+            backup_keep_daily: 7,
+            backup_keep_weekly: 4,
+            backup_keep_monthly: 12,
+            backup_encrypt: false,
+            backup_passphrase_ref: None,
+            trash_retention_days: 30,
+            pdf_font_path: None,
         }
     }
 }
@@ -126,6 +162,14 @@ impl Settings {
             ));
         }
 
+        Self::validate_mass_delete_thresholds(
+            self.sync_mass_delete_percent,
+            self.sync_mass_delete_count,
+        )?;
+
+        self.validate_backup_policy()?;
+        self.validate_trash_retention()?;
+
         Self::validate_card_dimensions(self.default_card_width, self.default_card_height)?;
 
         Ok(())
@@ -188,11 +232,65 @@ impl Settings {
             ));
         }
 
+        Self::validate_mass_delete_thresholds(
+            self.sync_mass_delete_percent,
+            self.sync_mass_delete_count,
+        )?;
+
+        self.validate_backup_policy()?;
+        self.validate_trash_retention()?;
+
         Self::validate_card_dimensions(self.default_card_width, self.default_card_height)?;
 
         Ok(())
     }
 
+    fn validate_mass_delete_thresholds(percent: i64, count: i64) -> Result<(), String> {
+        if !(0..=100).contains(&percent) {
+            return Err(format!("Invalid sync_mass_delete_percent: {}", percent));
+        }
+
+        if count < 0 {
+            return Err(format!("Invalid sync_mass_delete_count: {}", count));
+        }
+
+        Ok(())
+    }
+
+    fn validate_backup_policy(&self) -> Result<(), String> {
+        if !["off", "hourly", "daily", "weekly"].contains(&self.backup_interval.as_str()) {
+            return Err(format!("Invalid backup_interval: {}", self.backup_interval));
+        }
+
+        let keep_counts = [
+            ("backup_keep_hourly", self.backup_keep_hourly),
+            ("backup_keep_daily", self.backup_keep_daily),
+            ("backup_keep_weekly", self.backup_keep_weekly),
+            ("backup_keep_monthly", self.backup_keep_monthly),
+        ];
+        for (name, count) in keep_counts {
+            if !(0..=1000).contains(&count) {
+                return Err(format!("Invalid {}: {}", name, count));
+            }
+        }
+        if keep_counts.iter().all(|(_, count)| *count == 0) {
+            return Err("Backup retention must keep at least one backup".to_string());
+        }
+
+        Ok(())
+    }
+
+    fn validate_trash_retention(&self) -> Result<(), String> {
+        if !(1..=365).contains(&self.trash_retention_days) {
+            return Err(format!(
+                "Invalid trash_retention_days: {}",
+                self.trash_retention_days
+            ));
+        }
+
+        Ok(())
+    }
+
     fn validate_card_dimensions(width: f32, height: f32) -> Result<(), String> {
         const MIN_WIDTH: f32 = 20.0;
         const MAX_WIDTH: f32 = 600.0;
@@ -246,6 +344,24 @@ mod tests {
         assert!(settings.validate_without_theme().is_err());
     }
 
+    #[test]
+    fn test_validate_invalid_mass_delete_percent() {
+        let settings = Settings {
+            sync_mass_delete_percent: 150,
+            ..Settings::default()
+        };
+        assert!(settings.validate_without_theme().is_err());
+    }
+
+    #[test]
+    fn test_validate_invalid_backup_interval() {
+        let settings = Settings {
+            backup_interval: "monthly".to_string(),
+            ..Settings::default()
+        };
+        assert!(settings.validate_without_theme().is_err());
+    }
+
     #[test]
     fn test_validate_valid_settings() {
         let settings = Settings::default();
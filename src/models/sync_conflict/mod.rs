@@ -2,6 +2,13 @@
 
 use serde::{Deserialize, Serialize};
 
+mod snapshot;
+
+pub use snapshot::{
+    compare_fields, merge_snapshots, ConflictEventSnapshot, ConflictField, ConflictFieldState,
+    FieldChoice,
+};
+
 pub const SYNC_CONFLICT_STATUS_OPEN: &str = "open";
 pub const SYNC_CONFLICT_STATUS_RESOLVED: &str = "resolved";
 
@@ -11,6 +18,7 @@ pub const SYNC_CONFLICT_REASON_LOCAL_DELETE_PENDING: &str = "local_delete_pendin
 
 pub const SYNC_CONFLICT_RESOLUTION_REMOTE_WINS: &str = "remote_wins";
 pub const SYNC_CONFLICT_RESOLUTION_RETRY_LOCAL: &str = "retry_local";
+pub const SYNC_CONFLICT_RESOLUTION_MERGED: &str = "merged";
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SyncConflict {
@@ -24,6 +32,12 @@ pub struct SyncConflict {
     pub reason: String,
     pub resolution: Option<String>,
     pub status: String,
+    /// Event as it was last synced, before either side changed it.
+    pub base_snapshot: Option<ConflictEventSnapshot>,
+    /// Local version that was pending when the remote change arrived.
+    pub local_snapshot: Option<ConflictEventSnapshot>,
+    /// Remote version that was applied (None when the remote deleted the event).
+    pub remote_snapshot: Option<ConflictEventSnapshot>,
     pub created_at: Option<String>,
     pub resolved_at: Option<String>,
     pub updated_at: Option<String>,
@@ -58,15 +72,24 @@ impl SyncConflict {
 
         if let Some(resolution) = &self.resolution {
             let resolution = resolution.trim();
-            if resolution != SYNC_CONFLICT_RESOLUTION_REMOTE_WINS
-                && resolution != SYNC_CONFLICT_RESOLUTION_RETRY_LOCAL
-            {
-                return Err("resolution must be remote_wins or retry_local".to_string());
+            if !Self::is_valid_resolution(resolution) {
+                return Err("resolution must be remote_wins, retry_local or merged".to_string());
             }
         }
 
         Ok(())
     }
+
+    pub fn is_valid_resolution(resolution: &str) -> bool {
+        resolution == SYNC_CONFLICT_RESOLUTION_REMOTE_WINS
+            || resolution == SYNC_CONFLICT_RESOLUTION_RETRY_LOCAL
+            || resolution == SYNC_CONFLICT_RESOLUTION_MERGED
+    }
+
+    /// True when both versions are available, so fields can be merged individually.
+    pub fn supports_field_merge(&self) -> bool {
+        self.local_snapshot.is_some() && self.remote_snapshot.is_some()
+    }
 }
 
 #[cfg(test)]
@@ -85,6 +108,9 @@ mod tests {
             reason: SYNC_CONFLICT_REASON_LOCAL_UPDATE_PENDING.to_string(),
             resolution: Some(SYNC_CONFLICT_RESOLUTION_REMOTE_WINS.to_string()),
             status: SYNC_CONFLICT_STATUS_OPEN.to_string(),
+            base_snapshot: None,
+            local_snapshot: None,
+            remote_snapshot: None,
             created_at: None,
             resolved_at: None,
             updated_at: None,
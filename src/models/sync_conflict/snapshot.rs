@@ -0,0 +1,339 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::models::event::Event;
+
+/// Field-level copy of an event as seen by one side of a sync conflict.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictEventSnapshot {
+    pub title: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub all_day: bool,
+    pub category: Option<String>,
+    pub color: Option<String>,
+    pub recurrence_rule: Option<String>,
+    pub recurrence_exceptions: Option<Vec<DateTime<Local>>>,
+}
+
+/// Groups of event fields that can be chosen independently when merging.
+///
+/// Start, end and all-day are kept together (as are the recurrence rule and its
+/// exceptions) so that a merge can never produce an inconsistent event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConflictField {
+    Title,
+    Description,
+    Location,
+    Time,
+    Category,
+    Color,
+    Recurrence,
+}
+
+/// Which side a merged field is taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldChoice {
+    Local,
+    Remote,
+}
+
+/// Three-way comparison of a single field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictFieldState {
+    pub field: ConflictField,
+    pub base: Option<String>,
+    pub local: String,
+    pub remote: String,
+    pub local_changed: bool,
+    pub remote_changed: bool,
+}
+
+impl ConflictFieldState {
+    /// True when both sides changed the field to different values.
+    pub fn is_conflicting(&self) -> bool {
+        self.local_changed && self.remote_changed && self.local != self.remote
+    }
+
+    /// Suggested choice: keep whichever side changed the field, and fall back
+    /// to the remote value when both did (matching the automatic outcome).
+    pub fn suggested_choice(&self) -> FieldChoice {
+        if self.local_changed && !self.remote_changed {
+            FieldChoice::Local
+        } else {
+            FieldChoice::Remote
+        }
+    }
+}
+
+impl ConflictField {
+    pub const ALL: [ConflictField; 7] = [
+        ConflictField::Title,
+        ConflictField::Description,
+        ConflictField::Location,
+        ConflictField::Time,
+        ConflictField::Category,
+        ConflictField::Color,
+        ConflictField::Recurrence,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConflictField::Title => "Title",
+            ConflictField::Description => "Description",
+            ConflictField::Location => "Location",
+            ConflictField::Time => "Date & time",
+            ConflictField::Category => "Category",
+            ConflictField::Color => "Colour",
+            ConflictField::Recurrence => "Recurrence",
+        }
+    }
+}
+
+impl From<&Event> for ConflictEventSnapshot {
+    fn from(event: &Event) -> Self {
+        Self {
+            title: event.title.clone(),
+            description: event.description.clone(),
+            location: event.location.clone(),
+            start: event.start,
+            end: event.end,
+            all_day: event.all_day,
+            category: event.category.clone(),
+            color: event.color.clone(),
+            recurrence_rule: event.recurrence_rule.clone(),
+            recurrence_exceptions: event.recurrence_exceptions.clone(),
+        }
+    }
+}
+
+impl ConflictEventSnapshot {
+    pub fn to_json(&self) -> Option<String> {
+        serde_json::to_string(self).ok()
+    }
+
+    pub fn from_json(json: &str) -> Option<Self> {
+        serde_json::from_str(json).ok()
+    }
+
+    /// Human-readable value of one field group, used for display and comparison.
+    pub fn display_value(&self, field: ConflictField) -> String {
+        match field {
+            ConflictField::Title => self.title.clone(),
+            ConflictField::Description => self.description.clone().unwrap_or_default(),
+            ConflictField::Location => self.location.clone().unwrap_or_default(),
+            ConflictField::Time => {
+                if self.all_day {
+                    format!(
+                        "{} – {} (all day)",
+                        self.start.format("%Y-%m-%d"),
+                        self.end.format("%Y-%m-%d")
+                    )
+                } else {
+                    format!(
+                        "{} – {}",
+                        self.start.format("%Y-%m-%d %H:%M"),
+                        self.end.format("%Y-%m-%d %H:%M")
+                    )
+                }
+            }
+            ConflictField::Category => self.category.clone().unwrap_or_default(),
+            ConflictField::Color => self.color.clone().unwrap_or_default(),
+            ConflictField::Recurrence => {
+                let rule = self.recurrence_rule.clone().unwrap_or_default();
+                match self.recurrence_exceptions.as_ref() {
+                    Some(exceptions) if !exceptions.is_empty() => {
+                        format!("{} ({} exception(s))", rule, exceptions.len())
+                    }
+                    _ => rule,
+                }
+            }
+        }
+    }
+
+    fn field_equals(&self, other: &Self, field: ConflictField) -> bool {
+        match field {
+            ConflictField::Title => self.title == other.title,
+            ConflictField::Description => self.description == other.description,
+            ConflictField::Location => self.location == other.location,
+            ConflictField::Time => {
+                self.start == other.start && self.end == other.end && self.all_day == other.all_day
+            }
+            ConflictField::Category => self.category == other.category,
+            ConflictField::Color => self.color == other.color,
+            ConflictField::Recurrence => {
+                self.recurrence_rule == other.recurrence_rule
+                    && self.recurrence_exceptions == other.recurrence_exceptions
+            }
+        }
+    }
+
+    fn copy_field_from(&mut self, other: &Self, field: ConflictField) {
+        match field {
+            ConflictField::Title => self.title = other.title.clone(),
+            ConflictField::Description => self.description = other.description.clone(),
+            ConflictField::Location => self.location = other.location.clone(),
+            ConflictField::Time => {
+                self.start = other.start;
+                self.end = other.end;
+                self.all_day = other.all_day;
+            }
+            ConflictField::Category => self.category = other.category.clone(),
+            ConflictField::Color => self.color = other.color.clone(),
+            ConflictField::Recurrence => {
+                self.recurrence_rule = other.recurrence_rule.clone();
+                self.recurrence_exceptions = other.recurrence_exceptions.clone();
+            }
+        }
+    }
+
+    /// Writes the snapshot's fields onto an existing event, keeping its identity.
+    pub fn apply_to(&self, event: &mut Event) {
+        event.title = self.title.clone();
+        event.description = self.description.clone();
+        event.location = self.location.clone();
+        event.start = self.start;
+        event.end = self.end;
+        event.all_day = self.all_day;
+        event.category = self.category.clone();
+        event.color = self.color.clone();
+        event.recurrence_rule = self.recurrence_rule.clone();
+        event.recurrence_exceptions = self.recurrence_exceptions.clone();
+    }
+}
+
+/// Compares local and remote versions against the last-synced base, field by field.
+///
+/// Without a base every differing field is treated as changed on both sides.
+pub fn compare_fields(
+    base: Option<&ConflictEventSnapshot>,
+    local: &ConflictEventSnapshot,
+    remote: &ConflictEventSnapshot,
+) -> Vec<ConflictFieldState> {
+    ConflictField::ALL
+        .iter()
+        .map(|&field| {
+            let (local_changed, remote_changed) = match base {
+                Some(base) => (
+                    !local.field_equals(base, field),
+                    !remote.field_equals(base, field),
+                ),
+                None => {
+                    let differs = !local.field_equals(remote, field);
+                    (differs, differs)
+                }
+            };
+
+            ConflictFieldState {
+                field,
+                base: base.map(|base| base.display_value(field)),
+                local: local.display_value(field),
+                remote: remote.display_value(field),
+                local_changed,
+                remote_changed,
+            }
+        })
+        .collect()
+}
+
+/// Builds the merged version by taking each field from the chosen side.
+pub fn merge_snapshots(
+    local: &ConflictEventSnapshot,
+    remote: &ConflictEventSnapshot,
+    choices: &[(ConflictField, FieldChoice)],
+) -> ConflictEventSnapshot {
+    let mut merged = remote.clone();
+    for (field, choice) in choices {
+        if *choice == FieldChoice::Local {
+            merged.copy_field_from(local, *field);
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn snapshot(title: &str, location: Option<&str>) -> ConflictEventSnapshot {
+        let start = Local.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        ConflictEventSnapshot {
+            title: title.to_string(),
+            description: None,
+            location: location.map(str::to_string),
+            start,
+            end: start + Duration::hours(1),
+            all_day: false,
+            category: None,
+            color: None,
+            recurrence_rule: None,
+            recurrence_exceptions: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_fields_detects_one_sided_changes() {
+        let base = snapshot("Standup", Some("Room 1"));
+        let local = snapshot("Standup (moved)", Some("Room 1"));
+        let remote = snapshot("Standup", Some("Room 4"));
+
+        let states = compare_fields(Some(&base), &local, &remote);
+        let title = states
+            .iter()
+            .find(|s| s.field == ConflictField::Title)
+            .unwrap();
+        let location = states
+            .iter()
+            .find(|s| s.field == ConflictField::Location)
+            .unwrap();
+
+        assert!(title.local_changed && !title.remote_changed);
+        assert_eq!(title.suggested_choice(), FieldChoice::Local);
+        assert!(!location.local_changed && location.remote_changed);
+        assert_eq!(location.suggested_choice(), FieldChoice::Remote);
+        assert!(!states.iter().any(ConflictFieldState::is_conflicting));
+    }
+
+    #[test]
+    fn test_compare_fields_without_base_marks_differences_as_conflicts() {
+        let local = snapshot("Local", None);
+        let remote = snapshot("Remote", None);
+
+        let states = compare_fields(None, &local, &remote);
+        let title = states
+            .iter()
+            .find(|s| s.field == ConflictField::Title)
+            .unwrap();
+
+        assert!(title.is_conflicting());
+        assert_eq!(title.suggested_choice(), FieldChoice::Remote);
+    }
+
+    #[test]
+    fn test_merge_snapshots_takes_each_field_from_chosen_side() {
+        let local = snapshot("Local title", Some("Local room"));
+        let remote = snapshot("Remote title", Some("Remote room"));
+
+        let merged = merge_snapshots(
+            &local,
+            &remote,
+            &[
+                (ConflictField::Title, FieldChoice::Local),
+                (ConflictField::Location, FieldChoice::Remote),
+            ],
+        );
+
+        assert_eq!(merged.title, "Local title");
+        assert_eq!(merged.location.as_deref(), Some("Remote room"));
+    }
+
+    #[test]
+    fn test_snapshot_json_round_trip() {
+        let original = snapshot("Round trip", Some("Room 4"));
+        let json = original.to_json().unwrap();
+        assert_eq!(ConflictEventSnapshot::from_json(&json), Some(original));
+    }
+}
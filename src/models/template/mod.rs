@@ -2,9 +2,10 @@
 // Stores reusable event configurations
 
 use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
 
 /// Event template for quick event creation
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EventTemplate {
     pub id: Option<i64>,
     pub name: String,
@@ -0,0 +1,93 @@
+//! Passphrase-encrypted backup files.
+//!
+//! Layout: an 8-byte magic, the Argon2 salt, the ChaCha20-Poly1305 nonce and
+//! then the sealed SQLite file. The key derivation and cipher are shared with
+//! the secret vault in [`crate::services::secrets::crypto`].
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::services::secrets::crypto::{self, NONCE_LEN, SALT_LEN};
+
+const MAGIC: &[u8; 8] = b"RCALBAK1";
+
+/// Whether a file starts with the encrypted backup header.
+pub fn is_encrypted(path: &Path) -> Result<bool> {
+    let mut file =
+        fs::File::open(path).with_context(|| format!("Failed to open file: {:?}", path))?;
+    let mut header = [0u8; MAGIC.len()];
+    match file.read_exact(&mut header) {
+        Ok(()) => Ok(&header == MAGIC),
+        Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(err) => Err(err).with_context(|| format!("Failed to read file: {:?}", path)),
+    }
+}
+
+/// Encrypts `src` into `dest`, writing through a temporary file.
+pub fn encrypt_file(src: &Path, dest: &Path, passphrase: &str) -> Result<()> {
+    if passphrase.is_empty() {
+        anyhow::bail!("Backup passphrase cannot be empty");
+    }
+
+    let plaintext = fs::read(src).with_context(|| format!("Failed to read {:?}", src))?;
+    let salt = crypto::random_bytes::<SALT_LEN>();
+    let key = crypto::derive_key(passphrase, &salt)?;
+    let (nonce, ciphertext) = crypto::seal(&key, &plaintext)?;
+
+    let mut contents = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+    contents.extend_from_slice(MAGIC);
+    contents.extend_from_slice(&salt);
+    contents.extend_from_slice(&nonce);
+    contents.extend_from_slice(&ciphertext);
+
+    let partial_path = dest.with_extension("enc.partial");
+    fs::write(&partial_path, contents)
+        .with_context(|| format!("Failed to write encrypted backup {:?}", partial_path))?;
+    fs::rename(&partial_path, dest)
+        .with_context(|| format!("Failed to move encrypted backup into place at {:?}", dest))
+}
+
+/// Decrypts an encrypted backup at `src` into `dest`.
+pub fn decrypt_file(src: &Path, dest: &Path, passphrase: &str) -> Result<()> {
+    let contents = fs::read(src).with_context(|| format!("Failed to read {:?}", src))?;
+    let header_len = MAGIC.len() + SALT_LEN + NONCE_LEN;
+    if contents.len() < header_len || &contents[..MAGIC.len()] != MAGIC {
+        anyhow::bail!("{:?} is not an encrypted backup", src);
+    }
+
+    let salt = &contents[MAGIC.len()..MAGIC.len() + SALT_LEN];
+    let nonce = &contents[MAGIC.len() + SALT_LEN..header_len];
+    let key = crypto::derive_key(passphrase, salt)?;
+    let plaintext =
+        crypto::open(&key, nonce, &contents[header_len..]).context("Could not decrypt backup")?;
+
+    fs::write(dest, plaintext).with_context(|| format!("Failed to write {:?}", dest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_and_decrypt_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plain = temp_dir.path().join("plain.db");
+        let sealed = temp_dir.path().join("sealed.db.enc");
+        let opened = temp_dir.path().join("opened.db");
+        fs::write(&plain, b"SQLite format 3\0 private schedule").unwrap();
+
+        encrypt_file(&plain, &sealed, "hunter2").unwrap();
+
+        assert!(is_encrypted(&sealed).unwrap());
+        assert!(!is_encrypted(&plain).unwrap());
+        let raw = fs::read(&sealed).unwrap();
+        assert!(!raw.windows(8).any(|w| w == b"schedule"));
+
+        assert!(decrypt_file(&sealed, &opened, "wrong").is_err());
+        decrypt_file(&sealed, &opened, "hunter2").unwrap();
+        assert_eq!(fs::read(&opened).unwrap(), fs::read(&plain).unwrap());
+    }
+}
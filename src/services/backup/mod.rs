@@ -1,8 +1,21 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Local};
+use rusqlite::backup::Backup;
+use rusqlite::{Connection, OpenFlags};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+pub mod encryption;
+pub mod policy;
+
+/// File name prefix of backups taken by hand, which retention never prunes.
+const BACKUP_FILE_PREFIX: &str = "calendar_backup_";
+/// File name prefix of scheduled backups; only these are pruned.
+const SCHEDULED_BACKUP_FILE_PREFIX: &str = "calendar_scheduled_backup_";
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y-%m-%d_%H%M%S";
+/// Extension appended to the `.db` name of passphrase-encrypted backups.
+const ENCRYPTED_EXTENSION: &str = "enc";
+
 /// Information about a backup file
 #[derive(Debug, Clone)]
 pub struct BackupInfo {
@@ -10,6 +23,8 @@ pub struct BackupInfo {
     pub filename: String,
     pub created_at: DateTime<Local>,
     pub size_bytes: u64,
+    /// Whether the backup is passphrase-encrypted (`.db.enc`)
+    pub encrypted: bool,
 }
 
 impl BackupInfo {
@@ -24,19 +39,26 @@ impl BackupInfo {
             .unwrap_or("unknown")
             .to_string();
 
-        let created_at = metadata
-            .created()
-            .or_else(|_| metadata.modified())
-            .with_context(|| format!("Failed to get creation time for {:?}", path))?;
-
-        let created_at = DateTime::<Local>::from(created_at);
+        // Prefer the timestamp in the file name: copying a backup (or syncing
+        // its folder) resets the file's creation time.
+        let created_at = match BackupService::timestamp_from_filename(&filename) {
+            Some(created_at) => created_at,
+            None => DateTime::<Local>::from(
+                metadata
+                    .created()
+                    .or_else(|_| metadata.modified())
+                    .with_context(|| format!("Failed to get creation time for {:?}", path))?,
+            ),
+        };
         let size_bytes = metadata.len();
+        let encrypted = BackupService::has_encrypted_name(&path);
 
         Ok(BackupInfo {
             path,
             filename,
             created_at,
             size_bytes,
+            encrypted,
         })
     }
 }
@@ -68,10 +90,42 @@ impl BackupService {
     /// # Arguments
     /// * `db_path` - Path to the database file to backup
     /// * `backup_dir` - Directory to store the backup (uses default if None)
+    /// * `passphrase` - Encrypts the backup (`*.db.enc`) when given
     ///
     /// # Returns
     /// Path to the created backup file
-    pub fn create_backup(db_path: &Path, backup_dir: Option<&Path>) -> Result<PathBuf> {
+    ///
+    /// For encrypted backups the plaintext snapshot is staged next to the live
+    /// database rather than in the backup folder, which may be synced to the cloud.
+    pub fn create_backup(
+        db_path: &Path,
+        backup_dir: Option<&Path>,
+        passphrase: Option<&str>,
+    ) -> Result<PathBuf> {
+        Self::write_backup(db_path, backup_dir, passphrase, BACKUP_FILE_PREFIX)
+    }
+
+    /// Like [`BackupService::create_backup`], but named as a scheduled backup
+    /// so that retention may prune it later
+    pub fn create_scheduled_backup(
+        db_path: &Path,
+        backup_dir: Option<&Path>,
+        passphrase: Option<&str>,
+    ) -> Result<PathBuf> {
+        Self::write_backup(
+            db_path,
+            backup_dir,
+            passphrase,
+            SCHEDULED_BACKUP_FILE_PREFIX,
+        )
+    }
+
+    fn write_backup(
+        db_path: &Path,
+        backup_dir: Option<&Path>,
+        passphrase: Option<&str>,
+        prefix: &str,
+    ) -> Result<PathBuf> {
         // Ensure database file exists
         if !db_path.exists() {
             anyhow::bail!("Database file does not exist: {:?}", db_path);
@@ -91,64 +145,206 @@ impl BackupService {
         }
 
         // Generate backup filename with timestamp
-        let timestamp = Local::now().format("%Y-%m-%d_%H%M%S");
-        let backup_filename = format!("calendar_backup_{}.db", timestamp);
-        let backup_path = backup_dir.join(&backup_filename);
-
-        // Copy database file to backup location
-        fs::copy(db_path, &backup_path).with_context(|| {
-            format!(
-                "Failed to copy database from {:?} to {:?}",
-                db_path, backup_path
-            )
-        })?;
+        let timestamp = Local::now().format(BACKUP_TIMESTAMP_FORMAT);
+        let backup_filename = format!("{}{}.db", prefix, timestamp);
+        let mut backup_path = backup_dir.join(&backup_filename);
+
+        match passphrase {
+            None => Self::snapshot_database(db_path, &backup_path)?,
+            Some(passphrase) => {
+                backup_path = backup_path.with_extension(format!("db.{}", ENCRYPTED_EXTENSION));
+                let staged = Self::scratch_path(db_path, "backup");
+                let result = Self::snapshot_database(db_path, &staged)
+                    .and_then(|()| encryption::encrypt_file(&staged, &backup_path, passphrase));
+                fs::remove_file(&staged).ok();
+                result?;
+            }
+        }
 
         log::info!("Created backup: {:?}", backup_path);
         Ok(backup_path)
     }
 
+    /// Write a consistent, verified copy of a live database to `dest`
+    ///
+    /// The database runs in WAL mode, so copying the file would miss writes
+    /// still in the `-wal` file and could tear if a sync thread writes
+    /// mid-copy. `VACUUM INTO` reads a single transaction snapshot that
+    /// includes the WAL. The copy is written under a temporary name and only
+    /// renamed into place once it passes an integrity check.
+    fn snapshot_database(db_path: &Path, dest: &Path) -> Result<()> {
+        let partial_path = dest.with_extension("db.partial");
+        if partial_path.exists() {
+            fs::remove_file(&partial_path).ok();
+        }
+
+        let result = (|| {
+            let source = Connection::open_with_flags(
+                db_path,
+                OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            )
+            .with_context(|| format!("Failed to open database {:?}", db_path))?;
+            source
+                .busy_timeout(std::time::Duration::from_secs(5))
+                .context("Failed to set busy timeout")?;
+            source
+                .execute("VACUUM INTO ?1", [partial_path.to_string_lossy().as_ref()])
+                .with_context(|| {
+                    format!(
+                        "Failed to write database snapshot from {:?} to {:?}",
+                        db_path, partial_path
+                    )
+                })?;
+            drop(source);
+
+            Self::verify_integrity(&partial_path)?;
+            fs::rename(&partial_path, dest)
+                .with_context(|| format!("Failed to move backup into place at {:?}", dest))
+        })();
+
+        if result.is_err() {
+            fs::remove_file(&partial_path).ok();
+        }
+        result
+    }
+
+    /// Run SQLite's integrity check against a database file
+    pub fn verify_integrity(path: &Path) -> Result<()> {
+        Self::verify_sqlite_file(path)?;
+
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .with_context(|| format!("Failed to open {:?} for verification", path))?;
+        let mut stmt = conn
+            .prepare("PRAGMA integrity_check")
+            .context("Failed to run integrity check")?;
+        let problems = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read integrity check results")?;
+
+        match problems.as_slice() {
+            [ok] if ok == "ok" => Ok(()),
+            _ => anyhow::bail!(
+                "Integrity check failed for {:?}: {}",
+                path,
+                problems.join("; ")
+            ),
+        }
+    }
+
     /// Restore a database from a backup file
     ///
     /// # Arguments
     /// * `backup_path` - Path to the backup file to restore from
     /// * `db_path` - Path where the database should be restored
+    /// * `passphrase` - Required when the backup is encrypted
     ///
     /// # Safety
     /// This will overwrite the existing database file. The caller should ensure
     /// the database is not in use and ideally create a backup before restoring.
-    pub fn restore_backup(backup_path: &Path, db_path: &Path) -> Result<()> {
+    /// Encrypted backups are decrypted next to the database and checked first,
+    /// so a wrong passphrase fails before anything is touched.
+    pub fn restore_backup(
+        backup_path: &Path,
+        db_path: &Path,
+        passphrase: Option<&str>,
+    ) -> Result<()> {
         // Ensure backup file exists
         if !backup_path.exists() {
             anyhow::bail!("Backup file does not exist: {:?}", backup_path);
         }
 
-        // Verify backup file is a valid SQLite database
-        Self::verify_sqlite_file(backup_path)?;
+        if encryption::is_encrypted(backup_path)? {
+            let passphrase = passphrase
+                .filter(|p| !p.is_empty())
+                .context("This backup is encrypted; enter its passphrase to restore it")?;
+            let decrypted = Self::scratch_path(db_path, "restore");
+            let result = encryption::decrypt_file(backup_path, &decrypted, passphrase)
+                .and_then(|()| Self::restore_plain_backup(&decrypted, db_path));
+            fs::remove_file(&decrypted).ok();
+            result?;
+        } else {
+            Self::restore_plain_backup(backup_path, db_path)?;
+        }
+
+        log::info!("Restored backup from {:?} to {:?}", backup_path, db_path);
+        Ok(())
+    }
+
+    fn restore_plain_backup(backup_path: &Path, db_path: &Path) -> Result<()> {
+        // Refuse backups that are not intact SQLite databases
+        Self::verify_integrity(backup_path)?;
 
         // Create backup of current database before overwriting (safety measure)
         if db_path.exists() {
             let safety_backup = db_path.with_extension("db.before_restore");
-            fs::copy(db_path, &safety_backup)
+            if safety_backup.exists() {
+                fs::remove_file(&safety_backup)
+                    .context("Failed to replace previous safety backup")?;
+            }
+            Self::snapshot_database(db_path, &safety_backup)
                 .context("Failed to create safety backup before restore")?;
             log::info!("Created safety backup: {:?}", safety_backup);
         }
 
         // Ensure parent directory exists
-        if let Some(parent) = db_path.parent() {
+        if let Some(parent) = db_path.parent().filter(|p| !p.as_os_str().is_empty()) {
             fs::create_dir_all(parent)
                 .with_context(|| format!("Failed to create database directory: {:?}", parent))?;
         }
 
-        // Copy backup file to database location
-        fs::copy(backup_path, db_path).with_context(|| {
-            format!(
-                "Failed to restore backup from {:?} to {:?}",
-                backup_path, db_path
-            )
-        })?;
+        // Copy pages through the backup API rather than overwriting the file,
+        // so the database's WAL is reset along with it instead of being
+        // replayed over the restored contents.
+        let source = Connection::open_with_flags(backup_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .with_context(|| format!("Failed to open backup {:?}", backup_path))?;
+        let mut target = Connection::open(db_path)
+            .with_context(|| format!("Failed to open database {:?}", db_path))?;
+        target
+            .busy_timeout(std::time::Duration::from_secs(5))
+            .context("Failed to set busy timeout")?;
+        Backup::new(&source, &mut target)
+            .and_then(|backup| backup.step(-1).map(|_| ()))
+            .with_context(|| {
+                format!(
+                    "Failed to restore backup from {:?} to {:?}",
+                    backup_path, db_path
+                )
+            })?;
+        drop(target);
+        Self::verify_integrity(db_path)
+    }
 
-        log::info!("Restored backup from {:?} to {:?}", backup_path, db_path);
-        Ok(())
+    /// Check that a backup opens and passes `PRAGMA integrity_check`,
+    /// decrypting it first (next to `db_path`) when it is encrypted
+    pub fn verify_backup(
+        backup_path: &Path,
+        db_path: &Path,
+        passphrase: Option<&str>,
+    ) -> Result<()> {
+        if !encryption::is_encrypted(backup_path)? {
+            return Self::verify_integrity(backup_path);
+        }
+
+        let passphrase = passphrase.context("Encrypted backup needs a passphrase to verify")?;
+        let decrypted = Self::scratch_path(db_path, "verify");
+        let result = encryption::decrypt_file(backup_path, &decrypted, passphrase)
+            .and_then(|()| Self::verify_integrity(&decrypted));
+        fs::remove_file(&decrypted).ok();
+        result
+    }
+
+    /// Temporary plaintext file beside the live database
+    fn scratch_path(db_path: &Path, purpose: &str) -> PathBuf {
+        db_path.with_extension(format!("{}.tmp", purpose))
+    }
+
+    fn has_encrypted_name(path: &Path) -> bool {
+        path.extension().and_then(|ext| ext.to_str()) == Some(ENCRYPTED_EXTENSION)
+            && path
+                .file_stem()
+                .map(|stem| Path::new(stem).extension().and_then(|e| e.to_str()) == Some("db"))
+                .unwrap_or(false)
     }
 
     /// List all backups in the specified directory
@@ -179,8 +375,10 @@ impl BackupService {
             let entry = entry.context("Failed to read directory entry")?;
             let path = entry.path();
 
-            // Only include .db files
-            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("db") {
+            // Only include .db and encrypted .db.enc files
+            let is_backup_file = path.extension().and_then(|s| s.to_str()) == Some("db")
+                || Self::has_encrypted_name(&path);
+            if path.is_file() && is_backup_file {
                 if let Ok(info) = BackupInfo::from_path(path) {
                     backups.push(info);
                 }
@@ -193,65 +391,6 @@ impl BackupService {
         Ok(backups)
     }
 
-    /// Delete old backups, keeping only the specified number of most recent backups
-    ///
-    /// # Arguments
-    /// * `backup_dir` - Directory containing backups (uses default if None)
-    /// * `keep_count` - Number of recent backups to keep
-    pub fn cleanup_old_backups(backup_dir: Option<&Path>, keep_count: usize) -> Result<usize> {
-        let mut backups = Self::list_backups(backup_dir)?;
-
-        if backups.len() <= keep_count {
-            return Ok(0);
-        }
-
-        // Remove oldest backups
-        let to_remove = backups.split_off(keep_count);
-        let removed_count = to_remove.len();
-
-        for backup in to_remove {
-            if let Err(e) = fs::remove_file(&backup.path) {
-                log::warn!("Failed to delete old backup {:?}: {}", backup.path, e);
-            } else {
-                log::info!("Deleted old backup: {:?}", backup.path);
-            }
-        }
-
-        Ok(removed_count)
-    }
-
-    /// Create a backup on startup and clean up old backups
-    ///
-    /// # Arguments
-    /// * `db_path` - Path to the database file
-    /// * `keep_count` - Number of backups to keep (default: 5)
-    ///
-    /// # Returns
-    /// Path to the created backup file, or None if backup creation was skipped
-    pub fn auto_backup_on_startup(
-        db_path: &Path,
-        keep_count: Option<usize>,
-    ) -> Result<Option<PathBuf>> {
-        let keep_count = keep_count.unwrap_or(5);
-
-        // Only create backup if database exists
-        if !db_path.exists() {
-            log::info!("Skipping auto-backup: database file does not exist yet");
-            return Ok(None);
-        }
-
-        // Create backup
-        let backup_path = Self::create_backup(db_path, None)?;
-
-        // Clean up old backups
-        let removed = Self::cleanup_old_backups(None, keep_count)?;
-        if removed > 0 {
-            log::info!("Cleaned up {} old backup(s)", removed);
-        }
-
-        Ok(Some(backup_path))
-    }
-
     /// Delete a specific backup file
     pub fn delete_backup(backup_path: &Path) -> Result<()> {
         if !backup_path.exists() {
@@ -265,6 +404,29 @@ impl BackupService {
         Ok(())
     }
 
+    /// Whether a file was written by [`BackupService::create_scheduled_backup`]
+    pub fn is_scheduled_backup(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                name.starts_with(SCHEDULED_BACKUP_FILE_PREFIX)
+                    && Self::timestamp_from_filename(name).is_some()
+            })
+    }
+
+    fn timestamp_from_filename(filename: &str) -> Option<DateTime<Local>> {
+        let stem = filename
+            .strip_prefix(SCHEDULED_BACKUP_FILE_PREFIX)
+            .or_else(|| filename.strip_prefix(BACKUP_FILE_PREFIX))?;
+        let stamp = stem
+            .strip_suffix(".db.enc")
+            .or_else(|| stem.strip_suffix(".db"))?;
+        chrono::NaiveDateTime::parse_from_str(stamp, BACKUP_TIMESTAMP_FORMAT)
+            .ok()?
+            .and_local_timezone(Local)
+            .earliest()
+    }
+
     /// Verify that a file is a valid SQLite database
     fn verify_sqlite_file(path: &Path) -> Result<()> {
         let file =
@@ -326,7 +488,7 @@ mod tests {
         create_test_database(&db_path).unwrap();
 
         // Create backup
-        let backup_path = BackupService::create_backup(&db_path, Some(&backup_dir)).unwrap();
+        let backup_path = BackupService::create_backup(&db_path, Some(&backup_dir), None).unwrap();
 
         // Verify backup exists
         assert!(backup_path.exists());
@@ -341,7 +503,7 @@ mod tests {
         let backup_dir = temp_dir.path().join("backups");
 
         // Should fail with nonexistent database
-        let result = BackupService::create_backup(&db_path, Some(&backup_dir));
+        let result = BackupService::create_backup(&db_path, Some(&backup_dir), None);
         assert!(result.is_err());
     }
 
@@ -362,10 +524,11 @@ mod tests {
         }
 
         // Create backup
-        let backup_path = BackupService::create_backup(&original_db, Some(&backup_dir)).unwrap();
+        let backup_path =
+            BackupService::create_backup(&original_db, Some(&backup_dir), None).unwrap();
 
         // Restore to new location
-        BackupService::restore_backup(&backup_path, &restored_db).unwrap();
+        BackupService::restore_backup(&backup_path, &restored_db, None).unwrap();
 
         // Verify restored database
         assert!(restored_db.exists());
@@ -377,6 +540,93 @@ mod tests {
         assert_eq!(value, "test_value");
     }
 
+    #[test]
+    fn test_backup_includes_uncheckpointed_wal_writes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("live.db");
+        let backup_dir = temp_dir.path().join("backups");
+
+        // Keep the writer open so its changes stay in the -wal file.
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        conn.pragma_update(None, "journal_mode", "WAL").unwrap();
+        conn.pragma_update(None, "wal_autocheckpoint", 0).unwrap();
+        conn.execute("CREATE TABLE test (id INTEGER PRIMARY KEY, value TEXT)", [])
+            .unwrap();
+        conn.execute("INSERT INTO test (value) VALUES ('in the wal')", [])
+            .unwrap();
+
+        let backup_path = BackupService::create_backup(&db_path, Some(&backup_dir), None).unwrap();
+
+        BackupService::verify_integrity(&backup_path).unwrap();
+        let copy = rusqlite::Connection::open(&backup_path).unwrap();
+        let value: String = copy
+            .query_row("SELECT value FROM test", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(value, "in the wal");
+        assert!(!backup_path.with_extension("db.partial").exists());
+    }
+
+    #[test]
+    fn test_restore_rejects_corrupted_backup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let backup_dir = temp_dir.path().join("backups");
+        create_test_database(&db_path).unwrap();
+        let backup_path = BackupService::create_backup(&db_path, Some(&backup_dir), None).unwrap();
+
+        // Keep the header but truncate the rest of the file.
+        let bytes = fs::read(&backup_path).unwrap();
+        fs::write(&backup_path, &bytes[..100]).unwrap();
+
+        assert!(BackupService::restore_backup(&backup_path, &db_path, None).is_err());
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        let tables: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'test'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(tables, 1);
+    }
+
+    #[test]
+    fn test_encrypted_backup_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("calendar.db");
+        let backup_dir = temp_dir.path().join("backups");
+        {
+            let conn = rusqlite::Connection::open(&db_path).unwrap();
+            conn.execute("CREATE TABLE test (id INTEGER PRIMARY KEY, value TEXT)", [])
+                .unwrap();
+            conn.execute("INSERT INTO test (value) VALUES ('dentist at 3pm')", [])
+                .unwrap();
+        }
+
+        let backup_path =
+            BackupService::create_backup(&db_path, Some(&backup_dir), Some("correct horse"))
+                .unwrap();
+
+        assert!(backup_path.to_string_lossy().ends_with(".db.enc"));
+        assert!(!BackupService::is_scheduled_backup(&backup_path));
+        let listed = BackupService::list_backups(Some(&backup_dir)).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].encrypted);
+        assert!(!BackupService::scratch_path(&db_path, "backup").exists());
+        BackupService::verify_backup(&backup_path, &db_path, Some("correct horse")).unwrap();
+
+        let restored = temp_dir.path().join("restored.db");
+        assert!(BackupService::restore_backup(&backup_path, &restored, None).is_err());
+        assert!(BackupService::restore_backup(&backup_path, &restored, Some("wrong")).is_err());
+        BackupService::restore_backup(&backup_path, &restored, Some("correct horse")).unwrap();
+
+        let conn = rusqlite::Connection::open(&restored).unwrap();
+        let value: String = conn
+            .query_row("SELECT value FROM test", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(value, "dentist at 3pm");
+    }
+
     #[test]
     fn test_list_backups() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -386,11 +636,11 @@ mod tests {
         create_test_database(&db_path).unwrap();
 
         // Create multiple backups with longer delays to ensure different timestamps
-        BackupService::create_backup(&db_path, Some(&backup_dir)).unwrap();
+        BackupService::create_backup(&db_path, Some(&backup_dir), None).unwrap();
         std::thread::sleep(std::time::Duration::from_secs(1));
-        BackupService::create_backup(&db_path, Some(&backup_dir)).unwrap();
+        BackupService::create_backup(&db_path, Some(&backup_dir), None).unwrap();
         std::thread::sleep(std::time::Duration::from_secs(1));
-        BackupService::create_backup(&db_path, Some(&backup_dir)).unwrap();
+        BackupService::create_backup(&db_path, Some(&backup_dir), None).unwrap();
 
         // List backups
         let backups = BackupService::list_backups(Some(&backup_dir)).unwrap();
@@ -402,29 +652,6 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_cleanup_old_backups() {
-        let temp_dir = tempfile::tempdir().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-        let backup_dir = temp_dir.path().join("backups");
-
-        create_test_database(&db_path).unwrap();
-
-        // Create 5 backups with distinct timestamps
-        for _ in 0..5 {
-            BackupService::create_backup(&db_path, Some(&backup_dir)).unwrap();
-            std::thread::sleep(std::time::Duration::from_secs(1));
-        }
-
-        // Clean up, keeping only 2
-        let removed = BackupService::cleanup_old_backups(Some(&backup_dir), 2).unwrap();
-        assert_eq!(removed, 3);
-
-        // Verify only 2 remain
-        let backups = BackupService::list_backups(Some(&backup_dir)).unwrap();
-        assert_eq!(backups.len(), 2);
-    }
-
     #[test]
     fn test_verify_sqlite_file() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -459,7 +686,7 @@ mod tests {
         create_test_database(&db_path).unwrap();
 
         // Create backup
-        let backup_path = BackupService::create_backup(&db_path, Some(&backup_dir)).unwrap();
+        let backup_path = BackupService::create_backup(&db_path, Some(&backup_dir), None).unwrap();
         assert!(backup_path.exists());
 
         // Delete backup
@@ -468,29 +695,29 @@ mod tests {
     }
 
     #[test]
-    fn test_auto_backup_on_startup() {
+    fn test_backup_info_uses_filename_timestamp() {
         let temp_dir = tempfile::tempdir().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-
-        create_test_database(&db_path).unwrap();
-
-        // Set custom backup directory for testing
-        let backup_dir = temp_dir.path().join("backups");
-        fs::create_dir_all(&backup_dir).unwrap();
-
-        // Create multiple backups to test cleanup with distinct timestamps
-        for _ in 0..7 {
-            BackupService::create_backup(&db_path, Some(&backup_dir)).unwrap();
-            std::thread::sleep(std::time::Duration::from_secs(1));
-        }
-
-        // Verify all 7 backups exist
-        let backups = BackupService::list_backups(Some(&backup_dir)).unwrap();
-        assert_eq!(backups.len(), 7);
-
-        // Clean up to 3
-        BackupService::cleanup_old_backups(Some(&backup_dir), 3).unwrap();
-        let backups = BackupService::list_backups(Some(&backup_dir)).unwrap();
-        assert_eq!(backups.len(), 3);
+        let manual = temp_dir.path().join("calendar_backup_2024-02-29_073015.db");
+        let scheduled = temp_dir
+            .path()
+            .join("calendar_scheduled_backup_2024-03-01_080000.db");
+        create_test_database(&manual).unwrap();
+        create_test_database(&scheduled).unwrap();
+
+        let info = BackupInfo::from_path(manual.clone()).unwrap();
+        assert_eq!(
+            info.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            "2024-02-29 07:30:15"
+        );
+        let info = BackupInfo::from_path(scheduled.clone()).unwrap();
+        assert_eq!(
+            info.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            "2024-03-01 08:00:00"
+        );
+        assert!(BackupService::is_scheduled_backup(&scheduled));
+        assert!(!BackupService::is_scheduled_backup(&manual));
+        assert!(!BackupService::is_scheduled_backup(Path::new(
+            "calendar.db"
+        )));
     }
 }
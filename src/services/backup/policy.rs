@@ -0,0 +1,420 @@
+//! Scheduled backups with grandfather-father-son retention.
+//!
+//! The policy is built from the backup settings. [`BackupPolicy::run_if_due`]
+//! is called periodically from a background thread: it takes a backup when
+//! the newest one is older than the interval, re-opens it to verify it, and
+//! then prunes backups that no retention tier wants to keep. Only files named
+//! like the app's scheduled backups are ever pruned, so backups taken by hand
+//! are kept and a shared target folder is safe to use.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration as StdDuration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, Timelike};
+
+use super::{BackupInfo, BackupService};
+use crate::models::settings::Settings;
+use crate::services::secrets;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupInterval {
+    Off,
+    Hourly,
+    Daily,
+    Weekly,
+}
+
+impl BackupInterval {
+    pub const ALL: [BackupInterval; 4] = [
+        BackupInterval::Off,
+        BackupInterval::Hourly,
+        BackupInterval::Daily,
+        BackupInterval::Weekly,
+    ];
+
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "off" => Self::Off,
+            "hourly" => Self::Hourly,
+            "weekly" => Self::Weekly,
+            _ => Self::Daily,
+        }
+    }
+
+    pub fn as_setting(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Hourly => "hourly",
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Hourly => "Hourly",
+            Self::Daily => "Daily",
+            Self::Weekly => "Weekly",
+        }
+    }
+
+    pub fn period(self) -> Option<Duration> {
+        match self {
+            Self::Off => None,
+            Self::Hourly => Some(Duration::hours(1)),
+            Self::Daily => Some(Duration::days(1)),
+            Self::Weekly => Some(Duration::weeks(1)),
+        }
+    }
+}
+
+/// How many of the newest backups to keep per hour, day, ISO week and month.
+///
+/// Each tier keeps the newest backup of each of its most recent periods; a
+/// backup survives if any tier keeps it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub hourly: usize,
+    pub daily: usize,
+    pub weekly: usize,
+    pub monthly: usize,
+}
+
+impl RetentionPolicy {
+    /// Returns the backups to delete. `backups` must be sorted newest first.
+    pub fn backups_to_remove<'b>(&self, backups: &'b [BackupInfo]) -> Vec<&'b BackupInfo> {
+        let mut keep = vec![false; backups.len()];
+        if let Some(newest) = keep.first_mut() {
+            *newest = true;
+        }
+
+        type Bucket = fn(&DateTime<Local>) -> (i32, u32, u32);
+        let tiers: [(usize, Bucket); 4] = [
+            (self.hourly, |t| (t.year(), t.ordinal(), t.hour())),
+            (self.daily, |t| (t.year(), t.ordinal(), 0)),
+            (self.weekly, |t| {
+                let week = t.iso_week();
+                (week.year(), week.week(), 0)
+            }),
+            (self.monthly, |t| (t.year(), t.month(), 0)),
+        ];
+
+        for (limit, bucket) in tiers {
+            let mut seen = HashSet::new();
+            for (index, backup) in backups.iter().enumerate() {
+                if seen.len() >= limit {
+                    break;
+                }
+                if seen.insert(bucket(&backup.created_at)) {
+                    keep[index] = true;
+                }
+            }
+        }
+
+        backups
+            .iter()
+            .zip(keep)
+            .filter(|(_, keep)| !keep)
+            .map(|(backup, _)| backup)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupPolicy {
+    pub interval: BackupInterval,
+    /// Custom target folder; `None` uses [`BackupService::default_backup_dir`].
+    pub directory: Option<PathBuf>,
+    pub retention: RetentionPolicy,
+    /// Whether new backups are encrypted.
+    pub encrypt: bool,
+    /// Secret-store reference to the encryption passphrase.
+    pub passphrase_ref: Option<String>,
+}
+
+/// Outcome of a scheduled backup run.
+#[derive(Debug, Clone)]
+pub struct BackupRunReport {
+    pub backup_path: PathBuf,
+    pub removed: usize,
+}
+
+impl BackupPolicy {
+    pub fn from_settings(settings: &Settings) -> Self {
+        let count = |value: i64| value.max(0) as usize;
+        Self {
+            interval: BackupInterval::from_setting(&settings.backup_interval),
+            directory: settings
+                .backup_directory
+                .as_deref()
+                .map(str::trim)
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from),
+            retention: RetentionPolicy {
+                hourly: count(settings.backup_keep_hourly),
+                daily: count(settings.backup_keep_daily),
+                weekly: count(settings.backup_keep_weekly),
+                monthly: count(settings.backup_keep_monthly),
+            },
+            encrypt: settings.backup_encrypt,
+            passphrase_ref: settings.backup_passphrase_ref.clone(),
+        }
+    }
+
+    /// The folder backups are written to, created if missing.
+    pub fn backup_dir(&self) -> Result<PathBuf> {
+        match &self.directory {
+            Some(dir) => {
+                fs::create_dir_all(dir)
+                    .with_context(|| format!("Failed to create backup directory: {:?}", dir))?;
+                Ok(dir.clone())
+            }
+            None => BackupService::default_backup_dir(),
+        }
+    }
+
+    /// The scheduled backups in the target folder, newest first.
+    pub fn scheduled_backups(&self) -> Result<Vec<BackupInfo>> {
+        let dir = self.backup_dir()?;
+        Ok(BackupService::list_backups(Some(&dir))?
+            .into_iter()
+            .filter(|backup| BackupService::is_scheduled_backup(&backup.path))
+            .collect())
+    }
+
+    /// Time until the next backup is due, or `None` when scheduling is off.
+    pub fn due_in(
+        &self,
+        newest: Option<DateTime<Local>>,
+        now: DateTime<Local>,
+    ) -> Option<Duration> {
+        let period = self.interval.period()?;
+        Some(match newest {
+            Some(newest) => (newest + period - now).max(Duration::zero()),
+            None => Duration::zero(),
+        })
+    }
+
+    /// Takes, verifies and prunes a backup if one is due.
+    pub fn run_if_due(
+        &self,
+        db_path: &Path,
+        now: DateTime<Local>,
+    ) -> Result<Option<BackupRunReport>> {
+        let newest = self.scheduled_backups()?.first().map(|b| b.created_at);
+        match self.due_in(newest, now) {
+            Some(wait) if wait <= Duration::zero() => self.run(db_path).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// The encryption passphrase, or `None` when backups are not encrypted.
+    pub fn passphrase(&self) -> Result<Option<String>> {
+        if !self.encrypt {
+            return Ok(None);
+        }
+        let reference = self
+            .passphrase_ref
+            .as_deref()
+            .context("Backup encryption is on but no passphrase has been set")?;
+        secrets::resolve(reference)
+            .context("Failed to read the backup passphrase")
+            .map(Some)
+    }
+
+    /// Takes a single backup in the policy's folder, encrypted if configured.
+    pub fn create_backup(&self, db_path: &Path) -> Result<PathBuf> {
+        let dir = self.backup_dir()?;
+        let passphrase = self.passphrase()?;
+        BackupService::create_backup(db_path, Some(&dir), passphrase.as_deref())
+    }
+
+    /// Takes a scheduled backup now, verifies it and applies retention.
+    pub fn run(&self, db_path: &Path) -> Result<BackupRunReport> {
+        let dir = self.backup_dir()?;
+        let passphrase = self.passphrase()?;
+        let backup_path =
+            BackupService::create_scheduled_backup(db_path, Some(&dir), passphrase.as_deref())?;
+
+        // Re-open the finished file in its final location; a synced folder
+        // can still mangle it after the write.
+        if let Err(err) = BackupService::verify_backup(&backup_path, db_path, passphrase.as_deref())
+        {
+            fs::remove_file(&backup_path).ok();
+            return Err(err.context("New backup failed verification and was discarded"));
+        }
+
+        let backups = self.scheduled_backups()?;
+        let mut removed = 0;
+        for backup in self.retention.backups_to_remove(&backups) {
+            match fs::remove_file(&backup.path) {
+                Ok(()) => removed += 1,
+                Err(err) => log::warn!("Failed to prune backup {:?}: {}", backup.path, err),
+            }
+        }
+
+        Ok(BackupRunReport {
+            backup_path,
+            removed,
+        })
+    }
+
+    /// How long the background scheduler should wait before checking again.
+    pub fn next_check_in(&self) -> StdDuration {
+        const MAX_CHECK: StdDuration = StdDuration::from_secs(15 * 60);
+        let newest = self
+            .scheduled_backups()
+            .ok()
+            .and_then(|backups| backups.first().map(|b| b.created_at));
+        self.due_in(newest, Local::now())
+            .and_then(|wait| wait.to_std().ok())
+            .map(|wait| wait.clamp(StdDuration::from_secs(30), MAX_CHECK))
+            .unwrap_or(MAX_CHECK)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn backup_at(y: i32, m: u32, d: u32, h: u32) -> BackupInfo {
+        let created_at = Local.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap();
+        BackupInfo {
+            path: PathBuf::from(format!(
+                "calendar_scheduled_backup_{}.db",
+                created_at.format("%Y-%m-%d_%H%M%S")
+            )),
+            filename: String::new(),
+            created_at,
+            size_bytes: 0,
+            encrypted: false,
+        }
+    }
+
+    #[test]
+    fn test_retention_keeps_one_backup_per_period() {
+        // Hourly backups over three days, newest first.
+        let mut backups = Vec::new();
+        for day in (1..=3).rev() {
+            for hour in (0..24).rev() {
+                backups.push(backup_at(2026, 3, day, hour));
+            }
+        }
+        let retention = RetentionPolicy {
+            hourly: 6,
+            daily: 3,
+            weekly: 0,
+            monthly: 0,
+        };
+
+        let removed = retention.backups_to_remove(&backups);
+
+        // 6 newest hourly + the newest of March 2 and March 1.
+        assert_eq!(backups.len() - removed.len(), 8);
+        let kept: Vec<_> = backups
+            .iter()
+            .filter(|b| !removed.iter().any(|r| r.path == b.path))
+            .map(|b| b.created_at)
+            .collect();
+        assert!(kept.contains(&Local.with_ymd_and_hms(2026, 3, 2, 23, 0, 0).unwrap()));
+        assert!(kept.contains(&Local.with_ymd_and_hms(2026, 3, 1, 23, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_due_in_follows_interval() {
+        let policy = BackupPolicy::from_settings(&Settings::default());
+        let now = Local.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap();
+
+        assert_eq!(policy.due_in(None, now), Some(Duration::zero()));
+        assert_eq!(
+            policy.due_in(Some(now - Duration::hours(2)), now),
+            Some(Duration::hours(22))
+        );
+
+        let off = BackupPolicy {
+            interval: BackupInterval::Off,
+            ..policy
+        };
+        assert_eq!(off.due_in(None, now), None);
+    }
+
+    #[test]
+    fn test_run_prunes_only_scheduled_backups() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("calendar.db");
+        let backup_dir = temp_dir.path().join("synced");
+        rusqlite::Connection::open(&db_path)
+            .unwrap()
+            .execute("CREATE TABLE test (id INTEGER PRIMARY KEY)", [])
+            .unwrap();
+
+        fs::create_dir_all(&backup_dir).unwrap();
+        let unrelated = backup_dir.join("someone_elses.db");
+        fs::copy(&db_path, &unrelated).unwrap();
+        let manual = backup_dir.join("calendar_backup_2020-01-01_000000.db");
+        fs::copy(&db_path, &manual).unwrap();
+        let old = backup_dir.join("calendar_scheduled_backup_2020-01-01_000000.db");
+        fs::copy(&db_path, &old).unwrap();
+
+        let policy = BackupPolicy {
+            interval: BackupInterval::Daily,
+            directory: Some(backup_dir.clone()),
+            retention: RetentionPolicy {
+                hourly: 0,
+                daily: 1,
+                weekly: 0,
+                monthly: 0,
+            },
+            encrypt: false,
+            passphrase_ref: None,
+        };
+
+        let report = policy
+            .run_if_due(&db_path, Local::now())
+            .unwrap()
+            .expect("backup should be due");
+        assert!(report.backup_path.exists());
+        assert_eq!(report.removed, 1);
+        assert!(!old.exists());
+        assert!(manual.exists());
+        assert!(unrelated.exists());
+
+        // The fresh backup means nothing is due right away.
+        assert!(policy.run_if_due(&db_path, Local::now()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_run_encrypts_with_stored_passphrase() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("calendar.db");
+        rusqlite::Connection::open(&db_path)
+            .unwrap()
+            .execute("CREATE TABLE test (id INTEGER PRIMARY KEY)", [])
+            .unwrap();
+
+        let mut policy = BackupPolicy {
+            interval: BackupInterval::Daily,
+            directory: Some(temp_dir.path().join("backups")),
+            retention: RetentionPolicy {
+                hourly: 0,
+                daily: 7,
+                weekly: 0,
+                monthly: 0,
+            },
+            encrypt: true,
+            passphrase_ref: None,
+        };
+        assert!(policy.run(&db_path).is_err(), "no passphrase set");
+
+        policy.passphrase_ref = Some(secrets::put(None, "backup passphrase").unwrap());
+        let report = policy.run(&db_path).unwrap();
+
+        assert!(report.backup_path.to_string_lossy().ends_with(".db.enc"));
+        assert!(crate::services::backup::encryption::is_encrypted(&report.backup_path).unwrap());
+    }
+}
@@ -0,0 +1,217 @@
+//! Month and week layout shared by the PDF export and HTML publishing.
+//!
+//! Works in dates only: which days fill each row of a month grid, where a
+//! week starts, which events fall on a day and what colour they are shown
+//! in. Drawing is left to callers.
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone};
+use egui::Color32;
+
+use crate::models::category::Category;
+use crate::models::event::Event;
+use crate::ui_egui::theme::CalendarTheme;
+
+const DAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const FULL_DAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// The rows of a month grid. Cells before the 1st and after the last day
+/// are `None`; only as many weeks as the month touches are included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonthLayout {
+    pub first_day: NaiveDate,
+    pub last_day: NaiveDate,
+    pub weeks: Vec<[Option<NaiveDate>; 7]>,
+}
+
+impl MonthLayout {
+    /// The grid for the month containing `date`, with weeks starting on
+    /// `first_day_of_week` (0 = Sunday)
+    pub fn new(date: NaiveDate, first_day_of_week: u8) -> Self {
+        let first_day = date.with_day(1).unwrap_or(date);
+        let last_day =
+            first_day + Duration::days(days_in_month(date.year(), date.month()) as i64 - 1);
+
+        let mut weeks = Vec::new();
+        let mut week_start = week_start(first_day, first_day_of_week);
+        while week_start <= last_day {
+            let mut week = [None; 7];
+            for (offset, cell) in week.iter_mut().enumerate() {
+                let day = week_start + Duration::days(offset as i64);
+                if day >= first_day && day <= last_day {
+                    *cell = Some(day);
+                }
+            }
+            weeks.push(week);
+            week_start += Duration::days(7);
+        }
+
+        Self {
+            first_day,
+            last_day,
+            weeks,
+        }
+    }
+}
+
+/// The first day of the week containing `date`
+pub fn week_start(date: NaiveDate, first_day_of_week: u8) -> NaiveDate {
+    let weekday = date.weekday().num_days_from_sunday();
+    let days_to_subtract = (weekday as i64 - first_day_of_week as i64 + 7) % 7;
+    date - Duration::days(days_to_subtract)
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .map(|d| d.day())
+        .unwrap_or(30)
+}
+
+/// Short day names in column order
+pub fn day_names(first_day_of_week: u8) -> [&'static str; 7] {
+    std::array::from_fn(|i| DAY_NAMES[(first_day_of_week as usize + i) % 7])
+}
+
+/// Full day names in column order
+pub fn full_day_names(first_day_of_week: u8) -> [&'static str; 7] {
+    std::array::from_fn(|i| FULL_DAY_NAMES[(first_day_of_week as usize + i) % 7])
+}
+
+/// Local midnight at the start of `date`
+pub fn day_start(date: NaiveDate) -> DateTime<Local> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    // A DST change can skip midnight, in which case the day starts an hour on
+    [midnight, midnight + Duration::hours(1)]
+        .iter()
+        .find_map(|time| time.and_local_timezone(Local).earliest())
+        .unwrap_or_else(|| Local.from_utc_datetime(&midnight))
+}
+
+/// The span from the start of `first` to the end of `last`, for querying
+/// occurrences
+pub fn date_range(first: NaiveDate, last: NaiveDate) -> (DateTime<Local>, DateTime<Local>) {
+    (day_start(first), day_start(last + Duration::days(1)))
+}
+
+/// The events that take up part of `day`, all-day events first and then by
+/// start time. All-day ends are exclusive, as stored.
+pub fn events_on_day(events: &[Event], day: NaiveDate) -> Vec<&Event> {
+    let (start, end) = date_range(day, day);
+    let mut on_day: Vec<&Event> = events
+        .iter()
+        .filter(|event| {
+            if event.start == event.end {
+                event.start >= start && event.start < end
+            } else {
+                event.start < end && event.end > start
+            }
+        })
+        .collect();
+    on_day.sort_by_key(|event| (!event.all_day, event.start));
+    on_day
+}
+
+/// The colour `event` is drawn in: its own colour, else its category's,
+/// else `None` for the caller's default
+pub fn event_color(event: &Event, categories: &[Category]) -> Option<Color32> {
+    event
+        .color
+        .as_deref()
+        .and_then(|hex| CalendarTheme::hex_to_color(hex).ok())
+        .or_else(|| {
+            let name = event.category.as_deref()?;
+            categories
+                .iter()
+                .find(|category| category.name.eq_ignore_ascii_case(name))
+                .and_then(|category| CalendarTheme::hex_to_color(&category.color).ok())
+        })
+}
+
+/// Black or white, whichever reads better on `background`
+pub fn readable_text(background: Color32) -> Color32 {
+    let luminance = 0.299 * background.r() as f32
+        + 0.587 * background.g() as f32
+        + 0.114 * background.b() as f32;
+    if luminance > 150.0 {
+        Color32::BLACK
+    } else {
+        Color32::WHITE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{day_names, events_on_day, week_start, MonthLayout};
+    use crate::models::event::Event;
+    use chrono::{Local, NaiveDate, TimeZone};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_month_layout_rows() {
+        // February 2026 starts on a Sunday and has exactly four weeks
+        let february = MonthLayout::new(date(2026, 2, 14), 0);
+        assert_eq!(february.weeks.len(), 4);
+        assert_eq!(february.weeks[0][0], Some(date(2026, 2, 1)));
+        assert_eq!(february.last_day, date(2026, 2, 28));
+
+        // Weeks starting Monday push the 1st to the last column
+        let monday_first = MonthLayout::new(date(2026, 2, 1), 1);
+        assert_eq!(monday_first.weeks.len(), 5);
+        assert_eq!(monday_first.weeks[0][6], Some(date(2026, 2, 1)));
+        assert_eq!(monday_first.weeks[0][5], None);
+        assert_eq!(monday_first.weeks[4][5], Some(date(2026, 2, 28)));
+        assert_eq!(monday_first.weeks[4][6], None);
+    }
+
+    #[test]
+    fn test_week_start_and_day_names() {
+        assert_eq!(week_start(date(2026, 3, 4), 1), date(2026, 3, 2));
+        assert_eq!(week_start(date(2026, 3, 4), 0), date(2026, 3, 1));
+        assert_eq!(day_names(1)[0], "Mon");
+        assert_eq!(day_names(1)[6], "Sun");
+    }
+
+    #[test]
+    fn test_events_on_day_uses_exclusive_all_day_end() {
+        let leave = Event::builder()
+            .title("Leave")
+            .start(Local.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap())
+            .end(Local.with_ymd_and_hms(2026, 3, 4, 0, 0, 0).unwrap())
+            .all_day(true)
+            .build()
+            .unwrap();
+        let overnight = Event::builder()
+            .title("Night shift")
+            .start(Local.with_ymd_and_hms(2026, 3, 3, 22, 0, 0).unwrap())
+            .end(Local.with_ymd_and_hms(2026, 3, 4, 6, 0, 0).unwrap())
+            .build()
+            .unwrap();
+        let events = vec![overnight, leave];
+
+        let titles = |day| -> Vec<String> {
+            events_on_day(&events, day)
+                .iter()
+                .map(|e| e.title.clone())
+                .collect()
+        };
+        assert_eq!(titles(date(2026, 3, 2)), ["Leave"]);
+        assert_eq!(titles(date(2026, 3, 3)), ["Leave", "Night shift"]);
+        assert_eq!(titles(date(2026, 3, 4)), ["Night shift"]);
+    }
+}
@@ -0,0 +1,308 @@
+//! Redacted diagnostics bundles for calendar sync bug reports.
+//!
+//! A bundle is a zip of JSON files describing one source: its settings,
+//! recent sync runs, outbound queue state and open conflicts. Every URL,
+//! error message and token passes through [`super::sanitizer`] first, and
+//! event payloads and snapshots are left out entirely.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Local;
+use rusqlite::Connection;
+use serde_json::{json, Value};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::models::calendar_source::CalendarSource;
+use crate::services::outbound_sync::OutboundSyncService;
+use crate::services::sync_conflict::SyncConflictService;
+
+use super::sanitizer;
+use super::{CalendarSourceService, SYNC_RUN_HISTORY_LIMIT};
+
+/// Number of failed outbound operations and conflicts included in a bundle.
+const DIAGNOSTICS_ITEM_LIMIT: i64 = 100;
+
+pub struct SyncDiagnosticsExporter<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SyncDiagnosticsExporter<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Builds the bundle contents as `(file name, pretty JSON)` pairs.
+    pub fn build_entries(&self, source_id: i64) -> Result<Vec<(String, String)>> {
+        let source = CalendarSourceService::new(self.conn)
+            .get_by_id(source_id)?
+            .ok_or_else(|| anyhow!("Calendar source with id {} not found", source_id))?;
+        let sanitize = |message: &str| redact_error_message(&source, message);
+
+        let manifest = json!({
+            "app_version": env!("CARGO_PKG_VERSION"),
+            "generated_at": Local::now().to_rfc3339(),
+            "source_id": source_id,
+        });
+
+        let source_json = json!({
+            "id": source.id,
+            "name": source.name,
+            "source_type": source.source_type,
+            "ics_url": sanitizer::redact_url(&source.ics_url),
+            "enabled": source.enabled,
+            "poll_interval_minutes": source.poll_interval_minutes,
+            "sync_past_days": source.sync_past_days,
+            "sync_future_days": source.sync_future_days,
+            "sync_capability": source.sync_capability,
+            "has_api_sync_token": source.api_sync_token.is_some(),
+            "last_sync_at": source.last_sync_at,
+            "last_sync_status": source.last_sync_status,
+            "last_error": source.last_error.as_deref().map(sanitize),
+            "last_push_at": source.last_push_at,
+            "google_account_id": source.google_account_id,
+        });
+
+        let runs: Vec<Value> = CalendarSourceService::new(self.conn)
+            .list_recent_sync_runs(source_id, SYNC_RUN_HISTORY_LIMIT)?
+            .into_iter()
+            .map(|run| {
+                json!({
+                    "started_at": run.started_at,
+                    "finished_at": run.finished_at,
+                    "status": run.status,
+                    "duration_ms": run.duration_ms,
+                    "created": run.created_count,
+                    "updated": run.updated_count,
+                    "deleted": run.deleted_count,
+                    "unchanged": run.unchanged_count,
+                    "skipped": run.skipped_count,
+                    "errors": run.error_count,
+                    "error_message": run.error_message.as_deref().map(sanitize),
+                })
+            })
+            .collect();
+
+        let outbound_service = OutboundSyncService::new(self.conn);
+        let stats = outbound_service.queue_stats_for_source(source_id)?;
+        let failed: Vec<Value> = outbound_service
+            .list_failed_for_source(source_id, DIAGNOSTICS_ITEM_LIMIT)?
+            .into_iter()
+            .map(|operation| {
+                json!({
+                    "id": operation.id,
+                    "local_event_id": operation.local_event_id,
+                    "operation_type": operation.operation_type,
+                    "status": operation.status,
+                    "attempt_count": operation.attempt_count,
+                    "next_retry_at": operation.next_retry_at,
+                    "last_error": operation.last_error.as_deref().map(sanitize),
+                    "created_at": operation.created_at,
+                    "updated_at": operation.updated_at,
+                })
+            })
+            .collect();
+        let outbound = json!({
+            "stats": {
+                "pending": stats.pending,
+                "processing": stats.processing,
+                "failed": stats.failed,
+                "completed": stats.completed,
+            },
+            "failed_operations": failed,
+        });
+
+        let conflicts: Vec<Value> = SyncConflictService::new(self.conn)
+            .list_open_for_source(source_id, DIAGNOSTICS_ITEM_LIMIT)?
+            .into_iter()
+            .map(|conflict| {
+                json!({
+                    "id": conflict.id,
+                    "local_event_id": conflict.local_event_id,
+                    "outbound_operation_id": conflict.outbound_operation_id,
+                    "local_operation_type": conflict.local_operation_type,
+                    "remote_change_type": conflict.remote_change_type,
+                    "reason": conflict.reason,
+                    "resolution": conflict.resolution,
+                    "status": conflict.status,
+                    "has_base_snapshot": conflict.base_snapshot.is_some(),
+                    "created_at": conflict.created_at,
+                    "updated_at": conflict.updated_at,
+                })
+            })
+            .collect();
+
+        [
+            ("manifest.json", manifest),
+            ("source.json", source_json),
+            ("sync_runs.json", Value::Array(runs)),
+            ("outbound_queue.json", outbound),
+            ("conflicts.json", Value::Array(conflicts)),
+        ]
+        .into_iter()
+        .map(|(name, value)| {
+            serde_json::to_string_pretty(&value)
+                .map(|contents| (name.to_string(), contents))
+                .context("Failed to serialize diagnostics entry")
+        })
+        .collect()
+    }
+
+    /// Writes the bundle for a source to a zip file at `path`.
+    pub fn export_zip(&self, source_id: i64, path: &Path) -> Result<()> {
+        let entries = self.build_entries(source_id)?;
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create diagnostics file {}", path.display()))?;
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default();
+
+        for (name, contents) in entries {
+            zip.start_file(name.as_str(), options)
+                .context("Failed to add diagnostics entry")?;
+            zip.write_all(contents.as_bytes())
+                .context("Failed to write diagnostics entry")?;
+        }
+
+        zip.finish().context("Failed to finish diagnostics zip")?;
+        Ok(())
+    }
+}
+
+/// `message` with the source's URL, other URLs and bearer tokens redacted,
+/// for showing sync errors in the UI.
+pub fn redact_error_message(source: &CalendarSource, message: &str) -> String {
+    sanitizer::sanitize_error_message(message, &source.ics_url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::calendar_source::{GOOGLE_ICS_SOURCE_TYPE, SYNC_CAPABILITY_READ_ONLY};
+    use crate::services::calendar_sync::SyncRunDiagnostics;
+    use crate::services::database::Database;
+
+    const SECRET_URL: &str =
+        "https://calendar.google.com/calendar/ical/user%40gmail.com/private-secret/basic.ics";
+
+    fn create_source(conn: &Connection) -> i64 {
+        let service = CalendarSourceService::new(conn);
+        let source = service
+            .create(CalendarSource {
+                id: None,
+                name: "Work".to_string(),
+                source_type: GOOGLE_ICS_SOURCE_TYPE.to_string(),
+                ics_url: SECRET_URL.to_string(),
+                enabled: true,
+                poll_interval_minutes: 15,
+                sync_past_days: 90,
+                sync_future_days: 365,
+                last_sync_at: None,
+                last_sync_status: None,
+                last_error: None,
+                sync_capability: SYNC_CAPABILITY_READ_ONLY.to_string(),
+                api_sync_token: Some("sync-token-secret".to_string()),
+                last_push_at: None,
+                google_account_id: None,
+                remote_calendar_id: None,
+                color: None,
+            })
+            .unwrap();
+        let source_id = source.id.unwrap();
+
+        service
+            .record_sync_run(&SyncRunDiagnostics {
+                source_id,
+                started_at: "2026-03-06T10:00:00+10:00".to_string(),
+                finished_at: "2026-03-06T10:00:01+10:00".to_string(),
+                status: "error".to_string(),
+                duration_ms: 1000,
+                created_count: 0,
+                updated_count: 0,
+                deleted_count: 0,
+                unchanged_count: 0,
+                skipped_count: 0,
+                error_count: 1,
+                error_message: Some(format!(
+                    "fetch {} failed: Authorization: Bearer abc.def",
+                    SECRET_URL
+                )),
+            })
+            .unwrap();
+
+        source_id
+    }
+
+    #[test]
+    fn test_build_entries_redacts_urls_and_tokens() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let source_id = create_source(conn);
+
+        let entries = SyncDiagnosticsExporter::new(conn)
+            .build_entries(source_id)
+            .unwrap();
+        let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "manifest.json",
+                "source.json",
+                "sync_runs.json",
+                "outbound_queue.json",
+                "conflicts.json"
+            ]
+        );
+
+        for (name, contents) in &entries {
+            assert!(!contents.contains("private-secret"), "{} leaks URL", name);
+            assert!(!contents.contains("abc.def"), "{} leaks bearer token", name);
+            assert!(
+                !contents.contains("sync-token-secret"),
+                "{} leaks sync token",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_redact_error_message_hides_url_and_token() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let source_id = create_source(conn);
+        let source = CalendarSourceService::new(conn)
+            .get_by_id(source_id)
+            .unwrap()
+            .unwrap();
+
+        let redacted = redact_error_message(
+            &source,
+            &format!("fetch {} failed: Authorization: Bearer abc.def", SECRET_URL),
+        );
+        assert!(!redacted.contains("private-secret"));
+        assert!(!redacted.contains("abc.def"));
+        assert!(redacted.starts_with("fetch "));
+    }
+
+    #[test]
+    fn test_export_zip_writes_all_entries() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let source_id = create_source(conn);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("diagnostics.zip");
+
+        SyncDiagnosticsExporter::new(conn)
+            .export_zip(source_id, &path)
+            .unwrap();
+
+        let archive = zip::ZipArchive::new(File::open(&path).unwrap()).unwrap();
+        assert_eq!(archive.len(), 5);
+        assert!(archive.file_names().any(|name| name == "sync_runs.json"));
+    }
+}
@@ -0,0 +1,241 @@
+//! Calendars discovered through a connected Google account's calendar list.
+//!
+//! Subscribing creates a `google_api` source that syncs through the Calendar
+//! API with the account's tokens, so no secret ICS URL is needed. Its sync
+//! capability follows the access role Google reports for the calendar.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use rusqlite::Connection;
+
+use crate::models::calendar_source::{CalendarSource, GOOGLE_API_SOURCE_TYPE};
+use crate::services::google_account::GoogleAccountService;
+
+use super::google_api::GoogleCalendarApiClient;
+pub use super::google_api::GoogleCalendarListEntry;
+use super::CalendarSourceService;
+
+pub struct GoogleCalendarDiscovery<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> GoogleCalendarDiscovery<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Fetches the account's calendar list and refreshes the colour and
+    /// capability of calendars that are already subscribed.
+    pub fn fetch_calendar_list(&self, account_id: i64) -> Result<Vec<GoogleCalendarListEntry>> {
+        let access_token = GoogleAccountService::new(self.conn)?.valid_access_token(account_id)?;
+        let entries = GoogleCalendarApiClient::new(access_token)?.fetch_calendar_list()?;
+        self.apply_calendar_list(account_id, &entries)?;
+        Ok(entries)
+    }
+
+    /// Source ids of the account's subscribed calendars, keyed by calendar ID.
+    pub fn subscribed_sources(&self, account_id: i64) -> Result<HashMap<String, i64>> {
+        Ok(CalendarSourceService::new(self.conn)
+            .list_all()?
+            .into_iter()
+            .filter(|source| source.google_account_id == Some(account_id))
+            .filter_map(|source| Some((source.remote_calendar_id?, source.id?)))
+            .collect())
+    }
+
+    /// Creates a source for a calendar from the list, or returns the existing one.
+    pub fn subscribe(
+        &self,
+        account_id: i64,
+        entry: &GoogleCalendarListEntry,
+    ) -> Result<CalendarSource> {
+        let source_service = CalendarSourceService::new(self.conn);
+        if let Some(source_id) = self.subscribed_sources(account_id)?.get(&entry.id) {
+            if let Some(existing) = source_service.get_by_id(*source_id)? {
+                return Ok(existing);
+            }
+        }
+
+        let name = self.unique_source_name(account_id, entry.display_name())?;
+        source_service.create(CalendarSource {
+            name,
+            source_type: GOOGLE_API_SOURCE_TYPE.to_string(),
+            ics_url: String::new(),
+            sync_capability: CalendarSource::sync_capability_for_access_role(&entry.access_role)
+                .to_string(),
+            google_account_id: Some(account_id),
+            remote_calendar_id: Some(entry.id.clone()),
+            color: entry.background_color.clone(),
+            ..CalendarSource::default()
+        })
+    }
+
+    /// Updates colour and sync capability of subscribed calendars to match the list.
+    ///
+    /// Returns the number of sources that changed.
+    pub fn apply_calendar_list(
+        &self,
+        account_id: i64,
+        entries: &[GoogleCalendarListEntry],
+    ) -> Result<usize> {
+        let source_service = CalendarSourceService::new(self.conn);
+        let by_id: HashMap<&str, &GoogleCalendarListEntry> = entries
+            .iter()
+            .map(|entry| (entry.id.as_str(), entry))
+            .collect();
+
+        let mut changed = 0;
+        for mut source in source_service.list_all()? {
+            if source.google_account_id != Some(account_id)
+                || source.source_type != GOOGLE_API_SOURCE_TYPE
+            {
+                continue;
+            }
+            let Some(entry) = source
+                .remote_calendar_id
+                .as_deref()
+                .and_then(|id| by_id.get(id))
+            else {
+                continue;
+            };
+
+            let capability = CalendarSource::sync_capability_for_access_role(&entry.access_role);
+            if source.sync_capability != capability || source.color != entry.background_color {
+                source.sync_capability = capability.to_string();
+                source.color = entry.background_color.clone();
+                source_service.update(&source)?;
+                changed += 1;
+            }
+        }
+
+        Ok(changed)
+    }
+
+    /// Source names are unique, so calendars with the same title in different
+    /// accounts get the account email appended, then a counter.
+    fn unique_source_name(&self, account_id: i64, base: &str) -> Result<String> {
+        let taken: Vec<String> = CalendarSourceService::new(self.conn)
+            .list_all()?
+            .into_iter()
+            .map(|source| source.name.to_lowercase())
+            .collect();
+        let is_free = |name: &str| !taken.contains(&name.to_lowercase());
+
+        let base = base.trim();
+        if is_free(base) {
+            return Ok(base.to_string());
+        }
+
+        let email = GoogleAccountService::new(self.conn)?
+            .get_account(account_id)?
+            .and_then(|account| account.account_email)
+            .unwrap_or_else(|| format!("account {}", account_id));
+        let qualified = format!("{} ({})", base, email);
+        if is_free(&qualified) {
+            return Ok(qualified);
+        }
+
+        Ok((2..)
+            .map(|n| format!("{} {}", qualified, n))
+            .find(|name| is_free(name))
+            .expect("unbounded counter always finds a free name"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::calendar_source::{SYNC_CAPABILITY_READ_ONLY, SYNC_CAPABILITY_READ_WRITE};
+    use crate::services::database::Database;
+
+    fn entry(id: &str, summary: &str, role: &str) -> GoogleCalendarListEntry {
+        GoogleCalendarListEntry {
+            id: id.to_string(),
+            summary: Some(summary.to_string()),
+            summary_override: None,
+            background_color: Some("#9fe1e7".to_string()),
+            access_role: role.to_string(),
+            primary: false,
+        }
+    }
+
+    fn insert_account(conn: &Connection, email: &str) -> i64 {
+        conn.execute(
+            "INSERT INTO google_accounts (account_email, refresh_token) VALUES (?1, 'r')",
+            [email],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    #[test]
+    fn test_subscribe_sets_capability_from_access_role() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let account_id = insert_account(conn, "me@example.com");
+        let discovery = GoogleCalendarDiscovery::new(conn);
+
+        let owned = discovery
+            .subscribe(account_id, &entry("me@example.com", "Personal", "owner"))
+            .unwrap();
+        let shared = discovery
+            .subscribe(account_id, &entry("holidays", "Holidays", "reader"))
+            .unwrap();
+
+        assert_eq!(owned.sync_capability, SYNC_CAPABILITY_READ_WRITE);
+        assert_eq!(shared.sync_capability, SYNC_CAPABILITY_READ_ONLY);
+        assert_eq!(shared.source_type, GOOGLE_API_SOURCE_TYPE);
+        assert_eq!(shared.color.as_deref(), Some("#9fe1e7"));
+
+        // Subscribing twice returns the existing source.
+        let again = discovery
+            .subscribe(account_id, &entry("holidays", "Holidays", "reader"))
+            .unwrap();
+        assert_eq!(again.id, shared.id);
+        assert_eq!(discovery.subscribed_sources(account_id).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_subscribe_disambiguates_names_across_accounts() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let home = insert_account(conn, "home@example.com");
+        let work = insert_account(conn, "work@example.com");
+        let discovery = GoogleCalendarDiscovery::new(conn);
+
+        discovery
+            .subscribe(home, &entry("a", "Family", "owner"))
+            .unwrap();
+        let second = discovery
+            .subscribe(work, &entry("b", "Family", "owner"))
+            .unwrap();
+
+        assert_eq!(second.name, "Family (work@example.com)");
+    }
+
+    #[test]
+    fn test_apply_calendar_list_follows_role_changes() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let account_id = insert_account(conn, "me@example.com");
+        let discovery = GoogleCalendarDiscovery::new(conn);
+        let source = discovery
+            .subscribe(account_id, &entry("team", "Team", "writer"))
+            .unwrap();
+
+        let changed = discovery
+            .apply_calendar_list(account_id, &[entry("team", "Team", "reader")])
+            .unwrap();
+
+        assert_eq!(changed, 1);
+        let updated = CalendarSourceService::new(conn)
+            .get_by_id(source.id.unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.sync_capability, SYNC_CAPABILITY_READ_ONLY);
+    }
+}
@@ -1,10 +1,11 @@
 #![allow(dead_code)]
 
 use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
 use std::time::Instant;
 
 use anyhow::{anyhow, Context, Result};
-use chrono::{Duration, Local};
+use chrono::{DateTime, Duration, Local};
 use rusqlite::Connection;
 
 use crate::models::event::Event;
@@ -13,10 +14,11 @@ use crate::models::outbound_sync_operation::{
     OutboundSyncOperation, OUTBOUND_OPERATION_CREATE, OUTBOUND_OPERATION_DELETE,
 };
 use crate::models::sync_conflict::{
-    SyncConflict, SYNC_CONFLICT_REASON_LOCAL_CREATE_PENDING,
+    ConflictEventSnapshot, SyncConflict, SYNC_CONFLICT_REASON_LOCAL_CREATE_PENDING,
     SYNC_CONFLICT_REASON_LOCAL_DELETE_PENDING, SYNC_CONFLICT_REASON_LOCAL_UPDATE_PENDING,
     SYNC_CONFLICT_RESOLUTION_REMOTE_WINS,
 };
+use crate::services::event::history::ChangeOrigin;
 use crate::services::event::EventService;
 use crate::services::google_account::GoogleAccountService;
 use crate::services::icalendar::import::{self, ImportedIcsEvent};
@@ -31,7 +33,7 @@ use super::google_api::{
 use super::mapping::EventSyncMapService;
 use super::sanitizer;
 use super::{CalendarSourceService, SyncRunDiagnostics};
-use crate::models::calendar_source::CalendarSource;
+use crate::models::calendar_source::{CalendarSource, SYNC_STATUS_NEEDS_CONFIRMATION};
 use thiserror::Error;
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -45,10 +47,96 @@ pub struct SyncRunResult {
     pub skipped_missing_uid: usize,
     pub skipped_duplicate_uid: usize,
     pub skipped_filtered: usize,
+    /// Changes left out because they were not approved in a preview
+    pub skipped_unapproved: usize,
     pub error_count: usize,
     pub started_at: Option<String>,
     pub finished_at: Option<String>,
     pub duration_ms: u128,
+    /// Individual changes found by a preview; empty after an applied sync.
+    pub changes: Vec<SyncPreviewChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncChangeKind {
+    Create,
+    Update,
+    Delete,
+}
+
+/// A single would-be change reported by a sync preview.
+///
+/// `id` identifies the change when applying a selected subset via
+/// [`SyncApplyOptions::approved`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncPreviewChange {
+    pub id: String,
+    pub external_uid: String,
+    pub kind: SyncChangeKind,
+    pub title: String,
+    pub start: Option<DateTime<Local>>,
+}
+
+impl SyncPreviewChange {
+    fn new(kind: SyncChangeKind, external_uid: &str, event: Option<&Event>) -> Self {
+        Self {
+            id: Self::change_id(kind, external_uid, event),
+            external_uid: external_uid.to_string(),
+            kind,
+            title: event
+                .map(|event| event.title.clone())
+                .unwrap_or_else(|| external_uid.to_string()),
+            start: event.map(|event| event.start),
+        }
+    }
+
+    /// Identifies a change by its kind, UID and, for creates and updates, the
+    /// incoming content, so a remote edit made after the preview is not
+    /// applied under an earlier approval.
+    fn change_id(kind: SyncChangeKind, external_uid: &str, event: Option<&Event>) -> String {
+        match (kind, event) {
+            (SyncChangeKind::Delete, _) | (_, None) => format!("{:?}:{}", kind, external_uid),
+            (_, Some(event)) => {
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                ConflictEventSnapshot::from(event)
+                    .to_json()
+                    .unwrap_or_default()
+                    .hash(&mut hasher);
+                format!("{:?}:{}:{:016x}", kind, external_uid, hasher.finish())
+            }
+        }
+    }
+}
+
+/// Options for an applied sync run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncApplyOptions {
+    /// Ids of the previewed changes the user selected, or `None` to apply
+    /// everything. Any other change is left as it is and offered again later.
+    pub approved: Option<HashSet<String>>,
+    /// Apply deletions even when they exceed the mass-deletion threshold.
+    pub confirm_mass_deletion: bool,
+}
+
+impl SyncApplyOptions {
+    fn approves(&self, kind: SyncChangeKind, external_uid: &str, event: Option<&Event>) -> bool {
+        match &self.approved {
+            Some(approved) => {
+                approved.contains(&SyncPreviewChange::change_id(kind, external_uid, event))
+            }
+            None => true,
+        }
+    }
+}
+
+/// Raised instead of applying a run that would delete too many events at once.
+#[derive(Debug, Error)]
+#[error(
+    "Sync paused: {would_delete} of {mapped} synced event(s) would be deleted; review and confirm to continue"
+)]
+pub struct MassDeletionPaused {
+    pub would_delete: usize,
+    pub mapped: usize,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -57,6 +145,9 @@ pub struct SyncBatchResult {
     pub failed_sources: Vec<(i64, String)>,
 }
 
+/// Sources with fewer mapped events than this are only guarded by the count threshold.
+const MASS_DELETE_MIN_MAPPED_FOR_PERCENT: usize = 10;
+
 pub struct CalendarSyncEngine<'a> {
     conn: &'a Connection,
     fetcher: IcsFetcher,
@@ -79,6 +170,15 @@ impl<'a> CalendarSyncEngine<'a> {
     }
 
     pub fn sync_source(&self, source_id: i64) -> Result<SyncRunResult> {
+        self.sync_source_selected(source_id, &SyncApplyOptions::default())
+    }
+
+    /// Syncs a source with user choices from a preview or a paused run.
+    pub fn sync_source_selected(
+        &self,
+        source_id: i64,
+        options: &SyncApplyOptions,
+    ) -> Result<SyncRunResult> {
         let source_service = CalendarSourceService::new(self.conn);
         let source = source_service
             .get_by_id(source_id)?
@@ -87,14 +187,11 @@ impl<'a> CalendarSyncEngine<'a> {
         let started_at = chrono::Local::now();
         let timer = Instant::now();
 
-        let result = if source.sync_capability
-            == crate::models::calendar_source::SYNC_CAPABILITY_READ_WRITE
-        {
-            self.sync_source_from_google_api(source_id)
+        let result = if source.uses_google_api() {
+            self.sync_source_from_google_api(source_id, options)
         } else {
-            self.fetcher
-                .fetch_ics(&source.ics_url)
-                .and_then(|ics| self.sync_source_from_ics(source_id, &ics))
+            self.fetch_source_ics(&source)
+                .and_then(|ics| self.sync_source_from_ics_selected(source_id, &ics, options))
         };
 
         match result {
@@ -117,7 +214,8 @@ impl<'a> CalendarSyncEngine<'a> {
                     unchanged_count: success.unchanged as i64,
                     skipped_count: (success.skipped_missing_uid
                         + success.skipped_duplicate_uid
-                        + success.skipped_filtered) as i64,
+                        + success.skipped_filtered
+                        + success.skipped_unapproved) as i64,
                     error_count: 0,
                     error_message: None,
                 };
@@ -164,6 +262,15 @@ impl<'a> CalendarSyncEngine<'a> {
     }
 
     pub fn sync_source_from_ics(&self, source_id: i64, ics_content: &str) -> Result<SyncRunResult> {
+        self.sync_source_from_ics_selected(source_id, ics_content, &SyncApplyOptions::default())
+    }
+
+    fn sync_source_from_ics_selected(
+        &self,
+        source_id: i64,
+        ics_content: &str,
+        options: &SyncApplyOptions,
+    ) -> Result<SyncRunResult> {
         let source_service = CalendarSourceService::new(self.conn);
         let source = source_service
             .get_by_id(source_id)?
@@ -178,7 +285,7 @@ impl<'a> CalendarSyncEngine<'a> {
         }
 
         let (filtered, skipped_filtered) = Self::filter_imported_by_window(&source, imported);
-        let mut result = self.apply_imported(source_id, filtered)?;
+        let mut result = self.apply_imported(source_id, filtered, options)?;
         result.skipped_filtered = skipped_filtered;
         Ok(result)
     }
@@ -189,15 +296,25 @@ impl<'a> CalendarSyncEngine<'a> {
             .get_by_id(source_id)?
             .ok_or_else(|| anyhow!("Calendar source with id {} not found", source_id))?;
 
-        if source.sync_capability == crate::models::calendar_source::SYNC_CAPABILITY_READ_WRITE {
+        if source.uses_google_api() {
             self.preview_source_from_google_api(source_id)
         } else {
-            self.fetcher
-                .fetch_ics(&source.ics_url)
+            self.fetch_source_ics(&source)
                 .and_then(|ics| self.preview_source_from_ics(source_id, &ics))
         }
     }
 
+    /// An empty URL on an ICS source means its secret could not be read
+    fn fetch_source_ics(&self, source: &CalendarSource) -> Result<String> {
+        if source.ics_url.is_empty() {
+            return Err(anyhow!(
+                "The ICS URL of '{}' could not be read from the secret store; re-enter it in the source settings",
+                source.name
+            ));
+        }
+        self.fetcher.fetch_ics(&source.ics_url)
+    }
+
     pub fn preview_source_from_ics(
         &self,
         source_id: i64,
@@ -246,9 +363,45 @@ impl<'a> CalendarSyncEngine<'a> {
         source_id: i64,
         payload: GoogleEventsSyncPayload,
     ) -> Result<SyncRunResult> {
-        let mut result = self.reconcile_google_payload(source_id, &payload, true)?;
-        let source_service = CalendarSourceService::new(self.conn);
-        source_service.set_api_sync_token(source_id, payload.next_sync_token.as_deref())?;
+        self.sync_source_from_google_payload_selected(
+            source_id,
+            payload,
+            &SyncApplyOptions::default(),
+        )
+    }
+
+    fn sync_source_from_google_payload_selected(
+        &self,
+        source_id: i64,
+        payload: GoogleEventsSyncPayload,
+        options: &SyncApplyOptions,
+    ) -> Result<SyncRunResult> {
+        if !options.confirm_mass_deletion {
+            let map_service = EventSyncMapService::new(self.conn);
+            let mut would_delete = 0usize;
+            for remote in payload.items.iter().filter(|remote| remote.is_cancelled()) {
+                if options.approves(SyncChangeKind::Delete, &remote.external_uid, None)
+                    && map_service
+                        .get_by_source_and_uid(source_id, &remote.external_uid)?
+                        .is_some()
+                {
+                    would_delete += 1;
+                }
+            }
+            let mapped = map_service.list_by_source_id(source_id)?.len();
+            self.check_mass_deletion(would_delete, mapped)?;
+        }
+
+        let mut result = self.reconcile_google_payload(source_id, &payload, true, options)?;
+        // When changes were skipped, drop the token so the next run does a
+        // full fetch that offers them again, instead of keeping a token that
+        // only grows staler with every partial apply.
+        let next_sync_token = if result.skipped_unapproved == 0 {
+            payload.next_sync_token.as_deref()
+        } else {
+            None
+        };
+        CalendarSourceService::new(self.conn).set_api_sync_token(source_id, next_sync_token)?;
         result.source_id = source_id;
         Ok(result)
     }
@@ -258,13 +411,14 @@ impl<'a> CalendarSyncEngine<'a> {
         source_id: i64,
         payload: GoogleEventsSyncPayload,
     ) -> Result<SyncRunResult> {
-        self.reconcile_google_payload(source_id, &payload, false)
+        self.reconcile_google_payload(source_id, &payload, false, &SyncApplyOptions::default())
     }
 
     fn apply_imported(
         &self,
         source_id: i64,
         imported_events: Vec<ImportedIcsEvent>,
+        options: &SyncApplyOptions,
     ) -> Result<SyncRunResult> {
         let mut result = SyncRunResult {
             source_id,
@@ -277,7 +431,26 @@ impl<'a> CalendarSyncEngine<'a> {
             .ok_or_else(|| anyhow!("Calendar source with id {} not found", source_id))?;
 
         let map_service = EventSyncMapService::new(self.conn);
-        let event_service = EventService::new(self.conn);
+        let event_service = EventService::new(self.conn).with_origin(ChangeOrigin::Sync);
+
+        if !options.confirm_mass_deletion {
+            let incoming_uids: HashSet<String> = imported_events
+                .iter()
+                .filter_map(Self::effective_uid)
+                .collect();
+            let mappings = map_service.list_by_source_id(source_id)?;
+            // Deletions staged by an earlier run were already confirmed or
+            // below the threshold; only newly missing events count.
+            let would_delete = mappings
+                .iter()
+                .filter(|mapping| {
+                    mapping.first_missing_at.is_none()
+                        && !incoming_uids.contains(&mapping.external_uid)
+                        && options.approves(SyncChangeKind::Delete, &mapping.external_uid, None)
+                })
+                .count();
+            self.check_mass_deletion(would_delete, mappings.len())?;
+        }
 
         let mut seen_uids: HashSet<String> = HashSet::new();
 
@@ -298,6 +471,14 @@ impl<'a> CalendarSyncEngine<'a> {
                         if Self::is_effectively_unchanged(&existing_event, &imported.event) {
                             result.unchanged += 1;
                         } else {
+                            if !options.approves(
+                                SyncChangeKind::Update,
+                                &uid,
+                                Some(&imported.event),
+                            ) {
+                                result.skipped_unapproved += 1;
+                                continue;
+                            }
                             let mut updated_event = imported.event.clone();
                             updated_event.id = existing_event.id;
                             updated_event.created_at = existing_event.created_at;
@@ -305,6 +486,10 @@ impl<'a> CalendarSyncEngine<'a> {
                             result.updated += 1;
                         }
                     } else {
+                        if !options.approves(SyncChangeKind::Create, &uid, Some(&imported.event)) {
+                            result.skipped_unapproved += 1;
+                            continue;
+                        }
                         let created_event = event_service
                             .create(imported.event.clone())
                             .context("Failed to create event for stale mapping")?;
@@ -336,6 +521,10 @@ impl<'a> CalendarSyncEngine<'a> {
                     map_service.touch_last_seen(source_id, &uid)?;
                 }
                 None => {
+                    if !options.approves(SyncChangeKind::Create, &uid, Some(&imported.event)) {
+                        result.skipped_unapproved += 1;
+                        continue;
+                    }
                     let created_event = event_service
                         .create(imported.event.clone())
                         .context("Failed to create imported event")?;
@@ -366,6 +555,13 @@ impl<'a> CalendarSyncEngine<'a> {
         let grace_minutes = (source.poll_interval_minutes.max(1) * 3).max(30);
         for mapping in existing_maps {
             if !seen_uids.contains(&mapping.external_uid) {
+                if !options.approves(SyncChangeKind::Delete, &mapping.external_uid, None) {
+                    // An unapproved deletion restarts the grace period instead of purging.
+                    map_service.touch_last_seen(source_id, &mapping.external_uid)?;
+                    result.skipped_unapproved += 1;
+                    continue;
+                }
+
                 let should_purge = mapping
                     .purge_after_at
                     .as_deref()
@@ -402,40 +598,55 @@ impl<'a> CalendarSyncEngine<'a> {
         Ok(result)
     }
 
-    fn sync_source_from_google_api(&self, source_id: i64) -> Result<SyncRunResult> {
+    fn sync_source_from_google_api(
+        &self,
+        source_id: i64,
+        options: &SyncApplyOptions,
+    ) -> Result<SyncRunResult> {
         let source_service = CalendarSourceService::new(self.conn);
         let source = source_service
             .get_by_id(source_id)?
             .ok_or_else(|| anyhow!("Calendar source with id {} not found", source_id))?;
 
-        let account_service = GoogleAccountService::new(self.conn)?;
-        let access_token = account_service.valid_access_token()?;
+        let access_token = self.access_token_for_source(&source)?;
         let client = GoogleCalendarApiClient::new(access_token)?;
 
         self.process_pending_outbound_operations(&source, &client)?;
 
         match client.fetch_events_incremental(&source) {
-            Ok(payload) => self.sync_source_from_google_payload(source_id, payload),
+            Ok(payload) => {
+                self.sync_source_from_google_payload_selected(source_id, payload, options)
+            }
             Err(err) if Self::should_reset_api_sync_token(&err) => {
                 source_service.set_api_sync_token(source_id, None)?;
                 let refreshed_source = source_service
                     .get_by_id(source_id)?
                     .ok_or_else(|| anyhow!("Calendar source with id {} not found", source_id))?;
                 let payload = client.fetch_events_incremental(&refreshed_source)?;
-                self.sync_source_from_google_payload(source_id, payload)
+                self.sync_source_from_google_payload_selected(source_id, payload, options)
             }
             Err(err) => Err(err),
         }
     }
 
+    /// Returns a valid access token for the Google account linked to `source`.
+    fn access_token_for_source(&self, source: &CalendarSource) -> Result<String> {
+        let account_id = source.google_account_id.ok_or_else(|| {
+            anyhow!(
+                "Calendar source '{}' is not linked to a Google account",
+                source.name
+            )
+        })?;
+        GoogleAccountService::new(self.conn)?.valid_access_token(account_id)
+    }
+
     fn preview_source_from_google_api(&self, source_id: i64) -> Result<SyncRunResult> {
         let source_service = CalendarSourceService::new(self.conn);
         let source = source_service
             .get_by_id(source_id)?
             .ok_or_else(|| anyhow!("Calendar source with id {} not found", source_id))?;
 
-        let account_service = GoogleAccountService::new(self.conn)?;
-        let access_token = account_service.valid_access_token()?;
+        let access_token = self.access_token_for_source(&source)?;
         let client = GoogleCalendarApiClient::new(access_token)?;
 
         let payload = match client.fetch_events_incremental(&source) {
@@ -514,12 +725,9 @@ impl<'a> CalendarSyncEngine<'a> {
                     .payload_json
                     .as_deref()
                     .ok_or_else(|| anyhow!("Outbound create operation is missing payload_json"))?;
-                let (parent_external_uid, _) = external_uid.split_once("::RID::").ok_or_else(|| {
-                    anyhow!(
-                        "Outbound create for '{}' is unsupported without detached instance identity",
-                        external_uid
-                    )
-                })?;
+                let Some((parent_external_uid, _)) = external_uid.split_once("::RID::") else {
+                    return self.push_recreated_event(source, writer, operation, external_uid);
+                };
                 let parent_remote = map_service
                     .get_remote_metadata(source_id, parent_external_uid)?
                     .and_then(|metadata| metadata.remote_event_id)
@@ -573,6 +781,54 @@ impl<'a> CalendarSyncEngine<'a> {
         }
     }
 
+    /// Pushes an event restored from the trash after its delete was pushed.
+    /// Google keeps the deleted copy as cancelled, so the event is inserted
+    /// anew and its mapping moves to the iCalUID Google gives it.
+    fn push_recreated_event<W: GoogleOutboundWriter>(
+        &self,
+        source: &CalendarSource,
+        writer: &W,
+        operation: &OutboundSyncOperation,
+        external_uid: &str,
+    ) -> Result<()> {
+        let source_id = source
+            .id
+            .ok_or_else(|| anyhow!("Calendar source ID is required to push outbound operations"))?;
+        let payload_json = operation
+            .payload_json
+            .as_deref()
+            .ok_or_else(|| anyhow!("Outbound create operation is missing payload_json"))?;
+        let local_event_id = operation.local_event_id.ok_or_else(|| {
+            anyhow!(
+                "Outbound operation for '{}' is missing local_event_id",
+                external_uid
+            )
+        })?;
+
+        let remote = writer.insert_event(source, payload_json)?;
+        if remote.external_uid != external_uid {
+            let map_service = EventSyncMapService::new(self.conn);
+            self.clear_remote_identity_tracking(&map_service, source_id, external_uid)?;
+            map_service.create(EventSyncMap {
+                id: None,
+                source_id,
+                external_uid: remote.external_uid.clone(),
+                local_event_id,
+                external_last_modified: None,
+                external_etag_hash: None,
+                last_seen_at: None,
+                first_missing_at: None,
+                purge_after_at: None,
+            })?;
+        }
+        self.complete_outbound_upsert(
+            source_id,
+            &remote.external_uid,
+            Some(local_event_id),
+            &remote,
+        )
+    }
+
     fn complete_outbound_upsert(
         &self,
         source_id: i64,
@@ -593,7 +849,15 @@ impl<'a> CalendarSyncEngine<'a> {
             external_uid,
             local_event_id,
             remote,
-        )
+        )?;
+
+        // Both sides now hold what was pushed, so it is the base for the next merge.
+        if let Some(pushed) = EventService::new(self.conn).get(local_event_id)? {
+            let synced_snapshot = ConflictEventSnapshot::from(&pushed).to_json();
+            map_service.set_synced_snapshot(source_id, external_uid, synced_snapshot.as_deref())?;
+        }
+
+        Ok(())
     }
 
     fn clear_remote_identity_tracking(
@@ -619,9 +883,10 @@ impl<'a> CalendarSyncEngine<'a> {
         source_id: i64,
         payload: &GoogleEventsSyncPayload,
         apply: bool,
+        options: &SyncApplyOptions,
     ) -> Result<SyncRunResult> {
         let map_service = EventSyncMapService::new(self.conn);
-        let event_service = EventService::new(self.conn);
+        let event_service = EventService::new(self.conn).with_origin(ChangeOrigin::Sync);
         let outbound_service = OutboundSyncService::new(self.conn);
         let conflict_service = SyncConflictService::new(self.conn);
         let mut result = SyncRunResult {
@@ -635,6 +900,33 @@ impl<'a> CalendarSyncEngine<'a> {
                 outbound_service.active_operation_for_identity(source_id, &external_uid)?;
             let existing_map = map_service.get_by_source_and_uid(source_id, &external_uid)?;
 
+            let pending_change = if remote.is_cancelled() {
+                (existing_map.is_some() || active_outbound.is_some())
+                    .then_some(SyncChangeKind::Delete)
+            } else {
+                match existing_map
+                    .as_ref()
+                    .map(|mapping| event_service.get(mapping.local_event_id))
+                    .transpose()?
+                    .flatten()
+                {
+                    Some(existing_event) => remote
+                        .event
+                        .as_ref()
+                        .filter(|incoming| {
+                            !Self::is_effectively_unchanged(&existing_event, incoming)
+                        })
+                        .map(|_| SyncChangeKind::Update),
+                    None => Some(SyncChangeKind::Create),
+                }
+            };
+            if let Some(kind) = pending_change {
+                if !options.approves(kind, &external_uid, remote.event.as_ref()) {
+                    result.skipped_unapproved += 1;
+                    continue;
+                }
+            }
+
             if remote.is_cancelled() {
                 if let Some(mapping) = existing_map {
                     if let Some(operation) = active_outbound.as_ref() {
@@ -652,12 +944,18 @@ impl<'a> CalendarSyncEngine<'a> {
                         } else {
                             result.conflicts += 1;
                             if apply {
+                                let local_event = Self::stored_local_event(
+                                    &event_service,
+                                    mapping.local_event_id,
+                                )?;
                                 self.record_remote_wins_conflict(
                                     &conflict_service,
                                     &outbound_service,
                                     &external_uid,
                                     Some(mapping.local_event_id),
+                                    local_event.as_ref(),
                                     operation,
+                                    remote,
                                     "delete",
                                 )?;
                             }
@@ -670,6 +968,13 @@ impl<'a> CalendarSyncEngine<'a> {
                         if event_service.get(mapping.local_event_id)?.is_some() {
                             event_service.delete(mapping.local_event_id)?;
                         }
+                    } else {
+                        let local_event = event_service.get(mapping.local_event_id)?;
+                        result.changes.push(SyncPreviewChange::new(
+                            SyncChangeKind::Delete,
+                            &external_uid,
+                            local_event.as_ref(),
+                        ));
                     }
                     result.deleted += 1;
                 } else if let Some(operation) = active_outbound.as_ref() {
@@ -684,6 +989,12 @@ impl<'a> CalendarSyncEngine<'a> {
                                 SYNC_CONFLICT_RESOLUTION_REMOTE_WINS,
                             )?;
                             map_service.delete_remote_metadata(source_id, &external_uid)?;
+                        } else {
+                            result.changes.push(SyncPreviewChange::new(
+                                SyncChangeKind::Delete,
+                                &external_uid,
+                                None,
+                            ));
                         }
                         result.deleted += 1;
                     }
@@ -722,7 +1033,9 @@ impl<'a> CalendarSyncEngine<'a> {
                                         &outbound_service,
                                         &external_uid,
                                         existing_event.id.or(Some(mapping.local_event_id)),
+                                        Some(&existing_event),
                                         operation,
+                                        remote,
                                         "update",
                                     )?;
                                 }
@@ -753,35 +1066,56 @@ impl<'a> CalendarSyncEngine<'a> {
                                     updated_event.id.unwrap_or(mapping.local_event_id),
                                     remote,
                                 )?;
+                            } else {
+                                result.changes.push(SyncPreviewChange::new(
+                                    SyncChangeKind::Update,
+                                    &external_uid,
+                                    Some(&incoming_event),
+                                ));
                             }
                             result.updated += 1;
                         }
                     } else {
-                        if let Some(operation) = active_outbound.as_ref() {
+                        if active_outbound.is_some() {
                             result.conflicts += 1;
-                            if apply {
+                        }
+
+                        if apply {
+                            let created_event = event_service.create(incoming_event.clone())?;
+                            let created_id = created_event
+                                .id
+                                .ok_or_else(|| anyhow!("Created event missing ID"))?;
+                            if let Some(operation) = active_outbound.as_ref() {
+                                // The conflict points at the recreated row so a merge can
+                                // be applied to it.
+                                let local_event = Self::stored_local_event(
+                                    &event_service,
+                                    mapping.local_event_id,
+                                )?;
                                 self.record_remote_wins_conflict(
                                     &conflict_service,
                                     &outbound_service,
                                     &external_uid,
-                                    Some(mapping.local_event_id),
+                                    Some(created_id),
+                                    local_event.as_ref(),
                                     operation,
+                                    remote,
                                     "update",
                                 )?;
                             }
-                        }
-
-                        if apply {
-                            let created_event = event_service.create(incoming_event.clone())?;
                             self.update_remote_tracking(
                                 &map_service,
                                 source_id,
                                 &external_uid,
-                                created_event
-                                    .id
-                                    .ok_or_else(|| anyhow!("Created event missing ID"))?,
+                                created_id,
                                 remote,
                             )?;
+                        } else {
+                            result.changes.push(SyncPreviewChange::new(
+                                SyncChangeKind::Create,
+                                &external_uid,
+                                Some(&incoming_event),
+                            ));
                         }
                         result.created += 1;
                     }
@@ -810,6 +1144,12 @@ impl<'a> CalendarSyncEngine<'a> {
                             local_event_id,
                             remote,
                         )?;
+                    } else {
+                        result.changes.push(SyncPreviewChange::new(
+                            SyncChangeKind::Create,
+                            &external_uid,
+                            Some(&incoming_event),
+                        ));
                     }
                     result.created += 1;
                 }
@@ -835,7 +1175,7 @@ impl<'a> CalendarSyncEngine<'a> {
             .ok_or_else(|| anyhow!("Calendar source with id {} not found", source_id))?;
 
         let map_service = EventSyncMapService::new(self.conn);
-        let event_service = EventService::new(self.conn);
+        let event_service = EventService::new(self.conn).with_origin(ChangeOrigin::Sync);
 
         let mut seen_uids: HashSet<String> = HashSet::new();
 
@@ -856,14 +1196,29 @@ impl<'a> CalendarSyncEngine<'a> {
                         if Self::is_effectively_unchanged(&existing_event, &imported.event) {
                             result.unchanged += 1;
                         } else {
+                            result.changes.push(SyncPreviewChange::new(
+                                SyncChangeKind::Update,
+                                &uid,
+                                Some(&imported.event),
+                            ));
                             result.updated += 1;
                         }
                     } else {
                         // Mapping exists but points to missing event; apply path would recreate it.
+                        result.changes.push(SyncPreviewChange::new(
+                            SyncChangeKind::Create,
+                            &uid,
+                            Some(&imported.event),
+                        ));
                         result.created += 1;
                     }
                 }
                 None => {
+                    result.changes.push(SyncPreviewChange::new(
+                        SyncChangeKind::Create,
+                        &uid,
+                        Some(&imported.event),
+                    ));
                     result.created += 1;
                 }
             }
@@ -872,6 +1227,12 @@ impl<'a> CalendarSyncEngine<'a> {
         let existing_maps = map_service.list_by_source_id(source_id)?;
         for mapping in existing_maps {
             if !seen_uids.contains(&mapping.external_uid) {
+                let local_event = event_service.get(mapping.local_event_id)?;
+                result.changes.push(SyncPreviewChange::new(
+                    SyncChangeKind::Delete,
+                    &mapping.external_uid,
+                    local_event.as_ref(),
+                ));
                 result.deleted += 1;
             }
         }
@@ -959,6 +1320,12 @@ impl<'a> CalendarSyncEngine<'a> {
             remote_payload_hash: Some(remote.payload_hash.clone()),
         })?;
 
+        let synced_snapshot = remote
+            .event
+            .as_ref()
+            .and_then(|event| ConflictEventSnapshot::from(event).to_json());
+        map_service.set_synced_snapshot(source_id, external_uid, synced_snapshot.as_deref())?;
+
         Ok(())
     }
 
@@ -987,15 +1354,42 @@ impl<'a> CalendarSyncEngine<'a> {
         }
     }
 
+    /// The local side of a conflict: the event row, or its trashed copy when
+    /// the pending change deleted it.
+    fn stored_local_event(
+        event_service: &EventService<'_>,
+        event_id: i64,
+    ) -> Result<Option<Event>> {
+        match event_service.get(event_id)? {
+            Some(event) => Ok(Some(event)),
+            None => event_service.latest_trashed(event_id),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn record_remote_wins_conflict(
         &self,
         conflict_service: &SyncConflictService<'_>,
         outbound_service: &OutboundSyncService<'_>,
         external_uid: &str,
         local_event_id: Option<i64>,
+        local_event: Option<&Event>,
         operation: &OutboundSyncOperation,
+        remote: &GoogleRemoteEvent,
         remote_change_type: &str,
     ) -> Result<()> {
+        // Capture all three versions before the remote change overwrites the local row.
+        let base_snapshot = EventSyncMapService::new(self.conn)
+            .get_synced_snapshot(operation.source_id, external_uid)?
+            .as_deref()
+            .and_then(ConflictEventSnapshot::from_json);
+        let local_snapshot = local_event.map(ConflictEventSnapshot::from);
+        let remote_snapshot = if remote.is_cancelled() {
+            None
+        } else {
+            remote.event.as_ref().map(ConflictEventSnapshot::from)
+        };
+
         if let Some(operation_id) = operation.id {
             outbound_service.mark_operation_failed(
                 operation_id,
@@ -1014,6 +1408,9 @@ impl<'a> CalendarSyncEngine<'a> {
             reason: Self::conflict_reason_for_operation(&operation.operation_type).to_string(),
             resolution: Some(SYNC_CONFLICT_RESOLUTION_REMOTE_WINS.to_string()),
             status: crate::models::sync_conflict::SYNC_CONFLICT_STATUS_OPEN.to_string(),
+            base_snapshot,
+            local_snapshot,
+            remote_snapshot,
             created_at: None,
             resolved_at: None,
             updated_at: None,
@@ -1037,7 +1434,58 @@ impl<'a> CalendarSyncEngine<'a> {
             .is_some_and(GoogleCalendarApiError::is_sync_token_expired)
     }
 
+    /// Fails with [`MassDeletionPaused`] when a run would delete at least the
+    /// configured count, or the configured share of a source's mapped events.
+    ///
+    /// The share only applies once a source has a handful of events, so that
+    /// removing one of two events doesn't need confirmation.
+    fn check_mass_deletion(&self, would_delete: usize, mapped: usize) -> Result<()> {
+        if would_delete == 0 {
+            return Ok(());
+        }
+
+        let (percent, count) = self.mass_delete_thresholds()?;
+        let exceeds_count = count > 0 && would_delete as i64 >= count;
+        let exceeds_share = percent > 0
+            && mapped >= MASS_DELETE_MIN_MAPPED_FOR_PERCENT
+            && (would_delete as i64) * 100 >= percent * mapped as i64;
+
+        if exceeds_count || exceeds_share {
+            return Err(MassDeletionPaused {
+                would_delete,
+                mapped,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    fn mass_delete_thresholds(&self) -> Result<(i64, i64)> {
+        let result = self.conn.query_row(
+            "SELECT sync_mass_delete_percent, sync_mass_delete_count FROM settings WHERE id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        );
+
+        match result {
+            Ok(thresholds) => Ok(thresholds),
+            Err(rusqlite::Error::QueryReturnedNoRows) => {
+                let defaults = crate::models::settings::Settings::default();
+                Ok((
+                    defaults.sync_mass_delete_percent,
+                    defaults.sync_mass_delete_count,
+                ))
+            }
+            Err(err) => Err(err).context("Failed to load mass-deletion thresholds"),
+        }
+    }
+
     fn sync_status_for_error(err: &anyhow::Error) -> &'static str {
+        if err.downcast_ref::<MassDeletionPaused>().is_some() {
+            return SYNC_STATUS_NEEDS_CONFIRMATION;
+        }
+
         if err
             .downcast_ref::<GoogleCalendarApiError>()
             .and_then(GoogleCalendarApiError::retry_after_minutes)
@@ -1056,15 +1504,19 @@ impl<'a> CalendarSyncEngine<'a> {
 
 #[cfg(test)]
 mod tests {
-    use super::{CalendarSyncEngine, OutboundOperationError};
+    use super::{
+        CalendarSyncEngine, MassDeletionPaused, OutboundOperationError, SyncApplyOptions,
+        SyncChangeKind,
+    };
     use crate::models::calendar_source::CalendarSource;
     use crate::models::calendar_source::SYNC_CAPABILITY_READ_WRITE;
+    use crate::models::calendar_source::SYNC_STATUS_NEEDS_CONFIRMATION;
     use crate::models::outbound_sync_operation::{
         OUTBOUND_OPERATION_CREATE, OUTBOUND_OPERATION_DELETE, OUTBOUND_OPERATION_UPDATE,
         OUTBOUND_STATUS_FAILED,
     };
     use crate::models::sync_conflict::{
-        SYNC_CONFLICT_RESOLUTION_REMOTE_WINS, SYNC_CONFLICT_STATUS_OPEN,
+        ConflictEventSnapshot, SYNC_CONFLICT_RESOLUTION_REMOTE_WINS, SYNC_CONFLICT_STATUS_OPEN,
     };
     use crate::services::calendar_sync::google_api::GoogleCalendarApiError;
     use crate::services::calendar_sync::google_api::{GoogleOutboundWriter, GoogleRemoteEvent};
@@ -1074,6 +1526,7 @@ mod tests {
     use anyhow::anyhow;
     use chrono::{Duration, Local, TimeZone, Utc};
     use rusqlite::{params, Connection};
+    use std::collections::HashSet;
 
     fn create_source(conn: &Connection, name: &str, enabled: bool) -> i64 {
         conn.execute(
@@ -1160,6 +1613,7 @@ mod tests {
     struct FakeGoogleOutboundWriter {
         updated_ids: std::sync::Mutex<Vec<String>>,
         deleted_ids: std::sync::Mutex<Vec<String>>,
+        inserted_payloads: std::sync::Mutex<Vec<String>>,
         patched_instances: std::sync::Mutex<Vec<(String, String)>>,
     }
 
@@ -1204,6 +1658,33 @@ mod tests {
             Ok(())
         }
 
+        fn insert_event(
+            &self,
+            _source: &CalendarSource,
+            payload_json: &str,
+        ) -> anyhow::Result<GoogleRemoteEvent> {
+            self.inserted_payloads
+                .lock()
+                .unwrap()
+                .push(payload_json.to_string());
+            Ok(GoogleRemoteEvent {
+                remote_event_id: "remote-inserted-1".to_string(),
+                external_uid: "uid-api-inserted".to_string(),
+                etag: Some("\"etag-inserted\"".to_string()),
+                updated_at: Some("2026-03-06T03:00:00Z".to_string()),
+                payload_hash: "hash-inserted".to_string(),
+                status: Some("confirmed".to_string()),
+                event: Some(
+                    crate::models::event::Event::builder()
+                        .title("Planning")
+                        .start(Local::now())
+                        .end(Local::now() + Duration::hours(1))
+                        .build()
+                        .unwrap(),
+                ),
+            })
+        }
+
         fn patch_detached_instance(
             &self,
             _source: &CalendarSource,
@@ -1523,60 +2004,344 @@ mod tests {
     }
 
     #[test]
-    fn test_sync_source_from_ics_respects_source_date_window() {
+    fn test_sync_source_from_ics_selected_applies_only_approved_changes() {
         let db = Database::new(":memory:").unwrap();
         db.initialize_schema().unwrap();
         let conn = db.connection();
         let source_id = create_source(conn, "Work", true);
-        set_source_windows(conn, source_id, 0, 1);
 
         let engine = CalendarSyncEngine::new(conn).unwrap();
 
-        let now = Local::now();
-        let in_window_start = now + Duration::hours(6);
-        let in_window_end = in_window_start + Duration::hours(1);
-        let out_window_start = now + Duration::days(5);
-        let out_window_end = out_window_start + Duration::hours(1);
+        let initial = r#"BEGIN:VCALENDAR
+    VERSION:2.0
+    BEGIN:VEVENT
+    UID:uid-a
+    DTSTART:20260227T090000
+    DTEND:20260227T100000
+    SUMMARY:Event A
+    END:VEVENT
+    BEGIN:VEVENT
+    UID:uid-b
+    DTSTART:20260227T110000
+    DTEND:20260227T120000
+    SUMMARY:Event B
+    END:VEVENT
+    END:VCALENDAR"#;
 
-        let ics = format!(
-            "BEGIN:VCALENDAR\nVERSION:2.0\nBEGIN:VEVENT\nUID:keep-uid\nDTSTART:{}\nDTEND:{}\nSUMMARY:Keep Event\nEND:VEVENT\nBEGIN:VEVENT\nUID:skip-uid\nDTSTART:{}\nDTEND:{}\nSUMMARY:Skip Event\nEND:VEVENT\nEND:VCALENDAR",
-            in_window_start.format("%Y%m%dT%H%M%S"),
-            in_window_end.format("%Y%m%dT%H%M%S"),
-            out_window_start.format("%Y%m%dT%H%M%S"),
-            out_window_end.format("%Y%m%dT%H%M%S"),
+        let _ = engine.sync_source_from_ics(source_id, initial).unwrap();
+
+        let next = r#"BEGIN:VCALENDAR
+    VERSION:2.0
+    BEGIN:VEVENT
+    UID:uid-a
+    DTSTART:20260227T090000
+    DTEND:20260227T100000
+    SUMMARY:Event A (renamed)
+    END:VEVENT
+    BEGIN:VEVENT
+    UID:uid-c
+    DTSTART:20260228T090000
+    DTEND:20260228T100000
+    SUMMARY:Event C
+    END:VEVENT
+    END:VCALENDAR"#;
+
+        let preview = engine.preview_source_from_ics(source_id, next).unwrap();
+        let kinds: Vec<(&str, SyncChangeKind)> = preview
+            .changes
+            .iter()
+            .map(|change| (change.external_uid.as_str(), change.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("uid-a", SyncChangeKind::Update),
+                ("uid-c", SyncChangeKind::Create),
+                ("uid-b", SyncChangeKind::Delete),
+            ]
         );
+        assert_eq!(preview.changes[2].title, "Event B");
 
-        let result = engine.sync_source_from_ics(source_id, &ics).unwrap();
-        assert_eq!(result.created, 1);
-        assert_eq!(result.skipped_filtered, 1);
+        let options = SyncApplyOptions {
+            approved: Some(HashSet::from([preview.changes[0].id.clone()])),
+            ..SyncApplyOptions::default()
+        };
+        let result = engine
+            .sync_source_from_ics_selected(source_id, next, &options)
+            .unwrap();
+        assert_eq!(result.updated, 1);
+        assert_eq!(result.created, 0);
+        assert_eq!(result.skipped_unapproved, 2);
 
-        let event_service = EventService::new(conn);
-        let events = event_service.list_all().unwrap();
-        assert_eq!(events.len(), 1);
-        assert_eq!(events[0].title, "Keep Event");
+        let mut titles: Vec<String> = EventService::new(conn)
+            .list_all()
+            .unwrap()
+            .into_iter()
+            .map(|event| event.title)
+            .collect();
+        titles.sort();
+        assert_eq!(titles, vec!["Event A (renamed)", "Event B"]);
+
+        let mapping = EventSyncMapService::new(conn)
+            .get_by_source_and_uid(source_id, "uid-b")
+            .unwrap()
+            .unwrap();
+        assert!(mapping.first_missing_at.is_none());
     }
 
     #[test]
-    fn test_sync_source_from_ics_keeps_modified_instances_with_same_uid() {
+    fn test_sync_source_from_ics_selected_skips_changes_edited_after_preview() {
         let db = Database::new(":memory:").unwrap();
         db.initialize_schema().unwrap();
         let conn = db.connection();
         let source_id = create_source(conn, "Work", true);
-
         let engine = CalendarSyncEngine::new(conn).unwrap();
 
-        let ics = r#"BEGIN:VCALENDAR
-VERSION:2.0
-BEGIN:VEVENT
-UID:series-uid
-DTSTART:20260310T090000
-DTEND:20260310T100000
-SUMMARY:Series Master
-END:VEVENT
-BEGIN:VEVENT
-UID:series-uid
-RECURRENCE-ID:20260311T090000
-DTSTART:20260311T120000
+        let ics_titled = |title: &str| {
+            format!(
+                "BEGIN:VCALENDAR\nVERSION:2.0\nBEGIN:VEVENT\nUID:uid-a\nDTSTART:20260227T090000\nDTEND:20260227T100000\nSUMMARY:{title}\nEND:VEVENT\nEND:VCALENDAR\n"
+            )
+        };
+        engine
+            .sync_source_from_ics(source_id, &ics_titled("Original"))
+            .unwrap();
+
+        let preview = engine
+            .preview_source_from_ics(source_id, &ics_titled("Reviewed"))
+            .unwrap();
+        let options = SyncApplyOptions {
+            approved: Some(preview.changes.iter().map(|c| c.id.clone()).collect()),
+            ..SyncApplyOptions::default()
+        };
+
+        let result = engine
+            .sync_source_from_ics_selected(source_id, &ics_titled("Edited later"), &options)
+            .unwrap();
+        assert_eq!(result.updated, 0);
+        assert_eq!(result.skipped_unapproved, 1);
+        assert_eq!(
+            EventService::new(conn).list_all().unwrap()[0].title,
+            "Original"
+        );
+    }
+
+    #[test]
+    fn test_sync_source_from_google_payload_partial_apply_clears_sync_token() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let source_id = create_rw_source(conn, "API Source");
+        let engine = CalendarSyncEngine::new(conn).unwrap();
+
+        let payload = || {
+            super::super::google_api::GoogleCalendarApiClient::parse_events_response_body(
+                r#"{
+                "items": [
+                    {
+                        "id": "remote-a",
+                        "etag": "\"etag-a\"",
+                        "status": "confirmed",
+                        "summary": "Event A",
+                        "iCalUID": "uid-api-a",
+                        "updated": "2026-03-06T00:00:00Z",
+                        "start": { "dateTime": "2026-03-10T09:00:00Z" },
+                        "end": { "dateTime": "2026-03-10T10:00:00Z" }
+                    },
+                    {
+                        "id": "remote-b",
+                        "etag": "\"etag-b\"",
+                        "status": "confirmed",
+                        "summary": "Event B",
+                        "iCalUID": "uid-api-b",
+                        "updated": "2026-03-06T00:00:00Z",
+                        "start": { "dateTime": "2026-03-11T09:00:00Z" },
+                        "end": { "dateTime": "2026-03-11T10:00:00Z" }
+                    }
+                ],
+                "nextSyncToken": "sync-token-next"
+            }"#,
+            )
+            .unwrap()
+        };
+        let source_service = crate::services::calendar_sync::CalendarSourceService::new(conn);
+        let sync_token = || {
+            source_service
+                .get_by_id(source_id)
+                .unwrap()
+                .unwrap()
+                .api_sync_token
+        };
+
+        let preview = engine
+            .preview_source_from_google_payload(source_id, payload())
+            .unwrap();
+        assert_eq!(preview.changes.len(), 2);
+        let options = SyncApplyOptions {
+            approved: Some(HashSet::from([preview.changes[0].id.clone()])),
+            ..SyncApplyOptions::default()
+        };
+
+        let result = engine
+            .sync_source_from_google_payload_selected(source_id, payload(), &options)
+            .unwrap();
+        assert_eq!(result.created, 1);
+        assert_eq!(result.skipped_unapproved, 1);
+        assert_eq!(sync_token(), None);
+
+        // The next full run delivers the skipped event and keeps the new token
+        let result = engine
+            .sync_source_from_google_payload(source_id, payload())
+            .unwrap();
+        assert_eq!(result.created, 1);
+        assert_eq!(sync_token(), Some("sync-token-next".to_string()));
+    }
+
+    fn ics_with_events(count: usize) -> String {
+        let mut ics = String::from("BEGIN:VCALENDAR\nVERSION:2.0\n");
+        for index in 0..count {
+            ics.push_str(&format!(
+                "BEGIN:VEVENT\nUID:uid-{index}\nDTSTART:20260227T090000\nDTEND:20260227T100000\nSUMMARY:Event {index}\nEND:VEVENT\n"
+            ));
+        }
+        ics.push_str("END:VCALENDAR\n");
+        ics
+    }
+
+    #[test]
+    fn test_sync_source_from_ics_pauses_on_mass_deletion() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let source_id = create_source(conn, "Work", true);
+        let engine = CalendarSyncEngine::new(conn).unwrap();
+
+        engine
+            .sync_source_from_ics(source_id, &ics_with_events(12))
+            .unwrap();
+
+        let err = engine
+            .sync_source_from_ics(source_id, &ics_with_events(2))
+            .unwrap_err();
+        let paused = err.downcast_ref::<MassDeletionPaused>().unwrap();
+        assert_eq!(paused.would_delete, 10);
+        assert_eq!(paused.mapped, 12);
+        assert_eq!(
+            CalendarSyncEngine::sync_status_for_error(&err),
+            SYNC_STATUS_NEEDS_CONFIRMATION
+        );
+
+        let map_service = EventSyncMapService::new(conn);
+        assert!(map_service
+            .list_by_source_id(source_id)
+            .unwrap()
+            .iter()
+            .all(|mapping| mapping.first_missing_at.is_none()));
+
+        let options = SyncApplyOptions {
+            confirm_mass_deletion: true,
+            ..SyncApplyOptions::default()
+        };
+        engine
+            .sync_source_from_ics_selected(source_id, &ics_with_events(2), &options)
+            .unwrap();
+        let staged = map_service
+            .list_by_source_id(source_id)
+            .unwrap()
+            .into_iter()
+            .filter(|mapping| mapping.first_missing_at.is_some())
+            .count();
+        assert_eq!(staged, 10);
+
+        // Once the grace period ends an unattended sync purges what was confirmed
+        conn.execute(
+            "UPDATE event_sync_map SET purge_after_at = ?1 WHERE source_id = ?2",
+            params!["2000-01-01T00:00:00+00:00", source_id],
+        )
+        .unwrap();
+        let purge = engine
+            .sync_source_from_ics(source_id, &ics_with_events(2))
+            .unwrap();
+        assert_eq!(purge.deleted, 10);
+        assert_eq!(map_service.list_by_source_id(source_id).unwrap().len(), 2);
+        assert_eq!(EventService::new(conn).list_all().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_mass_deletion_thresholds_follow_settings() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let engine = CalendarSyncEngine::new(conn).unwrap();
+
+        // Defaults: 50% of at least ten events, or 25 events outright.
+        assert!(engine.check_mass_deletion(1, 2).is_ok());
+        assert!(engine.check_mass_deletion(4, 10).is_ok());
+        assert!(engine.check_mass_deletion(5, 10).is_err());
+        assert!(engine.check_mass_deletion(25, 1000).is_err());
+
+        conn.execute(
+            "UPDATE settings SET sync_mass_delete_percent = 0, sync_mass_delete_count = 0",
+            [],
+        )
+        .unwrap();
+        assert!(engine.check_mass_deletion(100, 100).is_ok());
+    }
+
+    #[test]
+    fn test_sync_source_from_ics_respects_source_date_window() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let source_id = create_source(conn, "Work", true);
+        set_source_windows(conn, source_id, 0, 1);
+
+        let engine = CalendarSyncEngine::new(conn).unwrap();
+
+        let now = Local::now();
+        let in_window_start = now + Duration::hours(6);
+        let in_window_end = in_window_start + Duration::hours(1);
+        let out_window_start = now + Duration::days(5);
+        let out_window_end = out_window_start + Duration::hours(1);
+
+        let ics = format!(
+            "BEGIN:VCALENDAR\nVERSION:2.0\nBEGIN:VEVENT\nUID:keep-uid\nDTSTART:{}\nDTEND:{}\nSUMMARY:Keep Event\nEND:VEVENT\nBEGIN:VEVENT\nUID:skip-uid\nDTSTART:{}\nDTEND:{}\nSUMMARY:Skip Event\nEND:VEVENT\nEND:VCALENDAR",
+            in_window_start.format("%Y%m%dT%H%M%S"),
+            in_window_end.format("%Y%m%dT%H%M%S"),
+            out_window_start.format("%Y%m%dT%H%M%S"),
+            out_window_end.format("%Y%m%dT%H%M%S"),
+        );
+
+        let result = engine.sync_source_from_ics(source_id, &ics).unwrap();
+        assert_eq!(result.created, 1);
+        assert_eq!(result.skipped_filtered, 1);
+
+        let event_service = EventService::new(conn);
+        let events = event_service.list_all().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].title, "Keep Event");
+    }
+
+    #[test]
+    fn test_sync_source_from_ics_keeps_modified_instances_with_same_uid() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let source_id = create_source(conn, "Work", true);
+
+        let engine = CalendarSyncEngine::new(conn).unwrap();
+
+        let ics = r#"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:series-uid
+DTSTART:20260310T090000
+DTEND:20260310T100000
+SUMMARY:Series Master
+END:VEVENT
+BEGIN:VEVENT
+UID:series-uid
+RECURRENCE-ID:20260311T090000
+DTSTART:20260311T120000
 DTEND:20260311T130000
 SUMMARY:Moved Instance
 END:VEVENT
@@ -1788,6 +2553,105 @@ END:VCALENDAR"#;
             conflict.2,
             crate::models::sync_conflict::SYNC_CONFLICT_REASON_LOCAL_UPDATE_PENDING
         );
+
+        let recorded = crate::services::sync_conflict::SyncConflictService::new(conn)
+            .list_open_for_source(source_id, 10)
+            .unwrap()
+            .remove(0);
+        assert_eq!(
+            recorded.base_snapshot.map(|snapshot| snapshot.title),
+            Some("Remote Baseline".to_string())
+        );
+        assert_eq!(
+            recorded.local_snapshot.map(|snapshot| snapshot.title),
+            Some("Local Edit".to_string())
+        );
+        assert_eq!(
+            recorded.remote_snapshot.map(|snapshot| snapshot.title),
+            Some("Remote Edit".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sync_source_from_google_payload_records_trashed_local_event_in_conflict() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let source_id = create_rw_source(conn, "API Source");
+        let engine = CalendarSyncEngine::new(conn).unwrap();
+
+        let initial_payload =
+            super::super::google_api::GoogleCalendarApiClient::parse_events_response_body(
+                r#"{
+                "items": [
+                    {
+                        "id": "remote-5",
+                        "etag": "\"etag-5\"",
+                        "status": "confirmed",
+                        "summary": "Remote Baseline",
+                        "iCalUID": "uid-api-5",
+                        "updated": "2026-03-06T00:00:00Z",
+                        "start": { "dateTime": "2026-03-10T09:00:00Z" },
+                        "end": { "dateTime": "2026-03-10T10:00:00Z" }
+                    }
+                ],
+                "nextSyncToken": "sync-token-6"
+            }"#,
+            )
+            .unwrap();
+        engine
+            .sync_source_from_google_payload(source_id, initial_payload)
+            .unwrap();
+
+        let mut local_edit = EventService::new(conn).list_all().unwrap().remove(0);
+        local_edit.title = "Local Edit".to_string();
+        EventService::new(conn).update_local(&local_edit).unwrap();
+
+        // Keep the mapping so the remote update finds it without a local row
+        conn.execute("PRAGMA foreign_keys = OFF", []).unwrap();
+        EventService::new(conn)
+            .delete_local(local_edit.id.unwrap())
+            .unwrap();
+        conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+
+        let remote_update_payload =
+            super::super::google_api::GoogleCalendarApiClient::parse_events_response_body(
+                r#"{
+                "items": [
+                    {
+                        "id": "remote-5",
+                        "etag": "\"etag-6\"",
+                        "status": "confirmed",
+                        "summary": "Remote Edit",
+                        "iCalUID": "uid-api-5",
+                        "updated": "2026-03-07T00:00:00Z",
+                        "start": { "dateTime": "2026-03-10T09:00:00Z" },
+                        "end": { "dateTime": "2026-03-10T10:00:00Z" }
+                    }
+                ],
+                "nextSyncToken": "sync-token-7"
+            }"#,
+            )
+            .unwrap();
+        let result = engine
+            .sync_source_from_google_payload(source_id, remote_update_payload)
+            .unwrap();
+        assert_eq!(result.conflicts, 1);
+
+        let recorded = crate::services::sync_conflict::SyncConflictService::new(conn)
+            .list_open_for_source(source_id, 10)
+            .unwrap()
+            .remove(0);
+        let recreated = EventService::new(conn).list_all().unwrap().remove(0);
+        assert_eq!(recorded.local_event_id, recreated.id);
+        assert_eq!(
+            recorded.local_snapshot.map(|snapshot| snapshot.title),
+            Some("Local Edit".to_string())
+        );
+        assert_eq!(
+            recorded.remote_snapshot.map(|snapshot| snapshot.title),
+            Some("Remote Edit".to_string())
+        );
     }
 
     #[test]
@@ -2105,6 +2969,14 @@ END:VCALENDAR"#;
             )
             .unwrap();
         assert!(last_push_at.is_some());
+
+        let synced_snapshot = EventSyncMapService::new(conn)
+            .get_synced_snapshot(source_id, "uid-api-series")
+            .unwrap()
+            .as_deref()
+            .and_then(ConflictEventSnapshot::from_json)
+            .unwrap();
+        assert_eq!(synced_snapshot.title, "Series Local Edit");
     }
 
     #[test]
@@ -2605,6 +3477,166 @@ END:VCALENDAR"#;
         assert!(deleted_ids.is_empty());
     }
 
+    #[test]
+    fn test_restoring_trashed_synced_event_requeues_update_before_delete_is_pushed() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let source_id = create_rw_source(conn, "API Source");
+        let engine = CalendarSyncEngine::new(conn).unwrap();
+
+        let payload =
+            super::super::google_api::GoogleCalendarApiClient::parse_events_response_body(
+                r#"{
+                "items": [
+                    {
+                        "id": "remote-trash-1",
+                        "etag": "\"etag-trash-1\"",
+                        "status": "confirmed",
+                        "summary": "Planning",
+                        "iCalUID": "uid-api-trash",
+                        "updated": "2026-03-06T00:00:00Z",
+                        "start": { "dateTime": "2026-03-10T09:00:00Z" },
+                        "end": { "dateTime": "2026-03-10T10:00:00Z" }
+                    }
+                ],
+                "nextSyncToken": "sync-token-trash"
+            }"#,
+            )
+            .unwrap();
+        engine
+            .sync_source_from_google_payload(source_id, payload)
+            .unwrap();
+
+        let event_service = EventService::new(conn);
+        let event_id = event_service.list_all().unwrap().remove(0).id.unwrap();
+        event_service.delete_local(event_id).unwrap();
+
+        let trash = event_service.list_trash().unwrap();
+        assert!(trash[0].was_synced);
+        let restored = event_service.restore_from_trash(trash[0].trash_id).unwrap();
+        assert_eq!(restored.event.id, Some(event_id));
+        assert_eq!(
+            restored.sync,
+            crate::services::event::trash::TrashRestoreSync::Requeued
+        );
+
+        let (operation, local_event_id): (String, Option<i64>) = conn
+            .query_row(
+                "SELECT operation_type, local_event_id FROM outbound_sync_operations
+                 WHERE source_id = ?1 AND external_uid = ?2",
+                params![source_id, "uid-api-trash"],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            operation,
+            crate::models::outbound_sync_operation::OUTBOUND_OPERATION_UPDATE
+        );
+        assert_eq!(local_event_id, Some(event_id));
+
+        let mapped_event_id: i64 = conn
+            .query_row(
+                "SELECT local_event_id FROM event_sync_map WHERE source_id = ?1 AND external_uid = ?2",
+                params![source_id, "uid-api-trash"],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(mapped_event_id, event_id);
+    }
+
+    #[test]
+    fn test_restoring_trashed_synced_event_after_delete_is_pushed_recreates_it() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let source_id = create_rw_source(conn, "API Source");
+        let source = crate::services::calendar_sync::CalendarSourceService::new(conn)
+            .get_by_id(source_id)
+            .unwrap()
+            .unwrap();
+        let engine = CalendarSyncEngine::new(conn).unwrap();
+
+        let payload =
+            super::super::google_api::GoogleCalendarApiClient::parse_events_response_body(
+                r#"{
+                "items": [
+                    {
+                        "id": "remote-trash-2",
+                        "etag": "\"etag-trash-2\"",
+                        "status": "confirmed",
+                        "summary": "Planning",
+                        "iCalUID": "uid-api-trash-pushed",
+                        "updated": "2026-03-06T00:00:00Z",
+                        "start": { "dateTime": "2026-03-10T09:00:00Z" },
+                        "end": { "dateTime": "2026-03-10T10:00:00Z" }
+                    }
+                ],
+                "nextSyncToken": "sync-token-trash-2"
+            }"#,
+            )
+            .unwrap();
+        engine
+            .sync_source_from_google_payload(source_id, payload)
+            .unwrap();
+
+        let event_service = EventService::new(conn);
+        let event_id = event_service.list_all().unwrap().remove(0).id.unwrap();
+        event_service.delete_local(event_id).unwrap();
+        let writer = FakeGoogleOutboundWriter::default();
+        engine
+            .process_pending_outbound_operations(&source, &writer)
+            .unwrap();
+        assert_eq!(
+            writer.deleted_ids.lock().unwrap().clone(),
+            vec!["remote-trash-2".to_string()]
+        );
+
+        let trash = event_service.list_trash().unwrap();
+        let restored = event_service.restore_from_trash(trash[0].trash_id).unwrap();
+        assert_eq!(
+            restored.sync,
+            crate::services::event::trash::TrashRestoreSync::Requeued
+        );
+
+        // Editing before the push keeps the pending create
+        let mut edited = restored.event.clone();
+        edited.title = "Planning (restored)".to_string();
+        event_service.update_local(&edited).unwrap();
+        let operation: String = conn
+            .query_row(
+                "SELECT operation_type FROM outbound_sync_operations
+                 WHERE source_id = ?1 AND external_uid = ?2",
+                params![source_id, "uid-api-trash-pushed"],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(operation, OUTBOUND_OPERATION_CREATE);
+
+        engine
+            .process_pending_outbound_operations(&source, &writer)
+            .unwrap();
+        let inserted = writer.inserted_payloads.lock().unwrap().clone();
+        assert_eq!(inserted.len(), 1);
+        assert!(inserted[0].contains("Planning (restored)"));
+
+        let map_service = EventSyncMapService::new(conn);
+        assert!(map_service
+            .get_by_source_and_uid(source_id, "uid-api-trash-pushed")
+            .unwrap()
+            .is_none());
+        let mapping = map_service
+            .get_by_source_and_uid(source_id, "uid-api-inserted")
+            .unwrap()
+            .unwrap();
+        assert_eq!(mapping.local_event_id, event_id);
+        let remote_event_id = map_service
+            .get_remote_metadata(source_id, "uid-api-inserted")
+            .unwrap()
+            .and_then(|metadata| metadata.remote_event_id);
+        assert_eq!(remote_event_id.as_deref(), Some("remote-inserted-1"));
+    }
+
     #[test]
     fn test_process_pending_outbound_operations_deletes_detached_instance_and_clears_metadata() {
         let db = Database::new(":memory:").unwrap();
@@ -16,6 +16,8 @@ use crate::models::calendar_source::CalendarSource;
 use crate::models::event::Event;
 
 const GOOGLE_CALENDAR_EVENTS_ENDPOINT: &str = "https://www.googleapis.com/calendar/v3/calendars";
+const GOOGLE_CALENDAR_LIST_ENDPOINT: &str =
+    "https://www.googleapis.com/calendar/v3/users/me/calendarList";
 const DEFAULT_GOOGLE_API_BACKOFF_MINUTES: i64 = 15;
 
 #[derive(Debug, Error)]
@@ -64,6 +66,30 @@ impl GoogleRemoteEvent {
     }
 }
 
+/// One calendar from the account's calendar list.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoogleCalendarListEntry {
+    pub id: String,
+    pub summary: Option<String>,
+    pub summary_override: Option<String>,
+    pub background_color: Option<String>,
+    pub access_role: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+impl GoogleCalendarListEntry {
+    /// Name shown to the user: their own override first, then the calendar's title.
+    pub fn display_name(&self) -> &str {
+        self.summary_override
+            .as_deref()
+            .or(self.summary.as_deref())
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or(&self.id)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct GoogleEventsSyncPayload {
     pub items: Vec<GoogleRemoteEvent>,
@@ -85,6 +111,13 @@ pub(crate) trait GoogleOutboundWriter {
 
     fn delete_event(&self, source: &CalendarSource, remote_event_id: &str) -> Result<()>;
 
+    /// Creates a new event; Google assigns its id and iCalUID.
+    fn insert_event(
+        &self,
+        source: &CalendarSource,
+        payload_json: &str,
+    ) -> Result<GoogleRemoteEvent>;
+
     fn patch_detached_instance(
         &self,
         source: &CalendarSource,
@@ -195,6 +228,52 @@ impl GoogleCalendarApiClient {
         }
     }
 
+    /// Lists every calendar the account can see, following pagination.
+    pub fn fetch_calendar_list(&self) -> Result<Vec<GoogleCalendarListEntry>> {
+        let mut page_token: Option<String> = None;
+        let mut entries = Vec::new();
+
+        loop {
+            let mut request = self
+                .client
+                .get(GOOGLE_CALENDAR_LIST_ENDPOINT)
+                .bearer_auth(&self.access_token)
+                .query(&[("maxResults", "250")]);
+            if let Some(page) = page_token.as_deref() {
+                request = request.query(&[("pageToken", page)]);
+            }
+
+            let response = request
+                .send()
+                .context("Failed to call Google Calendar list API")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().unwrap_or_default();
+                return Err(anyhow!(
+                    "Google Calendar list API failed ({status}): {}",
+                    body.trim()
+                ));
+            }
+
+            let page: GoogleCalendarListResponse = response
+                .json()
+                .context("Failed to parse Google Calendar list response")?;
+
+            entries.extend(page.items.unwrap_or_default());
+            page_token = page.next_page_token;
+            if page_token.is_none() {
+                return Ok(entries);
+            }
+        }
+    }
+
+    pub fn parse_calendar_list_response_body(body: &str) -> Result<Vec<GoogleCalendarListEntry>> {
+        let payload: GoogleCalendarListResponse = serde_json::from_str(body)
+            .context("Failed to parse Google Calendar list response body")?;
+        Ok(payload.items.unwrap_or_default())
+    }
+
     pub fn parse_events_response_body(body: &str) -> Result<GoogleEventsSyncPayload> {
         let payload: GoogleEventsResponse =
             serde_json::from_str(body).context("Failed to parse Google events response body")?;
@@ -229,6 +308,15 @@ impl GoogleCalendarApiClient {
         ))
     }
 
+    fn events_request_url(source: &CalendarSource) -> Result<String> {
+        let calendar_id = Self::calendar_id(source)?;
+        Ok(format!(
+            "{}/{}/events",
+            GOOGLE_CALENDAR_EVENTS_ENDPOINT,
+            urlencoding::encode(&calendar_id)
+        ))
+    }
+
     fn instances_request_url(source: &CalendarSource, remote_event_id: &str) -> Result<String> {
         let calendar_id = Self::calendar_id(source)?;
         Ok(format!(
@@ -350,6 +438,33 @@ impl GoogleOutboundWriter for GoogleCalendarApiClient {
         Ok(())
     }
 
+    fn insert_event(
+        &self,
+        source: &CalendarSource,
+        payload_json: &str,
+    ) -> Result<GoogleRemoteEvent> {
+        let url = Self::events_request_url(source)?;
+        let body = build_google_event_request_body(payload_json)?;
+        let response = self
+            .client
+            .post(url)
+            .bearer_auth(&self.access_token)
+            .json(&body)
+            .send()
+            .context("Failed to call Google Calendar event insert API")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_default();
+            return Err(anyhow!(
+                "Google Calendar event insert failed ({status}): {}",
+                body.trim()
+            ));
+        }
+
+        Self::parse_single_event_response_body(&response.text().unwrap_or_default())
+    }
+
     fn patch_detached_instance(
         &self,
         source: &CalendarSource,
@@ -524,6 +639,13 @@ fn detached_instance_window(recurrence_token: &str) -> Result<(String, String)>
     Ok((time_min, time_max))
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoogleCalendarListResponse {
+    items: Option<Vec<GoogleCalendarListEntry>>,
+    next_page_token: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct GoogleEventsResponse {
@@ -998,6 +1120,24 @@ mod tests {
         assert_eq!(body["recurrence"][1], "EXDATE;VALUE=DATE:20260311,20260318");
     }
 
+    #[test]
+    fn parse_calendar_list_response_reads_names_colours_and_roles() {
+        let body = r##"{
+          "items": [
+            {"id": "me@example.com", "summary": "me@example.com", "backgroundColor": "#9fe1e7", "accessRole": "owner", "primary": true},
+            {"id": "team@group.calendar.google.com", "summary": "Team", "summaryOverride": "Work team", "accessRole": "reader"}
+          ]
+        }"##;
+
+        let entries = GoogleCalendarApiClient::parse_calendar_list_response_body(body).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].primary);
+        assert_eq!(entries[0].background_color.as_deref(), Some("#9fe1e7"));
+        assert_eq!(entries[1].display_name(), "Work team");
+        assert_eq!(entries[1].access_role, "reader");
+        assert!(!entries[1].primary);
+    }
+
     #[test]
     fn parse_retry_after_minutes_rounds_seconds_up_to_minutes() {
         assert_eq!(parse_retry_after_minutes(Some("1")), Some(1));
@@ -466,6 +466,46 @@ impl<'a> EventSyncMapService<'a> {
         }
     }
 
+    /// Stores the event as it was last agreed with the remote, used as the
+    /// common ancestor when a later conflict needs a three-way comparison.
+    pub fn set_synced_snapshot(
+        &self,
+        source_id: i64,
+        external_uid: &str,
+        snapshot_json: Option<&str>,
+    ) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE event_remote_metadata
+                 SET synced_snapshot_json = ?1
+                 WHERE source_id = ?2 AND external_uid = ?3",
+                params![snapshot_json, source_id, external_uid],
+            )
+            .context("Failed to store synced event snapshot")?;
+
+        Ok(())
+    }
+
+    pub fn get_synced_snapshot(
+        &self,
+        source_id: i64,
+        external_uid: &str,
+    ) -> Result<Option<String>> {
+        let result = self.conn.query_row(
+            "SELECT synced_snapshot_json
+             FROM event_remote_metadata
+             WHERE source_id = ?1 AND external_uid = ?2",
+            params![source_id, external_uid],
+            |row| row.get::<_, Option<String>>(0),
+        );
+
+        match result {
+            Ok(snapshot) => Ok(snapshot),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(err) => Err(err).context("Failed to fetch synced event snapshot"),
+        }
+    }
+
     pub fn delete_remote_metadata(&self, source_id: i64, external_uid: &str) -> Result<()> {
         self.conn
             .execute(
@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+pub mod diagnostics;
+pub mod discovery;
 pub mod engine;
 pub mod fetcher;
 mod google_api;
@@ -9,9 +11,13 @@ pub mod scheduler;
 
 use anyhow::{anyhow, Context, Result};
 use chrono::Local;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 
 use crate::models::calendar_source::CalendarSource;
+use crate::services::secrets;
+
+/// Maximum number of sync runs returned by [`CalendarSourceService::list_recent_sync_runs`].
+pub const SYNC_RUN_HISTORY_LIMIT: i64 = 50;
 
 pub struct CalendarSourceService<'a> {
     conn: &'a Connection,
@@ -41,19 +47,22 @@ impl<'a> CalendarSourceService<'a> {
     pub fn create(&self, mut source: CalendarSource) -> Result<CalendarSource> {
         source.validate().map_err(|err| anyhow!(err))?;
 
+        let stored_url = Self::store_ics_url(None, &source.ics_url)?;
         let now = Local::now().to_rfc3339();
-        self.conn
+        let inserted = self
+            .conn
             .execute(
                 "INSERT INTO calendar_sources (
                     name, source_type, ics_url, enabled, poll_interval_minutes,
                     sync_past_days, sync_future_days,
                     sync_capability, api_sync_token, last_push_at,
-                    last_sync_at, last_sync_status, last_error, created_at, updated_at
-                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                    last_sync_at, last_sync_status, last_error, created_at, updated_at,
+                    google_account_id, remote_calendar_id, color
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
                 params![
                     source.name,
                     source.source_type,
-                    source.ics_url,
+                    stored_url,
                     source.enabled as i32,
                     source.poll_interval_minutes,
                     source.sync_past_days,
@@ -66,9 +75,13 @@ impl<'a> CalendarSourceService<'a> {
                     source.last_error,
                     now,
                     now,
+                    source.google_account_id,
+                    source.remote_calendar_id,
+                    source.color,
                 ],
             )
-            .context("Failed to insert calendar source")?;
+            .context("Failed to insert calendar source");
+        secrets::discard_on_error(inserted, None, Some(&stored_url))?;
 
         source.id = Some(self.conn.last_insert_rowid());
         Ok(source)
@@ -81,15 +94,20 @@ impl<'a> CalendarSourceService<'a> {
                 "SELECT id, name, source_type, ics_url, enabled, poll_interval_minutes,
                     sync_past_days, sync_future_days,
                     sync_capability, api_sync_token, last_push_at,
-                    last_sync_at, last_sync_status, last_error
+                    last_sync_at, last_sync_status, last_error, google_account_id,
+                    remote_calendar_id, color
                  FROM calendar_sources
                  ORDER BY name COLLATE NOCASE ASC",
             )
             .context("Failed to prepare calendar source list query")?;
 
         let rows = stmt.query_map([], Self::row_to_source)?;
-        rows.collect::<Result<Vec<_>, _>>()
-            .context("Failed to load calendar sources")
+        Ok(rows
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to load calendar sources")?
+            .into_iter()
+            .map(Self::resolve_secrets)
+            .collect())
     }
 
     pub fn get_by_id(&self, id: i64) -> Result<Option<CalendarSource>> {
@@ -97,7 +115,8 @@ impl<'a> CalendarSourceService<'a> {
             "SELECT id, name, source_type, ics_url, enabled, poll_interval_minutes,
                     sync_past_days, sync_future_days,
                     sync_capability, api_sync_token, last_push_at,
-                    last_sync_at, last_sync_status, last_error
+                    last_sync_at, last_sync_status, last_error, google_account_id,
+                    remote_calendar_id, color
              FROM calendar_sources
              WHERE id = ?1",
             [id],
@@ -105,7 +124,7 @@ impl<'a> CalendarSourceService<'a> {
         );
 
         match result {
-            Ok(source) => Ok(Some(source)),
+            Ok(source) => Ok(Some(Self::resolve_secrets(source))),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(err) => Err(err).context("Failed to fetch calendar source by id"),
         }
@@ -118,7 +137,9 @@ impl<'a> CalendarSourceService<'a> {
             .id
             .ok_or_else(|| anyhow!("Calendar source ID is required for update"))?;
 
-        let rows_affected = self
+        let existing_url = self.stored_ics_url(id)?;
+        let stored_url = Self::store_ics_url(existing_url.as_deref(), &source.ics_url)?;
+        let updated = self
             .conn
             .execute(
                 "UPDATE calendar_sources
@@ -132,12 +153,15 @@ impl<'a> CalendarSourceService<'a> {
                  sync_capability = ?8,
                  api_sync_token = ?9,
                  last_push_at = ?10,
-                 updated_at = ?11
-             WHERE id = ?12",
+                 google_account_id = ?11,
+                 remote_calendar_id = ?12,
+                 color = ?13,
+                 updated_at = ?14
+             WHERE id = ?15",
                 params![
                     source.name,
                     source.source_type,
-                    source.ics_url,
+                    stored_url,
                     source.enabled as i32,
                     source.poll_interval_minutes,
                     source.sync_past_days,
@@ -145,16 +169,23 @@ impl<'a> CalendarSourceService<'a> {
                     source.sync_capability,
                     source.api_sync_token,
                     source.last_push_at,
+                    source.google_account_id,
+                    source.remote_calendar_id,
+                    source.color,
                     Local::now().to_rfc3339(),
                     id,
                 ],
             )
-            .context("Failed to update calendar source")?;
-
-        if rows_affected == 0 {
-            return Err(anyhow!("Calendar source with id {} not found", id));
+            .context("Failed to update calendar source")
+            .and_then(|rows_affected| match rows_affected {
+                0 => Err(anyhow!("Calendar source with id {} not found", id)),
+                _ => Ok(()),
+            });
+        secrets::discard_on_error(updated, existing_url.as_deref(), Some(&stored_url))?;
+
+        if stored_url.is_empty() {
+            secrets::forget(existing_url.as_deref());
         }
-
         Ok(())
     }
 
@@ -340,7 +371,7 @@ impl<'a> CalendarSourceService<'a> {
         source_id: i64,
         limit: i64,
     ) -> Result<Vec<SyncRunDiagnostics>> {
-        let safe_limit = limit.clamp(1, 20);
+        let safe_limit = limit.clamp(1, SYNC_RUN_HISTORY_LIMIT);
         let mut stmt = self
             .conn
             .prepare(
@@ -360,6 +391,7 @@ impl<'a> CalendarSourceService<'a> {
     }
 
     pub fn delete(&self, id: i64) -> Result<()> {
+        let stored_url = self.stored_ics_url(id)?;
         let rows_affected = self
             .conn
             .execute("DELETE FROM calendar_sources WHERE id = ?1", [id])
@@ -369,9 +401,49 @@ impl<'a> CalendarSourceService<'a> {
             return Err(anyhow!("Calendar source with id {} not found", id));
         }
 
+        secrets::forget(stored_url.as_deref());
         Ok(())
     }
 
+    /// Raw `ics_url` column value, which is a secret-store reference for private URLs.
+    fn stored_ics_url(&self, id: i64) -> Result<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT ics_url FROM calendar_sources WHERE id = ?1",
+                [id],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to read stored calendar source URL")
+    }
+
+    /// ICS URLs embed a private key, so they go to the secret store and the
+    /// column keeps the reference. Empty URLs (API-only sources) stay empty.
+    fn store_ics_url(existing: Option<&str>, url: &str) -> Result<String> {
+        if url.is_empty() {
+            return Ok(String::new());
+        }
+        secrets::put(existing, url).context("Failed to store calendar source URL")
+    }
+
+    /// Replaces the stored reference with the URL. A URL that can't be read
+    /// is logged and left empty, so one broken secret doesn't hide the other
+    /// sources; syncing that source then reports it.
+    fn resolve_secrets(mut source: CalendarSource) -> CalendarSource {
+        match secrets::resolve(&source.ics_url) {
+            Ok(url) => source.ics_url = url,
+            Err(err) => {
+                log::warn!(
+                    "Failed to read the ICS URL of calendar source '{}': {:#}",
+                    source.name,
+                    err
+                );
+                source.ics_url = String::new();
+            }
+        }
+        source
+    }
+
     fn row_to_source(row: &rusqlite::Row<'_>) -> rusqlite::Result<CalendarSource> {
         Ok(CalendarSource {
             id: Some(row.get(0)?),
@@ -388,6 +460,9 @@ impl<'a> CalendarSourceService<'a> {
             last_sync_at: row.get(11)?,
             last_sync_status: row.get(12)?,
             last_error: row.get(13)?,
+            google_account_id: row.get(14)?,
+            remote_calendar_id: row.get(15)?,
+            color: row.get(16)?,
         })
     }
 
@@ -431,6 +506,9 @@ mod tests {
             sync_capability: crate::models::calendar_source::SYNC_CAPABILITY_READ_ONLY.to_string(),
             api_sync_token: None,
             last_push_at: None,
+            google_account_id: None,
+            remote_calendar_id: None,
+            color: None,
             last_sync_at: None,
             last_sync_status: None,
             last_error: None,
@@ -484,6 +562,9 @@ mod tests {
             sync_capability: crate::models::calendar_source::SYNC_CAPABILITY_READ_ONLY.to_string(),
             api_sync_token: None,
             last_push_at: None,
+            google_account_id: None,
+            remote_calendar_id: None,
+            color: None,
             last_sync_at: None,
             last_sync_status: None,
             last_error: None,
@@ -572,6 +653,29 @@ mod tests {
         assert!(service.get_by_id(source_id).unwrap().is_none());
     }
 
+    #[test]
+    fn test_list_all_keeps_sources_with_unreadable_urls() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let service = CalendarSourceService::new(db.connection());
+
+        let broken = service.create(build_source("Broken")).unwrap();
+        service.create(build_source("Work")).unwrap();
+        db.connection()
+            .execute(
+                "UPDATE calendar_sources SET ics_url = 'secret-ref:missing' WHERE id = ?1",
+                [broken.id.unwrap()],
+            )
+            .unwrap();
+
+        let sources = service.list_all().unwrap();
+        assert_eq!(sources.len(), 2);
+        let broken = sources.iter().find(|s| s.name == "Broken").unwrap();
+        assert!(broken.ics_url.is_empty());
+        let work = sources.iter().find(|s| s.name == "Work").unwrap();
+        assert!(work.ics_url.starts_with("https://"));
+    }
+
     #[test]
     fn test_record_and_read_latest_sync_run() {
         let db = Database::new(":memory:").unwrap();
@@ -218,6 +218,31 @@ impl<'a> CategoryService<'a> {
 
         Ok(count)
     }
+
+    /// Get the IDs of events using a specific category.
+    pub fn event_ids_using(&self, category_name: &str) -> Result<Vec<i64>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id FROM events WHERE category = ?1 ORDER BY id")?;
+        let ids = stmt
+            .query_map(params![category_name], |row| row.get(0))?
+            .collect::<Result<Vec<i64>, _>>()
+            .context("Failed to fetch events for category")?;
+        Ok(ids)
+    }
+
+    /// Set the category of the given events, e.g. when undoing a deletion.
+    pub fn assign_to_events(&self, category_name: &str, event_ids: &[i64]) -> Result<()> {
+        for id in event_ids {
+            self.conn
+                .execute(
+                    "UPDATE events SET category = ?1 WHERE id = ?2",
+                    params![category_name, id],
+                )
+                .context("Failed to assign category to event")?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
 use std::time::Instant;
 
 use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
 
 use super::models::{
     CountdownAutoDismissConfig, CountdownCardGeometry, CountdownCardId, CountdownCardState,
@@ -14,8 +15,9 @@ use super::palette::apply_event_palette_if_needed;
 /// Snapshot of a card's editable state, taken when the settings dialog opens.
 ///
 /// Used for apply/cancel semantics: changes preview live on the card, but
-/// are reverted from this snapshot if the user cancels.
-#[derive(Debug, Clone)]
+/// are reverted from this snapshot if the user cancels, and kept as the
+/// before/after state of the undo entry if they apply.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CardSettingsSnapshot {
     pub visuals: CountdownCardVisuals,
     pub title_override: Option<String>,
@@ -332,6 +334,19 @@ impl CountdownService {
         false
     }
 
+    /// Put back a card removed earlier (undo), keeping its id and geometry.
+    pub fn restore_card(&mut self, card: CountdownCardState) -> bool {
+        if self.cards.iter().any(|existing| existing.id == card.id) {
+            return false;
+        }
+        self.next_id = self.next_id.max(card.id.0 + 1);
+        self.card_order.push(card.id);
+        self.cards.push(card);
+        self.sort_cards_by_date();
+        self.dirty = true;
+        true
+    }
+
     /// Remove all countdown cards associated with a given event ID.
     /// Call this when deleting an event to keep the in-memory state in sync.
     pub fn remove_cards_for_event(&mut self, event_id: i64) -> usize {
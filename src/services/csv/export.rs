@@ -0,0 +1,121 @@
+//! Writing events to CSV, one row per occurrence.
+//!
+//! The columns use the same headers the importer recognises, so an exported
+//! file can be edited in a spreadsheet and imported again. All-day events
+//! show their last day as the end, the way a spreadsheet user expects.
+
+use super::chrono_date_format;
+use super::import::CsvField;
+use crate::models::event::Event;
+use anyhow::{Context, Result};
+use chrono::Duration;
+use std::fs;
+use std::path::Path;
+
+/// The CSV text for `events` (already expanded into occurrences), with dates
+/// in `date_format` (a `Settings.date_format` value) and 24-hour times.
+pub fn to_string(events: &[Event], date_format: &str) -> Result<String> {
+    let date_pattern = chrono_date_format(date_format);
+    let date_time_pattern = format!("{} %H:%M", date_pattern);
+
+    let mut writer = ::csv::WriterBuilder::new()
+        .terminator(::csv::Terminator::CRLF)
+        .from_writer(Vec::new());
+    writer.write_record(CsvField::ALL.iter().map(|field| field.label()))?;
+
+    for event in events {
+        let (start, end) = if event.all_day {
+            let last_day = (event.end - Duration::days(1)).max(event.start);
+            (
+                event.start.format(date_pattern).to_string(),
+                last_day.format(date_pattern).to_string(),
+            )
+        } else {
+            (
+                event.start.format(&date_time_pattern).to_string(),
+                event.end.format(&date_time_pattern).to_string(),
+            )
+        };
+        writer.write_record([
+            event.title.as_str(),
+            &start,
+            &end,
+            if event.all_day { "Yes" } else { "No" },
+            event.location.as_deref().unwrap_or(""),
+            event.category.as_deref().unwrap_or(""),
+            event.description.as_deref().unwrap_or(""),
+        ])?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| anyhow::anyhow!("Failed to finish CSV: {}", e.error()))?;
+    String::from_utf8(bytes).context("CSV output was not valid UTF-8")
+}
+
+/// Write `events` to a .csv file on disk
+pub fn to_file(events: &[Event], date_format: &str, path: &Path) -> Result<()> {
+    let content = to_string(events, date_format)?;
+    fs::write(path, content).context(format!("Failed to write .csv file: {:?}", path))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_string;
+    use crate::models::event::Event;
+    use crate::services::csv::import::{to_events, CsvColumnMapping, CsvTable};
+    use chrono::{Duration, Local, TimeZone};
+
+    fn events() -> Vec<Event> {
+        let start = Local.with_ymd_and_hms(2026, 4, 3, 22, 0, 0).unwrap();
+        let night = Event::builder()
+            .title("Night shift, ward 2")
+            .start(start)
+            .end(start + Duration::hours(8))
+            .location("Ward 2")
+            .category("Roster")
+            .description("Handover at \"06:00\"\nBring badge")
+            .build()
+            .unwrap();
+        let leave = Event::builder()
+            .title("Leave")
+            .start(Local.with_ymd_and_hms(2026, 4, 6, 0, 0, 0).unwrap())
+            .end(Local.with_ymd_and_hms(2026, 4, 9, 0, 0, 0).unwrap())
+            .all_day(true)
+            .build()
+            .unwrap();
+        vec![night, leave]
+    }
+
+    #[test]
+    fn test_export_rows() {
+        let csv = to_string(&events(), "DD/MM/YYYY").unwrap();
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(
+            lines[0],
+            "Title,Start,End,All Day,Location,Category,Description"
+        );
+        assert_eq!(
+            lines[1],
+            "\"Night shift, ward 2\",03/04/2026 22:00,04/04/2026 06:00,No,Ward 2,Roster,\"Handover at \"\"06:00\"\"\nBring badge\""
+        );
+        assert_eq!(lines[2], "Leave,06/04/2026,08/04/2026,Yes,,,");
+    }
+
+    #[test]
+    fn test_export_then_import_round_trips() {
+        let original = events();
+        for date_format in ["DD/MM/YYYY", "MM/DD/YYYY", "YYYY-MM-DD"] {
+            let csv = to_string(&original, date_format).unwrap();
+            let table = CsvTable::parse(&csv).unwrap();
+            let mapping = CsvColumnMapping::guess(&table.headers);
+            let import = to_events(&table, &mapping, date_format).unwrap();
+
+            assert!(import.errors.is_empty(), "{:?}", import.errors);
+            let imported: Vec<Event> = import.events.into_iter().map(|i| i.event).collect();
+            assert_eq!(imported, original, "{}", date_format);
+        }
+    }
+}
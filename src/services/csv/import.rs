@@ -0,0 +1,455 @@
+//! Reading events from a CSV file through a user-chosen column mapping.
+//!
+//! The first row holds the column headers. Dates are read in the format the
+//! user picks (by default `Settings.date_format`), with ISO `YYYY-MM-DD`
+//! always accepted as well, optionally followed by a 24- or 12-hour time.
+//! Rows become [`ImportedIcsEvent`]s so they go through the same preview,
+//! duplicate check and undo as an .ics import.
+
+use super::chrono_date_format;
+use crate::models::event::Event;
+use crate::services::icalendar::import::ImportedIcsEvent;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+
+/// Time patterns accepted after the date, and on their own in the end column
+const TIME_FORMATS: &[&str] = &["%H:%M", "%H:%M:%S", "%I:%M %p", "%I:%M%p", "%I %p", "%I%p"];
+
+/// The cells of a CSV file, split into the header row and the data rows
+#[derive(Debug, Clone, Default)]
+pub struct CsvTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl CsvTable {
+    /// Parse CSV text, detecting whether it is separated by commas,
+    /// semicolons or tabs.
+    pub fn parse(content: &str) -> Result<Self> {
+        let content = content.trim_start_matches('\u{feff}');
+        let mut reader = ::csv::ReaderBuilder::new()
+            .delimiter(detect_delimiter(content))
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(content.as_bytes());
+
+        let mut records = reader.records();
+        let headers = match records.next() {
+            Some(record) => record
+                .context("Failed to read the CSV header row")?
+                .iter()
+                .map(|cell| cell.trim().to_string())
+                .collect(),
+            None => bail!("The CSV file is empty"),
+        };
+        let mut rows = Vec::new();
+        for (index, record) in records.enumerate() {
+            let record = record.with_context(|| format!("Failed to read CSV row {}", index + 2))?;
+            rows.push(record.iter().map(str::to_string).collect());
+        }
+        Ok(Self { headers, rows })
+    }
+
+    /// The trimmed cell at `row` and `column`; empty when the row is short
+    fn cell(&self, row: usize, column: Option<usize>) -> &str {
+        column
+            .and_then(|column| self.rows[row].get(column))
+            .map(|cell| cell.trim())
+            .unwrap_or("")
+    }
+}
+
+/// The separator used most in the first line, outside quotes
+fn detect_delimiter(content: &str) -> u8 {
+    let first_line = content.lines().next().unwrap_or("");
+    let mut counts = [(b',', 0usize), (b';', 0), (b'\t', 0)];
+    let mut quoted = false;
+    for c in first_line.bytes() {
+        if c == b'"' {
+            quoted = !quoted;
+        } else if !quoted {
+            if let Some(entry) = counts.iter_mut().find(|(delimiter, _)| *delimiter == c) {
+                entry.1 += 1;
+            }
+        }
+    }
+    counts
+        .iter()
+        .max_by_key(|(_, count)| *count)
+        .filter(|(_, count)| *count > 0)
+        .map(|(delimiter, _)| *delimiter)
+        .unwrap_or(b',')
+}
+
+/// An event field a CSV column can be mapped to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvField {
+    Title,
+    Start,
+    End,
+    AllDay,
+    Location,
+    Category,
+    Description,
+}
+
+impl CsvField {
+    pub const ALL: [CsvField; 7] = [
+        CsvField::Title,
+        CsvField::Start,
+        CsvField::End,
+        CsvField::AllDay,
+        CsvField::Location,
+        CsvField::Category,
+        CsvField::Description,
+    ];
+
+    /// Display name, also used as the column header on export
+    pub fn label(self) -> &'static str {
+        match self {
+            CsvField::Title => "Title",
+            CsvField::Start => "Start",
+            CsvField::End => "End",
+            CsvField::AllDay => "All Day",
+            CsvField::Location => "Location",
+            CsvField::Category => "Category",
+            CsvField::Description => "Description",
+        }
+    }
+
+    /// Whether an import needs this field mapped
+    pub fn is_required(self) -> bool {
+        matches!(self, CsvField::Title | CsvField::Start)
+    }
+
+    /// Lower-case header names recognised when guessing the mapping
+    fn header_names(self) -> &'static [&'static str] {
+        match self {
+            CsvField::Title => &["title", "subject", "summary", "name", "event", "shift"],
+            CsvField::Start => &["start", "start date", "starts", "from", "date", "begin"],
+            CsvField::End => &["end", "end date", "ends", "to", "finish", "until"],
+            CsvField::AllDay => &["all day", "all-day", "allday", "all day event"],
+            CsvField::Location => &["location", "place", "where", "venue", "site"],
+            CsvField::Category => &["category", "categories", "type", "calendar"],
+            CsvField::Description => &["description", "notes", "note", "details", "comments"],
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|field| *field == self)
+            .unwrap_or(0)
+    }
+}
+
+/// Which column, if any, each event field is read from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CsvColumnMapping {
+    columns: [Option<usize>; 7],
+}
+
+impl CsvColumnMapping {
+    /// Map each field to the first header that names it
+    pub fn guess(headers: &[String]) -> Self {
+        let mut mapping = Self::default();
+        for field in CsvField::ALL {
+            mapping.columns[field.index()] = headers.iter().position(|header| {
+                let header = header.trim().to_lowercase();
+                field.header_names().contains(&header.as_str())
+            });
+        }
+        mapping
+    }
+
+    pub fn column(&self, field: CsvField) -> Option<usize> {
+        self.columns[field.index()]
+    }
+
+    pub fn set_column(&mut self, field: CsvField, column: Option<usize>) {
+        self.columns[field.index()] = column;
+    }
+
+    /// Required fields that have no column yet
+    pub fn missing_required(&self) -> Vec<CsvField> {
+        CsvField::ALL
+            .into_iter()
+            .filter(|field| field.is_required() && self.column(*field).is_none())
+            .collect()
+    }
+}
+
+/// A data row that could not be turned into an event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRowError {
+    /// Line number in the file, counting the header as line 1
+    pub line: usize,
+    pub message: String,
+}
+
+/// The events read from a table and the rows that were skipped
+#[derive(Debug, Clone, Default)]
+pub struct CsvImport {
+    pub events: Vec<ImportedIcsEvent>,
+    pub errors: Vec<CsvRowError>,
+}
+
+/// Convert every row of `table` using `mapping`, with dates in `date_format`
+/// (a `Settings.date_format` value).
+pub fn to_events(
+    table: &CsvTable,
+    mapping: &CsvColumnMapping,
+    date_format: &str,
+) -> Result<CsvImport> {
+    if let Some(field) = mapping.missing_required().first() {
+        bail!("Choose a column for {}", field.label());
+    }
+
+    let pattern = chrono_date_format(date_format);
+    let mut import = CsvImport::default();
+    for row in 0..table.rows.len() {
+        if table.rows[row].iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        match row_to_event(table, row, mapping, pattern) {
+            Ok(event) => import.events.push(ImportedIcsEvent {
+                event,
+                uid: None,
+                recurrence_id: None,
+                raw_last_modified: None,
+                sequence: None,
+                duration: None,
+                has_start: true,
+                has_end: true,
+            }),
+            Err(e) => import.errors.push(CsvRowError {
+                line: row + 2,
+                message: e.to_string(),
+            }),
+        }
+    }
+    Ok(import)
+}
+
+fn row_to_event(
+    table: &CsvTable,
+    row: usize,
+    mapping: &CsvColumnMapping,
+    date_pattern: &str,
+) -> Result<Event> {
+    let cell = |field: CsvField| table.cell(row, mapping.column(field));
+    let optional = |field: CsvField| Some(cell(field).to_string()).filter(|s| !s.is_empty());
+
+    let title = cell(CsvField::Title);
+    if title.is_empty() {
+        bail!("No title");
+    }
+
+    let start_cell = cell(CsvField::Start);
+    if start_cell.is_empty() {
+        bail!("No start date");
+    }
+    let (start_date, start_time) = parse_date_time(start_cell, date_pattern)
+        .with_context(|| format!("Unrecognised start date \"{}\"", start_cell))?;
+
+    let end_cell = cell(CsvField::End);
+    let end_time_only = parse_time(end_cell);
+    let end = if end_cell.is_empty() {
+        None
+    } else if let Some(time) = end_time_only {
+        Some((start_date, Some(time)))
+    } else {
+        Some(
+            parse_date_time(end_cell, date_pattern)
+                .with_context(|| format!("Unrecognised end date \"{}\"", end_cell))?,
+        )
+    };
+
+    let all_day = match cell(CsvField::AllDay) {
+        "" => start_time.is_none() && end.is_none_or(|(_, time)| time.is_none()),
+        value => is_truthy(value),
+    };
+
+    let (start, end) = if all_day {
+        // Spreadsheets give the last day; events store the day after it
+        let last_date = end.map(|(date, _)| date).unwrap_or(start_date);
+        if last_date < start_date {
+            bail!("Ends before it starts");
+        }
+        (
+            local_datetime(start_date.and_time(NaiveTime::MIN))?,
+            local_datetime(last_date.and_time(NaiveTime::MIN) + Duration::days(1))?,
+        )
+    } else {
+        let start = start_date.and_time(start_time.unwrap_or(NaiveTime::MIN));
+        let end = match end {
+            None => start + Duration::hours(1),
+            // An end time alone before the start time is the next morning,
+            // as in an overnight shift
+            Some((date, Some(time))) if end_time_only.is_some() && time <= start.time() => {
+                (date + Duration::days(1)).and_time(time)
+            }
+            Some((date, time)) => date.and_time(time.unwrap_or(NaiveTime::MIN)),
+        };
+        if end <= start {
+            bail!("Ends before it starts");
+        }
+        (local_datetime(start)?, local_datetime(end)?)
+    };
+
+    Ok(Event {
+        id: None,
+        title: title.to_string(),
+        description: optional(CsvField::Description),
+        location: optional(CsvField::Location),
+        start,
+        end,
+        all_day,
+        category: optional(CsvField::Category),
+        color: None,
+        recurrence_rule: None,
+        recurrence_exceptions: None,
+        created_at: None,
+        updated_at: None,
+        ics_raw_properties: None,
+    })
+}
+
+/// A date in `date_pattern` or ISO form, optionally followed by a time
+fn parse_date_time(value: &str, date_pattern: &str) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    let value = value.trim();
+    for pattern in [date_pattern, "%Y-%m-%d"] {
+        if let Ok(date) = NaiveDate::parse_from_str(value, pattern) {
+            return Some((date, None));
+        }
+        for separator in [" ", "T"] {
+            for time_format in TIME_FORMATS {
+                let format = format!("{}{}{}", pattern, separator, time_format);
+                if let Ok(dt) = NaiveDateTime::parse_from_str(value, &format) {
+                    return Some((dt.date(), Some(dt.time())));
+                }
+            }
+        }
+    }
+    None
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(value.trim(), format).ok())
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_lowercase().as_str(),
+        "yes" | "y" | "true" | "1" | "x" | "all day" | "all-day"
+    )
+}
+
+/// The local instant for a wall-clock time, taking the earlier one when the
+/// clocks go back
+fn local_datetime(naive: NaiveDateTime) -> Result<DateTime<Local>> {
+    naive
+        .and_local_timezone(Local)
+        .earliest()
+        .with_context(|| format!("{} does not exist in the local time zone", naive))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_events, CsvColumnMapping, CsvField, CsvTable};
+    use chrono::{NaiveDate, Timelike};
+
+    fn table(content: &str) -> CsvTable {
+        CsvTable::parse(content).unwrap()
+    }
+
+    #[test]
+    fn test_guess_mapping_from_headers() {
+        let table = table("Subject;Start Date;End;Where;Notes\nA;01/03/2026;;;\n");
+        let mapping = CsvColumnMapping::guess(&table.headers);
+
+        assert_eq!(mapping.column(CsvField::Title), Some(0));
+        assert_eq!(mapping.column(CsvField::Start), Some(1));
+        assert_eq!(mapping.column(CsvField::End), Some(2));
+        assert_eq!(mapping.column(CsvField::Location), Some(3));
+        assert_eq!(mapping.column(CsvField::Description), Some(4));
+        assert_eq!(mapping.column(CsvField::AllDay), None);
+        assert!(mapping.missing_required().is_empty());
+    }
+
+    #[test]
+    fn test_rows_in_day_first_format() {
+        let table = table(
+            "Title,Start,End,Location,Category\n\
+             \"Early, shift\",03/04/2026 06:00,03/04/2026 14:30,Ward 2,Roster\n\
+             Night shift,03/04/2026 22:00,06:00,Ward 2,Roster\n\
+             Leave,06/04/2026,08/04/2026,,\n",
+        );
+        let mapping = CsvColumnMapping::guess(&table.headers);
+        let import = to_events(&table, &mapping, "DD/MM/YYYY").unwrap();
+
+        assert!(import.errors.is_empty(), "{:?}", import.errors);
+        let events: Vec<_> = import.events.iter().map(|i| &i.event).collect();
+
+        assert_eq!(events[0].title, "Early, shift");
+        assert_eq!(
+            events[0].start.date_naive(),
+            NaiveDate::from_ymd_opt(2026, 4, 3).unwrap()
+        );
+        assert_eq!(events[0].end.hour(), 14);
+        assert_eq!(events[0].location.as_deref(), Some("Ward 2"));
+        assert_eq!(events[0].category.as_deref(), Some("Roster"));
+
+        // The end time alone is the next morning
+        assert_eq!(
+            events[1].end.date_naive(),
+            NaiveDate::from_ymd_opt(2026, 4, 4).unwrap()
+        );
+
+        // All-day, with the inclusive last day stored as the day after
+        assert!(events[2].all_day);
+        assert_eq!(
+            events[2].end.date_naive(),
+            NaiveDate::from_ymd_opt(2026, 4, 9).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_month_first_format_and_twelve_hour_times() {
+        let table =
+            table("Title,Start,All Day\nReview,04/03/2026 2:30 PM,no\nOff,04/05/2026,yes\n");
+        let mapping = CsvColumnMapping::guess(&table.headers);
+        let import = to_events(&table, &mapping, "MM/DD/YYYY").unwrap();
+
+        let review = &import.events[0].event;
+        assert_eq!(
+            review.start.date_naive(),
+            NaiveDate::from_ymd_opt(2026, 4, 3).unwrap()
+        );
+        assert_eq!(review.start.hour(), 14);
+        assert_eq!(review.end - review.start, chrono::Duration::hours(1));
+        assert!(import.events[1].event.all_day);
+    }
+
+    #[test]
+    fn test_bad_rows_are_reported_with_line_numbers() {
+        let table = table("Title,Start,End\n,01/01/2026,\nBad,31/02/2026,\nBackwards,02/01/2026 10:00,01/01/2026 09:00\nOk,2026-01-05,\n");
+        let mapping = CsvColumnMapping::guess(&table.headers);
+        let import = to_events(&table, &mapping, "DD/MM/YYYY").unwrap();
+
+        assert_eq!(import.events.len(), 1);
+        let lines: Vec<usize> = import.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_unmapped_required_field_is_an_error() {
+        let table = table("Name,When\nA,01/01/2026\n");
+        let mut mapping = CsvColumnMapping::guess(&table.headers);
+        mapping.set_column(CsvField::Start, None);
+
+        assert!(to_events(&table, &mapping, "DD/MM/YYYY").is_err());
+    }
+}
@@ -0,0 +1,14 @@
+//! CSV import and export of events, for rosters kept in spreadsheets.
+
+pub mod export;
+pub mod import;
+
+/// The chrono pattern for a `Settings.date_format` value such as
+/// `DD/MM/YYYY`. Unknown values fall back to day-first.
+pub fn chrono_date_format(date_format: &str) -> &'static str {
+    match date_format.to_ascii_uppercase().as_str() {
+        "MM/DD/YYYY" => "%m/%d/%Y",
+        "YYYY-MM-DD" => "%Y-%m-%d",
+        _ => "%d/%m/%Y",
+    }
+}
@@ -44,6 +44,21 @@ impl Database {
         &self.path
     }
 
+    /// A value that changes whenever this connection or another one writes
+    /// to the database. Cheap enough to check every frame to tell whether a
+    /// cached query result is stale.
+    pub fn change_stamp(&self) -> Result<(i64, i64)> {
+        let own_changes = self
+            .conn
+            .query_row("SELECT total_changes()", [], |row| row.get(0))
+            .context("Failed to read database change count")?;
+        let data_version = self
+            .conn
+            .query_row("PRAGMA data_version", [], |row| row.get(0))
+            .context("Failed to read database data version")?;
+        Ok((own_changes, data_version))
+    }
+
     /// Creates tables, runs migrations, and seeds default data.
     pub fn initialize_schema(&self) -> Result<()> {
         schema::initialize_schema(self.connection())
@@ -43,3 +43,246 @@ pub fn copy_column(conn: &Connection, table: &str, from: &str, to: &str) -> Resu
     })?;
     Ok(())
 }
+
+/// One numbered schema change.
+///
+/// Migrations run in ascending `version` order, each inside its own
+/// transaction, and are recorded in the `schema_version` table so they run
+/// exactly once per database.
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub up: fn(&Connection) -> Result<()>,
+    /// Reverts `up`; `None` for migrations that cannot be undone.
+    pub down: Option<fn(&Connection) -> Result<()>>,
+}
+
+/// Returned when the database was written by a newer build of the app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaTooNewError {
+    pub database_version: i64,
+    pub supported_version: i64,
+}
+
+impl std::fmt::Display for SchemaTooNewError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "This calendar database was created by a newer version of Rust Calendar \
+             (schema version {}, this version understands up to {}). \
+             Please update Rust Calendar to open it; your data has not been changed.",
+            self.database_version, self.supported_version
+        )
+    }
+}
+
+impl std::error::Error for SchemaTooNewError {}
+
+fn ensure_schema_version_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )
+    .context("Failed to create schema_version table")?;
+    Ok(())
+}
+
+/// Highest applied migration version, or 0 for a new or pre-versioning database.
+pub fn current_version(conn: &Connection) -> Result<i64> {
+    ensure_schema_version_table(conn)?;
+    conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+        [],
+        |row| row.get(0),
+    )
+    .context("Failed to read schema version")
+}
+
+/// Applies every migration newer than the database's current version.
+///
+/// Refuses with [`SchemaTooNewError`] before touching anything when the
+/// database is ahead of the newest migration this build knows about.
+pub fn run_migrations(conn: &Connection, migrations: &[Migration]) -> Result<()> {
+    let latest = migrations.last().map(|m| m.version).unwrap_or(0);
+    let current = current_version(conn)?;
+    if current > latest {
+        return Err(SchemaTooNewError {
+            database_version: current,
+            supported_version: latest,
+        }
+        .into());
+    }
+
+    for migration in migrations.iter().filter(|m| m.version > current) {
+        let tx = conn
+            .unchecked_transaction()
+            .context("Failed to begin migration transaction")?;
+        (migration.up)(&tx).with_context(|| {
+            format!(
+                "Migration {} ({}) failed",
+                migration.version, migration.name
+            )
+        })?;
+        tx.execute(
+            "INSERT INTO schema_version (version, name) VALUES (?1, ?2)",
+            rusqlite::params![migration.version, migration.name],
+        )
+        .context("Failed to record schema version")?;
+        tx.commit().with_context(|| {
+            format!(
+                "Failed to commit migration {} ({})",
+                migration.version, migration.name
+            )
+        })?;
+        log::info!(
+            "Applied schema migration {} ({})",
+            migration.version,
+            migration.name
+        );
+    }
+
+    Ok(())
+}
+
+/// Reverts applied migrations down to `target` using their `down` steps.
+///
+/// All steps run in one transaction, so a migration without a down path
+/// leaves the database unchanged.
+#[allow(dead_code)]
+pub fn rollback_to(conn: &Connection, migrations: &[Migration], target: i64) -> Result<()> {
+    let current = current_version(conn)?;
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to begin rollback transaction")?;
+
+    for migration in migrations
+        .iter()
+        .rev()
+        .filter(|m| m.version > target && m.version <= current)
+    {
+        let down = migration.down.with_context(|| {
+            format!(
+                "Migration {} ({}) cannot be rolled back",
+                migration.version, migration.name
+            )
+        })?;
+        down(&tx).with_context(|| {
+            format!(
+                "Rolling back migration {} ({}) failed",
+                migration.version, migration.name
+            )
+        })?;
+        tx.execute(
+            "DELETE FROM schema_version WHERE version = ?1",
+            [migration.version],
+        )
+        .context("Failed to update schema version")?;
+    }
+
+    tx.commit().context("Failed to commit rollback")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_notes(conn: &Connection) -> Result<()> {
+        conn.execute("CREATE TABLE notes (id INTEGER PRIMARY KEY)", [])?;
+        Ok(())
+    }
+
+    fn drop_notes(conn: &Connection) -> Result<()> {
+        conn.execute("DROP TABLE notes", [])?;
+        Ok(())
+    }
+
+    fn add_title(conn: &Connection) -> Result<()> {
+        conn.execute("ALTER TABLE notes ADD COLUMN title TEXT", [])?;
+        Ok(())
+    }
+
+    fn broken(conn: &Connection) -> Result<()> {
+        conn.execute("CREATE TABLE half_done (id INTEGER)", [])?;
+        anyhow::bail!("boom")
+    }
+
+    const MIGRATIONS: &[Migration] = &[
+        Migration {
+            version: 1,
+            name: "create_notes",
+            up: create_notes,
+            down: Some(drop_notes),
+        },
+        Migration {
+            version: 2,
+            name: "add_title",
+            up: add_title,
+            down: None,
+        },
+    ];
+
+    #[test]
+    fn test_run_migrations_applies_each_once() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn, &MIGRATIONS[..1]).unwrap();
+        assert_eq!(current_version(&conn).unwrap(), 1);
+
+        run_migrations(&conn, MIGRATIONS).unwrap();
+        run_migrations(&conn, MIGRATIONS).unwrap();
+        assert_eq!(current_version(&conn).unwrap(), 2);
+        assert!(column_exists(&conn, "notes", "title").unwrap());
+    }
+
+    #[test]
+    fn test_failed_migration_is_rolled_back() {
+        let conn = Connection::open_in_memory().unwrap();
+        let migrations = [Migration {
+            version: 1,
+            name: "broken",
+            up: broken,
+            down: None,
+        }];
+
+        assert!(run_migrations(&conn, &migrations).is_err());
+        assert_eq!(current_version(&conn).unwrap(), 0);
+        let tables: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'half_done'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(tables, 0);
+    }
+
+    #[test]
+    fn test_refuses_newer_database() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn, MIGRATIONS).unwrap();
+
+        let err = run_migrations(&conn, &MIGRATIONS[..1]).unwrap_err();
+        let too_new = err.downcast_ref::<SchemaTooNewError>().unwrap();
+        assert_eq!(too_new.database_version, 2);
+        assert_eq!(too_new.supported_version, 1);
+    }
+
+    #[test]
+    fn test_rollback_stops_at_irreversible_migration() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn, MIGRATIONS).unwrap();
+
+        assert!(rollback_to(&conn, MIGRATIONS, 0).is_err());
+        assert_eq!(current_version(&conn).unwrap(), 2);
+
+        let fresh = Connection::open_in_memory().unwrap();
+        run_migrations(&fresh, &MIGRATIONS[..1]).unwrap();
+        rollback_to(&fresh, MIGRATIONS, 0).unwrap();
+        assert_eq!(current_version(&fresh).unwrap(), 0);
+        assert!(!column_exists(&fresh, "notes", "id").unwrap());
+    }
+}
@@ -4,6 +4,7 @@ mod schema;
 mod schema_countdown;
 
 pub use connection::Database;
+pub use migrations::SchemaTooNewError;
 
 #[cfg(test)]
 mod tests {
@@ -30,6 +31,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_change_stamp_follows_writes_from_any_connection() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db_path_str = db_path.to_str().unwrap();
+        let db = Database::new(db_path_str).unwrap();
+        db.initialize_schema().unwrap();
+
+        let before = db.change_stamp().unwrap();
+        assert_eq!(db.change_stamp().unwrap(), before);
+
+        db.connection()
+            .execute("UPDATE settings SET theme = 'dark'", [])
+            .unwrap();
+        let after_own = db.change_stamp().unwrap();
+        assert_ne!(after_own, before);
+
+        let other = Database::new(db_path_str).unwrap();
+        other
+            .connection()
+            .execute("UPDATE settings SET theme = 'light'", [])
+            .unwrap();
+        assert_ne!(db.change_stamp().unwrap(), after_own);
+    }
+
     #[test]
     fn test_initialize_schema() {
         let db = Database::new(":memory:").unwrap();
@@ -265,4 +291,39 @@ mod tests {
         assert!(result.is_ok(), "Should be able to query sqlite_master");
         assert_eq!(result.unwrap(), 1, "sync_conflicts table should exist");
     }
+
+    #[test]
+    fn test_schema_version_recorded_and_newer_database_refused() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        db.initialize_schema().unwrap();
+
+        let latest = schema::MIGRATIONS.last().unwrap().version;
+        assert_eq!(
+            migrations::current_version(db.connection()).unwrap(),
+            latest
+        );
+
+        db.connection()
+            .execute(
+                "INSERT INTO schema_version (version, name) VALUES (?1, 'future')",
+                [latest + 1],
+            )
+            .unwrap();
+        let err = db.initialize_schema().unwrap_err();
+        assert!(err.downcast_ref::<SchemaTooNewError>().is_some());
+    }
+
+    #[test]
+    fn test_version_9_database_gains_pdf_font_path() {
+        let db = Database::new(":memory:").unwrap();
+        let version_9 = &schema::MIGRATIONS[..9];
+        assert_eq!(version_9.last().unwrap().version, 9);
+        migrations::run_migrations(db.connection(), version_9).unwrap();
+        assert!(!migrations::column_exists(db.connection(), "settings", "pdf_font_path").unwrap());
+
+        db.initialize_schema().unwrap();
+        assert!(migrations::column_exists(db.connection(), "settings", "pdf_font_path").unwrap());
+        assert_eq!(migrations::current_version(db.connection()).unwrap(), 10);
+    }
 }
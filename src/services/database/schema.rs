@@ -1,9 +1,85 @@
 use anyhow::{Context, Result};
 use rusqlite::Connection;
 
-use super::migrations;
+use super::migrations::{self, Migration};
+
+/// Ordered schema migrations; see [`migrations::run_migrations`].
+///
+/// Append new numbered entries here instead of editing earlier ones: a
+/// migration that has shipped has already run on users' databases.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "baseline",
+        up: baseline,
+        down: None,
+    },
+    Migration {
+        version: 2,
+        name: "events_fts",
+        up: create_events_fts,
+        down: Some(drop_events_fts),
+    },
+    Migration {
+        version: 3,
+        name: "saved_searches",
+        up: create_saved_searches_table,
+        down: Some(drop_saved_searches_table),
+    },
+    Migration {
+        version: 4,
+        name: "event_trash",
+        up: create_event_trash_table,
+        down: None,
+    },
+    Migration {
+        version: 5,
+        name: "event_history",
+        up: create_event_history_table,
+        down: Some(drop_event_history_table),
+    },
+    Migration {
+        version: 6,
+        name: "undo_history",
+        up: create_undo_history_table,
+        down: Some(drop_undo_history_table),
+    },
+    Migration {
+        version: 7,
+        name: "occurrence_cache",
+        up: create_occurrence_cache,
+        down: Some(drop_occurrence_cache),
+    },
+    Migration {
+        version: 8,
+        name: "event_import_identities",
+        up: create_event_import_identities_table,
+        down: Some(drop_event_import_identities_table),
+    },
+    Migration {
+        version: 9,
+        name: "event_ics_raw_properties",
+        up: add_ics_raw_properties_columns,
+        down: None,
+    },
+    Migration {
+        version: 10,
+        name: "settings_pdf_font_path",
+        up: add_pdf_font_path_column,
+        down: None,
+    },
+];
 
 pub fn initialize_schema(conn: &Connection) -> Result<()> {
+    migrations::run_migrations(conn, MIGRATIONS)?;
+    initialize_default_categories(conn)?;
+    normalize_all_day_event_times(conn)?;
+    Ok(())
+}
+
+/// Schema as it stood before versioning. Every step is idempotent so
+/// databases created by earlier releases (version 0) upgrade in place.
+fn baseline(conn: &Connection) -> Result<()> {
     create_settings_table(conn)?;
     run_settings_migrations(conn)?;
     create_custom_themes_table(conn)?;
@@ -16,13 +92,13 @@ pub fn initialize_schema(conn: &Connection) -> Result<()> {
     create_categories_table(conn)?;
     create_calendar_sources_table(conn)?;
     create_google_account_table(conn)?;
+    create_google_accounts_table(conn)?;
+    migrate_legacy_google_account(conn)?;
     create_event_sync_map_table(conn)?;
     create_event_remote_metadata_table(conn)?;
     create_outbound_sync_operations_table(conn)?;
     create_sync_conflicts_table(conn)?;
     create_calendar_sync_runs_table(conn)?;
-    initialize_default_categories(conn)?;
-    normalize_all_day_event_times(conn)?;
     Ok(())
 }
 
@@ -162,6 +238,76 @@ fn run_settings_migrations(conn: &Connection) -> Result<()> {
         "ALTER TABLE settings ADD COLUMN show_countdown_cards INTEGER NOT NULL DEFAULT 1",
     )?;
 
+    migrations::ensure_column(
+        conn,
+        "settings",
+        "sync_mass_delete_percent",
+        "ALTER TABLE settings ADD COLUMN sync_mass_delete_percent INTEGER NOT NULL DEFAULT 50",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "settings",
+        "sync_mass_delete_count",
+        "ALTER TABLE settings ADD COLUMN sync_mass_delete_count INTEGER NOT NULL DEFAULT 25",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "settings",
+        "backup_interval",
+        "ALTER TABLE settings ADD COLUMN backup_interval TEXT NOT NULL DEFAULT 'daily'",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "settings",
+        "backup_directory",
+        "ALTER TABLE settings ADD COLUMN backup_directory TEXT",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "settings",
+        "backup_keep_hourly",
+        "ALTER TABLE settings ADD COLUMN backup_keep_hourly INTEGER NOT NULL DEFAULT 24",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "settings",
+        "backup_keep_daily",
+        "ALTER TABLE settings ADD COLUMN backup_keep_daily INTEGER NOT NULL DEFAULT 7",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "settings",
+        "backup_keep_weekly",
+        "ALTER TABLE settings ADD COLUMN backup_keep_weekly INTEGER NOT NULL DEFAULT 4",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "settings",
+        "backup_keep_monthly",
+        "ALTER TABLE settings ADD COLUMN backup_keep_monthly INTEGER NOT NULL DEFAULT 12",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "settings",
+        "backup_encrypt",
+        "ALTER TABLE settings ADD COLUMN backup_encrypt INTEGER NOT NULL DEFAULT 0",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "settings",
+        "backup_passphrase_ref",
+        "ALTER TABLE settings ADD COLUMN backup_passphrase_ref TEXT",
+    )?;
+
     let had_time_slot = migrations::column_exists(conn, "settings", "time_slot_interval")?;
     let has_default_duration =
         migrations::column_exists(conn, "settings", "default_event_duration")?;
@@ -333,6 +479,265 @@ fn create_events_table(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Full-text index over event text, kept in step with `events` by triggers.
+fn create_events_fts(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS events_fts USING fts5(
+            title, description, location, category,
+            content='events', content_rowid='id',
+            tokenize='unicode61 remove_diacritics 2'
+        );
+
+        CREATE TRIGGER IF NOT EXISTS events_fts_insert AFTER INSERT ON events BEGIN
+            INSERT INTO events_fts(rowid, title, description, location, category)
+            VALUES (new.id, new.title, new.description, new.location, new.category);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS events_fts_delete AFTER DELETE ON events BEGIN
+            INSERT INTO events_fts(events_fts, rowid, title, description, location, category)
+            VALUES ('delete', old.id, old.title, old.description, old.location, old.category);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS events_fts_update AFTER UPDATE OF title, description, location, category
+        ON events BEGIN
+            INSERT INTO events_fts(events_fts, rowid, title, description, location, category)
+            VALUES ('delete', old.id, old.title, old.description, old.location, old.category);
+            INSERT INTO events_fts(rowid, title, description, location, category)
+            VALUES (new.id, new.title, new.description, new.location, new.category);
+        END;
+
+        INSERT INTO events_fts(events_fts) VALUES ('rebuild');",
+    )
+    .context("Failed to create events full-text index")?;
+
+    Ok(())
+}
+
+fn drop_events_fts(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "DROP TRIGGER IF EXISTS events_fts_insert;
+         DROP TRIGGER IF EXISTS events_fts_delete;
+         DROP TRIGGER IF EXISTS events_fts_update;
+         DROP TABLE IF EXISTS events_fts;",
+    )
+    .context("Failed to drop events full-text index")?;
+
+    Ok(())
+}
+
+fn create_saved_searches_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS saved_searches (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            query TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )
+    .context("Failed to create saved_searches table")?;
+
+    Ok(())
+}
+
+fn drop_saved_searches_table(conn: &Connection) -> Result<()> {
+    conn.execute("DROP TABLE IF EXISTS saved_searches", [])
+        .context("Failed to drop saved_searches table")?;
+
+    Ok(())
+}
+
+/// Deleted events, kept for restore until the retention period runs out.
+///
+/// `event_id` is the original row id; AUTOINCREMENT on `events` means it is
+/// never reused, so restores put the event back under the same id.
+fn create_event_trash_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS event_trash (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            event_id INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT,
+            location TEXT,
+            start_datetime TEXT NOT NULL,
+            end_datetime TEXT NOT NULL,
+            is_all_day INTEGER NOT NULL DEFAULT 0,
+            category TEXT,
+            color TEXT,
+            recurrence_rule TEXT,
+            recurrence_exceptions TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            sync_source_id INTEGER,
+            sync_external_uid TEXT,
+            deleted_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_event_trash_deleted_at ON event_trash(deleted_at);",
+    )
+    .context("Failed to create event_trash table")?;
+
+    migrations::ensure_column(
+        conn,
+        "settings",
+        "trash_retention_days",
+        "ALTER TABLE settings ADD COLUMN trash_retention_days INTEGER NOT NULL DEFAULT 30",
+    )?;
+
+    Ok(())
+}
+
+/// Audit log of event changes. Rows outlive the event so a deleted event's
+/// history can still be shown after a restore from the trash.
+fn create_event_history_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS event_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            event_id INTEGER NOT NULL,
+            change_kind TEXT NOT NULL,
+            origin TEXT NOT NULL,
+            changes_json TEXT NOT NULL DEFAULT '[]',
+            snapshot_json TEXT,
+            changed_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_event_history_event ON event_history(event_id, id);",
+    )
+    .context("Failed to create event_history table")
+}
+
+fn drop_event_history_table(conn: &Connection) -> Result<()> {
+    conn.execute("DROP TABLE IF EXISTS event_history", [])
+        .context("Failed to drop event_history table")?;
+
+    Ok(())
+}
+
+/// Persisted undo/redo stacks. The top of each stack is its highest id.
+fn create_undo_history_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS undo_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            stack TEXT NOT NULL CHECK(stack IN ('undo', 'redo')),
+            command_type TEXT NOT NULL,
+            description TEXT NOT NULL,
+            payload_json TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+        [],
+    )
+    .context("Failed to create undo_history table")?;
+
+    Ok(())
+}
+
+fn drop_undo_history_table(conn: &Connection) -> Result<()> {
+    conn.execute("DROP TABLE IF EXISTS undo_history", [])
+        .context("Failed to drop undo_history table")?;
+
+    Ok(())
+}
+
+/// Range indexes on `events` plus the materialised recurrence expansion.
+///
+/// `event_occurrence_windows` records which span of a series has been
+/// expanded; `event_occurrences` holds the occurrence starts inside it as
+/// Unix microseconds. Triggers drop a series' rows whenever its timing,
+/// rule or exceptions change, so every writer invalidates the cache.
+fn create_occurrence_cache(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_events_start ON events(start_datetime);
+        CREATE INDEX IF NOT EXISTS idx_events_end ON events(end_datetime);
+
+        CREATE TABLE IF NOT EXISTS event_occurrence_windows (
+            event_id INTEGER PRIMARY KEY,
+            window_start INTEGER NOT NULL,
+            window_end INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS event_occurrences (
+            event_id INTEGER NOT NULL,
+            occurrence_start INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_event_occurrences_event_start
+            ON event_occurrences(event_id, occurrence_start);
+
+        CREATE TRIGGER IF NOT EXISTS event_occurrences_invalidate_update
+        AFTER UPDATE OF start_datetime, end_datetime, is_all_day, recurrence_rule,
+            recurrence_exceptions ON events BEGIN
+            DELETE FROM event_occurrence_windows WHERE event_id = old.id;
+            DELETE FROM event_occurrences WHERE event_id = old.id;
+        END;
+        CREATE TRIGGER IF NOT EXISTS event_occurrences_invalidate_delete
+        AFTER DELETE ON events BEGIN
+            DELETE FROM event_occurrence_windows WHERE event_id = old.id;
+            DELETE FROM event_occurrences WHERE event_id = old.id;
+        END;",
+    )
+    .context("Failed to create occurrence cache")
+}
+
+fn drop_occurrence_cache(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "DROP TRIGGER IF EXISTS event_occurrences_invalidate_update;
+         DROP TRIGGER IF EXISTS event_occurrences_invalidate_delete;
+         DROP TABLE IF EXISTS event_occurrences;
+         DROP TABLE IF EXISTS event_occurrence_windows;
+         DROP INDEX IF EXISTS idx_events_start;
+         DROP INDEX IF EXISTS idx_events_end;",
+    )
+    .context("Failed to drop occurrence cache")
+}
+
+/// ICS identity of events imported from files. Rows outlive the event so an
+/// event restored from the trash keeps matching its UID.
+fn create_event_import_identities_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS event_import_identities (
+            uid TEXT NOT NULL,
+            recurrence_id TEXT NOT NULL DEFAULT '',
+            event_id INTEGER NOT NULL,
+            sequence INTEGER,
+            last_modified TEXT,
+            imported_at TEXT NOT NULL,
+            PRIMARY KEY (uid, recurrence_id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_event_import_identities_event
+            ON event_import_identities(event_id);",
+    )
+    .context("Failed to create event_import_identities table")
+}
+
+fn drop_event_import_identities_table(conn: &Connection) -> Result<()> {
+    conn.execute("DROP TABLE IF EXISTS event_import_identities", [])
+        .context("Failed to drop event_import_identities table")?;
+
+    Ok(())
+}
+
+/// Unmodelled iCalendar lines per event, kept in the trash copy as well.
+fn add_ics_raw_properties_columns(conn: &Connection) -> Result<()> {
+    migrations::ensure_column(
+        conn,
+        "events",
+        "ics_raw_properties",
+        "ALTER TABLE events ADD COLUMN ics_raw_properties TEXT",
+    )?;
+    migrations::ensure_column(
+        conn,
+        "event_trash",
+        "ics_raw_properties",
+        "ALTER TABLE event_trash ADD COLUMN ics_raw_properties TEXT",
+    )
+}
+
+/// The TrueType font chosen for PDF exports.
+fn add_pdf_font_path_column(conn: &Connection) -> Result<()> {
+    migrations::ensure_column(
+        conn,
+        "settings",
+        "pdf_font_path",
+        "ALTER TABLE settings ADD COLUMN pdf_font_path TEXT",
+    )
+}
+
 fn create_event_templates_table(conn: &Connection) -> Result<()> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS event_templates (
@@ -389,6 +794,9 @@ fn create_calendar_sources_table(conn: &Connection) -> Result<()> {
             last_sync_at TEXT,
             last_sync_status TEXT,
             last_error TEXT,
+            google_account_id INTEGER REFERENCES google_accounts(id) ON DELETE SET NULL,
+            remote_calendar_id TEXT,
+            color TEXT,
             created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
             updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
             UNIQUE(name)
@@ -432,6 +840,27 @@ fn create_calendar_sources_table(conn: &Connection) -> Result<()> {
         "ALTER TABLE calendar_sources ADD COLUMN last_push_at TEXT",
     )?;
 
+    migrations::ensure_column(
+        conn,
+        "calendar_sources",
+        "google_account_id",
+        "ALTER TABLE calendar_sources ADD COLUMN google_account_id INTEGER REFERENCES google_accounts(id) ON DELETE SET NULL",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "calendar_sources",
+        "remote_calendar_id",
+        "ALTER TABLE calendar_sources ADD COLUMN remote_calendar_id TEXT",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "calendar_sources",
+        "color",
+        "ALTER TABLE calendar_sources ADD COLUMN color TEXT",
+    )?;
+
     Ok(())
 }
 
@@ -572,6 +1001,83 @@ fn create_google_account_table(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Connected Google identities. The single-row `google_account` table keeps
+/// only the shared OAuth client ID.
+fn create_google_accounts_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS google_accounts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            account_email TEXT NOT NULL,
+            access_token TEXT,
+            refresh_token TEXT,
+            token_type TEXT,
+            scope TEXT,
+            expires_at TEXT,
+            last_error TEXT,
+            connected_at TEXT,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            UNIQUE(account_email)
+        )",
+        [],
+    )
+    .context("Failed to create google_accounts table")?;
+
+    Ok(())
+}
+
+/// Moves tokens from the old single-account row into `google_accounts` and
+/// links existing read/write sources to the migrated account.
+fn migrate_legacy_google_account(conn: &Connection) -> Result<()> {
+    let migrated = conn
+        .execute(
+            "INSERT OR IGNORE INTO google_accounts (
+                account_email, access_token, refresh_token, token_type, scope,
+                expires_at, last_error, connected_at, updated_at
+             )
+             SELECT account_email, access_token, refresh_token, token_type, scope,
+                    expires_at, last_error, connected_at, updated_at
+             FROM google_account
+             WHERE id = 1
+               AND TRIM(COALESCE(account_email, '')) != ''
+               AND TRIM(COALESCE(refresh_token, '')) != ''",
+            [],
+        )
+        .context("Failed to migrate legacy Google account")?;
+
+    if migrated == 0 {
+        return Ok(());
+    }
+
+    conn.execute(
+        "UPDATE calendar_sources
+         SET google_account_id = (
+             SELECT ga.id FROM google_accounts ga
+             JOIN google_account legacy ON legacy.account_email = ga.account_email
+             WHERE legacy.id = 1
+         )
+         WHERE google_account_id IS NULL AND sync_capability = 'read_write'",
+        [],
+    )
+    .context("Failed to link calendar sources to migrated Google account")?;
+
+    conn.execute(
+        "UPDATE google_account
+         SET account_email = NULL,
+             access_token = NULL,
+             refresh_token = NULL,
+             token_type = NULL,
+             scope = NULL,
+             expires_at = NULL,
+             last_error = NULL,
+             connected_at = NULL
+         WHERE id = 1",
+        [],
+    )
+    .context("Failed to clear legacy Google account tokens")?;
+
+    Ok(())
+}
+
 fn create_calendar_sync_runs_table(conn: &Connection) -> Result<()> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS calendar_sync_runs (
@@ -619,6 +1125,7 @@ fn create_event_remote_metadata_table(conn: &Connection) -> Result<()> {
             remote_event_id TEXT,
             remote_etag TEXT,
             remote_payload_hash TEXT,
+            synced_snapshot_json TEXT,
             updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
             UNIQUE(source_id, external_uid),
             FOREIGN KEY (source_id) REFERENCES calendar_sources(id) ON DELETE CASCADE
@@ -627,6 +1134,13 @@ fn create_event_remote_metadata_table(conn: &Connection) -> Result<()> {
     )
     .context("Failed to create event_remote_metadata table")?;
 
+    migrations::ensure_column(
+        conn,
+        "event_remote_metadata",
+        "synced_snapshot_json",
+        "ALTER TABLE event_remote_metadata ADD COLUMN synced_snapshot_json TEXT",
+    )?;
+
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_event_remote_metadata_source_id ON event_remote_metadata(source_id)",
         [],
@@ -689,6 +1203,9 @@ fn create_sync_conflicts_table(conn: &Connection) -> Result<()> {
             reason TEXT NOT NULL,
             resolution TEXT,
             status TEXT NOT NULL DEFAULT 'open',
+            base_snapshot_json TEXT,
+            local_snapshot_json TEXT,
+            remote_snapshot_json TEXT,
             created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
             resolved_at TEXT,
             updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
@@ -700,6 +1217,27 @@ fn create_sync_conflicts_table(conn: &Connection) -> Result<()> {
     )
     .context("Failed to create sync_conflicts table")?;
 
+    migrations::ensure_column(
+        conn,
+        "sync_conflicts",
+        "base_snapshot_json",
+        "ALTER TABLE sync_conflicts ADD COLUMN base_snapshot_json TEXT",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "sync_conflicts",
+        "local_snapshot_json",
+        "ALTER TABLE sync_conflicts ADD COLUMN local_snapshot_json TEXT",
+    )?;
+
+    migrations::ensure_column(
+        conn,
+        "sync_conflicts",
+        "remote_snapshot_json",
+        "ALTER TABLE sync_conflicts ADD COLUMN remote_snapshot_json TEXT",
+    )?;
+
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_sync_conflicts_source_status
          ON sync_conflicts(source_id, status)",
@@ -1,3 +1,4 @@
+use super::history::{diff_events, ChangeKind};
 use super::shared::{deserialize_exceptions, serialize_exceptions, to_local_datetime};
 use super::EventService;
 use crate::models::event::Event;
@@ -6,6 +7,7 @@ use crate::models::outbound_sync_operation::{
     OUTBOUND_OPERATION_CREATE, OUTBOUND_OPERATION_UPDATE,
 };
 use crate::services::calendar_sync::mapping::EventSyncMapService;
+use crate::services::icalendar::export;
 use crate::services::outbound_sync::OutboundSyncService;
 use anyhow::{anyhow, Context, Result};
 use chrono::{Local, TimeZone, Utc};
@@ -30,11 +32,28 @@ impl<'a> EventService<'a> {
     }
 
     /// Update a user-initiated local event and enqueue outbound sync when mapped to a writable source.
+    /// An edit that keeps the stored raw iCalendar properties raises their
+    /// SEQUENCE, so the exported copy supersedes the one imported.
     pub fn update_local(&self, event: &Event) -> Result<()> {
-        self.update(event)?;
+        self.update_local_inner(event, true)
+    }
+
+    /// Apply a newer imported copy of an event, keeping the SEQUENCE it
+    /// arrived with, and enqueue outbound sync as for a local edit.
+    pub(super) fn update_local_from_import(&self, event: &Event) -> Result<()> {
+        self.update_local_inner(event, false)
+    }
+
+    fn update_local_inner(&self, event: &Event, revise_sequence: bool) -> Result<()> {
+        let event = if revise_sequence {
+            self.with_revised_sequence(event)?
+        } else {
+            event.clone()
+        };
+        self.update(&event)?;
 
         if let Some(event_id) = event.id {
-            let payload = Self::build_outbound_payload(event, event_id).to_string();
+            let payload = Self::build_outbound_payload(&event, event_id).to_string();
             let outbound = OutboundSyncService::new(self.conn);
             let _ = outbound.enqueue_upsert_for_local_event(
                 event_id,
@@ -46,11 +65,34 @@ impl<'a> EventService<'a> {
         Ok(())
     }
 
-    /// Delete a user-initiated local event and enqueue outbound deletion for writable mapped sources.
+    /// `event` with the SEQUENCE of its raw properties bumped when its content
+    /// changed and the properties are the ones already stored.
+    fn with_revised_sequence(&self, event: &Event) -> Result<Event> {
+        let mut revised = event.clone();
+        let Some(previous) = event.id.map(|id| self.get(id)).transpose()?.flatten() else {
+            return Ok(revised);
+        };
+        if let Some(raw) = event.ics_raw_properties.as_deref() {
+            if previous.ics_raw_properties.as_deref() == Some(raw)
+                && !diff_events(Some(&previous), Some(event)).is_empty()
+            {
+                revised.ics_raw_properties = Some(export::bump_sequence(raw));
+            }
+        }
+        Ok(revised)
+    }
+
+    /// Move a user-initiated deletion to the trash and enqueue outbound deletion for writable mapped sources.
     pub fn delete_local(&self, id: i64) -> Result<()> {
         let outbound = OutboundSyncService::new(self.conn);
         let mapping = outbound.writable_identity_for_local_event(id)?;
 
+        // The trash copy, the delete and the queued push land together
+        let tx = self
+            .conn
+            .unchecked_transaction()
+            .context("Failed to begin event deletion")?;
+        self.move_to_trash(id, mapping.as_ref())?;
         self.delete(id)?;
 
         if let Some((source_id, external_uid)) = mapping {
@@ -63,7 +105,7 @@ impl<'a> EventService<'a> {
             outbound.enqueue_delete_for_identity(source_id, &external_uid, Some(&payload))?;
         }
 
-        Ok(())
+        tx.commit().context("Failed to commit event deletion")
     }
 
     /// Create a new event in the database.
@@ -78,8 +120,8 @@ impl<'a> EventService<'a> {
                 "INSERT INTO events (
                     title, description, location, start_datetime, end_datetime,
                     is_all_day, category, color, recurrence_rule, recurrence_exceptions,
-                    created_at, updated_at
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                    created_at, updated_at, ics_raw_properties
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
                 params![
                     event.title,
                     event.description,
@@ -93,6 +135,7 @@ impl<'a> EventService<'a> {
                     exceptions_json,
                     &now,
                     &now,
+                    event.ics_raw_properties,
                 ],
             )
             .context("Failed to insert event")?;
@@ -101,6 +144,7 @@ impl<'a> EventService<'a> {
         event.id = Some(id);
         event.created_at = Some(Local::now());
         event.updated_at = Some(Local::now());
+        self.record_change(id, ChangeKind::Created, None, Some(&event))?;
 
         Ok(event)
     }
@@ -110,7 +154,7 @@ impl<'a> EventService<'a> {
         let result = self.conn.query_row(
             "SELECT id, title, description, location, start_datetime, end_datetime,
                     is_all_day, category, color, recurrence_rule, recurrence_exceptions,
-                    created_at, updated_at
+                    created_at, updated_at, ics_raw_properties
              FROM events WHERE id = ?",
             [id],
             |row| {
@@ -130,6 +174,7 @@ impl<'a> EventService<'a> {
                     recurrence_exceptions,
                     created_at: Some(to_local_datetime(row.get::<_, String>(11)?)?),
                     updated_at: Some(to_local_datetime(row.get::<_, String>(12)?)?),
+                    ics_raw_properties: row.get(13)?,
                 })
             },
         );
@@ -141,12 +186,15 @@ impl<'a> EventService<'a> {
         }
     }
 
-    /// Update an existing event.
+    /// Update an existing event, including its raw iCalendar properties.
     pub fn update(&self, event: &Event) -> Result<()> {
         let id = event
             .id
             .ok_or_else(|| anyhow!("Event ID is required for update"))?;
         event.validate().map_err(|e| anyhow!(e))?;
+        let previous = self
+            .get(id)?
+            .ok_or_else(|| anyhow!("Event with id {} not found", id))?;
 
         let exceptions_json = serialize_exceptions(event.recurrence_exceptions.as_ref());
         let rows_affected = self
@@ -155,7 +203,8 @@ impl<'a> EventService<'a> {
                 "UPDATE events SET
                     title = ?, description = ?, location = ?, start_datetime = ?, end_datetime = ?,
                     is_all_day = ?, category = ?, color = ?, recurrence_rule = ?,
-                    recurrence_exceptions = ?, updated_at = ?
+                    recurrence_exceptions = ?, updated_at = ?,
+                    ics_raw_properties = ?
                  WHERE id = ?",
                 params![
                     event.title,
@@ -169,6 +218,7 @@ impl<'a> EventService<'a> {
                     event.recurrence_rule,
                     exceptions_json,
                     Local::now().to_rfc3339(),
+                    event.ics_raw_properties,
                     id,
                 ],
             )
@@ -177,20 +227,20 @@ impl<'a> EventService<'a> {
         if rows_affected == 0 {
             return Err(anyhow!("Event with id {} not found", id));
         }
+        self.record_change(id, ChangeKind::Updated, Some(&previous), Some(event))?;
 
         Ok(())
     }
 
     /// Delete an event by ID.
     pub fn delete(&self, id: i64) -> Result<()> {
-        let rows_affected = self
-            .conn
+        let previous = self
+            .get(id)?
+            .ok_or_else(|| anyhow!("Event with id {} not found", id))?;
+        self.conn
             .execute("DELETE FROM events WHERE id = ?", [id])
             .context("Failed to delete event")?;
-
-        if rows_affected == 0 {
-            return Err(anyhow!("Event with id {} not found", id));
-        }
+        self.record_change(id, ChangeKind::Deleted, Some(&previous), None)?;
 
         Ok(())
     }
@@ -310,7 +360,7 @@ impl<'a> EventService<'a> {
         Ok(())
     }
 
-    fn build_outbound_payload(event: &Event, event_id: i64) -> serde_json::Value {
+    pub(super) fn build_outbound_payload(event: &Event, event_id: i64) -> serde_json::Value {
         let recurrence_exceptions = event.recurrence_exceptions.as_ref().map(|dates| {
             dates
                 .iter()
@@ -0,0 +1,423 @@
+//! Per-event change history.
+//!
+//! Every create, update, delete and trash restore that goes through
+//! [`EventService`] appends a row to `event_history` with a field-level diff,
+//! a snapshot of the event and the [`ChangeOrigin`] of the service that made
+//! the change, so a synced meeting that moved can be traced to a local edit
+//! or to the calendar sync.
+
+use super::shared::to_local_datetime;
+use super::EventService;
+use crate::models::event::Event;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use rusqlite::params;
+use serde_json::{json, Value};
+
+/// Who made a change to an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeOrigin {
+    /// Edited in the app
+    User,
+    /// Imported from a file
+    Import,
+    /// Pulled from a synced calendar
+    Sync,
+}
+
+impl ChangeOrigin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeOrigin::User => "user",
+            ChangeOrigin::Import => "import",
+            ChangeOrigin::Sync => "sync",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeOrigin::User => "You",
+            ChangeOrigin::Import => "Import",
+            ChangeOrigin::Sync => "Calendar sync",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "import" => ChangeOrigin::Import,
+            "sync" => ChangeOrigin::Sync,
+            _ => ChangeOrigin::User,
+        }
+    }
+}
+
+/// What kind of change was recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+    Restored,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Created => "created",
+            ChangeKind::Updated => "updated",
+            ChangeKind::Deleted => "deleted",
+            ChangeKind::Restored => "restored",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Created => "Created",
+            ChangeKind::Updated => "Updated",
+            ChangeKind::Deleted => "Deleted",
+            ChangeKind::Restored => "Restored from trash",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "created" => ChangeKind::Created,
+            "deleted" => ChangeKind::Deleted,
+            "restored" => ChangeKind::Restored,
+            _ => ChangeKind::Updated,
+        }
+    }
+}
+
+/// One changed field, with values as stored (dates in RFC 3339).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// A recorded change to an event.
+#[derive(Debug, Clone)]
+pub struct EventChange {
+    pub kind: ChangeKind,
+    pub origin: ChangeOrigin,
+    pub changes: Vec<FieldChange>,
+    /// The event after the change, or before it for deletions
+    pub snapshot: Option<Event>,
+    pub changed_at: DateTime<Local>,
+}
+
+impl<'a> EventService<'a> {
+    /// Tag changes made through this service with an origin other than the user.
+    pub fn with_origin(mut self, origin: ChangeOrigin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// List the recorded changes for an event, newest first.
+    pub fn history(&self, event_id: i64) -> Result<Vec<EventChange>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT change_kind, origin, changes_json, snapshot_json, changed_at
+             FROM event_history
+             WHERE event_id = ?1
+             ORDER BY id DESC",
+        )?;
+
+        let rows = stmt
+            .query_map([event_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    to_local_datetime(row.get(4)?)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to load event history")?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(kind, origin, changes_json, snapshot_json, changed_at)| EventChange {
+                    kind: ChangeKind::parse(&kind),
+                    origin: ChangeOrigin::parse(&origin),
+                    changes: parse_changes(&changes_json),
+                    snapshot: snapshot_json
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .and_then(|value| snapshot_to_event(&value, event_id)),
+                    changed_at,
+                },
+            )
+            .collect())
+    }
+
+    /// Append a history row. Updates that change nothing are not recorded.
+    pub(super) fn record_change(
+        &self,
+        event_id: i64,
+        kind: ChangeKind,
+        before: Option<&Event>,
+        after: Option<&Event>,
+    ) -> Result<()> {
+        let changes = diff_events(before, after);
+        if kind == ChangeKind::Updated && changes.is_empty() {
+            return Ok(());
+        }
+
+        let changes_json = Value::Array(
+            changes
+                .iter()
+                .map(|change| json!({"field": change.field, "old": change.old, "new": change.new}))
+                .collect(),
+        );
+        let snapshot = match kind {
+            ChangeKind::Deleted => before,
+            _ => after,
+        };
+
+        self.conn
+            .execute(
+                "INSERT INTO event_history
+                    (event_id, change_kind, origin, changes_json, snapshot_json, changed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    event_id,
+                    kind.as_str(),
+                    self.origin.as_str(),
+                    changes_json.to_string(),
+                    snapshot.map(|event| event_to_snapshot(event).to_string()),
+                    Local::now().to_rfc3339(),
+                ],
+            )
+            .context("Failed to record event history")?;
+
+        Ok(())
+    }
+}
+
+/// Field-level differences between two versions of an event.
+pub(super) fn diff_events(before: Option<&Event>, after: Option<&Event>) -> Vec<FieldChange> {
+    let fields = |event: Option<&Event>| {
+        event
+            .map(event_fields)
+            .unwrap_or_else(|| vec![None; EVENT_FIELDS.len()])
+    };
+    let (before, after) = (fields(before), fields(after));
+
+    EVENT_FIELDS
+        .iter()
+        .zip(before.into_iter().zip(after))
+        .filter(|(_, (old, new))| old != new)
+        .map(|(field, (old, new))| FieldChange {
+            field: field.to_string(),
+            old,
+            new,
+        })
+        .collect()
+}
+
+const EVENT_FIELDS: [&str; 10] = [
+    "title",
+    "description",
+    "location",
+    "start",
+    "end",
+    "all_day",
+    "category",
+    "color",
+    "recurrence_rule",
+    "recurrence_exceptions",
+];
+
+fn event_fields(event: &Event) -> Vec<Option<String>> {
+    let non_empty = |value: &Option<String>| value.clone().filter(|v| !v.is_empty());
+    vec![
+        Some(event.title.clone()),
+        non_empty(&event.description),
+        non_empty(&event.location),
+        Some(event.start.to_rfc3339()),
+        Some(event.end.to_rfc3339()),
+        Some(event.all_day.to_string()),
+        non_empty(&event.category),
+        non_empty(&event.color),
+        non_empty(&event.recurrence_rule),
+        event
+            .recurrence_exceptions
+            .as_ref()
+            .filter(|dates| !dates.is_empty())
+            .map(|dates| {
+                dates
+                    .iter()
+                    .map(|dt| dt.to_rfc3339())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }),
+    ]
+}
+
+fn parse_changes(json: &str) -> Vec<FieldChange> {
+    let Ok(Value::Array(items)) = serde_json::from_str::<Value>(json) else {
+        return Vec::new();
+    };
+
+    items
+        .iter()
+        .filter_map(|item| {
+            Some(FieldChange {
+                field: item.get("field")?.as_str()?.to_string(),
+                old: item.get("old").and_then(Value::as_str).map(str::to_string),
+                new: item.get("new").and_then(Value::as_str).map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+fn event_to_snapshot(event: &Event) -> Value {
+    json!({
+        "title": event.title,
+        "description": event.description,
+        "location": event.location,
+        "start": event.start.to_rfc3339(),
+        "end": event.end.to_rfc3339(),
+        "all_day": event.all_day,
+        "category": event.category,
+        "color": event.color,
+        "recurrence_rule": event.recurrence_rule,
+        "recurrence_exceptions": event
+            .recurrence_exceptions
+            .as_ref()
+            .map(|dates| dates.iter().map(|dt| dt.to_rfc3339()).collect::<Vec<_>>()),
+        "ics_raw_properties": event.ics_raw_properties,
+    })
+}
+
+fn snapshot_to_event(value: &Value, event_id: i64) -> Option<Event> {
+    let text = |key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+    let datetime = |key: &str| {
+        DateTime::parse_from_rfc3339(value.get(key)?.as_str()?)
+            .ok()
+            .map(|dt| dt.with_timezone(&Local))
+    };
+
+    Some(Event {
+        id: Some(event_id),
+        title: text("title")?,
+        description: text("description"),
+        location: text("location"),
+        start: datetime("start")?,
+        end: datetime("end")?,
+        all_day: value
+            .get("all_day")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        category: text("category"),
+        color: text("color"),
+        recurrence_rule: text("recurrence_rule"),
+        recurrence_exceptions: value
+            .get("recurrence_exceptions")
+            .and_then(Value::as_array)
+            .map(|dates| {
+                dates
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .filter_map(|dt| DateTime::parse_from_rfc3339(dt).ok())
+                    .map(|dt| dt.with_timezone(&Local))
+                    .collect()
+            }),
+        created_at: None,
+        updated_at: None,
+        ics_raw_properties: text("ics_raw_properties"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::database::Database;
+    use chrono::{Duration, TimeZone};
+
+    fn setup_test_db() -> Database {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        db
+    }
+
+    fn meeting() -> Event {
+        let start = Local
+            .with_ymd_and_hms(2026, 5, 4, 9, 0, 0)
+            .single()
+            .unwrap();
+        Event::new("Standup", start, start + Duration::minutes(30)).unwrap()
+    }
+
+    #[test]
+    fn records_field_diffs_and_origin_for_each_change() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+
+        let created = service.create(meeting()).unwrap();
+        let id = created.id.unwrap();
+
+        let mut moved = created.clone();
+        moved.start = created.start + Duration::hours(1);
+        moved.end = created.end + Duration::hours(1);
+        EventService::new(db.connection())
+            .with_origin(ChangeOrigin::Sync)
+            .update(&moved)
+            .unwrap();
+
+        // Saving without changes is not recorded.
+        service.update(&moved).unwrap();
+
+        let mut renamed = moved.clone();
+        renamed.title = "Daily standup".to_string();
+        service.update(&renamed).unwrap();
+        service.delete(id).unwrap();
+
+        let history = service.history(id).unwrap();
+        let kinds: Vec<_> = history.iter().map(|change| change.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ChangeKind::Deleted,
+                ChangeKind::Updated,
+                ChangeKind::Updated,
+                ChangeKind::Created
+            ]
+        );
+
+        let sync_move = &history[2];
+        assert_eq!(sync_move.origin, ChangeOrigin::Sync);
+        let fields: Vec<_> = sync_move.changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["start", "end"]);
+        assert_eq!(
+            sync_move.changes[0].old.as_deref(),
+            Some(created.start.to_rfc3339().as_str())
+        );
+
+        assert_eq!(history[1].origin, ChangeOrigin::User);
+        assert_eq!(history[1].changes[0].field, "title");
+        assert_eq!(history[1].changes[0].new.as_deref(), Some("Daily standup"));
+
+        // Deletions keep the last version so it can be inspected or restored.
+        let deleted = history[0].snapshot.as_ref().unwrap();
+        assert_eq!(deleted.title, "Daily standup");
+        assert_eq!(deleted.start, moved.start);
+    }
+
+    #[test]
+    fn purging_from_trash_drops_history() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+
+        let id = service.create(meeting()).unwrap().id.unwrap();
+        service.delete_local(id).unwrap();
+        assert_eq!(service.history(id).unwrap().len(), 2);
+
+        let trash_id = service.list_trash().unwrap()[0].trash_id;
+        service.purge_from_trash(trash_id).unwrap();
+        assert!(service.history(id).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,517 @@
+//! Importing events from iCalendar files by UID.
+//!
+//! Each imported event remembers the UID and RECURRENCE-ID it came from,
+//! together with the SEQUENCE and LAST-MODIFIED of the copy last applied. A
+//! later import of the same UID updates that event in place when the incoming
+//! copy is newer, and leaves it alone otherwise. Events without a UID fall
+//! back to matching title and times.
+
+use super::history::diff_events;
+use super::EventService;
+use crate::models::event::Event;
+use crate::services::icalendar::import::ImportedIcsEvent;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use rusqlite::{params, OptionalExtension};
+
+/// What importing one ICS event did to the calendar.
+#[derive(Debug, Clone)]
+pub enum IcsImportOutcome {
+    /// A new event was created
+    Created(Event),
+    /// An event imported earlier was replaced by a newer copy
+    Updated { before: Box<Event>, after: Event },
+    /// The event is already in the calendar and the incoming copy is not newer
+    Unchanged(Event),
+}
+
+/// What importing an ICS event would do, decided before anything is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IcsImportStatus {
+    /// No matching event; a new one would be created
+    New,
+    /// A matching event would be replaced by the newer incoming copy
+    Update,
+    /// A matching event is already up to date
+    Unchanged,
+}
+
+/// Preview of one ICS event for the import dialog.
+#[derive(Debug, Clone)]
+pub struct IcsImportPreview {
+    pub status: IcsImportStatus,
+    /// The calendar event the incoming copy matched by UID or fields
+    pub existing: Option<Event>,
+    /// Timed calendar events overlapping the incoming event's first occurrence
+    pub overlaps: Vec<Event>,
+}
+
+/// The calendar state an ICS event is matched against.
+struct ResolvedIcsEvent {
+    /// Trimmed UID and RECURRENCE-ID, when the event has a UID
+    identity: Option<(String, String)>,
+    /// The matching event with the revision last applied to it
+    existing: Option<(Event, IcsRevision)>,
+    revision: IcsRevision,
+}
+
+impl ResolvedIcsEvent {
+    fn status(&self, incoming: &Event) -> IcsImportStatus {
+        let Some((existing, stored)) = &self.existing else {
+            return IcsImportStatus::New;
+        };
+        // Without a UID a field match is all we know, so it is never updated.
+        if self.identity.is_none() || !self.revision.is_newer_than(stored) {
+            return IcsImportStatus::Unchanged;
+        }
+        if diff_events(Some(existing), Some(&updated_copy(existing, incoming))).is_empty() {
+            IcsImportStatus::Unchanged
+        } else {
+            IcsImportStatus::Update
+        }
+    }
+}
+
+/// `incoming` carrying the identity of the event it replaces.
+fn updated_copy(existing: &Event, incoming: &Event) -> Event {
+    let mut updated = incoming.clone();
+    updated.id = existing.id;
+    updated.created_at = existing.created_at;
+    updated
+}
+
+/// The revision of an ICS event last applied to the calendar.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct IcsRevision {
+    sequence: Option<i64>,
+    last_modified: Option<DateTime<Local>>,
+}
+
+impl IcsRevision {
+    fn of(imported: &ImportedIcsEvent) -> Self {
+        Self {
+            sequence: imported.sequence,
+            last_modified: imported
+                .raw_last_modified
+                .as_ref()
+                .and(imported.event.updated_at),
+        }
+    }
+
+    /// Whether `self` supersedes `stored`. A missing SEQUENCE counts as 0; a
+    /// higher SEQUENCE wins and on a tie a later LAST-MODIFIED does. When no
+    /// revision is recorded for `stored` the incoming content decides.
+    fn is_newer_than(&self, stored: &IcsRevision) -> bool {
+        if *stored == IcsRevision::default() {
+            return true;
+        }
+        let (incoming, existing) = (self.sequence.unwrap_or(0), stored.sequence.unwrap_or(0));
+        if incoming != existing {
+            return incoming > existing;
+        }
+        match (self.last_modified, stored.last_modified) {
+            (Some(incoming), Some(existing)) => incoming > existing,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+impl<'a> EventService<'a> {
+    /// Import one parsed ICS event, updating the event previously imported
+    /// under the same UID when the incoming copy is newer.
+    pub fn import_ics_event(&self, imported: &ImportedIcsEvent) -> Result<IcsImportOutcome> {
+        let resolved = self.resolve_ics_event(imported)?;
+        let status = resolved.status(&imported.event);
+
+        let Some((existing, stored)) = resolved.existing else {
+            let created = self.create_local(imported.event.clone())?;
+            if let (Some((uid, recurrence_id)), Some(event_id)) = (&resolved.identity, created.id) {
+                self.save_import_identity(uid, recurrence_id, event_id, &resolved.revision)?;
+            }
+            return Ok(IcsImportOutcome::Created(created));
+        };
+        let event_id = existing.id.context("Existing event has no ID")?;
+        let Some((uid, recurrence_id)) = &resolved.identity else {
+            return Ok(IcsImportOutcome::Unchanged(existing));
+        };
+
+        if !resolved.revision.is_newer_than(&stored) {
+            self.save_import_identity(uid, recurrence_id, event_id, &stored)?;
+            return Ok(IcsImportOutcome::Unchanged(existing));
+        }
+
+        let outcome = if status == IcsImportStatus::Update {
+            let updated = updated_copy(&existing, &imported.event);
+            self.update_local_from_import(&updated)?;
+            IcsImportOutcome::Updated {
+                before: Box::new(existing),
+                after: updated,
+            }
+        } else {
+            IcsImportOutcome::Unchanged(existing)
+        };
+        self.save_import_identity(uid, recurrence_id, event_id, &resolved.revision)?;
+        Ok(outcome)
+    }
+
+    /// What [`import_ics_event`](Self::import_ics_event) would do with
+    /// `imported`, plus the timed events it would overlap. Nothing is written.
+    pub fn preview_ics_event(&self, imported: &ImportedIcsEvent) -> Result<IcsImportPreview> {
+        let resolved = self.resolve_ics_event(imported)?;
+        let status = resolved.status(&imported.event);
+        let existing = resolved.existing.map(|(event, _)| event);
+
+        let overlaps = if imported.event.all_day {
+            Vec::new()
+        } else {
+            let ignored_id = existing.as_ref().and_then(|event| event.id);
+            self.expand_recurring_events(imported.event.start, imported.event.end)?
+                .into_iter()
+                .filter(|event| {
+                    !event.all_day
+                        && event.id != ignored_id
+                        && event.start < imported.event.end
+                        && event.end > imported.event.start
+                })
+                .collect()
+        };
+
+        Ok(IcsImportPreview {
+            status,
+            existing,
+            overlaps,
+        })
+    }
+
+    /// Drop the revision recorded for an imported event, so the next import
+    /// of its UID is applied again. Used when an import update is undone.
+    pub fn forget_import_revision(&self, event_id: i64) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE event_import_identities SET sequence = NULL, last_modified = NULL
+                 WHERE event_id = ?1",
+                [event_id],
+            )
+            .context("Failed to reset import revision")?;
+        Ok(())
+    }
+
+    fn resolve_ics_event(&self, imported: &ImportedIcsEvent) -> Result<ResolvedIcsEvent> {
+        let revision = IcsRevision::of(imported);
+        let Some(uid) = imported
+            .uid
+            .as_deref()
+            .map(str::trim)
+            .filter(|uid| !uid.is_empty())
+        else {
+            return Ok(ResolvedIcsEvent {
+                identity: None,
+                existing: self
+                    .find_matching_event(&imported.event)?
+                    .map(|event| (event, IcsRevision::default())),
+                revision,
+            });
+        };
+        let recurrence_id = imported.recurrence_id.as_deref().unwrap_or("").trim();
+
+        let known = match self.import_identity(uid, recurrence_id)? {
+            Some((event_id, stored)) => self.get(event_id)?.map(|event| (event, stored)),
+            None => None,
+        };
+        // Events imported before UIDs were kept are adopted by their fields,
+        // with no revision so the incoming copy's content decides.
+        let existing = match known {
+            Some(known) => Some(known),
+            None => self
+                .find_matching_event(&imported.event)?
+                .map(|event| (event, IcsRevision::default())),
+        };
+
+        Ok(ResolvedIcsEvent {
+            identity: Some((uid.to_string(), recurrence_id.to_string())),
+            existing,
+            revision,
+        })
+    }
+
+    fn import_identity(
+        &self,
+        uid: &str,
+        recurrence_id: &str,
+    ) -> Result<Option<(i64, IcsRevision)>> {
+        self.conn
+            .query_row(
+                "SELECT event_id, sequence, last_modified FROM event_import_identities
+                 WHERE uid = ?1 AND recurrence_id = ?2",
+                params![uid, recurrence_id],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, Option<i64>>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                    ))
+                },
+            )
+            .optional()
+            .context("Failed to look up imported UID")
+            .map(|row| {
+                row.map(|(event_id, sequence, last_modified)| {
+                    let last_modified = last_modified
+                        .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+                        .map(|dt| dt.with_timezone(&Local));
+                    (
+                        event_id,
+                        IcsRevision {
+                            sequence,
+                            last_modified,
+                        },
+                    )
+                })
+            })
+    }
+
+    fn save_import_identity(
+        &self,
+        uid: &str,
+        recurrence_id: &str,
+        event_id: i64,
+        revision: &IcsRevision,
+    ) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO event_import_identities
+                    (uid, recurrence_id, event_id, sequence, last_modified, imported_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    uid,
+                    recurrence_id,
+                    event_id,
+                    revision.sequence,
+                    revision.last_modified.map(|dt| dt.to_rfc3339()),
+                    Local::now().to_rfc3339(),
+                ],
+            )
+            .context("Failed to save imported UID")?;
+        Ok(())
+    }
+
+    /// An existing event with the same title, start and end.
+    fn find_matching_event(&self, candidate: &Event) -> Result<Option<Event>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT id FROM events WHERE title = ?1 ORDER BY id")?;
+        let ids = stmt
+            .query_map([&candidate.title], |row| row.get::<_, i64>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to look up matching events")?;
+
+        for id in ids {
+            if let Some(event) = self.get(id)? {
+                if event.start == candidate.start && event.end == candidate.end {
+                    return Ok(Some(event));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IcsImportOutcome, IcsImportStatus};
+    use crate::models::event::Event;
+    use crate::services::database::Database;
+    use crate::services::event::EventService;
+    use crate::services::icalendar::import::{from_str_with_metadata, ImportedIcsEvent};
+    use chrono::{Duration, Local, TimeZone};
+
+    fn setup_test_db() -> Database {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        db
+    }
+
+    fn parse(extra: &str, summary: &str) -> ImportedIcsEvent {
+        let ics = format!(
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:planning@example.com\r\n\
+             DTSTART:20260310T090000\r\nDTEND:20260310T100000\r\nSUMMARY:{}\r\n{}\
+             END:VEVENT\r\nEND:VCALENDAR\r\n",
+            summary, extra
+        );
+        from_str_with_metadata(&ics).unwrap().remove(0)
+    }
+
+    fn event_count(db: &Database) -> i64 {
+        db.connection()
+            .query_row("SELECT COUNT(*) FROM events", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn reimporting_a_uid_updates_only_when_newer() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+
+        let first = service
+            .import_ics_event(&parse("SEQUENCE:1\r\n", "Planning"))
+            .unwrap();
+        let IcsImportOutcome::Created(created) = first else {
+            panic!("expected a new event, got {:?}", first);
+        };
+
+        // Same revision with different content is not applied.
+        let stale = service
+            .import_ics_event(&parse("SEQUENCE:1\r\n", "Planning (old copy)"))
+            .unwrap();
+        assert!(matches!(stale, IcsImportOutcome::Unchanged(_)));
+
+        let newer = service
+            .import_ics_event(&parse("SEQUENCE:2\r\n", "Planning (moved)"))
+            .unwrap();
+        let IcsImportOutcome::Updated { before, after } = newer else {
+            panic!("expected an update, got {:?}", newer);
+        };
+        assert_eq!(before.title, "Planning");
+        assert_eq!(after.id, created.id);
+        assert_eq!(
+            service.get(created.id.unwrap()).unwrap().unwrap().title,
+            "Planning (moved)"
+        );
+
+        // An equal SEQUENCE defers to LAST-MODIFIED.
+        let touched = service
+            .import_ics_event(&parse(
+                "SEQUENCE:2\r\nLAST-MODIFIED:20260301T120000Z\r\n",
+                "Planning (room 4)",
+            ))
+            .unwrap();
+        assert!(matches!(touched, IcsImportOutcome::Updated { .. }));
+        let older = service
+            .import_ics_event(&parse(
+                "SEQUENCE:2\r\nLAST-MODIFIED:20260201T120000Z\r\n",
+                "Planning (room 1)",
+            ))
+            .unwrap();
+        assert!(matches!(older, IcsImportOutcome::Unchanged(_)));
+
+        // A copy without SEQUENCE counts as revision 0.
+        let unversioned = service
+            .import_ics_event(&parse("", "Planning (unversioned)"))
+            .unwrap();
+        assert!(matches!(unversioned, IcsImportOutcome::Unchanged(_)));
+        assert_eq!(event_count(&db), 1);
+    }
+
+    #[test]
+    fn unversioned_copies_update_when_content_changes() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+
+        service.import_ics_event(&parse("", "Standup")).unwrap();
+        let edited = service
+            .import_ics_event(&parse("", "Standup (moved)"))
+            .unwrap();
+        assert!(matches!(edited, IcsImportOutcome::Updated { .. }));
+
+        let versioned = service
+            .import_ics_event(&parse("SEQUENCE:1\r\n", "Standup (v1)"))
+            .unwrap();
+        assert!(matches!(versioned, IcsImportOutcome::Updated { .. }));
+        let unversioned = service
+            .import_ics_event(&parse("", "Standup (stale)"))
+            .unwrap();
+        assert!(matches!(unversioned, IcsImportOutcome::Unchanged(_)));
+        assert_eq!(event_count(&db), 1);
+    }
+
+    #[test]
+    fn deleted_events_are_recreated_and_undone_updates_reapply() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+
+        let imported = parse("SEQUENCE:4\r\n", "Review");
+        let IcsImportOutcome::Created(created) = service.import_ics_event(&imported).unwrap()
+        else {
+            panic!("expected a new event");
+        };
+        let id = created.id.unwrap();
+
+        service.forget_import_revision(id).unwrap();
+        assert!(matches!(
+            service.import_ics_event(&imported).unwrap(),
+            IcsImportOutcome::Unchanged(_)
+        ));
+
+        service.delete(id).unwrap();
+        let IcsImportOutcome::Created(recreated) = service.import_ics_event(&imported).unwrap()
+        else {
+            panic!("expected the deleted event to be recreated");
+        };
+        assert_ne!(recreated.id, Some(id));
+        assert!(matches!(
+            service.import_ics_event(&imported).unwrap(),
+            IcsImportOutcome::Unchanged(_)
+        ));
+        assert_eq!(event_count(&db), 1);
+    }
+
+    #[test]
+    fn events_without_uid_match_on_title_and_times() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+
+        let mut imported = parse("", "Standup");
+        imported.uid = None;
+        assert!(matches!(
+            service.import_ics_event(&imported).unwrap(),
+            IcsImportOutcome::Created(_)
+        ));
+        assert!(matches!(
+            service.import_ics_event(&imported).unwrap(),
+            IcsImportOutcome::Unchanged(_)
+        ));
+
+        // The same event later arriving with a UID is adopted, not copied.
+        let with_uid = parse("SEQUENCE:1\r\n", "Standup");
+        assert!(matches!(
+            service.import_ics_event(&with_uid).unwrap(),
+            IcsImportOutcome::Unchanged(_)
+        ));
+        assert_eq!(event_count(&db), 1);
+    }
+
+    #[test]
+    fn preview_reports_status_and_overlaps_without_writing() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+        let busy_start = Local.with_ymd_and_hms(2026, 3, 10, 9, 30, 0).unwrap();
+        service
+            .create(Event::new("Dentist", busy_start, busy_start + Duration::hours(1)).unwrap())
+            .unwrap();
+
+        let preview = service
+            .preview_ics_event(&parse("SEQUENCE:1\r\n", "Planning"))
+            .unwrap();
+        assert_eq!(preview.status, IcsImportStatus::New);
+        assert!(preview.existing.is_none());
+        assert_eq!(preview.overlaps.len(), 1);
+        assert_eq!(preview.overlaps[0].title, "Dentist");
+        assert_eq!(event_count(&db), 1);
+
+        service
+            .import_ics_event(&parse("SEQUENCE:1\r\n", "Planning"))
+            .unwrap();
+        let again = service
+            .preview_ics_event(&parse("SEQUENCE:1\r\n", "Planning"))
+            .unwrap();
+        assert_eq!(again.status, IcsImportStatus::Unchanged);
+        // The matched event itself is not reported as an overlap.
+        assert_eq!(again.overlaps.len(), 1);
+        let newer = service
+            .preview_ics_event(&parse("SEQUENCE:2\r\n", "Planning (moved)"))
+            .unwrap();
+        assert_eq!(newer.status, IcsImportStatus::Update);
+        assert_eq!(newer.existing.unwrap().title, "Planning");
+    }
+}
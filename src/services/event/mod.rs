@@ -5,19 +5,27 @@
 use rusqlite::Connection;
 
 pub mod crud;
+pub mod history;
+pub mod ics_import;
 pub mod queries;
 pub mod recurrence;
 mod shared;
+pub mod trash;
 
 /// Service for managing calendar events stored in SQLite.
 pub struct EventService<'a> {
     pub(crate) conn: &'a Connection,
+    /// Recorded in the change history for every write
+    origin: history::ChangeOrigin,
 }
 
 impl<'a> EventService<'a> {
     /// Create a new EventService with a database connection
     pub fn new(conn: &'a Connection) -> Self {
-        Self { conn }
+        Self {
+            conn,
+            origin: history::ChangeOrigin::User,
+        }
     }
 }
 
@@ -26,7 +34,7 @@ mod tests {
     use super::*;
     use crate::models::event::Event;
     use crate::services::database::Database;
-    use chrono::{Duration, Local};
+    use chrono::{Duration, Local, TimeZone};
 
     fn setup_test_db() -> Database {
         let db = Database::new(":memory:").unwrap();
@@ -152,6 +160,79 @@ mod tests {
         assert!(found.is_none());
     }
 
+    #[test]
+    fn test_delete_local_moves_event_to_trash_and_restores_it() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+
+        let mut event = sample_event();
+        event.location = Some("Room 4".to_string());
+        let id = service.create(event).unwrap().id.unwrap();
+
+        service.delete_local(id).unwrap();
+        assert!(service.get(id).unwrap().is_none());
+
+        let trash = service.list_trash().unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].event.id, Some(id));
+        assert!(!trash[0].was_synced);
+
+        let restored = service.restore_from_trash(trash[0].trash_id).unwrap();
+        assert_eq!(restored.event.id, Some(id));
+        assert_eq!(restored.sync, trash::TrashRestoreSync::NotSynced);
+        assert_eq!(
+            service.get(id).unwrap().unwrap().location.as_deref(),
+            Some("Room 4")
+        );
+        assert!(service.list_trash().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_failed_restore_leaves_event_in_trash() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+
+        let id = service.create(sample_event()).unwrap().id.unwrap();
+        service.delete_local(id).unwrap();
+        let trash_id = service.list_trash().unwrap()[0].trash_id;
+
+        // Recording the change is the last step of a restore
+        db.connection()
+            .execute("DROP TABLE event_history", [])
+            .unwrap();
+        assert!(service.restore_from_trash(trash_id).is_err());
+
+        assert!(service.get(id).unwrap().is_none());
+        assert_eq!(service.list_trash().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_purge_expired_trash_keeps_recent_entries() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+
+        let old_id = service.create(sample_event()).unwrap().id.unwrap();
+        let recent_id = service.create(sample_event()).unwrap().id.unwrap();
+        service.delete_local(old_id).unwrap();
+        service.delete_local(recent_id).unwrap();
+
+        let forty_days_ago = (Local::now() - Duration::days(40)).to_rfc3339();
+        db.connection()
+            .execute(
+                "UPDATE event_trash SET deleted_at = ?1 WHERE event_id = ?2",
+                rusqlite::params![forty_days_ago, old_id],
+            )
+            .unwrap();
+
+        assert_eq!(service.purge_expired_trash(30, Local::now()).unwrap(), 1);
+        let remaining = service.list_trash().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].event.id, Some(recent_id));
+
+        assert_eq!(service.empty_trash().unwrap(), 1);
+        assert!(service.list_trash().unwrap().is_empty());
+    }
+
     #[test]
     fn test_delete_nonexistent_event() {
         let db = setup_test_db();
@@ -233,4 +314,211 @@ mod tests {
             Some("FREQ=WEEKLY;BYDAY=MO".to_string())
         );
     }
+
+    fn search_titles(service: &EventService, query: &str) -> Vec<String> {
+        service
+            .search(query)
+            .unwrap()
+            .into_iter()
+            .map(|hit| hit.event.title)
+            .collect()
+    }
+
+    #[test]
+    fn test_search_ranks_prefix_and_phrase_matches() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+        let start = Local::now();
+        let end = start + Duration::hours(1);
+
+        let notes = Event::builder()
+            .title("Quarterly review")
+            .description("Bring the budget meeting notes")
+            .start(start)
+            .end(end)
+            .build()
+            .unwrap();
+        service.create(notes).unwrap();
+        service
+            .create(Event::new("Budget meeting", start, end).unwrap())
+            .unwrap();
+        service
+            .create(Event::new("Meeting budget follow-up", start, end).unwrap())
+            .unwrap();
+
+        assert_eq!(
+            search_titles(&service, "budg"),
+            vec![
+                "Budget meeting".to_string(),
+                "Meeting budget follow-up".to_string(),
+                "Quarterly review".to_string()
+            ]
+        );
+        assert_eq!(
+            search_titles(&service, "\"budget meeting\""),
+            vec!["Budget meeting".to_string(), "Quarterly review".to_string()]
+        );
+        assert_eq!(
+            search_titles(&service, "budget NOT review"),
+            vec![
+                "Budget meeting".to_string(),
+                "Meeting budget follow-up".to_string()
+            ]
+        );
+        assert!(search_titles(&service, "OR \"").is_empty());
+
+        let hit = service.search("notes").unwrap().into_iter().next().unwrap();
+        assert_eq!(
+            queries::highlight_spans(hit.snippet.as_deref().unwrap()),
+            vec![("Bring the budget meeting ", false), ("notes", true)]
+        );
+    }
+
+    #[test]
+    fn test_local_edits_bump_sequence_and_raw_properties_can_be_cleared() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+
+        let mut event = sample_event();
+        event.ics_raw_properties = Some("UID:dentist@example.com\nSEQUENCE:2".to_string());
+        let mut event = service.create(event).unwrap();
+        let id = event.id.unwrap();
+
+        // Saving without a change leaves the revision alone
+        service.update_local(&event).unwrap();
+        let stored = service.get(id).unwrap().unwrap();
+        assert_eq!(stored.ics_raw_properties, event.ics_raw_properties);
+
+        event.title = "Orthodontist".to_string();
+        service.update_local(&event).unwrap();
+        let stored = service.get(id).unwrap().unwrap();
+        assert_eq!(
+            stored.ics_raw_properties.as_deref(),
+            Some("UID:dentist@example.com\nSEQUENCE:3")
+        );
+
+        let mut cleared = stored;
+        cleared.ics_raw_properties = None;
+        service.update(&cleared).unwrap();
+        assert!(service
+            .get(id)
+            .unwrap()
+            .unwrap()
+            .ics_raw_properties
+            .is_none());
+    }
+
+    #[test]
+    fn test_search_index_follows_updates_and_deletes() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+        let start = Local::now();
+
+        let mut event = service
+            .create(Event::new("Dentist", start, start + Duration::hours(1)).unwrap())
+            .unwrap();
+        assert_eq!(search_titles(&service, "dentist").len(), 1);
+
+        event.title = "Orthodontist".to_string();
+        service.update(&event).unwrap();
+        assert!(search_titles(&service, "dentist").is_empty());
+        assert_eq!(search_titles(&service, "ortho").len(), 1);
+
+        service.delete(event.id.unwrap()).unwrap();
+        assert!(search_titles(&service, "ortho").is_empty());
+    }
+
+    #[test]
+    fn test_event_query_parses_filters_and_free_text() {
+        let query = queries::EventQuery::parse(
+            "budget category:Work location:\"Room 4\" after:2026-01-01 \
+             before:2026-06-30 is:recurring source:\"Team Calendar\" \"kick off\"",
+        )
+        .unwrap();
+
+        assert_eq!(query.text, "budget \"kick off\"");
+        assert_eq!(query.categories, vec!["Work".to_string()]);
+        assert_eq!(query.locations, vec!["Room 4".to_string()]);
+        assert_eq!(query.sources, vec!["Team Calendar".to_string()]);
+        assert_eq!(query.after, chrono::NaiveDate::from_ymd_opt(2026, 1, 1));
+        assert_eq!(query.before, chrono::NaiveDate::from_ymd_opt(2026, 6, 30));
+        assert_eq!(query.recurring, Some(true));
+
+        assert!(queries::EventQuery::parse("after:soon").is_err());
+        assert!(queries::EventQuery::parse("is:busy").is_err());
+        assert!(queries::EventQuery::parse("category:").is_err());
+        assert_eq!(
+            queries::EventQuery::parse("meet at 10:30").unwrap().text,
+            "meet at 10:30"
+        );
+    }
+
+    #[test]
+    fn test_search_applies_structured_filters() {
+        let db = setup_test_db();
+        let conn = db.connection();
+        let service = EventService::new(conn);
+        let day = |d| Local.with_ymd_and_hms(2026, 3, d, 9, 0, 0).unwrap();
+
+        let planning = Event::builder()
+            .title("Planning")
+            .location("Room 4, Level 2")
+            .category("Work")
+            .start(day(2))
+            .end(day(2) + Duration::hours(1))
+            .build()
+            .unwrap();
+        let planning = service.create(planning).unwrap();
+        let lunch = Event::builder()
+            .title("Lunch")
+            .category("Personal")
+            .start(day(20))
+            .end(day(20) + Duration::hours(1))
+            .build()
+            .unwrap();
+        service.create(lunch).unwrap();
+        let standup = Event::builder()
+            .title("Standup")
+            .category("work")
+            .start(day(1))
+            .end(day(1) + Duration::minutes(15))
+            .recurrence_rule("FREQ=DAILY")
+            .build()
+            .unwrap();
+        service.create(standup).unwrap();
+
+        conn.execute(
+            "INSERT INTO calendar_sources (name, source_type, ics_url, enabled, poll_interval_minutes)
+             VALUES ('Team Calendar', 'google_ics', 'https://example.com/team.ics', 1, 15)",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO event_sync_map (source_id, external_uid, local_event_id)
+             VALUES (?1, 'uid-planning', ?2)",
+            rusqlite::params![conn.last_insert_rowid(), planning.id.unwrap()],
+        )
+        .unwrap();
+
+        let titles = |query: &str| -> Vec<String> {
+            service
+                .search(query)
+                .unwrap()
+                .into_iter()
+                .map(|hit| hit.event.title)
+                .collect()
+        };
+
+        assert_eq!(titles("category:Work"), vec!["Standup", "Planning"]);
+        assert_eq!(titles("category:work is:single"), vec!["Planning"]);
+        assert_eq!(titles("location:\"room 4\""), vec!["Planning"]);
+        assert_eq!(titles("source:\"team calendar\""), vec!["Planning"]);
+        assert_eq!(titles("after:2026-03-10"), vec!["Standup", "Lunch"]);
+        assert_eq!(titles("before:2026-02-28"), Vec::<String>::new());
+        assert_eq!(
+            titles("plan after:2026-03-02 before:2026-03-02"),
+            vec!["Planning"]
+        );
+        assert!(titles("category:Personal standup").is_empty());
+    }
 }
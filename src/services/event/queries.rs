@@ -1,9 +1,169 @@
 use super::shared::{deserialize_exceptions, to_local_datetime};
 use super::EventService;
 use crate::models::event::Event;
-use anyhow::Result;
-use chrono::{DateTime, Local};
-use rusqlite::{self, Row};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone};
+use rusqlite::types::Value;
+use rusqlite::{self, params_from_iter, Row};
+use std::collections::HashSet;
+
+const EVENT_COLUMNS: &str = "e.id, e.title, e.description, e.location, e.start_datetime,
+    e.end_datetime, e.is_all_day, e.category, e.color, e.recurrence_rule,
+    e.recurrence_exceptions, e.created_at, e.updated_at, e.ics_raw_properties";
+
+const RECURRING_CONDITION: &str =
+    "(e.recurrence_rule IS NOT NULL AND e.recurrence_rule != '' AND e.recurrence_rule != 'None')";
+
+/// Marks the start of a matched term in [`SearchHit`] text.
+pub const HIGHLIGHT_START: char = '\u{2}';
+/// Marks the end of a matched term in [`SearchHit`] text.
+pub const HIGHLIGHT_END: char = '\u{3}';
+
+/// One ranked search result.
+///
+/// `title`, `location` and `snippet` wrap matched terms in
+/// [`HIGHLIGHT_START`] / [`HIGHLIGHT_END`]; see [`highlight_spans`].
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub event: Event,
+    pub title: String,
+    pub location: Option<String>,
+    /// Excerpt of the description around the match, if it matched there.
+    pub snippet: Option<String>,
+}
+
+/// A parsed search such as
+/// `budget category:Work location:"Room 4" after:2026-01-01 is:recurring`.
+///
+/// Repeating a key matches any of its values; different keys must all
+/// match. Anything that is not a recognised `key:value` is free text for
+/// the full-text index.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventQuery {
+    pub text: String,
+    pub categories: Vec<String>,
+    /// Case-insensitive substrings of the location
+    pub locations: Vec<String>,
+    /// Names of the calendar sources events were synced from
+    pub sources: Vec<String>,
+    /// Events ending on or after this day
+    pub after: Option<NaiveDate>,
+    /// Events starting on or before this day
+    pub before: Option<NaiveDate>,
+    pub recurring: Option<bool>,
+}
+
+impl EventQuery {
+    /// Parses the query language, failing on malformed `key:value` terms.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut query = Self::default();
+        let mut text = Vec::new();
+
+        for token in split_query_tokens(input) {
+            let Some((key, value)) = token.split_once(':') else {
+                text.push(token);
+                continue;
+            };
+            let key = key.to_ascii_lowercase();
+            if !matches!(
+                key.as_str(),
+                "category" | "location" | "source" | "after" | "before" | "is"
+            ) {
+                text.push(token);
+                continue;
+            }
+
+            let value = value.trim_matches('"').trim();
+            if value.is_empty() {
+                anyhow::bail!("'{}:' needs a value", key);
+            }
+            match key.as_str() {
+                "category" => query.categories.push(value.to_string()),
+                "location" => query.locations.push(value.to_string()),
+                "source" => query.sources.push(value.to_string()),
+                "after" => query.after = Some(parse_query_date(&key, value)?),
+                "before" => query.before = Some(parse_query_date(&key, value)?),
+                _ => match value.to_ascii_lowercase().as_str() {
+                    "recurring" => query.recurring = Some(true),
+                    "single" => query.recurring = Some(false),
+                    other => {
+                        anyhow::bail!("Unknown 'is:{}' (use is:recurring or is:single)", other)
+                    }
+                },
+            }
+        }
+
+        query.text = text.join(" ");
+        Ok(query)
+    }
+
+    /// Whether the query has no text and no filters.
+    pub fn is_empty(&self) -> bool {
+        let filters = Self {
+            text: String::new(),
+            ..self.clone()
+        };
+        build_match_query(&self.text).is_none() && filters == Self::default()
+    }
+
+    /// Whether one (possibly recurring) occurrence falls within the date bounds.
+    pub fn includes_occurrence(&self, event: &Event) -> bool {
+        self.after
+            .is_none_or(|after| event.end.date_naive() >= after)
+            && self
+                .before
+                .is_none_or(|before| event.start.date_naive() <= before)
+    }
+
+    /// `AND`-able SQL conditions over `events e`, with their parameters in order.
+    fn sql_conditions(&self) -> (Vec<String>, Vec<Value>) {
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+
+        if !self.categories.is_empty() {
+            conditions.push(any_of(
+                "e.category = ? COLLATE NOCASE",
+                self.categories.len(),
+            ));
+            params.extend(self.categories.iter().cloned().map(Value::Text));
+        }
+        if !self.locations.is_empty() {
+            conditions.push(any_of(
+                "instr(LOWER(COALESCE(e.location, '')), ?) > 0",
+                self.locations.len(),
+            ));
+            params.extend(self.locations.iter().map(|l| Value::Text(l.to_lowercase())));
+        }
+        if !self.sources.is_empty() {
+            conditions.push(format!(
+                "e.id IN (SELECT m.local_event_id FROM event_sync_map m
+                          JOIN calendar_sources s ON s.id = m.source_id
+                          WHERE {})",
+                any_of("s.name = ? COLLATE NOCASE", self.sources.len())
+            ));
+            params.extend(self.sources.iter().cloned().map(Value::Text));
+        }
+        if let Some(after) = self.after {
+            conditions.push(format!(
+                "({RECURRING_CONDITION} OR datetime(e.end_datetime) >= datetime(?))"
+            ));
+            params.push(Value::Text(local_midnight(after).to_rfc3339()));
+        }
+        if let Some(before) = self.before {
+            conditions.push("datetime(e.start_datetime) < datetime(?)".to_string());
+            params.push(Value::Text(
+                local_midnight(before + Duration::days(1)).to_rfc3339(),
+            ));
+        }
+        match self.recurring {
+            Some(true) => conditions.push(RECURRING_CONDITION.to_string()),
+            Some(false) => conditions.push(format!("NOT {RECURRING_CONDITION}")),
+            None => {}
+        }
+
+        (conditions, params)
+    }
+}
 
 impl<'a> EventService<'a> {
     /// List every event ordered by start date.
@@ -12,7 +172,7 @@ impl<'a> EventService<'a> {
         let mut stmt = self.conn.prepare(
             "SELECT id, title, description, location, start_datetime, end_datetime,
                     is_all_day, category, color, recurrence_rule, recurrence_exceptions,
-                    created_at, updated_at
+                    created_at, updated_at, ics_raw_properties
              FROM events
              ORDER BY start_datetime ASC",
         )?;
@@ -24,60 +184,126 @@ impl<'a> EventService<'a> {
         Ok(events)
     }
 
-    /// Search events by title, description, location, or category.
-    pub fn search(&self, query: &str) -> Result<Vec<Event>> {
-        if query.trim().is_empty() {
+    /// Searches events with the structured query language of [`EventQuery`].
+    ///
+    /// Free text goes through the full-text index: words match as prefixes,
+    /// `"quoted text"` matches a phrase and `AND`, `OR` and `NOT` combine
+    /// terms. Text results are ranked best-first with title matches weighted
+    /// highest; filter-only queries are ordered by start date.
+    pub fn search(&self, query: &str) -> Result<Vec<SearchHit>> {
+        let query = EventQuery::parse(query)?;
+        if query.is_empty() {
             return Ok(vec![]);
         }
 
-        let search_pattern = format!("%{}%", query.to_lowercase());
-        let mut stmt = self.conn.prepare(
-            "SELECT id, title, description, location, start_datetime, end_datetime,
-                    is_all_day, category, color, recurrence_rule, recurrence_exceptions,
-                    created_at, updated_at
-             FROM events
-             WHERE LOWER(title) LIKE ?1
-                OR LOWER(COALESCE(description, '')) LIKE ?1
-                OR LOWER(COALESCE(location, '')) LIKE ?1
-                OR LOWER(COALESCE(category, '')) LIKE ?1
-             ORDER BY start_datetime ASC",
-        )?;
+        let (conditions, mut params) = query.sql_conditions();
+        let match_query = build_match_query(&query.text);
+        let sql = match &match_query {
+            Some(match_query) => {
+                params.insert(0, Value::Text(match_query.clone()));
+                format!(
+                    "SELECT {EVENT_COLUMNS},
+                            highlight(events_fts, 0, char(2), char(3)),
+                            highlight(events_fts, 2, char(2), char(3)),
+                            snippet(events_fts, 1, char(2), char(3), '…', 12)
+                     FROM events_fts
+                     JOIN events e ON e.id = events_fts.rowid
+                     WHERE events_fts MATCH ?{}
+                     ORDER BY bm25(events_fts, 10.0, 1.0, 3.0, 5.0), e.start_datetime ASC
+                     LIMIT 200",
+                    and_conditions(&conditions)
+                )
+            }
+            None => format!(
+                "SELECT {EVENT_COLUMNS}, e.title, e.location, NULL
+                 FROM events e
+                 WHERE 1 = 1{}
+                 ORDER BY e.start_datetime ASC
+                 LIMIT 200",
+                and_conditions(&conditions)
+            ),
+        };
 
-        let events = stmt
-            .query_map([&search_pattern], map_event_row)?
-            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let mut stmt = self.conn.prepare(&sql)?;
+        let hits = stmt
+            .query_map(params_from_iter(params), |row| {
+                let snippet: Option<String> = row.get(16)?;
+                Ok(SearchHit {
+                    event: map_event_row(row)?,
+                    title: row.get(14)?,
+                    location: row.get(15)?,
+                    snippet: snippet.filter(|text| text.contains(HIGHLIGHT_START)),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to run event search")?;
 
-        Ok(events)
+        Ok(hits)
+    }
+
+    /// IDs of every event matching `query`, for filtering rendered views.
+    ///
+    /// Date bounds are applied per occurrence by
+    /// [`EventQuery::includes_occurrence`]; here recurring series only need
+    /// to have started before `before:`.
+    pub fn matching_event_ids(&self, query: &EventQuery) -> Result<HashSet<i64>> {
+        let (conditions, mut params) = query.sql_conditions();
+        let sql = match build_match_query(&query.text) {
+            Some(match_query) => {
+                params.insert(0, Value::Text(match_query));
+                format!(
+                    "SELECT e.id FROM events_fts
+                     JOIN events e ON e.id = events_fts.rowid
+                     WHERE events_fts MATCH ?{}",
+                    and_conditions(&conditions)
+                )
+            }
+            None => format!(
+                "SELECT e.id FROM events e WHERE 1 = 1{}",
+                and_conditions(&conditions)
+            ),
+        };
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let ids = stmt
+            .query_map(params_from_iter(params), |row| row.get(0))?
+            .collect::<rusqlite::Result<HashSet<i64>>>()
+            .context("Failed to filter events")?;
+
+        Ok(ids)
     }
 
-    /// Find events, expanding the window slightly when recurrence is present.
+    /// Find single events overlapping the range, plus every recurring series
+    /// that started before its end (their occurrences are expanded later).
+    ///
+    /// The two halves are separate selects so each can use the start/end
+    /// indexes instead of scanning the whole table.
     pub fn find_by_date_range(
         &self,
         start: DateTime<Local>,
         end: DateTime<Local>,
     ) -> Result<Vec<Event>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, title, description, location, start_datetime, end_datetime,
-                    is_all_day, category, color, recurrence_rule, recurrence_exceptions,
-                    created_at, updated_at
-             FROM events
-             WHERE (start_datetime <= ? AND end_datetime >= ?)
-                OR (recurrence_rule IS NOT NULL AND recurrence_rule != '' AND recurrence_rule != 'None' AND start_datetime <= ?)
-             ORDER BY start_datetime ASC",
-        )?;
+        let mut stmt = self.conn.prepare_cached(&format!(
+            "SELECT {EVENT_COLUMNS}
+             FROM events e
+             WHERE e.start_datetime <= ?1 AND e.end_datetime >= ?2
+               AND NOT {RECURRING_CONDITION}
+             UNION ALL
+             SELECT {EVENT_COLUMNS}
+             FROM events e
+             WHERE e.start_datetime <= ?1 AND {RECURRING_CONDITION}
+             ORDER BY 5 ASC"
+        ))?;
 
         let events = stmt
-            .query_map(
-                [end.to_rfc3339(), start.to_rfc3339(), end.to_rfc3339()],
-                map_event_row,
-            )?
+            .query_map([end.to_rfc3339(), start.to_rfc3339()], map_event_row)?
             .collect::<rusqlite::Result<Vec<_>>>()?;
 
         Ok(events)
     }
 }
 
-fn map_event_row(row: &Row<'_>) -> Result<Event, rusqlite::Error> {
+pub(super) fn map_event_row(row: &Row<'_>) -> Result<Event, rusqlite::Error> {
     let recurrence_exceptions = deserialize_exceptions(row.get(10)?)?;
 
     Ok(Event {
@@ -94,5 +320,150 @@ fn map_event_row(row: &Row<'_>) -> Result<Event, rusqlite::Error> {
         recurrence_exceptions,
         created_at: Some(to_local_datetime(row.get::<_, String>(11)?)?),
         updated_at: Some(to_local_datetime(row.get::<_, String>(12)?)?),
+        ics_raw_properties: row.get(13)?,
     })
 }
+
+/// Splits highlighted text into `(text, is_match)` runs.
+pub fn highlight_spans(text: &str) -> Vec<(&str, bool)> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+    while let Some(open) = rest.find(HIGHLIGHT_START) {
+        if open > 0 {
+            spans.push((&rest[..open], false));
+        }
+        rest = &rest[open + HIGHLIGHT_START.len_utf8()..];
+        let close = rest.find(HIGHLIGHT_END).unwrap_or(rest.len());
+        if close > 0 {
+            spans.push((&rest[..close], true));
+        }
+        rest = rest
+            .get(close + HIGHLIGHT_END.len_utf8()..)
+            .unwrap_or_default();
+    }
+    if !rest.is_empty() {
+        spans.push((rest, false));
+    }
+    spans
+}
+
+enum QueryToken {
+    Term(String),
+    Operator(&'static str),
+}
+
+/// Turns what the user typed into an FTS5 MATCH expression.
+///
+/// Every term is quoted so punctuation can't break the FTS5 syntax. Bare
+/// words get a prefix `*` so results update while typing, and operators are
+/// only kept where they sit between two terms.
+fn build_match_query(input: &str) -> Option<String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let phrase: String = chars.by_ref().take_while(|&c| c != '"').collect();
+            if !phrase.trim().is_empty() {
+                tokens.push(QueryToken::Term(quote_fts(phrase.trim())));
+            }
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            match word.as_str() {
+                "AND" => tokens.push(QueryToken::Operator("AND")),
+                "OR" => tokens.push(QueryToken::Operator("OR")),
+                "NOT" => tokens.push(QueryToken::Operator("NOT")),
+                _ => {
+                    let word = word.trim_end_matches('*');
+                    if word.chars().any(char::is_alphanumeric) {
+                        tokens.push(QueryToken::Term(format!("{}*", quote_fts(word))));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut parts: Vec<String> = Vec::new();
+    let mut pending_operator = None;
+    for token in tokens {
+        match token {
+            QueryToken::Operator(op) => pending_operator = Some(op),
+            QueryToken::Term(term) => {
+                if let Some(op) = pending_operator.take().filter(|_| !parts.is_empty()) {
+                    parts.push(op.to_string());
+                }
+                parts.push(term);
+            }
+        }
+    }
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" "))
+    }
+}
+
+fn quote_fts(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+/// Splits on whitespace, keeping `"quoted runs"` (and `key:"quoted"`) whole.
+fn split_query_tokens(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+            current.push(c);
+        } else if c.is_whitespace() && !in_quotes {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+fn parse_query_date(key: &str, value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("Invalid date '{}:{}' (use YYYY-MM-DD)", key, value))
+}
+
+fn local_midnight(date: NaiveDate) -> DateTime<Local> {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&midnight))
+}
+
+fn any_of(condition: &str, count: usize) -> String {
+    let alternatives = vec![condition; count];
+    format!("({})", alternatives.join(" OR "))
+}
+
+fn and_conditions(conditions: &[String]) -> String {
+    conditions
+        .iter()
+        .map(|condition| format!(" AND {}", condition))
+        .collect()
+}
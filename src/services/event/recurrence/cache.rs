@@ -0,0 +1,271 @@
+//! Materialised recurrence expansion.
+//!
+//! Expanding a series walks it from its first occurrence, which gets slow for
+//! long-running daily or weekly events viewed years later. Expanded starts
+//! are stored per series for a year-aligned window and read back with an
+//! indexed range query. The invalidation triggers live with the tables in
+//! the schema, so any write to a series' timing, rule or exceptions clears
+//! its rows.
+
+use super::EventService;
+use crate::models::event::Event;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Utc};
+use rusqlite::{params, OptionalExtension};
+
+/// Requests wider than this (e.g. exports) are expanded without the cache.
+const MAX_CACHED_REQUEST_DAYS: i64 = 2 * 366;
+/// A cached window grows to cover new requests up to this span, after which
+/// it is replaced instead.
+const MAX_CACHED_WINDOW_DAYS: i64 = 10 * 366;
+
+impl<'a> EventService<'a> {
+    /// Occurrences of a recurring `event` starting within `[start, end]`,
+    /// served from the occurrence cache and filled on a miss.
+    pub(super) fn cached_occurrences(
+        &self,
+        event: &Event,
+        start: DateTime<Local>,
+        end: DateTime<Local>,
+    ) -> Result<Vec<Event>> {
+        let Some(event_id) = event.id else {
+            return self.generate_occurrences(event, start, end);
+        };
+        if end - start > Duration::days(MAX_CACHED_REQUEST_DAYS) {
+            return self.generate_occurrences(event, start, end);
+        }
+
+        let (start_us, end_us) = (start.timestamp_micros(), end.timestamp_micros());
+        let cached = self.occurrence_window(event_id)?;
+        if !cached.is_some_and(|(from, to)| from <= start_us && end_us <= to) {
+            let (from, to) = cache_window(start, end, cached);
+            self.fill_occurrence_window(event, from, to)?;
+        }
+
+        let duration = event.end - event.start;
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT occurrence_start FROM event_occurrences
+             WHERE event_id = ?1 AND occurrence_start BETWEEN ?2 AND ?3
+             ORDER BY occurrence_start",
+        )?;
+        let starts = stmt
+            .query_map(params![event_id, start_us, end_us], |row| {
+                row.get::<_, i64>(0)
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read cached occurrences")?;
+
+        Ok(starts
+            .into_iter()
+            .filter_map(DateTime::<Utc>::from_timestamp_micros)
+            .map(|start| {
+                let mut occurrence = event.clone();
+                occurrence.start = start.with_timezone(&Local);
+                occurrence.end = occurrence.start + duration;
+                occurrence
+            })
+            .collect())
+    }
+
+    fn occurrence_window(&self, event_id: i64) -> Result<Option<(i64, i64)>> {
+        self.conn
+            .query_row(
+                "SELECT window_start, window_end FROM event_occurrence_windows
+                 WHERE event_id = ?1",
+                [event_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .context("Failed to read occurrence cache window")
+    }
+
+    /// Replace the cached rows of `event` with its expansion over `[from, to]`.
+    fn fill_occurrence_window(
+        &self,
+        event: &Event,
+        from: DateTime<Local>,
+        to: DateTime<Local>,
+    ) -> Result<()> {
+        let event_id = event.id.context("Cannot cache an unsaved event")?;
+        let occurrences = self.generate_occurrences(event, from, to)?;
+
+        self.conn.execute_batch("SAVEPOINT occurrence_cache")?;
+        let result = (|| -> Result<()> {
+            self.conn.execute(
+                "DELETE FROM event_occurrences WHERE event_id = ?1",
+                [event_id],
+            )?;
+            let mut insert = self.conn.prepare_cached(
+                "INSERT INTO event_occurrences (event_id, occurrence_start) VALUES (?1, ?2)",
+            )?;
+            for occurrence in &occurrences {
+                insert.execute(params![event_id, occurrence.start.timestamp_micros()])?;
+            }
+            self.conn.execute(
+                "INSERT OR REPLACE INTO event_occurrence_windows
+                    (event_id, window_start, window_end)
+                 VALUES (?1, ?2, ?3)",
+                params![event_id, from.timestamp_micros(), to.timestamp_micros()],
+            )?;
+            Ok(())
+        })();
+
+        match result {
+            Ok(()) => self.conn.execute_batch("RELEASE occurrence_cache")?,
+            Err(_) => self
+                .conn
+                .execute_batch("ROLLBACK TO occurrence_cache; RELEASE occurrence_cache")?,
+        }
+        result.context("Failed to cache occurrences")
+    }
+}
+
+/// The window to expand for a request: whole calendar years around it, merged
+/// with the existing window while the result stays within the size limit.
+fn cache_window(
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+    cached: Option<(i64, i64)>,
+) -> (DateTime<Local>, DateTime<Local>) {
+    let from = start_of_year(start.year());
+    let to = start_of_year(end.year() + 1);
+
+    let Some((cached_from, cached_to)) = cached else {
+        return (from, to);
+    };
+    let merged_from = from.timestamp_micros().min(cached_from);
+    let merged_to = to.timestamp_micros().max(cached_to);
+    if merged_to - merged_from
+        > Duration::days(MAX_CACHED_WINDOW_DAYS)
+            .num_microseconds()
+            .unwrap_or(i64::MAX)
+    {
+        return (from, to);
+    }
+
+    match (
+        DateTime::<Utc>::from_timestamp_micros(merged_from),
+        DateTime::<Utc>::from_timestamp_micros(merged_to),
+    ) {
+        (Some(merged_from), Some(merged_to)) => (
+            merged_from.with_timezone(&Local),
+            merged_to.with_timezone(&Local),
+        ),
+        _ => (from, to),
+    }
+}
+
+fn start_of_year(year: i32) -> DateTime<Local> {
+    Local
+        .with_ymd_and_hms(year, 1, 1, 0, 0, 0)
+        .earliest()
+        .unwrap_or_else(|| {
+            Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0)
+                .unwrap()
+                .with_timezone(&Local)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::event::Event;
+    use crate::services::database::Database;
+    use crate::services::event::EventService;
+    use chrono::{Duration, Local, TimeZone};
+
+    fn setup_test_db() -> Database {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        db
+    }
+
+    fn cached_window_count(db: &Database) -> i64 {
+        db.connection()
+            .query_row("SELECT COUNT(*) FROM event_occurrence_windows", [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn cached_expansion_matches_direct_generation() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+        let start = Local.with_ymd_and_hms(2020, 1, 6, 9, 0, 0).unwrap();
+        let mut event = Event::new("Standup", start, start + Duration::minutes(15)).unwrap();
+        event.recurrence_rule = Some("FREQ=WEEKLY;BYDAY=MO,WE,FR".to_string());
+        let event = service.create(event).unwrap();
+
+        for (from, to) in [
+            ((2026, 3, 1), (2026, 4, 1)),
+            ((2026, 3, 9), (2026, 3, 16)),
+            ((2025, 12, 28), (2026, 1, 4)),
+        ] {
+            let range_start = Local
+                .with_ymd_and_hms(from.0, from.1, from.2, 0, 0, 0)
+                .unwrap();
+            let range_end = Local.with_ymd_and_hms(to.0, to.1, to.2, 0, 0, 0).unwrap();
+            let direct = service
+                .generate_occurrences(&event, range_start, range_end)
+                .unwrap();
+            let cached = service
+                .cached_occurrences(&event, range_start, range_end)
+                .unwrap();
+            let starts = |events: &[Event]| events.iter().map(|e| e.start).collect::<Vec<_>>();
+            assert_eq!(starts(&cached), starts(&direct));
+            assert!(cached
+                .iter()
+                .all(|e| e.end - e.start == Duration::minutes(15)));
+        }
+        assert_eq!(cached_window_count(&db), 1);
+    }
+
+    #[test]
+    fn changing_a_series_invalidates_its_cached_occurrences() {
+        let db = setup_test_db();
+        let service = EventService::new(db.connection());
+        let start = Local.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let mut event = Event::new("Gym", start, start + Duration::hours(1)).unwrap();
+        event.recurrence_rule = Some("FREQ=DAILY".to_string());
+        let id = service.create(event).unwrap().id.unwrap();
+
+        let range_start = start;
+        let range_end = start + Duration::days(6);
+        assert_eq!(
+            service
+                .expand_recurring_events(range_start, range_end)
+                .unwrap()
+                .len(),
+            7
+        );
+        assert_eq!(cached_window_count(&db), 1);
+
+        // Deleting one occurrence adds an exception, which must show up.
+        service
+            .delete_occurrence_local(id, start + Duration::days(2))
+            .unwrap();
+        assert_eq!(cached_window_count(&db), 0);
+        assert_eq!(
+            service
+                .expand_recurring_events(range_start, range_end)
+                .unwrap()
+                .len(),
+            6
+        );
+
+        let mut series = service.get(id).unwrap().unwrap();
+        series.recurrence_rule = Some("FREQ=DAILY;INTERVAL=2".to_string());
+        series.recurrence_exceptions = None;
+        service.update(&series).unwrap();
+        assert_eq!(
+            service
+                .expand_recurring_events(range_start, range_end)
+                .unwrap()
+                .len(),
+            4
+        );
+
+        service.delete(id).unwrap();
+        assert_eq!(cached_window_count(&db), 0);
+    }
+}
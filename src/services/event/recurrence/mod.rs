@@ -3,6 +3,7 @@ use crate::models::event::Event;
 use anyhow::Result;
 use chrono::{DateTime, Local};
 
+mod cache;
 mod daily;
 mod monthly;
 mod parser;
@@ -14,7 +15,8 @@ use parser::{detect_frequency, parse_count, parse_until, RecurrenceFrequency};
 
 impl<'a> EventService<'a> {
     /// Expand recurring events into individual occurrences within the date range.
-    /// Non-recurring events are returned as-is.
+    /// Non-recurring events are returned as-is. Occurrences come from the
+    /// occurrence cache, which is filled on first use.
     pub fn expand_recurring_events(
         &self,
         start: DateTime<Local>,
@@ -26,7 +28,7 @@ impl<'a> EventService<'a> {
         for event in base_events {
             if let Some(ref rrule) = event.recurrence_rule {
                 if rrule != "None" && !rrule.is_empty() {
-                    let occurrences = self.generate_occurrences(&event, start, end)?;
+                    let occurrences = self.cached_occurrences(&event, start, end)?;
                     expanded_events.extend(occurrences);
                 } else {
                     expanded_events.push(event);
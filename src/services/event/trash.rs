@@ -0,0 +1,296 @@
+//! Trash bin for events deleted by the user.
+//!
+//! [`EventService::delete_local`] copies the row here before deleting it,
+//! together with the writable sync identity it had, so a restore can put the
+//! event back under its original id and push it to its calendar again.
+
+use super::history::ChangeKind;
+use super::queries::map_event_row;
+use super::EventService;
+use crate::models::event::Event;
+use crate::models::event_sync_map::EventSyncMap;
+use crate::models::outbound_sync_operation::{
+    OUTBOUND_OPERATION_CREATE, OUTBOUND_OPERATION_UPDATE,
+};
+use crate::services::calendar_sync::mapping::EventSyncMapService;
+use crate::services::outbound_sync::OutboundSyncService;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, Local};
+use rusqlite::{self, params, OptionalExtension};
+
+const TRASH_EVENT_COLUMNS: &str = "event_id, title, description, location, start_datetime,
+    end_datetime, is_all_day, category, color, recurrence_rule, recurrence_exceptions,
+    created_at, updated_at, ics_raw_properties";
+
+/// An event waiting in the trash.
+#[derive(Debug, Clone)]
+pub struct TrashedEvent {
+    pub trash_id: i64,
+    /// The event as it was when deleted, with its original id
+    pub event: Event,
+    pub deleted_at: DateTime<Local>,
+    /// Whether it was synced to a writable calendar when deleted
+    pub was_synced: bool,
+}
+
+/// What happened to a restored event's calendar sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrashRestoreSync {
+    /// The event was not synced to a writable calendar
+    NotSynced,
+    /// The event was queued to be pushed back to its calendar
+    Requeued,
+    /// The calendar can no longer take it, so the event is now local only
+    LocalOnly,
+}
+
+#[derive(Debug, Clone)]
+pub struct RestoredEvent {
+    pub event: Event,
+    pub sync: TrashRestoreSync,
+}
+
+impl<'a> EventService<'a> {
+    /// Copy an event into the trash before it is deleted.
+    pub(super) fn move_to_trash(
+        &self,
+        id: i64,
+        sync_identity: Option<&(i64, String)>,
+    ) -> Result<i64> {
+        let rows_affected = self
+            .conn
+            .execute(
+                &format!(
+                    "INSERT INTO event_trash ({TRASH_EVENT_COLUMNS},
+                         sync_source_id, sync_external_uid, deleted_at)
+                     SELECT id, title, description, location, start_datetime, end_datetime,
+                            is_all_day, category, color, recurrence_rule, recurrence_exceptions,
+                            created_at, updated_at, ics_raw_properties, ?2, ?3, ?4
+                     FROM events WHERE id = ?1"
+                ),
+                params![
+                    id,
+                    sync_identity.map(|(source_id, _)| *source_id),
+                    sync_identity.map(|(_, uid)| uid.as_str()),
+                    Local::now().to_rfc3339(),
+                ],
+            )
+            .context("Failed to move event to trash")?;
+
+        if rows_affected == 0 {
+            return Err(anyhow!("Event with id {} not found", id));
+        }
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// List trashed events, most recently deleted first.
+    pub fn list_trash(&self) -> Result<Vec<TrashedEvent>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {TRASH_EVENT_COLUMNS}, id, deleted_at, sync_source_id IS NOT NULL
+             FROM event_trash
+             ORDER BY deleted_at DESC, id DESC"
+        ))?;
+
+        let trashed = stmt
+            .query_map([], |row| {
+                Ok(TrashedEvent {
+                    event: map_event_row(row)?,
+                    trash_id: row.get(14)?,
+                    deleted_at: super::shared::to_local_datetime(row.get(15)?)?,
+                    was_synced: row.get(16)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to list trash")?;
+
+        Ok(trashed)
+    }
+
+    /// Restore a trashed event, re-enqueueing it for writable synced calendars.
+    pub fn restore_from_trash(&self, trash_id: i64) -> Result<RestoredEvent> {
+        // The restored event, its sync state and the trash removal land together
+        let tx = self
+            .conn
+            .unchecked_transaction()
+            .context("Failed to begin event restore")?;
+        let (event_id, sync_source_id, sync_external_uid): (i64, Option<i64>, Option<String>) =
+            self.conn
+                .query_row(
+                    "SELECT event_id, sync_source_id, sync_external_uid
+                     FROM event_trash WHERE id = ?1",
+                    [trash_id],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                )
+                .optional()
+                .context("Failed to read trash entry")?
+                .ok_or_else(|| anyhow!("Trash entry {} not found", trash_id))?;
+
+        // Ids are never reused, but fall back to a fresh one rather than fail.
+        let id_column = if self.get(event_id)?.is_some() {
+            "NULL"
+        } else {
+            "event_id"
+        };
+        self.conn
+            .execute(
+                &format!(
+                    "INSERT INTO events (id, title, description, location, start_datetime,
+                         end_datetime, is_all_day, category, color, recurrence_rule,
+                         recurrence_exceptions, created_at, updated_at, ics_raw_properties)
+                     SELECT {id_column}, title, description, location, start_datetime,
+                            end_datetime, is_all_day, category, color, recurrence_rule,
+                            recurrence_exceptions, created_at, ?2, ics_raw_properties
+                     FROM event_trash WHERE id = ?1"
+                ),
+                params![trash_id, Local::now().to_rfc3339()],
+            )
+            .context("Failed to restore event")?;
+        let restored_id = self.conn.last_insert_rowid();
+
+        let sync = match (sync_source_id, sync_external_uid) {
+            (Some(source_id), Some(external_uid)) => {
+                self.resync_restored_event(restored_id, source_id, &external_uid)?
+            }
+            _ => TrashRestoreSync::NotSynced,
+        };
+
+        self.conn
+            .execute("DELETE FROM event_trash WHERE id = ?1", [trash_id])
+            .context("Failed to remove restored event from trash")?;
+
+        let event = self
+            .get(restored_id)?
+            .ok_or_else(|| anyhow!("Restored event {} not found", restored_id))?;
+        self.record_change(restored_id, ChangeKind::Restored, None, Some(&event))?;
+        tx.commit().context("Failed to commit event restore")?;
+        Ok(RestoredEvent { event, sync })
+    }
+
+    /// Restore the most recent trash entry for an event id, if it is still there.
+    pub fn restore_latest_trashed(&self, event_id: i64) -> Result<Option<RestoredEvent>> {
+        let trash_id: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT id FROM event_trash WHERE event_id = ?1 ORDER BY id DESC LIMIT 1",
+                [event_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to look up trashed event")?;
+
+        trash_id
+            .map(|trash_id| self.restore_from_trash(trash_id))
+            .transpose()
+    }
+
+    /// The most recently trashed copy of an event, if it is still there.
+    pub fn latest_trashed(&self, event_id: i64) -> Result<Option<Event>> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {TRASH_EVENT_COLUMNS} FROM event_trash
+                     WHERE event_id = ?1 ORDER BY id DESC LIMIT 1"
+                ),
+                [event_id],
+                map_event_row,
+            )
+            .optional()
+            .context("Failed to look up trashed event")
+    }
+
+    /// Permanently delete one trash entry.
+    pub fn purge_from_trash(&self, trash_id: i64) -> Result<()> {
+        self.purge_trash_where("id = ?1", params![trash_id])
+            .context("Failed to purge trashed event")?;
+        Ok(())
+    }
+
+    /// Permanently delete everything in the trash.
+    pub fn empty_trash(&self) -> Result<usize> {
+        self.purge_trash_where("1 = 1", params![])
+            .context("Failed to empty trash")
+    }
+
+    /// Permanently delete trash entries older than the retention period.
+    pub fn purge_expired_trash(&self, retention_days: i64, now: DateTime<Local>) -> Result<usize> {
+        let cutoff = now - Duration::days(retention_days);
+        self.purge_trash_where(
+            "datetime(deleted_at) < datetime(?1)",
+            params![cutoff.to_rfc3339()],
+        )
+        .context("Failed to purge expired trash")
+    }
+
+    /// Delete matching trash rows along with the change history of events
+    /// that are gone for good.
+    fn purge_trash_where(&self, condition: &str, params: &[&dyn rusqlite::ToSql]) -> Result<usize> {
+        self.conn.execute(
+            &format!(
+                "DELETE FROM event_history
+                 WHERE event_id IN (SELECT event_id FROM event_trash WHERE {condition})
+                   AND event_id NOT IN (SELECT id FROM events)"
+            ),
+            params,
+        )?;
+        Ok(self.conn.execute(
+            &format!("DELETE FROM event_trash WHERE {condition}"),
+            params,
+        )?)
+    }
+
+    /// Re-link a restored event to its calendar and queue it for upload: as
+    /// an update while the remote copy still exists (the delete has not been
+    /// pushed yet), otherwise as a new event.
+    fn resync_restored_event(
+        &self,
+        event_id: i64,
+        source_id: i64,
+        external_uid: &str,
+    ) -> Result<TrashRestoreSync> {
+        let map_service = EventSyncMapService::new(self.conn);
+        if map_service
+            .get_by_source_and_uid(source_id, external_uid)?
+            .is_some()
+        {
+            return Ok(TrashRestoreSync::LocalOnly);
+        }
+        let remote_exists = map_service
+            .get_remote_metadata(source_id, external_uid)?
+            .and_then(|metadata| metadata.remote_event_id)
+            .is_some();
+        let operation = if remote_exists {
+            OUTBOUND_OPERATION_UPDATE
+        } else {
+            OUTBOUND_OPERATION_CREATE
+        };
+
+        map_service.create(EventSyncMap {
+            id: None,
+            source_id,
+            external_uid: external_uid.to_string(),
+            local_event_id: event_id,
+            external_last_modified: None,
+            external_etag_hash: None,
+            last_seen_at: None,
+            first_missing_at: None,
+            purge_after_at: None,
+        })?;
+
+        let event = self
+            .get(event_id)?
+            .ok_or_else(|| anyhow!("Restored event {} not found", event_id))?;
+        let payload = Self::build_outbound_payload(&event, event_id).to_string();
+        let queued = OutboundSyncService::new(self.conn).enqueue_upsert_for_local_event(
+            event_id,
+            operation,
+            Some(&payload),
+        )?;
+        if queued {
+            Ok(TrashRestoreSync::Requeued)
+        } else {
+            map_service.delete_by_source_and_uid(source_id, external_uid)?;
+            Ok(TrashRestoreSync::LocalOnly)
+        }
+    }
+}
@@ -7,6 +7,7 @@ use rusqlite::{params, Connection, OptionalExtension};
 use serde::Deserialize;
 
 use crate::models::google_account::GoogleAccount;
+use crate::services::secrets;
 
 const GOOGLE_DEVICE_CODE_ENDPOINT: &str = "https://oauth2.googleapis.com/device/code";
 const GOOGLE_TOKEN_ENDPOINT: &str = "https://oauth2.googleapis.com/token";
@@ -60,38 +61,17 @@ impl<'a> GoogleAccountService<'a> {
         Ok(Self { conn, client })
     }
 
-    pub fn load(&self) -> Result<GoogleAccount> {
+    /// Returns the OAuth client ID shared by all connected accounts.
+    pub fn client_id(&self) -> Result<Option<String>> {
         self.conn
             .query_row(
-                "SELECT id, oauth_client_id, account_email, access_token, refresh_token,
-                        token_type, scope, expires_at, last_error, connected_at, updated_at
-                 FROM google_account
-                 WHERE id = 1",
+                "SELECT oauth_client_id FROM google_account WHERE id = 1",
                 [],
-                |row| {
-                    Ok(GoogleAccount {
-                        id: row.get(0)?,
-                        oauth_client_id: row.get(1)?,
-                        account_email: row.get(2)?,
-                        access_token: row.get(3)?,
-                        refresh_token: row.get(4)?,
-                        token_type: row.get(5)?,
-                        scope: row.get(6)?,
-                        expires_at: row.get(7)?,
-                        last_error: row.get(8)?,
-                        connected_at: row.get(9)?,
-                        updated_at: row.get(10)?,
-                    })
-                },
+                |row| row.get::<_, Option<String>>(0),
             )
             .optional()
-            .map(|maybe| {
-                maybe.unwrap_or(GoogleAccount {
-                    id: 1,
-                    ..GoogleAccount::default()
-                })
-            })
-            .context("Failed to load Google account state")
+            .map(Option::flatten)
+            .context("Failed to load Google OAuth client ID")
     }
 
     pub fn set_client_id(&self, client_id: &str) -> Result<()> {
@@ -115,33 +95,134 @@ impl<'a> GoogleAccountService<'a> {
         Ok(())
     }
 
-    pub fn disconnect(&self) -> Result<()> {
-        let now = Local::now().to_rfc3339();
+    pub fn list_accounts(&self) -> Result<Vec<GoogleAccount>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, account_email, access_token, refresh_token, token_type, scope,
+                        expires_at, last_error, connected_at, updated_at
+                 FROM google_accounts
+                 ORDER BY account_email COLLATE NOCASE ASC",
+            )
+            .context("Failed to prepare Google account list query")?;
+
+        let rows = stmt.query_map([], Self::row_to_account)?;
+        Ok(rows
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to load Google accounts")?
+            .into_iter()
+            .map(Self::resolve_tokens)
+            .collect())
+    }
+
+    pub fn get_account(&self, account_id: i64) -> Result<Option<GoogleAccount>> {
+        self.conn
+            .query_row(
+                "SELECT id, account_email, access_token, refresh_token, token_type, scope,
+                        expires_at, last_error, connected_at, updated_at
+                 FROM google_accounts
+                 WHERE id = ?1",
+                [account_id],
+                Self::row_to_account,
+            )
+            .optional()
+            .context("Failed to load Google account")
+            .map(|account| account.map(Self::resolve_tokens))
+    }
+
+    /// Raw `(access_token, refresh_token)` column values, which are secret-store references.
+    fn stored_token_refs(
+        &self,
+        sql: &str,
+        key: &dyn rusqlite::ToSql,
+    ) -> Result<(Option<String>, Option<String>)> {
+        Ok(self
+            .conn
+            .query_row(sql, [key], |row| Ok((row.get(0)?, row.get(1)?)))
+            .optional()
+            .context("Failed to read stored Google account tokens")?
+            .unwrap_or((None, None)))
+    }
+
+    fn forget_tokens(&self, account_id: i64) -> Result<()> {
+        let (access_ref, refresh_ref) = self.stored_token_refs(
+            "SELECT access_token, refresh_token FROM google_accounts WHERE id = ?1",
+            &account_id,
+        )?;
+        secrets::forget(access_ref.as_deref());
+        secrets::forget(refresh_ref.as_deref());
+        Ok(())
+    }
+
+    /// Replaces the stored references with the tokens. A token that can't be
+    /// read is logged and dropped, and the account shows it as its error, so
+    /// the other accounts still load and this one can be reconnected.
+    fn resolve_tokens(mut account: GoogleAccount) -> GoogleAccount {
+        let access = secrets::resolve_optional(account.access_token.take());
+        let refresh = secrets::resolve_optional(account.refresh_token.take());
+        for (kind, result) in [("access", &access), ("refresh", &refresh)] {
+            if let Err(err) = result {
+                log::warn!(
+                    "Failed to read the Google {} token of {}: {:#}",
+                    kind,
+                    account
+                        .account_email
+                        .as_deref()
+                        .unwrap_or("unknown account"),
+                    err
+                );
+                account.last_error = Some(format!(
+                    "The {} token could not be read from the secret store; reconnect the account",
+                    kind
+                ));
+            }
+        }
+        account.access_token = access.unwrap_or_default();
+        account.refresh_token = refresh.unwrap_or_default();
+        account
+    }
+
+    fn require_account(&self, account_id: i64) -> Result<GoogleAccount> {
+        self.get_account(account_id)?
+            .ok_or_else(|| anyhow!("Google account with id {} not found", account_id))
+    }
+
+    /// Clears an account's tokens but keeps it, so linked sources stay linked
+    /// until the same identity reconnects.
+    pub fn disconnect(&self, account_id: i64) -> Result<()> {
+        self.forget_tokens(account_id)?;
         self.conn
             .execute(
-                "INSERT INTO google_account (id, updated_at)
-                 VALUES (1, ?1)
-                 ON CONFLICT(id) DO UPDATE SET
-                   account_email = NULL,
-                   access_token = NULL,
-                   refresh_token = NULL,
-                   token_type = NULL,
-                   scope = NULL,
-                   expires_at = NULL,
-                   connected_at = NULL,
-                   last_error = NULL,
-                   updated_at = excluded.updated_at",
-                params![now],
+                "UPDATE google_accounts
+                 SET access_token = NULL,
+                     refresh_token = NULL,
+                     token_type = NULL,
+                     scope = NULL,
+                     expires_at = NULL,
+                     connected_at = NULL,
+                     last_error = NULL,
+                     updated_at = ?1
+                 WHERE id = ?2",
+                params![Local::now().to_rfc3339(), account_id],
             )
             .context("Failed to disconnect Google account")?;
 
         Ok(())
     }
 
-    pub fn refresh_access_token(&self) -> Result<GoogleAccount> {
-        let state = self.load()?;
-        let client_id = state
-            .oauth_client_id
+    /// Deletes an account; sources linked to it are left without an account.
+    pub fn remove_account(&self, account_id: i64) -> Result<()> {
+        self.forget_tokens(account_id)?;
+        self.conn
+            .execute("DELETE FROM google_accounts WHERE id = ?1", [account_id])
+            .context("Failed to remove Google account")?;
+        Ok(())
+    }
+
+    pub fn refresh_access_token(&self, account_id: i64) -> Result<GoogleAccount> {
+        let state = self.require_account(account_id)?;
+        let client_id = self
+            .client_id()?
             .as_deref()
             .map(str::trim)
             .filter(|v| !v.is_empty())
@@ -155,6 +236,10 @@ impl<'a> GoogleAccountService<'a> {
             .filter(|v| !v.is_empty())
             .ok_or_else(|| anyhow!("No refresh token available; reconnect Google account"))?
             .to_string();
+        let email = state
+            .account_email
+            .clone()
+            .ok_or_else(|| anyhow!("Google account {} has no email", account_id))?;
 
         let response = self
             .client
@@ -171,10 +256,13 @@ impl<'a> GoogleAccountService<'a> {
             let status = response.status();
             let body = response.text().unwrap_or_default();
             let error = Self::decode_token_error(&body).unwrap_or_else(|| body.trim().to_string());
-            self.record_last_error(&format!("Token refresh failed ({status}): {error}"))?;
+            self.record_last_error(
+                account_id,
+                &format!("Token refresh failed ({status}): {error}"),
+            )?;
             if error.contains("invalid_grant") {
-                // Token revoked or invalid: keep client_id, clear auth material.
-                self.disconnect()?;
+                // Token revoked or invalid: keep the account, clear auth material.
+                self.disconnect(account_id)?;
             }
             return Err(anyhow!("Token refresh failed: {}", error));
         }
@@ -184,7 +272,7 @@ impl<'a> GoogleAccountService<'a> {
             .context("Failed to parse Google token refresh response")?;
 
         self.persist_token_update(
-            state.account_email.as_deref(),
+            &email,
             &token.access_token,
             token
                 .refresh_token
@@ -196,13 +284,16 @@ impl<'a> GoogleAccountService<'a> {
         )
         .context("Failed to persist refreshed token")?;
 
-        self.load()
+        self.require_account(account_id)
     }
 
-    pub fn valid_access_token(&self) -> Result<String> {
-        let state = self.load()?;
+    pub fn valid_access_token(&self, account_id: i64) -> Result<String> {
+        let state = self.require_account(account_id)?;
         if !state.is_connected() {
-            return Err(anyhow!("Google account is not connected"));
+            return Err(anyhow!(
+                "Google account {} is not connected",
+                state.account_email.as_deref().unwrap_or_default()
+            ));
         }
 
         let should_refresh = state
@@ -215,7 +306,7 @@ impl<'a> GoogleAccountService<'a> {
             .unwrap_or(false);
 
         if should_refresh {
-            let refreshed = self.refresh_access_token()?;
+            let refreshed = self.refresh_access_token(account_id)?;
             return refreshed
                 .access_token
                 .filter(|token| !token.trim().is_empty())
@@ -228,6 +319,8 @@ impl<'a> GoogleAccountService<'a> {
             .ok_or_else(|| anyhow!("Google account is missing an access token; refresh required"))
     }
 
+    /// Links a Google identity through the device flow. Connecting an account
+    /// that is already known refreshes its tokens instead of adding a duplicate.
     pub fn connect_with_device_flow(&self, client_id: &str) -> Result<GoogleAccount> {
         let client_id = client_id.trim();
         if client_id.is_empty() {
@@ -261,17 +354,18 @@ impl<'a> GoogleAccountService<'a> {
         let token = self.poll_for_device_token(client_id, &device)?;
         let email = self.fetch_account_email(&token.access_token)?;
 
-        self.persist_token_update(
-            Some(email.as_str()),
-            &token.access_token,
-            token.refresh_token.as_deref(),
-            token.token_type.as_deref(),
-            token.scope.as_deref(),
-            token.expires_in,
-        )
-        .context("Failed to persist connected Google account")?;
+        let account_id = self
+            .persist_token_update(
+                &email,
+                &token.access_token,
+                token.refresh_token.as_deref(),
+                token.token_type.as_deref(),
+                token.scope.as_deref(),
+                token.expires_in,
+            )
+            .context("Failed to persist connected Google account")?;
 
-        self.load()
+        self.require_account(account_id)
     }
 
     fn request_device_code(&self, client_id: &str) -> Result<DeviceCodeResponse> {
@@ -372,36 +466,55 @@ impl<'a> GoogleAccountService<'a> {
             .ok_or_else(|| anyhow!("Google user info response did not include account email"))
     }
 
+    /// Upserts tokens for the account identified by `account_email` and
+    /// returns its id.
     fn persist_token_update(
         &self,
-        account_email: Option<&str>,
+        account_email: &str,
         access_token: &str,
         refresh_token: Option<&str>,
         token_type: Option<&str>,
         scope: Option<&str>,
         expires_in: Option<i64>,
-    ) -> Result<()> {
+    ) -> Result<i64> {
         let now = Local::now();
         let expires_at =
             expires_in.map(|seconds| (now + Duration::seconds(seconds.max(1))).to_rfc3339());
 
-        self.conn
-            .execute(
-                "INSERT INTO google_account (
-                    id, account_email, access_token, refresh_token,
+        // Tokens live in the secret store; the row keeps only references.
+        let (access_ref, refresh_ref) = self.stored_token_refs(
+            "SELECT access_token, refresh_token FROM google_accounts WHERE account_email = ?1",
+            &account_email,
+        )?;
+        let access_token = secrets::put(access_ref.as_deref(), access_token)
+            .context("Failed to store Google access token")?;
+        let refresh_token = secrets::discard_on_error(
+            refresh_token
+                .map(|token| secrets::put(refresh_ref.as_deref(), token))
+                .transpose()
+                .context("Failed to store Google refresh token"),
+            access_ref.as_deref(),
+            Some(&access_token),
+        )?;
+
+        let persisted = self
+            .conn
+            .query_row(
+                "INSERT INTO google_accounts (
+                    account_email, access_token, refresh_token,
                     token_type, scope, expires_at, connected_at, updated_at, last_error
                  )
-                 VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, NULL)
-                 ON CONFLICT(id) DO UPDATE SET
-                   account_email = excluded.account_email,
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, NULL)
+                 ON CONFLICT(account_email) DO UPDATE SET
                    access_token = excluded.access_token,
-                   refresh_token = COALESCE(excluded.refresh_token, google_account.refresh_token),
+                   refresh_token = COALESCE(excluded.refresh_token, google_accounts.refresh_token),
                    token_type = excluded.token_type,
                    scope = excluded.scope,
                    expires_at = excluded.expires_at,
-                   connected_at = COALESCE(google_account.connected_at, excluded.connected_at),
+                   connected_at = COALESCE(google_accounts.connected_at, excluded.connected_at),
                    updated_at = excluded.updated_at,
-                   last_error = NULL",
+                   last_error = NULL
+                 RETURNING id",
                 params![
                     account_email,
                     access_token,
@@ -412,26 +525,41 @@ impl<'a> GoogleAccountService<'a> {
                     now.to_rfc3339(),
                     now.to_rfc3339(),
                 ],
+                |row| row.get(0),
             )
-            .context("Failed to persist Google account token update")?;
-
-        Ok(())
+            .context("Failed to persist Google account token update");
+        let persisted =
+            secrets::discard_on_error(persisted, access_ref.as_deref(), Some(&access_token));
+        secrets::discard_on_error(persisted, refresh_ref.as_deref(), refresh_token.as_deref())
     }
 
-    fn record_last_error(&self, message: &str) -> Result<()> {
+    fn record_last_error(&self, account_id: i64, message: &str) -> Result<()> {
         self.conn
             .execute(
-                "INSERT INTO google_account (id, last_error, updated_at)
-                 VALUES (1, ?1, ?2)
-                 ON CONFLICT(id) DO UPDATE SET
-                   last_error = excluded.last_error,
-                   updated_at = excluded.updated_at",
-                params![message, Local::now().to_rfc3339()],
+                "UPDATE google_accounts
+                 SET last_error = ?1, updated_at = ?2
+                 WHERE id = ?3",
+                params![message, Local::now().to_rfc3339(), account_id],
             )
             .context("Failed to store Google account error")?;
         Ok(())
     }
 
+    fn row_to_account(row: &rusqlite::Row<'_>) -> rusqlite::Result<GoogleAccount> {
+        Ok(GoogleAccount {
+            id: row.get(0)?,
+            account_email: row.get(1)?,
+            access_token: row.get(2)?,
+            refresh_token: row.get(3)?,
+            token_type: row.get(4)?,
+            scope: row.get(5)?,
+            expires_at: row.get(6)?,
+            last_error: row.get(7)?,
+            connected_at: row.get(8)?,
+            updated_at: row.get(9)?,
+        })
+    }
+
     fn decode_token_error(body: &str) -> Option<String> {
         serde_json::from_str::<TokenErrorResponse>(body)
             .ok()
@@ -449,6 +577,7 @@ impl<'a> GoogleAccountService<'a> {
 mod tests {
     use super::GoogleAccountService;
     use crate::services::database::Database;
+    use crate::services::secrets;
 
     #[test]
     fn saves_client_id_and_disconnects() {
@@ -460,15 +589,137 @@ mod tests {
             .set_client_id("client-id.apps.googleusercontent.com")
             .unwrap();
 
-        let loaded = service.load().unwrap();
         assert_eq!(
-            loaded.oauth_client_id.as_deref(),
+            service.client_id().unwrap().as_deref(),
             Some("client-id.apps.googleusercontent.com")
         );
 
-        service.disconnect().unwrap();
-        let disconnected = service.load().unwrap();
-        assert!(disconnected.account_email.is_none());
+        let account_id = service
+            .persist_token_update(
+                "user@example.com",
+                "access",
+                Some("refresh"),
+                None,
+                None,
+                Some(3600),
+            )
+            .unwrap();
+        assert!(service
+            .get_account(account_id)
+            .unwrap()
+            .unwrap()
+            .is_connected());
+
+        service.disconnect(account_id).unwrap();
+        let disconnected = service.get_account(account_id).unwrap().unwrap();
+        assert_eq!(
+            disconnected.account_email.as_deref(),
+            Some("user@example.com")
+        );
         assert!(disconnected.access_token.is_none());
+        assert!(!disconnected.is_connected());
+    }
+
+    #[test]
+    fn keeps_tokens_separate_per_account() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let service = GoogleAccountService::new(db.connection()).unwrap();
+
+        let home = service
+            .persist_token_update(
+                "home@example.com",
+                "home-access",
+                Some("home-refresh"),
+                None,
+                None,
+                Some(3600),
+            )
+            .unwrap();
+        let work = service
+            .persist_token_update(
+                "work@example.com",
+                "work-access",
+                Some("work-refresh"),
+                None,
+                None,
+                Some(3600),
+            )
+            .unwrap();
+        assert_ne!(home, work);
+
+        // Reconnecting an existing identity updates it in place.
+        let again = service
+            .persist_token_update(
+                "home@example.com",
+                "home-access-2",
+                None,
+                None,
+                None,
+                Some(3600),
+            )
+            .unwrap();
+        assert_eq!(again, home);
+
+        assert_eq!(service.list_accounts().unwrap().len(), 2);
+        let stored_refresh: String = db
+            .connection()
+            .query_row(
+                "SELECT refresh_token FROM google_accounts WHERE id = ?1",
+                [home],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(secrets::is_reference(&stored_refresh));
+        assert_eq!(service.valid_access_token(home).unwrap(), "home-access-2");
+        assert_eq!(service.valid_access_token(work).unwrap(), "work-access");
+        assert_eq!(
+            service
+                .get_account(home)
+                .unwrap()
+                .unwrap()
+                .refresh_token
+                .as_deref(),
+            Some("home-refresh")
+        );
+    }
+
+    #[test]
+    fn migrates_legacy_single_account_row() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        conn.execute(
+            "INSERT OR REPLACE INTO google_account (id, account_email, access_token, refresh_token)
+             VALUES (1, 'legacy@example.com', 'a', 'r')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO calendar_sources (name, ics_url, sync_capability)
+             VALUES ('Work', 'https://calendar.google.com/calendar/ical/x/basic.ics', 'read_write')",
+            [],
+        )
+        .unwrap();
+        // Databases from before schema versioning have no version table.
+        conn.execute("DROP TABLE schema_version", []).unwrap();
+
+        db.initialize_schema().unwrap();
+
+        let service = GoogleAccountService::new(conn).unwrap();
+        let accounts = service.list_accounts().unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(
+            accounts[0].account_email.as_deref(),
+            Some("legacy@example.com")
+        );
+        let linked: Option<i64> = conn
+            .query_row(
+                "SELECT google_account_id FROM calendar_sources WHERE name = 'Work'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(linked, Some(accounts[0].id));
     }
 }
@@ -0,0 +1,5 @@
+//! Static HTML publishing of calendar views
+
+pub mod service;
+
+pub use service::HtmlPublishService;
@@ -0,0 +1,779 @@
+//! Static HTML publishing implementation
+//!
+//! Writes a folder of plain HTML pages and one stylesheet, with no scripts
+//! or external resources, so it can be copied onto any web server. Each
+//! month in the range gets a grid page and an agenda page; `index.html`
+//! links to them.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate};
+use egui::Color32;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::category::Category;
+use crate::models::event::Event;
+use crate::services::calendar_layout::{self, readable_text, MonthLayout};
+use crate::services::event::EventService;
+use crate::ui_egui::theme::CalendarTheme;
+
+/// Subfolder of the chosen folder the site is written to, so publishing
+/// never overwrites files it did not create
+pub const SITE_FOLDER: &str = "calendar-site";
+
+/// Service for publishing calendar data as a static website
+pub struct HtmlPublishService;
+
+/// Publishing options
+pub struct HtmlPublishOptions {
+    /// Site title, shown on every page
+    pub title: String,
+    /// First day to publish
+    pub start: NaiveDate,
+    /// Last day to publish (inclusive)
+    pub end: NaiveDate,
+    /// Names of the categories to publish; `None` publishes every event
+    pub categories: Option<Vec<String>>,
+    /// Whether events without a category are published when `categories`
+    /// is set
+    pub include_uncategorized: bool,
+    /// 0 = Sunday, 1 = Monday, ...
+    pub first_day_of_week: u8,
+    /// 24-hour times instead of 12-hour
+    pub use_24_hour: bool,
+    /// Include event descriptions on agenda pages
+    pub include_descriptions: bool,
+    /// Include event locations
+    pub include_locations: bool,
+}
+
+impl Default for HtmlPublishOptions {
+    fn default() -> Self {
+        let today = Local::now().date_naive();
+        let start = today.with_day(1).unwrap_or(today);
+        Self {
+            title: "Calendar".to_string(),
+            start,
+            end: (start + Months::new(1)) - Duration::days(1),
+            categories: None,
+            include_uncategorized: true,
+            first_day_of_week: 0,
+            use_24_hour: false,
+            include_descriptions: true,
+            include_locations: true,
+        }
+    }
+}
+
+/// One file of a generated site
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteFile {
+    pub name: String,
+    pub content: String,
+}
+
+impl HtmlPublishService {
+    /// Publish the occurrences in the options' range to the [`SITE_FOLDER`]
+    /// in `folder`, creating it if needed. Month pages from an earlier
+    /// publish that fall outside the range are removed. Returns the number
+    /// of occurrences published.
+    pub fn publish(
+        event_service: &EventService,
+        categories: &[Category],
+        theme: &CalendarTheme,
+        options: &HtmlPublishOptions,
+        folder: &Path,
+    ) -> Result<usize> {
+        let (start, end) = calendar_layout::date_range(options.start, options.end);
+        let events: Vec<Event> = event_service
+            .expand_recurring_events(start, end)
+            .context("Failed to load events for publishing")?
+            .into_iter()
+            .filter(|event| Self::is_published(event, options))
+            .collect();
+
+        let folder = Self::site_folder(folder);
+        fs::create_dir_all(&folder)
+            .with_context(|| format!("Failed to create folder {:?}", folder))?;
+        let files = Self::build_site(&events, categories, theme, options);
+        Self::remove_stale_pages(&folder, &files)?;
+        for file in files {
+            let path = folder.join(&file.name);
+            fs::write(&path, file.content)
+                .with_context(|| format!("Failed to write {:?}", path))?;
+        }
+
+        Ok(events.len())
+    }
+
+    /// Where [`publish`](Self::publish) writes the site for `folder`
+    pub fn site_folder(folder: &Path) -> PathBuf {
+        folder.join(SITE_FOLDER)
+    }
+
+    /// Delete month and agenda pages in `folder` that are not among `files`
+    fn remove_stale_pages(folder: &Path, files: &[SiteFile]) -> Result<()> {
+        let entries =
+            fs::read_dir(folder).with_context(|| format!("Failed to read folder {:?}", folder))?;
+        for entry in entries {
+            let entry = entry.with_context(|| format!("Failed to read folder {:?}", folder))?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_page = (name.starts_with("month-") || name.starts_with("agenda-"))
+                && name.ends_with(".html");
+            if is_page && !files.iter().any(|file| file.name == name) {
+                fs::remove_file(entry.path())
+                    .with_context(|| format!("Failed to remove {:?}", entry.path()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The files of the site for `events` (already expanded into
+    /// occurrences and filtered)
+    pub fn build_site(
+        events: &[Event],
+        categories: &[Category],
+        theme: &CalendarTheme,
+        options: &HtmlPublishOptions,
+    ) -> Vec<SiteFile> {
+        let site = Site {
+            events,
+            categories,
+            theme,
+            options,
+            months: Self::months(options.start, options.end),
+        };
+
+        let mut files = vec![
+            SiteFile {
+                name: "style.css".to_string(),
+                content: site.stylesheet(),
+            },
+            SiteFile {
+                name: "index.html".to_string(),
+                content: site.index_page(),
+            },
+        ];
+        for (index, month) in site.months.iter().enumerate() {
+            files.push(SiteFile {
+                name: month_file(*month),
+                content: site.month_page(index),
+            });
+            files.push(SiteFile {
+                name: agenda_file(*month),
+                content: site.agenda_page(index),
+            });
+        }
+        files
+    }
+
+    /// Whether the category filter lets `event` through
+    fn is_published(event: &Event, options: &HtmlPublishOptions) -> bool {
+        let Some(selected) = &options.categories else {
+            return true;
+        };
+        match event.category.as_deref().filter(|name| !name.is_empty()) {
+            Some(name) => selected
+                .iter()
+                .any(|selected| selected.eq_ignore_ascii_case(name)),
+            None => options.include_uncategorized,
+        }
+    }
+
+    /// The first day of each month the range touches
+    fn months(start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        let mut months = Vec::new();
+        let mut month = start.with_day(1).unwrap_or(start);
+        while month <= end {
+            months.push(month);
+            month = month + Months::new(1);
+        }
+        months
+    }
+}
+
+/// Everything the pages are built from
+struct Site<'a> {
+    events: &'a [Event],
+    categories: &'a [Category],
+    theme: &'a CalendarTheme,
+    options: &'a HtmlPublishOptions,
+    months: Vec<NaiveDate>,
+}
+
+impl Site<'_> {
+    fn stylesheet(&self) -> String {
+        let theme = self.theme;
+        let hex = CalendarTheme::color_to_hex;
+        format!(
+            r#":root {{
+  color-scheme: {scheme};
+  --background: {background};
+  --calendar: {calendar};
+  --day: {day};
+  --weekend: {weekend};
+  --border: {border};
+  --text: {text};
+  --muted: {muted};
+  --header-background: {header_background};
+  --header-text: {header_text};
+  --accent: {accent};
+  --accent-background: {accent_background};
+}}
+* {{ box-sizing: border-box; }}
+body {{ margin: 0; padding: 1.5rem; background: var(--background); color: var(--text); font: 15px/1.4 system-ui, -apple-system, "Segoe UI", Roboto, sans-serif; }}
+a {{ color: var(--accent); }}
+header h1 {{ margin: 0 0 0.25rem; }}
+header p {{ margin: 0 0 1rem; color: var(--muted); }}
+nav {{ display: flex; flex-wrap: wrap; gap: 0.5rem 1rem; margin-bottom: 1rem; }}
+nav .current {{ font-weight: 600; padding: 0 0.3rem; border-radius: 3px; background: var(--accent-background); }}
+table.overview {{ border-collapse: collapse; margin-bottom: 1rem; }}
+table.overview th, table.overview td {{ padding: 0.3rem 1.5rem 0.3rem 0; text-align: left; border-bottom: 1px solid var(--border); }}
+table.month {{ width: 100%; border-collapse: collapse; table-layout: fixed; background: var(--calendar); }}
+table.month th {{ padding: 0.4rem; background: var(--header-background); color: var(--header-text); border: 1px solid var(--border); }}
+table.month td {{ height: 7rem; padding: 0.3rem; vertical-align: top; background: var(--day); border: 1px solid var(--border); overflow: hidden; }}
+table.month td.weekend {{ background: var(--weekend); }}
+table.month td.empty {{ background: var(--calendar); }}
+table.month td.outside {{ color: var(--muted); opacity: 0.5; }}
+.day-number {{ display: block; font-weight: 600; margin-bottom: 0.2rem; }}
+ul.events {{ list-style: none; margin: 0; padding: 0; }}
+ul.events li {{ margin: 0 0 0.15rem; padding: 0.05rem 0.3rem; border-radius: 3px; font-size: 0.8rem; white-space: nowrap; overflow: hidden; text-overflow: ellipsis; }}
+.time {{ font-variant-numeric: tabular-nums; }}
+section.day {{ margin-bottom: 1.25rem; }}
+section.day h2 {{ margin: 0 0 0.4rem; padding: 0.3rem 0.5rem; font-size: 1rem; background: var(--header-background); color: var(--header-text); border-left: 4px solid var(--accent); }}
+ul.agenda {{ list-style: none; margin: 0; padding: 0; }}
+ul.agenda li {{ display: grid; grid-template-columns: 9rem 1fr; gap: 0.75rem; padding: 0.4rem 0.5rem; background: var(--day); border-left: 4px solid var(--event); margin-bottom: 0.25rem; }}
+ul.agenda .details {{ color: var(--muted); font-size: 0.9rem; }}
+.category {{ display: inline-block; padding: 0 0.4rem; border-radius: 3px; font-size: 0.75rem; margin-left: 0.4rem; }}
+.legend {{ display: flex; flex-wrap: wrap; gap: 0.5rem; padding: 0; list-style: none; }}
+.empty-note {{ color: var(--muted); }}
+footer {{ margin-top: 2rem; color: var(--muted); font-size: 0.8rem; }}
+"#,
+            scheme = if theme.is_dark { "dark" } else { "light" },
+            background = hex(theme.app_background),
+            calendar = hex(theme.calendar_background),
+            day = hex(theme.day_background),
+            weekend = hex(theme.weekend_background),
+            border = hex(theme.day_border),
+            text = hex(theme.text_primary),
+            muted = hex(theme.text_secondary),
+            header_background = hex(theme.header_background),
+            header_text = hex(theme.header_text),
+            accent = hex(theme.today_border),
+            accent_background = hex(theme.today_background),
+        )
+    }
+
+    fn index_page(&self) -> String {
+        let mut body = String::from(
+            "<table class=\"overview\">\n<tr><th>Month</th><th>Events</th><th></th></tr>\n",
+        );
+        for month in &self.months {
+            let (start, end) = calendar_layout::date_range(
+                (*month).max(self.options.start),
+                month_end(*month).min(self.options.end),
+            );
+            let count = self
+                .events
+                .iter()
+                .filter(|event| event.start < end && event.end > start)
+                .count();
+            body.push_str(&format!(
+                "<tr><td><a href=\"{}\">{}</a></td><td>{}</td><td><a href=\"{}\">Agenda</a></td></tr>\n",
+                month_file(*month),
+                month.format("%B %Y"),
+                count,
+                agenda_file(*month),
+            ));
+        }
+        body.push_str("</table>\n");
+
+        let legend = self.legend();
+        if !legend.is_empty() {
+            body.push_str("<h2>Categories</h2>\n");
+            body.push_str(&legend);
+        }
+        self.page(&self.options.title, "", &body)
+    }
+
+    fn month_page(&self, index: usize) -> String {
+        let month = self.months[index];
+        let layout = MonthLayout::new(month, self.options.first_day_of_week);
+        let weekend = weekend_columns(self.options.first_day_of_week);
+
+        let mut body = String::from("<table class=\"month\">\n<tr>");
+        for name in calendar_layout::day_names(self.options.first_day_of_week) {
+            body.push_str(&format!("<th>{}</th>", name));
+        }
+        body.push_str("</tr>\n");
+
+        for week in &layout.weeks {
+            body.push_str("<tr>");
+            for (column, day) in week.iter().enumerate() {
+                let Some(day) = *day else {
+                    body.push_str("<td class=\"empty\"></td>");
+                    continue;
+                };
+                let mut classes = Vec::new();
+                if weekend[column] {
+                    classes.push("weekend");
+                }
+                let in_range = day >= self.options.start && day <= self.options.end;
+                if !in_range {
+                    classes.push("outside");
+                }
+                body.push_str(&format!(
+                    "<td class=\"{}\"><span class=\"day-number\">{}</span>",
+                    classes.join(" "),
+                    day.day()
+                ));
+                let day_events = calendar_layout::events_on_day(self.events, day);
+                if in_range && !day_events.is_empty() {
+                    body.push_str("<ul class=\"events\">");
+                    for event in day_events {
+                        let (background, text) = self.event_colors(event);
+                        let time = if event.all_day || event.start.date_naive() != day {
+                            String::new()
+                        } else {
+                            format!(
+                                "<span class=\"time\">{}</span> ",
+                                self.format_time(event.start)
+                            )
+                        };
+                        body.push_str(&format!(
+                            "<li style=\"background:{};color:{}\" title=\"{}\">{}{}</li>",
+                            background,
+                            text,
+                            escape_html(&self.tooltip(event)),
+                            time,
+                            escape_html(&event.title)
+                        ));
+                    }
+                    body.push_str("</ul>");
+                }
+                body.push_str("</td>");
+            }
+            body.push_str("</tr>\n");
+        }
+        body.push_str("</table>\n");
+
+        let heading = format!("{} — {}", self.options.title, month.format("%B %Y"));
+        self.page(&heading, &self.navigation(index, false), &body)
+    }
+
+    fn agenda_page(&self, index: usize) -> String {
+        let month = self.months[index];
+        let first = month.max(self.options.start);
+        let last = month_end(month).min(self.options.end);
+
+        let mut body = String::new();
+        let mut day = first;
+        while day <= last {
+            let day_events = calendar_layout::events_on_day(self.events, day);
+            if !day_events.is_empty() {
+                body.push_str(&format!(
+                    "<section class=\"day\">\n<h2>{}</h2>\n<ul class=\"agenda\">\n",
+                    day.format("%A %-d %B %Y")
+                ));
+                for event in day_events {
+                    body.push_str(&self.agenda_item(event, day));
+                }
+                body.push_str("</ul>\n</section>\n");
+            }
+            day += Duration::days(1);
+        }
+        if body.is_empty() {
+            body.push_str("<p class=\"empty-note\">No events this month.</p>\n");
+        }
+
+        let heading = format!("{} — {} agenda", self.options.title, month.format("%B %Y"));
+        self.page(&heading, &self.navigation(index, true), &body)
+    }
+
+    fn agenda_item(&self, event: &Event, day: NaiveDate) -> String {
+        let (background, text) = self.event_colors(event);
+        let mut details = String::new();
+        if let Some(category) = event.category.as_deref().filter(|c| !c.is_empty()) {
+            details.push_str(&format!(
+                "<span class=\"category\" style=\"background:{};color:{}\">{}</span>",
+                background,
+                text,
+                escape_html(category)
+            ));
+        }
+        if self.options.include_locations {
+            if let Some(location) = event.location.as_deref().filter(|l| !l.is_empty()) {
+                details.push_str(&format!(
+                    "<div class=\"details\">📍 {}</div>",
+                    escape_html(location)
+                ));
+            }
+        }
+        if self.options.include_descriptions {
+            if let Some(description) = event.description.as_deref().filter(|d| !d.is_empty()) {
+                details.push_str(&format!(
+                    "<div class=\"details\">{}</div>",
+                    escape_html(description).replace('\n', "<br>")
+                ));
+            }
+        }
+
+        format!(
+            "<li style=\"--event:{}\"><span class=\"time\">{}</span><div><strong>{}</strong>{}</div></li>\n",
+            background,
+            self.time_span(event, day),
+            escape_html(&event.title),
+            details
+        )
+    }
+
+    /// Links to the index, the neighbouring months and the other view of
+    /// this month
+    fn navigation(&self, index: usize, agenda: bool) -> String {
+        let file = if agenda { agenda_file } else { month_file };
+        let month = self.months[index];
+        let mut links = vec!["<a href=\"index.html\">All months</a>".to_string()];
+        if let Some(previous) = index.checked_sub(1).map(|i| self.months[i]) {
+            links.push(format!(
+                "<a href=\"{}\">← {}</a>",
+                file(previous),
+                previous.format("%B %Y")
+            ));
+        }
+        if let Some(next) = self.months.get(index + 1) {
+            links.push(format!(
+                "<a href=\"{}\">{} →</a>",
+                file(*next),
+                next.format("%B %Y")
+            ));
+        }
+        if agenda {
+            links.push(format!("<a href=\"{}\">Month grid</a>", month_file(month)));
+            links.push("<span class=\"current\">Agenda</span>".to_string());
+        } else {
+            links.push("<span class=\"current\">Month grid</span>".to_string());
+            links.push(format!("<a href=\"{}\">Agenda</a>", agenda_file(month)));
+        }
+        format!("<nav>{}</nav>\n", links.join(" "))
+    }
+
+    /// The colour key for the categories that appear in the site
+    fn legend(&self) -> String {
+        let mut names: Vec<&str> = self
+            .events
+            .iter()
+            .filter_map(|event| event.category.as_deref())
+            .filter(|name| !name.is_empty())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+
+        let items: String = names
+            .iter()
+            .map(|name| {
+                let background = self
+                    .category_color(name)
+                    .unwrap_or(self.theme.event_colors.default);
+                format!(
+                    "<li class=\"category\" style=\"background:{};color:{}\">{}</li>",
+                    CalendarTheme::color_to_hex(background),
+                    CalendarTheme::color_to_hex(readable_text(background)),
+                    escape_html(name)
+                )
+            })
+            .collect();
+        if items.is_empty() {
+            String::new()
+        } else {
+            format!("<ul class=\"legend\">{}</ul>\n", items)
+        }
+    }
+
+    fn page(&self, heading: &str, navigation: &str, body: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{title}</title>\n<link rel=\"stylesheet\" href=\"style.css\">\n</head>\n<body>\n<header>\n<h1>{title}</h1>\n<p>{start} – {end}</p>\n</header>\n{navigation}<main>\n{body}</main>\n<footer>Published {published}</footer>\n</body>\n</html>\n",
+            title = escape_html(heading),
+            start = self.options.start.format("%-d %B %Y"),
+            end = self.options.end.format("%-d %B %Y"),
+            navigation = navigation,
+            body = body,
+            published = Local::now().format("%-d %B %Y %H:%M"),
+        )
+    }
+
+    /// Background and text colour of an event: its own colour, else its
+    /// category's, else the theme default
+    fn event_colors(&self, event: &Event) -> (String, String) {
+        let background = calendar_layout::event_color(event, self.categories)
+            .unwrap_or(self.theme.event_colors.default);
+        (
+            CalendarTheme::color_to_hex(background),
+            CalendarTheme::color_to_hex(readable_text(background)),
+        )
+    }
+
+    fn category_color(&self, name: &str) -> Option<Color32> {
+        self.categories
+            .iter()
+            .find(|category| category.name.eq_ignore_ascii_case(name))
+            .and_then(|category| CalendarTheme::hex_to_color(&category.color).ok())
+    }
+
+    fn format_time(&self, time: DateTime<Local>) -> String {
+        if self.options.use_24_hour {
+            time.format("%H:%M").to_string()
+        } else {
+            time.format("%-I:%M %p").to_string()
+        }
+    }
+
+    /// The part of `event` that falls on `day`, e.g. `9:00 AM – 10:00 AM`,
+    /// with `…` where it runs over from or into another day
+    fn time_span(&self, event: &Event, day: NaiveDate) -> String {
+        if event.all_day {
+            return "All day".to_string();
+        }
+        let next_day = day + Duration::days(1);
+        let from = if event.start.date_naive() == day {
+            self.format_time(event.start)
+        } else {
+            "…".to_string()
+        };
+        let until =
+            if event.end.date_naive() == day || event.end == calendar_layout::day_start(next_day) {
+                self.format_time(event.end)
+            } else {
+                "…".to_string()
+            };
+        format!("{} – {}", from, until)
+    }
+
+    fn tooltip(&self, event: &Event) -> String {
+        let mut tooltip = if event.all_day {
+            event.title.clone()
+        } else {
+            format!(
+                "{} – {} {}",
+                self.format_time(event.start),
+                self.format_time(event.end),
+                event.title
+            )
+        };
+        if self.options.include_locations {
+            if let Some(location) = event.location.as_deref().filter(|l| !l.is_empty()) {
+                tooltip.push_str(&format!(" ({})", location));
+            }
+        }
+        tooltip
+    }
+}
+
+fn month_file(month: NaiveDate) -> String {
+    format!("month-{}.html", month.format("%Y-%m"))
+}
+
+fn agenda_file(month: NaiveDate) -> String {
+    format!("agenda-{}.html", month.format("%Y-%m"))
+}
+
+fn month_end(month: NaiveDate) -> NaiveDate {
+    (month + Months::new(1)) - Duration::days(1)
+}
+
+/// Which grid columns are Saturday or Sunday
+fn weekend_columns(first_day_of_week: u8) -> [bool; 7] {
+    std::array::from_fn(|column| matches!((first_day_of_week as usize + column) % 7, 0 | 6))
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HtmlPublishOptions, HtmlPublishService, SiteFile};
+    use crate::models::category::Category;
+    use crate::models::event::Event;
+    use crate::services::database::Database;
+    use crate::services::event::EventService;
+    use crate::ui_egui::theme::CalendarTheme;
+    use chrono::{Duration, Local, NaiveDate, TimeZone};
+
+    fn options() -> HtmlPublishOptions {
+        HtmlPublishOptions {
+            title: "Team <Roster>".to_string(),
+            start: NaiveDate::from_ymd_opt(2026, 3, 10).unwrap(),
+            end: NaiveDate::from_ymd_opt(2026, 4, 5).unwrap(),
+            categories: Some(vec!["Work".to_string()]),
+            include_uncategorized: false,
+            first_day_of_week: 1,
+            use_24_hour: true,
+            ..Default::default()
+        }
+    }
+
+    fn events() -> Vec<Event> {
+        let start = Local.with_ymd_and_hms(2026, 3, 12, 9, 30, 0).unwrap();
+        let review = Event::builder()
+            .title("Review & plan")
+            .start(start)
+            .end(start + Duration::hours(1))
+            .location("Room 4")
+            .category("Work")
+            .build()
+            .unwrap();
+        let lunch = Event::builder()
+            .title("Lunch")
+            .start(start + Duration::hours(3))
+            .end(start + Duration::hours(4))
+            .build()
+            .unwrap();
+        vec![review, lunch]
+    }
+
+    fn categories() -> Vec<Category> {
+        vec![Category {
+            id: Some(1),
+            name: "Work".to_string(),
+            color: "#1E3A8A".to_string(),
+            icon: None,
+            is_system: false,
+        }]
+    }
+
+    fn site(events: &[Event]) -> Vec<SiteFile> {
+        HtmlPublishService::build_site(events, &categories(), &CalendarTheme::light(), &options())
+    }
+
+    fn file<'a>(files: &'a [SiteFile], name: &str) -> &'a str {
+        &files
+            .iter()
+            .find(|file| file.name == name)
+            .unwrap_or_else(|| panic!("{} missing", name))
+            .content
+    }
+
+    #[test]
+    fn test_site_has_a_grid_and_agenda_per_month() {
+        let files = site(&events());
+        let names: Vec<&str> = files.iter().map(|file| file.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "style.css",
+                "index.html",
+                "month-2026-03.html",
+                "agenda-2026-03.html",
+                "month-2026-04.html",
+                "agenda-2026-04.html"
+            ]
+        );
+
+        let index = file(&files, "index.html");
+        assert!(index.contains("<title>Team &lt;Roster&gt;</title>"));
+        assert!(index.contains("<a href=\"month-2026-04.html\">April 2026</a>"));
+
+        let css = file(&files, "style.css");
+        let theme = CalendarTheme::light();
+        assert!(css.contains(&format!(
+            "--weekend: {};",
+            CalendarTheme::color_to_hex(theme.weekend_background)
+        )));
+    }
+
+    #[test]
+    fn test_events_use_category_colour_and_are_escaped() {
+        let files = site(&events());
+        let month = file(&files, "month-2026-03.html");
+        assert!(month.contains(
+            "<li style=\"background:#1E3A8A;color:#FFFFFF\" title=\"09:30 – 10:30 Review &amp; plan (Room 4)\"><span class=\"time\">09:30</span> Review &amp; plan</li>"
+        ));
+        // Weeks start on Monday: March 2026 begins on a Sunday
+        assert!(month.contains("<tr><th>Mon</th>"));
+        assert!(month.contains("<td class=\"empty\"></td><td class=\"empty\"></td>"));
+
+        let agenda = file(&files, "agenda-2026-03.html");
+        assert!(agenda.contains("<h2>Thursday 12 March 2026</h2>"));
+        assert!(agenda.contains("<span class=\"time\">09:30 – 10:30</span>"));
+        assert!(file(&files, "agenda-2026-04.html").contains("No events this month."));
+    }
+
+    #[test]
+    fn test_category_filter() {
+        let options = options();
+        let published: Vec<String> = events()
+            .into_iter()
+            .filter(|event| HtmlPublishService::is_published(event, &options))
+            .map(|event| event.title)
+            .collect();
+        assert_eq!(published, ["Review & plan"]);
+
+        let everything = HtmlPublishOptions {
+            categories: None,
+            ..HtmlPublishOptions::default()
+        };
+        assert!(events()
+            .iter()
+            .all(|event| HtmlPublishService::is_published(event, &everything)));
+    }
+
+    #[test]
+    fn test_days_outside_the_range_are_dimmed_and_empty() {
+        let mut early = events().remove(0);
+        early.start -= Duration::days(5);
+        early.end -= Duration::days(5);
+        let files = site(&[early]);
+
+        let month = file(&files, "month-2026-03.html");
+        assert!(month.contains("<td class=\"outside\"><span class=\"day-number\">9</span></td>"));
+        assert!(!month.contains("Review"));
+    }
+
+    #[test]
+    fn test_publish_writes_to_a_subfolder_and_removes_stale_pages() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let event_service = EventService::new(db.connection());
+        let folder = tempfile::tempdir().unwrap();
+        let site = HtmlPublishService::site_folder(folder.path());
+        std::fs::write(folder.path().join("index.html"), "mine").unwrap();
+
+        let publish = |options: &HtmlPublishOptions| {
+            HtmlPublishService::publish(
+                &event_service,
+                &categories(),
+                &CalendarTheme::light(),
+                options,
+                folder.path(),
+            )
+            .unwrap()
+        };
+        publish(&options());
+        std::fs::write(site.join("notes.html"), "kept").unwrap();
+        publish(&HtmlPublishOptions {
+            start: NaiveDate::from_ymd_opt(2026, 4, 1).unwrap(),
+            ..options()
+        });
+
+        assert_eq!(
+            std::fs::read_to_string(folder.path().join("index.html")).unwrap(),
+            "mine"
+        );
+        assert!(site.join("index.html").exists());
+        assert!(site.join("notes.html").exists());
+        assert!(site.join("month-2026-04.html").exists());
+        assert!(!site.join("month-2026-03.html").exists());
+        assert!(!site.join("agenda-2026-03.html").exists());
+    }
+}
@@ -1,89 +1,514 @@
 use crate::models::event::Event;
 use anyhow::Result;
-use chrono::{Local, Utc};
+use chrono::{DateTime, Datelike, Local};
+use chrono_tz::Tz;
+use std::str::FromStr;
 
-use super::utils::{escape_text, format_date, format_datetime};
+use super::color::nearest_css_name;
+use super::timezone::vtimezone;
+use super::utils::{escape_text, fold_line, format_date, format_utc_datetime};
+
+/// Zone names that mean UTC, for which times are written with `Z` instead
+const UTC_ZONE_NAMES: &[&str] = &[
+    "UTC",
+    "Etc/UTC",
+    "Etc/UCT",
+    "UCT",
+    "Etc/Universal",
+    "Universal",
+    "Etc/Zulu",
+    "Zulu",
+];
 
 pub fn single(event: &Event) -> Result<String> {
-    let mut ics = calendar_header();
-    append_event(&mut ics, event);
-    ics.push_str("END:VCALENDAR\r\n");
-    Ok(ics)
+    multiple(std::slice::from_ref(event))
 }
 
 pub fn multiple(events: &[Event]) -> Result<String> {
-    let mut ics = calendar_header();
-    for event in events {
-        append_event(&mut ics, event);
+    Ok(write_calendar(events, local_timezone()))
+}
+
+/// The IANA zone this machine runs in, used as the TZID of timed events.
+/// `None` when it is UTC or cannot be determined, in which case times are
+/// written in UTC.
+fn local_timezone() -> Option<Tz> {
+    let name = iana_time_zone::get_timezone()
+        .map_err(|e| log::warn!("Could not determine the local time zone: {}", e))
+        .ok()?;
+    if UTC_ZONE_NAMES.contains(&name.as_str()) {
+        return None;
+    }
+    Tz::from_str(&name)
+        .map_err(|e| log::warn!("Unknown local time zone '{}': {}", name, e))
+        .ok()
+}
+
+/// Write `events` as a VCALENDAR, with timed events in `zone` (or UTC) and a
+/// VTIMEZONE for every zone referred to.
+fn write_calendar(events: &[Event], zone: Option<Tz>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Rust Calendar//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    let raw_components: Vec<Vec<RawItem>> = events.iter().map(raw_items).collect();
+    let mut zones: Vec<Tz> = Vec::new();
+    if let Some(zone) = zone {
+        if events.iter().any(|event| !event.all_day) {
+            zones.push(zone);
+        }
+    }
+    for tzid in raw_components.iter().flatten().flat_map(RawItem::tzids) {
+        match Tz::from_str(&tzid) {
+            Ok(tz) if !zones.contains(&tz) => zones.push(tz),
+            Ok(_) => {}
+            Err(_) => log::warn!("No time zone definition available for TZID '{}'", tzid),
+        }
+    }
+    if let Some((first_year, last_year)) = year_range(events) {
+        for tz in zones {
+            lines.extend(vtimezone(tz, first_year, last_year));
+        }
+    }
+
+    for (event, raw) in events.iter().zip(&raw_components) {
+        append_event(&mut lines, event, raw, zone);
     }
-    ics.push_str("END:VCALENDAR\r\n");
-    Ok(ics)
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect()
 }
 
-fn calendar_header() -> String {
-    let mut ics = String::new();
-    ics.push_str("BEGIN:VCALENDAR\r\n");
-    ics.push_str("VERSION:2.0\r\n");
-    ics.push_str("PRODID:-//Rust Calendar//EN\r\n");
-    ics.push_str("CALSCALE:GREGORIAN\r\n");
-    ics
+/// Years the VTIMEZONE definitions have to cover. Always at least two, so
+/// the yearly rule of a zone can be recognised.
+fn year_range(events: &[Event]) -> Option<(i32, i32)> {
+    let first_year = events.iter().map(|e| e.start.year()).min()?;
+    let last_year = events.iter().map(|e| e.end.year()).max()?;
+    Some((first_year, last_year.max(first_year + 1)))
 }
 
-fn append_event(buffer: &mut String, event: &Event) {
-    buffer.push_str("BEGIN:VEVENT\r\n");
-    buffer.push_str(&format!("UID:{}\r\n", build_uid(event)));
+fn append_event(lines: &mut Vec<String>, event: &Event, raw: &[RawItem], zone: Option<Tz>) {
+    lines.push("BEGIN:VEVENT".to_string());
 
-    let dtstamp = event.created_at.unwrap_or_else(Local::now);
-    buffer.push_str(&format!("DTSTAMP:{}\r\n", format_datetime(&dtstamp)));
+    // An imported event keeps the UID it arrived with.
+    match raw.iter().find_map(|item| item.property("UID")) {
+        Some(line) => lines.push(line.to_string()),
+        None => lines.push(format!("UID:{}", build_uid(event))),
+    }
+
+    // Without a METHOD, DTSTAMP is when the event was last revised. An
+    // imported event not edited since keeps the stamp it arrived with.
+    match (
+        event.updated_at,
+        raw.iter().find_map(|item| item.property("DTSTAMP")),
+    ) {
+        (None, Some(line)) => lines.push(line.to_string()),
+        (updated_at, _) => {
+            let dtstamp = updated_at.or(event.created_at).unwrap_or_else(Local::now);
+            lines.push(format!("DTSTAMP:{}", format_utc_datetime(&dtstamp)));
+        }
+    }
 
     if event.all_day {
-        buffer.push_str(&format!(
-            "DTSTART;VALUE=DATE:{}\r\n",
-            format_date(&event.start)
-        ));
-        buffer.push_str(&format!("DTEND;VALUE=DATE:{}\r\n", format_date(&event.end)));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", format_date(&event.start)));
+        lines.push(format!("DTEND;VALUE=DATE:{}", format_date(&event.end)));
     } else {
-        buffer.push_str(&format!("DTSTART:{}\r\n", format_datetime(&event.start)));
-        buffer.push_str(&format!("DTEND:{}\r\n", format_datetime(&event.end)));
+        lines.push(date_time_property("DTSTART", &[event.start], zone));
+        lines.push(date_time_property("DTEND", &[event.end], zone));
     }
 
-    buffer.push_str(&format!("SUMMARY:{}\r\n", escape_text(&event.title)));
+    lines.push(format!("SUMMARY:{}", escape_text(&event.title)));
 
     if let Some(desc) = &event.description {
-        buffer.push_str(&format!("DESCRIPTION:{}\r\n", escape_text(desc)));
+        lines.push(format!("DESCRIPTION:{}", escape_text(desc)));
     }
     if let Some(location) = &event.location {
-        buffer.push_str(&format!("LOCATION:{}\r\n", escape_text(location)));
+        lines.push(format!("LOCATION:{}", escape_text(location)));
     }
     if let Some(category) = &event.category {
-        buffer.push_str(&format!("CATEGORIES:{}\r\n", escape_text(category)));
+        lines.push(format!("CATEGORIES:{}", escape_text(category)));
     }
     if let Some(color) = &event.color {
-        buffer.push_str(&format!("X-APPLE-CALENDAR-COLOR:{}\r\n", color));
+        // RFC 7986 COLOR only takes CSS3 names; the exact shade goes in the
+        // property Apple Calendar reads when the name is an approximation.
+        if let Some((name, exact)) = nearest_css_name(color) {
+            lines.push(format!("COLOR:{}", name));
+            if !exact {
+                lines.push(format!("X-APPLE-CALENDAR-COLOR:{}", color));
+            }
+        }
     }
     if let Some(rrule) = &event.recurrence_rule {
-        buffer.push_str(&format!("RRULE:{}\r\n", rrule));
+        lines.push(format!("RRULE:{}", rrule));
     }
     if let Some(exceptions) = &event.recurrence_exceptions {
         if !exceptions.is_empty() {
-            let exdates: Vec<String> = exceptions.iter().map(format_datetime).collect();
-            buffer.push_str(&format!("EXDATE:{}\r\n", exdates.join(",")));
+            if event.all_day {
+                let exdates: Vec<String> = exceptions.iter().map(format_date).collect();
+                lines.push(format!("EXDATE;VALUE=DATE:{}", exdates.join(",")));
+            } else {
+                lines.push(date_time_property("EXDATE", exceptions, zone));
+            }
         }
     }
     if let Some(updated) = &event.updated_at {
-        buffer.push_str(&format!("LAST-MODIFIED:{}\r\n", format_datetime(updated)));
+        lines.push(format!("LAST-MODIFIED:{}", format_utc_datetime(updated)));
     }
     if let Some(created) = &event.created_at {
-        buffer.push_str(&format!("CREATED:{}\r\n", format_datetime(created)));
+        lines.push(format!("CREATED:{}", format_utc_datetime(created)));
     }
 
-    buffer.push_str("END:VEVENT\r\n");
+    // Properties and sub-components the model does not know, as imported
+    for item in raw {
+        match item {
+            RawItem::Property(line) => {
+                if item.property("UID").is_none() && item.property("DTSTAMP").is_none() {
+                    lines.push(line.to_string());
+                }
+            }
+            RawItem::Component(component) => {
+                if let Some(component) = checked_component(component, event) {
+                    lines.extend(component);
+                }
+            }
+        }
+    }
+
+    lines.push("END:VEVENT".to_string());
+}
+
+/// A date-time property in `zone` with TZID, or in UTC without one.
+fn date_time_property(name: &str, values: &[DateTime<Local>], zone: Option<Tz>) -> String {
+    match zone {
+        Some(tz) => {
+            let values: Vec<String> = values
+                .iter()
+                .map(|dt| dt.with_timezone(&tz).format("%Y%m%dT%H%M%S").to_string())
+                .collect();
+            format!("{};TZID={}:{}", name, tz.name(), values.join(","))
+        }
+        None => {
+            let values: Vec<String> = values.iter().map(format_utc_datetime).collect();
+            format!("{}:{}", name, values.join(","))
+        }
+    }
 }
 
+/// A raw line of the event itself, or a whole sub-component such as VALARM
+enum RawItem<'a> {
+    Property(&'a str),
+    Component(Vec<&'a str>),
+}
+
+impl<'a> RawItem<'a> {
+    /// The line, when this is the property `name` with or without parameters
+    fn property(&self, name: &str) -> Option<&'a str> {
+        match self {
+            RawItem::Property(line) if property_name(line).eq_ignore_ascii_case(name) => Some(line),
+            _ => None,
+        }
+    }
+
+    /// TZID parameter values used anywhere in the item
+    fn tzids(&self) -> Vec<String> {
+        let lines = match self {
+            RawItem::Property(line) => std::slice::from_ref(line),
+            RawItem::Component(lines) => lines.as_slice(),
+        };
+        lines
+            .iter()
+            .filter_map(|line| tzid_parameter(line))
+            .collect()
+    }
+}
+
+/// Group an event's raw lines into its own properties and sub-components.
+fn raw_items(event: &Event) -> Vec<RawItem<'_>> {
+    let mut items = Vec::new();
+    let mut component: Vec<&str> = Vec::new();
+    let mut depth = 0usize;
+    for line in event
+        .ics_raw_properties
+        .as_deref()
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| !line.is_empty())
+    {
+        if line.starts_with("BEGIN:") {
+            depth += 1;
+        }
+        if depth == 0 {
+            items.push(RawItem::Property(line));
+            continue;
+        }
+        component.push(line);
+        if line.starts_with("END:") {
+            depth -= 1;
+            if depth == 0 {
+                items.push(RawItem::Component(std::mem::take(&mut component)));
+            }
+        }
+    }
+    items
+}
+
+/// A sub-component ready to write, or `None` for an alarm that calendar
+/// apps would reject. DISPLAY alarms without text get the event title.
+fn checked_component(component: &[&str], event: &Event) -> Option<Vec<String>> {
+    let mut lines: Vec<String> = component.iter().map(|line| line.to_string()).collect();
+    if component.first().copied() != Some("BEGIN:VALARM") {
+        return Some(lines);
+    }
+
+    // Only the alarm's own properties, not those of anything nested in it
+    let own_properties: Vec<&str> = raw_items_of(&component[1..component.len() - 1]);
+    let has = |name: &str| {
+        own_properties
+            .iter()
+            .any(|line| property_name(line).eq_ignore_ascii_case(name))
+    };
+    let action = own_properties
+        .iter()
+        .find(|line| property_name(line).eq_ignore_ascii_case("ACTION"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, value)| value.trim().to_ascii_uppercase());
+
+    let Some(action) = action.filter(|_| has("TRIGGER")) else {
+        log::warn!(
+            "Dropping alarm without ACTION or TRIGGER from '{}' on export",
+            event.title
+        );
+        return None;
+    };
+    if action == "DISPLAY" && !has("DESCRIPTION") {
+        lines.insert(
+            lines.len() - 1,
+            format!("DESCRIPTION:{}", escape_text(&event.title)),
+        );
+    }
+    Some(lines)
+}
+
+/// The top-level property lines among `lines`, skipping nested components
+fn raw_items_of<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    let mut depth = 0usize;
+    let mut properties = Vec::new();
+    for line in lines {
+        if line.starts_with("BEGIN:") {
+            depth += 1;
+        } else if line.starts_with("END:") {
+            depth = depth.saturating_sub(1);
+        } else if depth == 0 {
+            properties.push(*line);
+        }
+    }
+    properties
+}
+
+/// The name of a content line, before its parameters and value.
+fn property_name(line: &str) -> &str {
+    let end = line.find([';', ':']).unwrap_or(line.len());
+    &line[..end]
+}
+
+/// The TZID parameter of a content line, without quotes
+fn tzid_parameter(line: &str) -> Option<String> {
+    let colon = line.find(':')?;
+    line[..colon].split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.eq_ignore_ascii_case("TZID")
+            .then(|| value.trim_matches('"').to_string())
+    })
+}
+
+/// The UID an event is exported with: the one it was imported with, or
+/// [`build_uid`].
+pub(super) fn event_uid(event: &Event) -> String {
+    raw_items(event)
+        .iter()
+        .find_map(|item| item.property("UID"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, uid)| uid.to_string())
+        .unwrap_or_else(|| build_uid(event))
+}
+
+/// `raw` with its SEQUENCE raised by one, or set to 1 when it has none, so
+/// calendars that already hold the event take an edited copy as newer.
+pub fn bump_sequence(raw: &str) -> String {
+    let mut depth = 0usize;
+    let mut bumped = false;
+    let mut lines: Vec<String> = raw
+        .lines()
+        .map(|line| {
+            if line.starts_with("BEGIN:") {
+                depth += 1;
+            } else if line.starts_with("END:") {
+                depth = depth.saturating_sub(1);
+            } else if depth == 0 && property_name(line).eq_ignore_ascii_case("SEQUENCE") {
+                let sequence = line
+                    .split_once(':')
+                    .and_then(|(_, value)| value.trim().parse::<i64>().ok())
+                    .unwrap_or(0);
+                bumped = true;
+                return format!("SEQUENCE:{}", sequence + 1);
+            }
+            line.to_string()
+        })
+        .collect();
+    if !bumped {
+        lines.insert(0, "SEQUENCE:1".to_string());
+    }
+    lines.join("\n")
+}
+
+/// A UID that stays the same every time the event is exported: the database
+/// id once saved, otherwise a hash of what identifies the event.
 fn build_uid(event: &Event) -> String {
     if let Some(id) = event.id {
-        format!("rust-calendar-{}", id)
-    } else {
-        format!("rust-calendar-temp-{}", Utc::now().timestamp())
+        return format!("rust-calendar-{}", id);
+    }
+
+    // FNV-1a, which unlike `DefaultHasher` is fixed across Rust releases
+    let identity = format!(
+        "{}\u{1f}{}\u{1f}{}",
+        event.title,
+        event.start.to_rfc3339(),
+        event.end.to_rfc3339()
+    );
+    let hash = identity
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("rust-calendar-temp-{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_uid, bump_sequence, write_calendar};
+    use crate::models::event::Event;
+    use chrono::{Local, TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> chrono::DateTime<Local> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0)
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    fn timed_event() -> Event {
+        let mut event = Event::builder()
+            .title("Quarterly planning, part 2")
+            .description(
+                "Agenda: budget; hiring; roadmap.\nBring the draft figures for the Sydney and Melbourne offices.",
+            )
+            .location("Level 3 – Board Room")
+            .start(utc(2026, 3, 2, 22, 30))
+            .end(utc(2026, 3, 2, 23, 30))
+            .category("Work")
+            .color("#3B82F6")
+            .recurrence_rule("FREQ=WEEKLY;COUNT=10")
+            .build()
+            .unwrap();
+        event.id = Some(42);
+        event.recurrence_exceptions = Some(vec![utc(2026, 3, 9, 22, 30)]);
+        event.created_at = Some(utc(2026, 2, 1, 0, 0));
+        event.updated_at = Some(utc(2026, 2, 20, 5, 15));
+        event
+    }
+
+    fn all_day_event() -> Event {
+        let mut event = Event::builder()
+            .title("Public holiday")
+            .start(Local.with_ymd_and_hms(2026, 4, 3, 0, 0, 0).unwrap())
+            .end(Local.with_ymd_and_hms(2026, 4, 4, 0, 0, 0).unwrap())
+            .all_day(true)
+            .color("#FF0000")
+            .build()
+            .unwrap();
+        event.id = Some(7);
+        event.created_at = Some(utc(2026, 1, 10, 9, 0));
+        event
+    }
+
+    fn imported_event() -> Event {
+        let mut event = Event::builder()
+            .title("Dentist")
+            .start(utc(2026, 5, 12, 1, 0))
+            .end(utc(2026, 5, 12, 2, 0))
+            .build()
+            .unwrap();
+        event.ics_raw_properties = Some(
+            [
+                "UID:dentist-2026@example.com",
+                "DTSTAMP:20260401T080000Z",
+                "X-CUSTOM-FLAG:yes",
+                "BEGIN:VALARM",
+                "ACTION:DISPLAY",
+                "TRIGGER:-PT30M",
+                "END:VALARM",
+                "BEGIN:VALARM",
+                "ACTION:AUDIO",
+                "END:VALARM",
+            ]
+            .join("\n"),
+        );
+        event
+    }
+
+    /// Compare with a golden file, which is stored with plain newlines
+    fn assert_golden(exported: &str, golden: &str) {
+        assert!(exported.ends_with("\r\n"));
+        assert!(!exported.replace("\r\n", "").contains('\n'));
+        pretty_assertions::assert_eq!(exported.replace("\r\n", "\n"), golden);
+    }
+
+    #[test]
+    fn test_export_in_time_zone_matches_golden_file() {
+        let exported = write_calendar(
+            &[timed_event(), all_day_event()],
+            Some(Tz::Australia__Sydney),
+        );
+        assert_golden(
+            &exported,
+            include_str!("../../../tests/fixtures/ics/export_sydney.ics"),
+        );
+    }
+
+    #[test]
+    fn test_export_in_utc_matches_golden_file() {
+        let exported = write_calendar(&[imported_event()], None);
+        assert_golden(
+            &exported,
+            include_str!("../../../tests/fixtures/ics/export_utc.ics"),
+        );
+    }
+
+    #[test]
+    fn test_build_uid_is_stable_without_id() {
+        let mut event = timed_event();
+        event.id = None;
+
+        let uid = build_uid(&event);
+        assert!(uid.starts_with("rust-calendar-temp-"));
+        assert_eq!(uid, build_uid(&event.clone()));
+
+        event.title.push('!');
+        assert_ne!(uid, build_uid(&event));
+    }
+
+    #[test]
+    fn test_bump_sequence_raises_only_the_event_sequence() {
+        let raw = "UID:a@example.com\nSEQUENCE:4\nBEGIN:VALARM\nSEQUENCE:9\nEND:VALARM";
+        assert_eq!(
+            bump_sequence(raw),
+            "UID:a@example.com\nSEQUENCE:5\nBEGIN:VALARM\nSEQUENCE:9\nEND:VALARM"
+        );
+        assert_eq!(
+            bump_sequence("UID:b@example.com"),
+            "SEQUENCE:1\nUID:b@example.com"
+        );
     }
 }
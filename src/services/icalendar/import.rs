@@ -2,7 +2,8 @@ use crate::models::event::Event;
 use anyhow::Result;
 use chrono::{Duration, Local};
 
-use super::utils::{parse_date, parse_datetime_with_tzid, unescape_text};
+use super::color::css_name_to_hex;
+use super::utils::{parse_date, parse_datetime_with_tzid, parse_duration, unescape_text};
 
 #[derive(Debug, Clone)]
 pub struct ImportedIcsEvent {
@@ -10,6 +11,10 @@ pub struct ImportedIcsEvent {
     pub uid: Option<String>,
     pub recurrence_id: Option<String>,
     pub raw_last_modified: Option<String>,
+    /// Revision counter from SEQUENCE; absent means the first revision
+    pub sequence: Option<i64>,
+    /// DURATION, used for the end when there is no DTEND
+    pub duration: Option<Duration>,
     pub has_start: bool,
     pub has_end: bool,
 }
@@ -25,17 +30,23 @@ pub fn from_str_with_metadata(ics_content: &str) -> Result<Vec<ImportedIcsEvent>
 
     let mut in_event = false;
     let mut current_event: Option<ImportedIcsEvent> = None;
+    let mut raw_lines: Vec<String> = Vec::new();
+    let mut nested_depth = 0usize;
 
     for line in lines {
         let line = line.trim();
 
         if line == "BEGIN:VEVENT" {
             in_event = true;
+            raw_lines.clear();
+            nested_depth = 0;
             current_event = Some(ImportedIcsEvent {
                 event: blank_event(),
                 uid: None,
                 recurrence_id: None,
                 raw_last_modified: None,
+                sequence: None,
+                duration: None,
                 has_start: false,
                 has_end: false,
             });
@@ -43,13 +54,28 @@ pub fn from_str_with_metadata(ics_content: &str) -> Result<Vec<ImportedIcsEvent>
             in_event = false;
             if let Some(mut event) = current_event.take() {
                 finalize_event_times(&mut event);
+                if !raw_lines.is_empty() {
+                    event.event.ics_raw_properties = Some(raw_lines.join("\n"));
+                }
                 if !event.event.title.is_empty() {
                     events.push(event);
                 }
             }
         } else if in_event {
             if let Some(event) = current_event.as_mut() {
-                parse_event_property(line, event)?;
+                // Sub-components such as VALARM are kept whole; their
+                // properties are not the event's own.
+                if line.starts_with("BEGIN:") {
+                    nested_depth += 1;
+                }
+                if nested_depth > 0 {
+                    if line.starts_with("END:") {
+                        nested_depth -= 1;
+                    }
+                    raw_lines.push(line.to_string());
+                } else if !parse_event_property(line, event)? {
+                    raw_lines.push(line.to_string());
+                }
             }
         }
     }
@@ -57,7 +83,7 @@ pub fn from_str_with_metadata(ics_content: &str) -> Result<Vec<ImportedIcsEvent>
     Ok(events)
 }
 
-fn unfold_ics_lines(ics_content: &str) -> Vec<String> {
+pub(super) fn unfold_ics_lines(ics_content: &str) -> Vec<String> {
     let mut unfolded: Vec<String> = Vec::new();
 
     for raw_line in ics_content.lines() {
@@ -66,18 +92,22 @@ fn unfold_ics_lines(ics_content: &str) -> Vec<String> {
 
         if is_single_space_continuation || is_tab_continuation {
             if let Some(last) = unfolded.last_mut() {
-                last.push_str(raw_line[1..].trim_end());
+                // A fold may fall right after a space, so only the line
+                // ending is dropped
+                last.push_str(raw_line[1..].trim_end_matches('\r'));
             }
             continue;
         }
 
-        unfolded.push(raw_line.trim_end().to_string());
+        unfolded.push(raw_line.trim_end_matches('\r').to_string());
     }
 
     unfolded
 }
 
-fn parse_event_property(line: &str, imported: &mut ImportedIcsEvent) -> Result<()> {
+/// Apply one property line to `imported`. Returns whether the value is now
+/// held by the `Event`; anything else is kept as a raw line for export.
+fn parse_event_property(line: &str, imported: &mut ImportedIcsEvent) -> Result<bool> {
     if let Some(colon_pos) = line.find(':') {
         let (key_part, value) = line.split_at(colon_pos);
         let value = &value[1..];
@@ -90,8 +120,11 @@ fn parse_event_property(line: &str, imported: &mut ImportedIcsEvent) -> Result<(
         };
 
         match key {
+            // Identity properties are read for matching but the event has no
+            // field for them, so they stay raw as well.
             "UID" => {
                 imported.uid = Some(unescape_text(value));
+                return Ok(false);
             }
             "RECURRENCE-ID" => {
                 let parsed = if key_part.contains("VALUE=DATE") {
@@ -100,6 +133,11 @@ fn parse_event_property(line: &str, imported: &mut ImportedIcsEvent) -> Result<(
                     parse_datetime_with_tzid(value, tzid)?
                 };
                 imported.recurrence_id = Some(parsed.to_rfc3339());
+                return Ok(false);
+            }
+            "SEQUENCE" => {
+                imported.sequence = value.trim().parse().ok();
+                return Ok(false);
             }
             "SUMMARY" => {
                 imported.event.title = unescape_text(value);
@@ -113,9 +151,17 @@ fn parse_event_property(line: &str, imported: &mut ImportedIcsEvent) -> Result<(
             "CATEGORIES" => {
                 imported.event.category = Some(unescape_text(value));
             }
+            // The Apple property holds the exact shade, so it wins over the
+            // CSS3 name in RFC 7986 COLOR whichever comes first.
             "X-APPLE-CALENDAR-COLOR" => {
                 imported.event.color = Some(value.to_string());
             }
+            "COLOR" => match css_name_to_hex(value) {
+                Some(hex) => {
+                    imported.event.color.get_or_insert(hex);
+                }
+                None => return Ok(false),
+            },
             "DTSTART" => {
                 if key_part.contains("VALUE=DATE") {
                     imported.event.all_day = true;
@@ -133,6 +179,9 @@ fn parse_event_property(line: &str, imported: &mut ImportedIcsEvent) -> Result<(
                 }
                 imported.has_end = true;
             }
+            "DURATION" => {
+                imported.duration = Some(parse_duration(value)?);
+            }
             "RRULE" => {
                 imported.event.recurrence_rule = Some(value.to_string());
             }
@@ -148,7 +197,11 @@ fn parse_event_property(line: &str, imported: &mut ImportedIcsEvent) -> Result<(
                         }
                     })
                     .collect();
-                imported.event.recurrence_exceptions = Some(dates?);
+                imported
+                    .event
+                    .recurrence_exceptions
+                    .get_or_insert_with(Vec::new)
+                    .extend(dates?);
             }
             "CREATED" => {
                 imported.event.created_at = Some(parse_datetime_with_tzid(value, tzid)?);
@@ -157,10 +210,36 @@ fn parse_event_property(line: &str, imported: &mut ImportedIcsEvent) -> Result<(
                 imported.raw_last_modified = Some(value.to_string());
                 imported.event.updated_at = Some(parse_datetime_with_tzid(value, tzid)?);
             }
-            _ => {}
+            _ => return Ok(false),
         }
+        return Ok(true);
     }
 
+    Ok(false)
+}
+
+/// Fill the UID, RECURRENCE-ID and SEQUENCE of `imported` from the raw
+/// lines its event already carries, for events that were not read from .ics
+/// text.
+pub(super) fn read_identity(imported: &mut ImportedIcsEvent) -> Result<()> {
+    let raw = imported
+        .event
+        .ics_raw_properties
+        .clone()
+        .unwrap_or_default();
+    let mut depth = 0usize;
+    for line in raw.lines() {
+        if line.starts_with("BEGIN:") {
+            depth += 1;
+        } else if line.starts_with("END:") {
+            depth = depth.saturating_sub(1);
+        } else if depth == 0 {
+            let name = line.split([';', ':']).next().unwrap_or_default();
+            if matches!(name, "UID" | "RECURRENCE-ID" | "SEQUENCE") {
+                parse_event_property(line, imported)?;
+            }
+        }
+    }
     Ok(())
 }
 
@@ -176,6 +255,15 @@ fn finalize_event_times(imported: &mut ImportedIcsEvent) {
     }
 
     if !imported.has_end {
+        // A duration past the end of the supported dates falls back as well
+        if let Some(end) = imported
+            .duration
+            .filter(|d| *d > Duration::zero())
+            .and_then(|d| imported.event.start.checked_add_signed(d))
+        {
+            imported.event.end = end;
+            return;
+        }
         imported.event.end = if imported.event.all_day {
             imported.event.start + Duration::days(1)
         } else {
@@ -208,6 +296,7 @@ fn blank_event() -> Event {
         recurrence_exceptions: None,
         created_at: None,
         updated_at: None,
+        ics_raw_properties: None,
     }
 }
 
@@ -225,12 +314,14 @@ DTSTART:20260227T090000
 DTEND:20260227T100000
 SUMMARY:Test Event
 LAST-MODIFIED:20260227T010203Z
+SEQUENCE:3
 END:VEVENT
 END:VCALENDAR"#;
 
         let imported = from_str_with_metadata(ics).unwrap();
         assert_eq!(imported.len(), 1);
         assert_eq!(imported[0].uid.as_deref(), Some("test-uid-123"));
+        assert_eq!(imported[0].sequence, Some(3));
         assert_eq!(
             imported[0].raw_last_modified.as_deref(),
             Some("20260227T010203Z")
@@ -344,4 +435,83 @@ END:VCALENDAR"#;
         assert_eq!(imported[0].uid.as_deref(), Some("series-123"));
         assert!(imported[0].recurrence_id.is_some());
     }
+
+    #[test]
+    fn test_import_keeps_unknown_properties_and_components_raw() {
+        let ics = r#"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:raw-uid
+DTSTART:20260227T090000
+DTEND:20260227T100000
+SUMMARY:Standup
+DESCRIPTION:Daily sync
+X-MS-OLK-BUSYSTATUS:BUSY
+ATTENDEE;CN=Sam:mailto:sam@example.com
+BEGIN:VALARM
+ACTION:DISPLAY
+DESCRIPTION:Reminder text
+TRIGGER:-PT15M
+END:VALARM
+END:VEVENT
+END:VCALENDAR"#;
+
+        let imported = from_str_with_metadata(ics).unwrap();
+        assert_eq!(imported.len(), 1);
+        let event = &imported[0].event;
+        assert_eq!(event.description.as_deref(), Some("Daily sync"));
+
+        let raw = event
+            .ics_raw_properties
+            .as_deref()
+            .expect("unknown properties should be kept");
+        let lines: Vec<&str> = raw.lines().collect();
+        assert!(lines.contains(&"UID:raw-uid"));
+        assert!(lines.contains(&"X-MS-OLK-BUSYSTATUS:BUSY"));
+        assert!(lines.contains(&"ATTENDEE;CN=Sam:mailto:sam@example.com"));
+        assert!(raw.contains(
+            "BEGIN:VALARM\nACTION:DISPLAY\nDESCRIPTION:Reminder text\nTRIGGER:-PT15M\nEND:VALARM"
+        ));
+        assert!(!lines.contains(&"SUMMARY:Standup"));
+        assert!(!lines.contains(&"DESCRIPTION:Daily sync"));
+    }
+
+    #[test]
+    fn test_import_duration_sets_end() {
+        let ics = r#"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:duration-uid
+DURATION:PT1H30M
+DTSTART:20260227T090000
+SUMMARY:Ninety minutes
+END:VEVENT
+END:VCALENDAR"#;
+
+        let imported = from_str_with_metadata(ics).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(
+            imported[0].event.end,
+            imported[0].event.start + chrono::Duration::minutes(90)
+        );
+    }
+
+    #[test]
+    fn test_import_duration_past_supported_dates_falls_back() {
+        let ics = r#"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:long-duration-uid
+DURATION:P99999999W
+DTSTART:20260227T090000
+SUMMARY:Forever
+END:VEVENT
+END:VCALENDAR"#;
+
+        let imported = from_str_with_metadata(ics).unwrap();
+        assert_eq!(
+            imported[0].event.end,
+            imported[0].event.start + chrono::Duration::hours(1)
+        );
+    }
 }
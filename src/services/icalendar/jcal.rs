@@ -0,0 +1,617 @@
+//! jCal (RFC 7265), the JSON form of iCalendar.
+//!
+//! jCal maps content lines one to one, so it is produced from the .ics
+//! writer's output and read by turning it back into .ics text for the .ics
+//! importer. Both directions therefore share time zones, alarms and
+//! preserved properties with the .ics format.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Map, Value};
+
+use super::export;
+use super::import::{self, unfold_ics_lines, ImportedIcsEvent};
+use super::utils::{escape_text, fold_line, unescape_text};
+use crate::models::event::Event;
+
+/// The jCal document for `events`, pretty-printed
+pub fn multiple(events: &[Event]) -> Result<String> {
+    let ics = export::multiple(events)?;
+    let calendar = ics_to_jcal(&ics)?;
+    serde_json::to_string_pretty(&calendar).context("Failed to serialise jCal")
+}
+
+/// Import the events of a jCal document, either one `vcalendar` or an array
+/// of them.
+pub fn from_str_with_metadata(content: &str) -> Result<Vec<ImportedIcsEvent>> {
+    let document: Value = serde_json::from_str(content).context("Failed to parse jCal JSON")?;
+    let calendars = match document.as_array() {
+        Some(items) if items.first().is_some_and(Value::is_string) => vec![&document],
+        Some(items) => items.iter().collect(),
+        None => bail!("A jCal document must be a JSON array"),
+    };
+
+    let mut events = Vec::new();
+    for calendar in calendars {
+        let mut lines = Vec::new();
+        component_to_ics(calendar, &mut lines)?;
+        let ics: String = lines.iter().map(|line| fold_line(line)).collect();
+        events.extend(import::from_str_with_metadata(&ics)?);
+    }
+    Ok(events)
+}
+
+/// The value type a property has when no VALUE parameter says otherwise.
+/// Properties not listed here are `unknown`, kept as raw text.
+fn default_type(name: &str) -> &'static str {
+    match name {
+        "DTSTART" | "DTEND" | "DUE" | "RECURRENCE-ID" | "EXDATE" | "RDATE" | "DTSTAMP"
+        | "CREATED" | "LAST-MODIFIED" | "COMPLETED" => "date-time",
+        "SEQUENCE" | "PRIORITY" | "PERCENT-COMPLETE" | "REPEAT" => "integer",
+        "DURATION" | "TRIGGER" => "duration",
+        "RRULE" | "EXRULE" => "recur",
+        "TZOFFSETFROM" | "TZOFFSETTO" => "utc-offset",
+        "URL" | "TZURL" | "ATTACH" => "uri",
+        "ORGANIZER" | "ATTENDEE" => "cal-address",
+        "GEO" => "float",
+        "SUMMARY" | "DESCRIPTION" | "LOCATION" | "COMMENT" | "CATEGORIES" | "RESOURCES" | "UID"
+        | "PRODID" | "VERSION" | "CALSCALE" | "METHOD" | "TZID" | "TZNAME" | "STATUS" | "CLASS"
+        | "TRANSP" | "ACTION" | "CONTACT" | "RELATED-TO" | "COLOR" | "NAME" => "text",
+        _ => "unknown",
+    }
+}
+
+/// Text properties whose value is a comma-separated list
+fn is_multi_valued(name: &str) -> bool {
+    matches!(name, "CATEGORIES" | "RESOURCES" | "EXDATE" | "RDATE")
+}
+
+/// Recurrence rule parts whose values are integers
+const RECUR_INTEGER_PARTS: &[&str] = &[
+    "count",
+    "interval",
+    "bysecond",
+    "byminute",
+    "byhour",
+    "bymonthday",
+    "byyearday",
+    "byweekno",
+    "bymonth",
+    "bysetpos",
+];
+
+// ---------- .ics to jCal ----------
+
+/// A component being built: name, properties and sub-components
+struct JcalComponent {
+    name: String,
+    properties: Vec<Value>,
+    components: Vec<Value>,
+}
+
+impl JcalComponent {
+    fn into_value(self) -> Value {
+        json!([self.name, self.properties, self.components])
+    }
+}
+
+fn ics_to_jcal(ics: &str) -> Result<Value> {
+    let mut stack: Vec<JcalComponent> = Vec::new();
+    let mut calendar = None;
+
+    for line in unfold_ics_lines(ics) {
+        if line.is_empty() {
+            continue;
+        }
+        let (name, params, value) = split_content_line(&line)
+            .with_context(|| format!("Malformed iCalendar line: {}", line))?;
+        match name.as_str() {
+            "BEGIN" => stack.push(JcalComponent {
+                name: value.to_lowercase(),
+                properties: Vec::new(),
+                components: Vec::new(),
+            }),
+            "END" => {
+                let component = stack.pop().context("END without BEGIN")?.into_value();
+                match stack.last_mut() {
+                    Some(parent) => parent.components.push(component),
+                    None => calendar = Some(component),
+                }
+            }
+            _ => {
+                let property = property_to_jcal(&name, params, &value);
+                stack
+                    .last_mut()
+                    .context("Property outside a component")?
+                    .properties
+                    .push(property);
+            }
+        }
+    }
+
+    calendar.context("No VCALENDAR in the iCalendar output")
+}
+
+/// A parameter name with its values
+type Parameter = (String, Vec<String>);
+
+/// Split a content line into its upper-case name, parameters and raw value.
+/// Parameter values lose their quotes and are split on unquoted commas.
+fn split_content_line(line: &str) -> Option<(String, Vec<Parameter>, String)> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(index, c)| {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        (c == ':' && !quoted).then_some(index)
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+
+    let mut parts = split_unquoted(head, ';').into_iter();
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|param| {
+            let (key, values) = param.split_once('=')?;
+            let values = split_unquoted(values, ',')
+                .into_iter()
+                .map(|v| v.trim_matches('"').to_string())
+                .collect();
+            Some((key.trim().to_ascii_uppercase(), values))
+        })
+        .collect();
+    Some((name, params, value.to_string()))
+}
+
+fn split_unquoted(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+    for (index, c) in text.char_indices() {
+        if c == '"' {
+            quoted = !quoted;
+        } else if c == separator && !quoted {
+            parts.push(&text[start..index]);
+            start = index + c.len_utf8();
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Split a text value on commas that are not escaped
+fn split_text_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push(c);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            ',' => items.push(unescape_text(&std::mem::take(&mut current))),
+            _ => current.push(c),
+        }
+    }
+    items.push(unescape_text(&current));
+    items
+}
+
+fn property_to_jcal(name: &str, params: Vec<Parameter>, value: &str) -> Value {
+    let mut value_type = default_type(name).to_string();
+    let mut jcal_params = Map::new();
+    for (key, mut values) in params {
+        if key == "VALUE" {
+            if let Some(explicit) = values.first() {
+                value_type = explicit.to_lowercase();
+            }
+            continue;
+        }
+        let param_value = if values.len() == 1 {
+            Value::String(values.remove(0))
+        } else {
+            json!(values)
+        };
+        jcal_params.insert(key.to_lowercase(), param_value);
+    }
+
+    let raw_values: Vec<String> = if value_type == "text" && is_multi_valued(name) {
+        split_text_list(value)
+    } else if is_multi_valued(name) {
+        value.split(',').map(str::to_string).collect()
+    } else if value_type == "text" {
+        vec![unescape_text(value)]
+    } else {
+        vec![value.to_string()]
+    };
+
+    let mut property = vec![
+        Value::String(name.to_lowercase()),
+        Value::Object(jcal_params),
+        Value::String(value_type.clone()),
+    ];
+    for raw in raw_values {
+        property.push(value_to_jcal(name, &value_type, &raw));
+    }
+    Value::Array(property)
+}
+
+fn value_to_jcal(name: &str, value_type: &str, raw: &str) -> Value {
+    match value_type {
+        "date" => Value::String(jcal_date(raw)),
+        "date-time" => Value::String(jcal_date_time(raw)),
+        "integer" => raw
+            .trim()
+            .parse::<i64>()
+            .map(Value::from)
+            .unwrap_or_else(|_| Value::String(raw.to_string())),
+        "float" if name == "GEO" => {
+            let parts: Vec<Value> = raw
+                .split(';')
+                .map(|part| {
+                    part.trim()
+                        .parse::<f64>()
+                        .map(Value::from)
+                        .unwrap_or(Value::Null)
+                })
+                .collect();
+            Value::Array(parts)
+        }
+        "float" => raw
+            .trim()
+            .parse::<f64>()
+            .map(Value::from)
+            .unwrap_or_else(|_| Value::String(raw.to_string())),
+        "boolean" => Value::Bool(raw.trim().eq_ignore_ascii_case("TRUE")),
+        "utc-offset" => Value::String(jcal_utc_offset(raw)),
+        "recur" => recur_to_jcal(raw),
+        _ => Value::String(raw.to_string()),
+    }
+}
+
+/// `20260303` → `2026-03-03`
+fn jcal_date(raw: &str) -> String {
+    if raw.len() == 8 && raw.bytes().all(|b| b.is_ascii_digit()) {
+        format!("{}-{}-{}", &raw[..4], &raw[4..6], &raw[6..8])
+    } else {
+        raw.to_string()
+    }
+}
+
+/// `20260303T093000Z` → `2026-03-03T09:30:00Z`; a date alone is a date
+fn jcal_date_time(raw: &str) -> String {
+    let Some((date, time)) = raw.split_once('T') else {
+        return jcal_date(raw);
+    };
+    let (time, utc) = match time.strip_suffix('Z') {
+        Some(time) => (time, "Z"),
+        None => (time, ""),
+    };
+    if time.len() != 6 {
+        return raw.to_string();
+    }
+    format!(
+        "{}T{}:{}:{}{}",
+        jcal_date(date),
+        &time[..2],
+        &time[2..4],
+        &time[4..6],
+        utc
+    )
+}
+
+/// `+1100` → `+11:00`
+fn jcal_utc_offset(raw: &str) -> String {
+    let raw = raw.trim();
+    if raw.len() < 5 {
+        return raw.to_string();
+    }
+    let mut offset = format!("{}:{}", &raw[..3], &raw[3..5]);
+    if raw.len() >= 7 {
+        offset.push(':');
+        offset.push_str(&raw[5..7]);
+    }
+    offset
+}
+
+fn recur_to_jcal(raw: &str) -> Value {
+    let mut recur = Map::new();
+    for part in raw.split(';').filter(|part| !part.is_empty()) {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let values: Vec<Value> = value
+            .split(',')
+            .map(|item| {
+                if key == "until" {
+                    Value::String(jcal_date_time(item))
+                } else if RECUR_INTEGER_PARTS.contains(&key.as_str()) {
+                    item.parse::<i64>()
+                        .map(Value::from)
+                        .unwrap_or_else(|_| Value::String(item.to_string()))
+                } else {
+                    Value::String(item.to_string())
+                }
+            })
+            .collect();
+        let value = if values.len() == 1 {
+            values.into_iter().next().unwrap_or(Value::Null)
+        } else {
+            Value::Array(values)
+        };
+        recur.insert(key, value);
+    }
+    Value::Object(recur)
+}
+
+// ---------- jCal to .ics ----------
+
+fn component_to_ics(component: &Value, lines: &mut Vec<String>) -> Result<()> {
+    let parts = component
+        .as_array()
+        .filter(|parts| parts.len() == 3)
+        .context("A jCal component must be [name, properties, components]")?;
+    let name = parts[0]
+        .as_str()
+        .context("A jCal component name must be a string")?
+        .to_ascii_uppercase();
+
+    lines.push(format!("BEGIN:{}", name));
+    for property in parts[1]
+        .as_array()
+        .context("jCal properties must be an array")?
+    {
+        lines.push(property_to_ics(property)?);
+    }
+    for child in parts[2]
+        .as_array()
+        .context("jCal components must be an array")?
+    {
+        component_to_ics(child, lines)?;
+    }
+    lines.push(format!("END:{}", name));
+    Ok(())
+}
+
+fn property_to_ics(property: &Value) -> Result<String> {
+    let parts = property
+        .as_array()
+        .filter(|parts| parts.len() >= 4)
+        .context("A jCal property must be [name, parameters, type, value...]")?;
+    let name = parts[0]
+        .as_str()
+        .context("A jCal property name must be a string")?
+        .to_ascii_uppercase();
+    let value_type = parts[2]
+        .as_str()
+        .context("A jCal value type must be a string")?
+        .to_lowercase();
+
+    let mut line = name.clone();
+    if let Some(params) = parts[1].as_object() {
+        for (key, value) in params {
+            let values: Vec<String> = match value {
+                Value::Array(items) => items.iter().map(param_text).collect(),
+                other => vec![param_text(other)],
+            };
+            line.push_str(&format!(
+                ";{}={}",
+                key.to_ascii_uppercase(),
+                values.join(",")
+            ));
+        }
+    }
+    if value_type != default_type(&name) && value_type != "unknown" {
+        line.push_str(&format!(";VALUE={}", value_type.to_ascii_uppercase()));
+    }
+
+    let values: Vec<String> = parts[3..]
+        .iter()
+        .map(|value| value_to_ics(&name, &value_type, value))
+        .collect();
+    line.push(':');
+    line.push_str(&values.join(","));
+    Ok(line)
+}
+
+/// A parameter value, quoted when it contains a separator
+fn param_text(value: &Value) -> String {
+    let text = match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    if text.contains([':', ';', ',']) {
+        format!("\"{}\"", text.replace('"', "'"))
+    } else {
+        text
+    }
+}
+
+fn value_to_ics(name: &str, value_type: &str, value: &Value) -> String {
+    match (value_type, value) {
+        ("text", Value::String(text)) => escape_text(text),
+        ("date" | "date-time", Value::String(text)) => text.replace([':', '-'], ""),
+        ("utc-offset", Value::String(text)) => text.replace(':', ""),
+        ("float", Value::Array(items)) if name == "GEO" => items
+            .iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>()
+            .join(";"),
+        ("recur", Value::Object(recur)) => recur_to_ics(recur),
+        ("boolean", Value::Bool(flag)) => if *flag { "TRUE" } else { "FALSE" }.to_string(),
+        (_, Value::String(text)) => text.clone(),
+        (_, other) => other.to_string(),
+    }
+}
+
+fn recur_to_ics(recur: &Map<String, Value>) -> String {
+    // FREQ first, as some readers expect
+    let mut keys: Vec<&String> = recur.keys().collect();
+    keys.sort_by_key(|key| *key != "freq");
+    keys.iter()
+        .map(|key| {
+            let values: Vec<String> = match &recur[key.as_str()] {
+                Value::Array(items) => items.iter().map(|item| recur_value(key, item)).collect(),
+                other => vec![recur_value(key, other)],
+            };
+            format!("{}={}", key.to_ascii_uppercase(), values.join(","))
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn recur_value(key: &str, value: &Value) -> String {
+    match value {
+        Value::String(text) if key == "until" => text.replace([':', '-'], ""),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_str_with_metadata, ics_to_jcal, multiple};
+    use crate::models::event::Event;
+    use chrono::{Duration, Local, TimeZone};
+    use serde_json::json;
+
+    fn recurring_event() -> Event {
+        let start = Local.with_ymd_and_hms(2026, 3, 2, 9, 30, 0).unwrap();
+        let mut event = Event::builder()
+            .title("Stand-up; daily, short")
+            .description("Line one\nLine two")
+            .start(start)
+            .end(start + Duration::minutes(15))
+            .category("Work")
+            .recurrence_rule("FREQ=WEEKLY;BYDAY=MO,WE,FR;UNTIL=20260601T000000Z")
+            .build()
+            .unwrap();
+        event.recurrence_exceptions = Some(vec![start + Duration::days(2)]);
+        event.ics_raw_properties = Some(
+            [
+                "UID:standup@example.com",
+                "X-TEAM;X-ROLE=lead:Platform",
+                "BEGIN:VALARM",
+                "ACTION:DISPLAY",
+                "DESCRIPTION:Soon",
+                "TRIGGER:-PT5M",
+                "END:VALARM",
+            ]
+            .join("\n"),
+        );
+        event
+    }
+
+    #[test]
+    fn test_values_follow_rfc_7265() {
+        let ics = [
+            "BEGIN:VCALENDAR",
+            "BEGIN:VEVENT",
+            "DTSTART;TZID=Australia/Sydney:20260302T093000",
+            "DTEND;VALUE=DATE:20260303",
+            "EXDATE:20260304T093000Z,20260306T093000Z",
+            "RRULE:FREQ=WEEKLY;BYDAY=MO,WE;COUNT=4",
+            "CATEGORIES:Work,Home\\, garden",
+            "SEQUENCE:2",
+            "GEO:-33.8;151.2",
+            "X-THING:a\\,b",
+            "END:VEVENT",
+            "BEGIN:VTIMEZONE",
+            "BEGIN:STANDARD",
+            "TZOFFSETFROM:+1100",
+            "END:STANDARD",
+            "END:VTIMEZONE",
+            "END:VCALENDAR",
+        ]
+        .join("\r\n");
+        let jcal = ics_to_jcal(&ics).unwrap();
+        let event = &jcal[2][0][1];
+
+        assert_eq!(jcal[0], "vcalendar");
+        assert_eq!(
+            event[0],
+            json!(["dtstart", {"tzid": "Australia/Sydney"}, "date-time", "2026-03-02T09:30:00"])
+        );
+        assert_eq!(event[1], json!(["dtend", {}, "date", "2026-03-03"]));
+        assert_eq!(
+            event[2],
+            json!([
+                "exdate",
+                {},
+                "date-time",
+                "2026-03-04T09:30:00Z",
+                "2026-03-06T09:30:00Z"
+            ])
+        );
+        assert_eq!(
+            event[3],
+            json!(["rrule", {}, "recur", {"freq": "WEEKLY", "byday": ["MO", "WE"], "count": 4}])
+        );
+        assert_eq!(
+            event[4],
+            json!(["categories", {}, "text", "Work", "Home, garden"])
+        );
+        assert_eq!(event[5], json!(["sequence", {}, "integer", 2]));
+        assert_eq!(event[6], json!(["geo", {}, "float", [-33.8, 151.2]]));
+        assert_eq!(event[7], json!(["x-thing", {}, "unknown", "a\\,b"]));
+        assert_eq!(
+            jcal[2][1][2][0][1][0],
+            json!(["tzoffsetfrom", {}, "utc-offset", "+11:00"])
+        );
+    }
+
+    #[test]
+    fn test_round_trip_through_jcal() {
+        let original = recurring_event();
+        let jcal = multiple(std::slice::from_ref(&original)).unwrap();
+        let imported = from_str_with_metadata(&jcal).unwrap();
+
+        assert_eq!(imported.len(), 1);
+        let event = &imported[0].event;
+        assert_eq!(imported[0].uid.as_deref(), Some("standup@example.com"));
+        assert_eq!(event.title, original.title);
+        assert_eq!(event.description, original.description);
+        assert_eq!(event.start, original.start);
+        assert_eq!(event.end, original.end);
+        assert_eq!(event.recurrence_rule, original.recurrence_rule);
+        assert_eq!(event.recurrence_exceptions, original.recurrence_exceptions);
+        let raw = event.ics_raw_properties.as_deref().unwrap();
+        assert!(raw.contains("X-TEAM;X-ROLE=lead:Platform"), "{}", raw);
+        assert!(raw.contains("TRIGGER:-PT5M"), "{}", raw);
+    }
+
+    #[test]
+    fn test_import_accepts_an_array_of_calendars() {
+        let calendar = json!([
+            "vcalendar",
+            [["version", {}, "text", "2.0"]],
+            [[
+                "vevent",
+                [
+                    ["uid", {}, "text", "a-1"],
+                    ["summary", {}, "text", "Holiday"],
+                    ["dtstart", {}, "date", "2026-12-24"],
+                    ["dtend", {}, "date", "2026-12-27"]
+                ],
+                []
+            ]]
+        ]);
+        let content = json!([calendar.clone(), calendar]).to_string();
+        let imported = from_str_with_metadata(&content).unwrap();
+
+        assert_eq!(imported.len(), 2);
+        assert!(imported[0].event.all_day);
+        assert_eq!(
+            imported[0].event.end - imported[0].event.start,
+            Duration::days(3)
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_jcal() {
+        assert!(from_str_with_metadata("{\"events\": []}").is_err());
+        assert!(from_str_with_metadata("[\"vcalendar\", []]").is_err());
+    }
+}
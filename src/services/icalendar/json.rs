@@ -0,0 +1,339 @@
+//! A plain JSON format for events, simpler to produce and read than jCal.
+//!
+//! The layout is described by `schemas/rust-calendar-events-v1.schema.json`:
+//! an envelope with a `schema_version` and an `events` array. Each event
+//! carries every field of the model, with recurrence as an RRULE string and
+//! exceptions as a list. Timed values are RFC 3339 with their UTC offset;
+//! all-day values are plain `YYYY-MM-DD` dates (the end exclusive, as in
+//! .ics) so they land on the same days in any time zone. Properties the model
+//! has no field for travel as .ics content lines in `ics_properties`.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+use super::export::event_uid;
+use super::import::{read_identity, ImportedIcsEvent};
+use crate::models::event::Event;
+
+/// Schema version for forward-compatibility checks.
+const EVENTS_SCHEMA_VERSION: u32 = 1;
+
+/// The whole file: a version and the events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventsJsonExport {
+    /// Schema version — importers should reject files with a higher version.
+    pub schema_version: u32,
+    pub events: Vec<JsonEvent>,
+}
+
+/// One event as written to JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonEvent {
+    /// Stable identity, used to update the same event on a later import
+    pub uid: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// RFC 3339 date-time, or `YYYY-MM-DD` for all-day events
+    pub start: String,
+    /// RFC 3339 date-time, or the exclusive end date for all-day events
+    pub end: String,
+    #[serde(default)]
+    pub all_day: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// `#RRGGBB`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// RFC 5545 RRULE value, e.g. `FREQ=WEEKLY;BYDAY=MO,WE`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence_rule: Option<String>,
+    /// Skipped occurrences, in the same form as `start`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recurrence_exceptions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Local>>,
+    /// Other .ics content lines (alarms, attendees, ...), unfolded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ics_properties: Vec<String>,
+}
+
+/// The JSON document for `events`, pretty-printed
+pub fn multiple(events: &[Event]) -> Result<String> {
+    let export = EventsJsonExport {
+        schema_version: EVENTS_SCHEMA_VERSION,
+        events: events.iter().map(JsonEvent::from_event).collect(),
+    };
+    serde_json::to_string_pretty(&export).context("Failed to serialise events JSON")
+}
+
+/// Import the events of a JSON document written by [`multiple`]
+pub fn from_str_with_metadata(content: &str) -> Result<Vec<ImportedIcsEvent>> {
+    let export: EventsJsonExport =
+        serde_json::from_str(content).context("Failed to parse events JSON")?;
+
+    if export.schema_version > EVENTS_SCHEMA_VERSION {
+        bail!(
+            "Events file uses schema version {} but this app only supports up to version {}",
+            export.schema_version,
+            EVENTS_SCHEMA_VERSION,
+        );
+    }
+
+    export
+        .events
+        .into_iter()
+        .enumerate()
+        .map(|(index, event)| {
+            let title = event.title.clone();
+            event
+                .into_imported()
+                .with_context(|| format!("Event {} ('{}')", index + 1, title))
+        })
+        .collect()
+}
+
+impl JsonEvent {
+    pub fn from_event(event: &Event) -> Self {
+        let moment = |value: &DateTime<Local>| {
+            if event.all_day {
+                value.format("%Y-%m-%d").to_string()
+            } else {
+                value.to_rfc3339()
+            }
+        };
+        let ics_properties = event
+            .ics_raw_properties
+            .as_deref()
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| !line.is_empty() && !is_uid_line(line))
+            .map(str::to_string)
+            .collect();
+
+        Self {
+            uid: event_uid(event),
+            title: event.title.clone(),
+            description: event.description.clone(),
+            location: event.location.clone(),
+            start: moment(&event.start),
+            end: moment(&event.end),
+            all_day: event.all_day,
+            category: event.category.clone(),
+            color: event.color.clone(),
+            recurrence_rule: event.recurrence_rule.clone(),
+            recurrence_exceptions: event
+                .recurrence_exceptions
+                .iter()
+                .flatten()
+                .map(moment)
+                .collect(),
+            created_at: event.created_at,
+            updated_at: event.updated_at,
+            ics_properties,
+        }
+    }
+
+    fn into_imported(self) -> Result<ImportedIcsEvent> {
+        if self.title.trim().is_empty() {
+            bail!("Event title cannot be empty");
+        }
+        let all_day = self.all_day;
+        let moment = |value: &str| parse_moment(value, all_day);
+
+        let start = moment(&self.start)?;
+        let end = moment(&self.end)?;
+        if end <= start {
+            bail!("Event end must be after its start");
+        }
+        let exceptions = self
+            .recurrence_exceptions
+            .iter()
+            .map(|value| moment(value))
+            .collect::<Result<Vec<_>>>()?;
+
+        let raw = std::iter::once(format!("UID:{}", self.uid))
+            .chain(
+                self.ics_properties
+                    .into_iter()
+                    .filter(|line| !is_uid_line(line)),
+            )
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let event = Event {
+            id: None,
+            title: self.title,
+            description: self.description,
+            location: self.location,
+            start,
+            end,
+            all_day,
+            category: self.category,
+            color: self.color,
+            recurrence_rule: self.recurrence_rule,
+            recurrence_exceptions: (!exceptions.is_empty()).then_some(exceptions),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            ics_raw_properties: Some(raw),
+        };
+        let mut imported = ImportedIcsEvent {
+            raw_last_modified: event.updated_at.map(|updated| updated.to_rfc3339()),
+            event,
+            uid: None,
+            recurrence_id: None,
+            sequence: None,
+            duration: None,
+            has_start: true,
+            has_end: true,
+        };
+        read_identity(&mut imported)?;
+        Ok(imported)
+    }
+}
+
+fn is_uid_line(line: &str) -> bool {
+    line.strip_prefix("UID")
+        .is_some_and(|rest| rest.starts_with([':', ';']))
+}
+
+/// An RFC 3339 date-time, or for all-day events a date at local midnight
+fn parse_moment(value: &str, all_day: bool) -> Result<DateTime<Local>> {
+    if all_day {
+        // Accept a full date-time too, keeping only its date
+        let date = value.get(..10).unwrap_or(value);
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .with_context(|| format!("Invalid date: {}", value))?;
+        return date
+            .and_time(NaiveTime::MIN)
+            .and_local_timezone(Local)
+            .earliest()
+            .with_context(|| format!("{} has no midnight in the local time zone", date));
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|parsed| parsed.with_timezone(&Local))
+        .with_context(|| format!("Invalid RFC 3339 date-time: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_str_with_metadata, multiple, EVENTS_SCHEMA_VERSION};
+    use crate::models::event::Event;
+    use chrono::{Duration, Local, TimeZone};
+
+    fn recurring_event() -> Event {
+        let start = Local.with_ymd_and_hms(2026, 3, 2, 9, 30, 0).unwrap();
+        let mut event = Event::builder()
+            .title("Stand-up")
+            .description("Daily sync\nBring blockers")
+            .location("Room 4")
+            .start(start)
+            .end(start + Duration::minutes(15))
+            .category("Work")
+            .color("#3366CC")
+            .recurrence_rule("FREQ=WEEKLY;BYDAY=MO,WE,FR;COUNT=12")
+            .build()
+            .unwrap();
+        event.recurrence_exceptions = Some(vec![start + Duration::days(2)]);
+        event.created_at = Some(Local.with_ymd_and_hms(2026, 2, 20, 8, 0, 0).unwrap());
+        event.updated_at = Some(Local.with_ymd_and_hms(2026, 2, 21, 8, 0, 0).unwrap());
+        event.ics_raw_properties = Some(
+            [
+                "UID:standup@example.com",
+                "SEQUENCE:3",
+                "BEGIN:VALARM",
+                "ACTION:DISPLAY",
+                "TRIGGER:-PT5M",
+                "END:VALARM",
+            ]
+            .join("\n"),
+        );
+        event
+    }
+
+    fn all_day_event() -> Event {
+        Event::builder()
+            .title("Conference")
+            .start(Local.with_ymd_and_hms(2026, 5, 11, 0, 0, 0).unwrap())
+            .end(Local.with_ymd_and_hms(2026, 5, 14, 0, 0, 0).unwrap())
+            .all_day(true)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_keeps_every_field() {
+        let original = vec![recurring_event(), all_day_event()];
+        let json = multiple(&original).unwrap();
+        let imported = from_str_with_metadata(&json).unwrap();
+
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported[0].uid.as_deref(), Some("standup@example.com"));
+        assert_eq!(imported[0].sequence, Some(3));
+        assert_eq!(imported[0].event, original[0]);
+
+        let conference = &imported[1].event;
+        assert!(imported[1]
+            .uid
+            .as_deref()
+            .unwrap()
+            .starts_with("rust-calendar-temp-"));
+        assert_eq!(conference.start, original[1].start);
+        assert_eq!(conference.end, original[1].end);
+        assert!(conference.all_day);
+    }
+
+    #[test]
+    fn test_all_day_values_are_dates() {
+        let json = multiple(&[all_day_event()]).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let event = &value["events"][0];
+
+        assert_eq!(value["schema_version"], EVENTS_SCHEMA_VERSION);
+        assert_eq!(event["start"], "2026-05-11");
+        assert_eq!(event["end"], "2026-05-14");
+        assert!(event.get("recurrence_exceptions").is_none());
+    }
+
+    #[test]
+    fn test_hand_written_file_imports() {
+        let json = r#"{
+            "schema_version": 1,
+            "events": [{
+                "uid": "gym-1",
+                "title": "Gym",
+                "start": "2026-06-01T18:00:00+00:00",
+                "end": "2026-06-01T19:00:00+00:00",
+                "recurrence_rule": "FREQ=DAILY;COUNT=5",
+                "recurrence_exceptions": ["2026-06-03T18:00:00+00:00"]
+            }]
+        }"#;
+        let imported = from_str_with_metadata(json).unwrap();
+        let event = &imported[0].event;
+
+        assert_eq!(imported[0].uid.as_deref(), Some("gym-1"));
+        assert_eq!(event.end - event.start, Duration::hours(1));
+        assert_eq!(
+            event.recurrence_exceptions.as_ref().unwrap()[0] - event.start,
+            Duration::days(2)
+        );
+    }
+
+    #[test]
+    fn test_newer_schema_and_bad_events_are_rejected() {
+        let newer = r#"{"schema_version": 2, "events": []}"#;
+        assert!(from_str_with_metadata(newer).is_err());
+
+        let backwards = r#"{"schema_version": 1, "events": [{
+            "uid": "x", "title": "Backwards",
+            "start": "2026-06-01T18:00:00Z", "end": "2026-06-01T17:00:00Z"
+        }]}"#;
+        let error = from_str_with_metadata(backwards).unwrap_err();
+        assert!(format!("{:#}", error).contains("Backwards"));
+    }
+}
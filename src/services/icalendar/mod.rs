@@ -1,11 +1,16 @@
 #![allow(dead_code)]
 
-//! RFC 5545 (.ics) import/export service.
+//! RFC 5545 (.ics) import/export service, with jCal and plain JSON
+//! variants of the same data.
 
+mod color;
 pub mod export;
 pub mod import;
+pub mod jcal;
+pub mod json;
 mod service;
+mod timezone;
 mod utils;
 
 #[allow(unused_imports)]
-pub use service::ICalendarService;
+pub use service::{EventFileFormat, ICalendarService};
@@ -1,9 +1,60 @@
-use super::{export, import};
+use super::import::ImportedIcsEvent;
+use super::{export, import, jcal, json};
 use crate::models::event::Event;
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
 
+/// The text formats events are exported to and imported from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EventFileFormat {
+    /// iCalendar (RFC 5545)
+    #[default]
+    Ics,
+    /// jCal (RFC 7265), iCalendar as JSON
+    Jcal,
+    /// The app's own JSON schema (`schemas/rust-calendar-events-v1.schema.json`)
+    Json,
+}
+
+impl EventFileFormat {
+    /// File extension, without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            EventFileFormat::Ics => "ics",
+            EventFileFormat::Jcal => "jcal",
+            EventFileFormat::Json => "json",
+        }
+    }
+
+    /// Name shown in file dialog filters
+    pub fn filter_name(self) -> &'static str {
+        match self {
+            EventFileFormat::Ics => "iCalendar",
+            EventFileFormat::Jcal => "jCal",
+            EventFileFormat::Json => "JSON",
+        }
+    }
+
+    /// Recognise a format from file content: jCal is a JSON array, the JSON
+    /// schema an object, and .ics text has a VCALENDAR or VEVENT.
+    pub fn detect(content: &str) -> Option<Self> {
+        match content
+            .trim_start_matches('\u{feff}')
+            .trim_start()
+            .chars()
+            .next()
+        {
+            Some('[') => Some(EventFileFormat::Jcal),
+            Some('{') => Some(EventFileFormat::Json),
+            _ if content.contains("BEGIN:VCALENDAR") || content.contains("BEGIN:VEVENT") => {
+                Some(EventFileFormat::Ics)
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Service for importing and exporting iCalendar (.ics) files
 #[derive(Default)]
 pub struct ICalendarService;
@@ -29,6 +80,27 @@ impl ICalendarService {
         import::from_str(ics_content)
     }
 
+    /// Export multiple events as text in `format`
+    pub fn export_events_as(&self, events: &[Event], format: EventFileFormat) -> Result<String> {
+        match format {
+            EventFileFormat::Ics => export::multiple(events),
+            EventFileFormat::Jcal => jcal::multiple(events),
+            EventFileFormat::Json => json::multiple(events),
+        }
+    }
+
+    /// Import events with their identity from text in any supported format,
+    /// or `None` when the text is in none of them
+    pub fn import_any_format(&self, content: &str) -> Option<Result<Vec<ImportedIcsEvent>>> {
+        let content = content.trim_start_matches('\u{feff}');
+        let imported = match EventFileFormat::detect(content)? {
+            EventFileFormat::Ics => import::from_str_with_metadata(content),
+            EventFileFormat::Jcal => jcal::from_str_with_metadata(content),
+            EventFileFormat::Json => json::from_str_with_metadata(content),
+        };
+        Some(imported)
+    }
+
     /// Import events from a .ics file on disk
     pub fn import_from_file(&self, path: &Path) -> Result<Vec<Event>> {
         let content =
@@ -45,15 +117,29 @@ impl ICalendarService {
 
     /// Export multiple events to a .ics file on disk
     pub fn export_events_to_file(&self, events: &[Event], path: &Path) -> Result<()> {
-        let content = self.export_events(events)?;
-        fs::write(path, content).context(format!("Failed to write .ics file: {:?}", path))?;
+        self.export_events_to_file_as(events, EventFileFormat::Ics, path)
+    }
+
+    /// Export multiple events to a file on disk in `format`
+    pub fn export_events_to_file_as(
+        &self,
+        events: &[Event],
+        format: EventFileFormat,
+        path: &Path,
+    ) -> Result<()> {
+        let content = self.export_events_as(events, format)?;
+        fs::write(path, content).context(format!(
+            "Failed to write .{} file: {:?}",
+            format.extension(),
+            path
+        ))?;
         Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ICalendarService;
+    use super::{EventFileFormat, ICalendarService};
     use crate::models::event::Event;
     use chrono::{Duration, Local, TimeZone};
 
@@ -253,6 +339,70 @@ END:VCALENDAR"#;
         assert_eq!(imported_event.category, original.category);
     }
 
+    #[test]
+    fn test_round_trip_keeps_unknown_properties() {
+        let service = ICalendarService::new();
+        let ics = r#"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:keep-me@example.com
+DTSTAMP:20260101T120000Z
+DTSTART:20260227T090000
+DTEND:20260227T100000
+SUMMARY:Planning
+X-CUSTOM-FLAG;X-PARAM=1:yes
+BEGIN:VALARM
+UID:alarm-uid
+ACTION:DISPLAY
+TRIGGER:-PT10M
+END:VALARM
+END:VEVENT
+END:VCALENDAR"#;
+
+        let imported = service.import_events(ics).unwrap();
+        let exported = service.export_events(&imported).unwrap();
+
+        assert_eq!(
+            exported.matches("\r\nUID:keep-me@example.com\r\n").count(),
+            1
+        );
+        assert!(exported.contains("DTSTAMP:20260101T120000Z\r\n"));
+        assert!(exported.contains("X-CUSTOM-FLAG;X-PARAM=1:yes\r\n"));
+        // A DISPLAY alarm needs a DESCRIPTION, so the title is filled in
+        assert!(exported.contains(
+            "BEGIN:VALARM\r\nUID:alarm-uid\r\nACTION:DISPLAY\r\nTRIGGER:-PT10M\r\nDESCRIPTION:Planning\r\nEND:VALARM\r\n"
+        ));
+        assert_eq!(exported.matches("DTSTAMP:").count(), 1);
+
+        let reimported = service.import_events(&exported).unwrap();
+        assert_eq!(service.export_events(&reimported).unwrap(), exported);
+    }
+
+    #[test]
+    fn test_round_trip_keeps_colour_and_long_text() {
+        let service = ICalendarService::new();
+        let mut original = sample_event();
+        original.description = Some(format!("Über {} – end", "lang ".repeat(40)));
+
+        let ics = service.export_event(&original).unwrap();
+        assert!(ics.contains("COLOR:tomato\r\n"));
+        assert!(ics.lines().all(|line| line.len() <= 75));
+
+        let imported = service.import_events(&ics).unwrap();
+        assert_eq!(imported[0].description, original.description);
+        assert_eq!(imported[0].color, original.color);
+        assert_eq!(imported[0].start, original.start);
+    }
+
+    #[test]
+    fn test_import_rfc7986_colour_name() {
+        let service = ICalendarService::new();
+        let ics = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\nUID:colour\r\nDTSTART:20260227T090000Z\r\nDTEND:20260227T100000Z\r\nSUMMARY:Colour\r\nCOLOR:teal\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        let events = service.import_events(ics).unwrap();
+        assert_eq!(events[0].color.as_deref(), Some("#008080"));
+    }
+
     #[test]
     fn test_escape_text() {
         use super::super::utils::escape_text;
@@ -276,4 +426,28 @@ END:VCALENDAR"#;
         assert!(unescaped.contains(";and;semicolons"));
         assert!(unescaped.contains("\\backslash"));
     }
+
+    #[test]
+    fn test_every_format_imports_what_it_exports() {
+        let service = ICalendarService::new();
+        let event = sample_event();
+
+        for format in [
+            EventFileFormat::Ics,
+            EventFileFormat::Jcal,
+            EventFileFormat::Json,
+        ] {
+            let content = service
+                .export_events_as(std::slice::from_ref(&event), format)
+                .unwrap();
+            assert_eq!(EventFileFormat::detect(&content), Some(format));
+
+            let imported = service.import_any_format(&content).unwrap().unwrap();
+            assert_eq!(imported.len(), 1, "{:?}", format);
+            assert_eq!(imported[0].event.title, event.title, "{:?}", format);
+            assert_eq!(imported[0].event.start, event.start, "{:?}", format);
+            assert_eq!(imported[0].event.color, event.color, "{:?}", format);
+        }
+        assert!(service.import_any_format("just some notes").is_none());
+    }
 }
@@ -0,0 +1,242 @@
+//! VTIMEZONE components built from the IANA database in `chrono-tz`.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+
+/// The UTC offset in effect at an instant
+#[derive(Debug, Clone, PartialEq)]
+struct Observance {
+    offset_seconds: i32,
+    daylight: bool,
+    name: String,
+}
+
+/// A change from one observance to another
+struct Transition {
+    at: DateTime<Utc>,
+    from: Observance,
+    to: Observance,
+}
+
+/// The VTIMEZONE lines (unfolded) describing `tz` from the start of
+/// `first_year` to the end of `last_year`. The last daylight and standard
+/// transitions carry a yearly RRULE when the zone follows a fixed rule, so
+/// recurring events beyond that range still resolve correctly.
+pub(super) fn vtimezone(tz: Tz, first_year: i32, last_year: i32) -> Vec<String> {
+    let range_start = Utc.with_ymd_and_hms(first_year, 1, 1, 0, 0, 0).unwrap();
+    let range_end = Utc.with_ymd_and_hms(last_year + 1, 1, 1, 0, 0, 0).unwrap();
+    let transitions = transitions(tz, range_start, range_end);
+    let initial = observance_at(tz, range_start);
+    // Starts at local midnight on January 1st
+    let initial_start = range_start - Duration::seconds(initial.offset_seconds as i64);
+
+    let mut lines = vec!["BEGIN:VTIMEZONE".to_string(), format!("TZID:{}", tz.name())];
+    append_observance(
+        &mut lines,
+        &Transition {
+            at: initial_start,
+            from: initial.clone(),
+            to: initial,
+        },
+        None,
+    );
+    for (index, transition) in transitions.iter().enumerate() {
+        let is_last_of_kind = !transitions[index + 1..]
+            .iter()
+            .any(|later| later.to.daylight == transition.to.daylight);
+        let rrule = if is_last_of_kind {
+            yearly_rule(transition, &transitions[..index])
+        } else {
+            None
+        };
+        append_observance(&mut lines, transition, rrule);
+    }
+    lines.push("END:VTIMEZONE".to_string());
+    lines
+}
+
+fn observance_at(tz: Tz, instant: DateTime<Utc>) -> Observance {
+    let offset = tz.offset_from_utc_datetime(&instant.naive_utc());
+    Observance {
+        offset_seconds: (offset.base_utc_offset() + offset.dst_offset()).num_seconds() as i32,
+        daylight: !offset.dst_offset().is_zero(),
+        name: offset.abbreviation().to_string(),
+    }
+}
+
+/// Every offset change in `[start, end)`, found a day at a time and then
+/// narrowed to the second.
+fn transitions(tz: Tz, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<Transition> {
+    let mut transitions = Vec::new();
+    let mut cursor = start;
+    let mut current = observance_at(tz, cursor);
+    while cursor < end {
+        let probe = cursor + Duration::days(1);
+        if observance_at(tz, probe) == current {
+            cursor = probe;
+            continue;
+        }
+        // First second after `cursor` at which `current` no longer applies
+        let (mut low, mut high) = (cursor, probe);
+        while high - low > Duration::seconds(1) {
+            let mid = low + (high - low) / 2;
+            if observance_at(tz, mid) == current {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        let to = observance_at(tz, high);
+        transitions.push(Transition {
+            at: high,
+            from: current,
+            to: to.clone(),
+        });
+        current = to;
+        cursor = high;
+    }
+    transitions
+}
+
+fn append_observance(lines: &mut Vec<String>, transition: &Transition, rrule: Option<String>) {
+    let kind = if transition.to.daylight {
+        "DAYLIGHT"
+    } else {
+        "STANDARD"
+    };
+    lines.push(format!("BEGIN:{}", kind));
+    lines.push(format!(
+        "DTSTART:{}",
+        local_start(transition).format("%Y%m%dT%H%M%S")
+    ));
+    if let Some(rrule) = rrule {
+        lines.push(format!("RRULE:{}", rrule));
+    }
+    lines.push(format!(
+        "TZOFFSETFROM:{}",
+        format_offset(transition.from.offset_seconds)
+    ));
+    lines.push(format!(
+        "TZOFFSETTO:{}",
+        format_offset(transition.to.offset_seconds)
+    ));
+    lines.push(format!("TZNAME:{}", transition.to.name));
+    lines.push(format!("END:{}", kind));
+}
+
+/// Wall-clock time of a transition in the offset it leaves, as DTSTART
+/// inside an observance is expressed.
+fn local_start(transition: &Transition) -> chrono::NaiveDateTime {
+    transition.at.naive_utc() + Duration::seconds(transition.from.offset_seconds as i64)
+}
+
+fn format_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    let (hours, minutes, secs) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if secs == 0 {
+        format!("{}{:02}{:02}", sign, hours, minutes)
+    } else {
+        format!("{}{:02}{:02}{:02}", sign, hours, minutes, secs)
+    }
+}
+
+/// A `FREQ=YEARLY` rule for `transition` when the previous transition of the
+/// same kind happened on the same weekday rule a year earlier.
+fn yearly_rule(transition: &Transition, earlier: &[Transition]) -> Option<String> {
+    let previous = earlier
+        .iter()
+        .rev()
+        .find(|t| t.to.daylight == transition.to.daylight)?;
+    let start = local_start(transition);
+    let previous_start = local_start(previous);
+
+    let rule = weekday_rule(start.date());
+    let same_rule = previous_start.year() + 1 == start.year()
+        && weekday_rule(previous_start.date()) == rule
+        && previous_start.time() == start.time()
+        && previous.from == transition.from
+        && previous.to == transition.to;
+    same_rule.then(|| {
+        let (month, ordinal, weekday) = rule;
+        format!(
+            "FREQ=YEARLY;BYMONTH={};BYDAY={}{}",
+            month,
+            ordinal,
+            weekday_code(weekday)
+        )
+    })
+}
+
+/// Month, ordinal (`-1` for the last) and weekday of `date`
+fn weekday_rule(date: NaiveDate) -> (u32, i32, Weekday) {
+    let next_month = if date.month() == 12 {
+        NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
+    };
+    let days_in_month = next_month
+        .and_then(|first| first.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(31);
+    let ordinal = if date.day() + 7 > days_in_month {
+        -1
+    } else {
+        ((date.day() - 1) / 7 + 1) as i32
+    };
+    (date.month(), ordinal, date.weekday())
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::vtimezone;
+    use chrono_tz::Tz;
+
+    #[test]
+    fn test_vtimezone_for_zone_with_daylight_saving() {
+        let lines = vtimezone(Tz::America__New_York, 2025, 2026);
+        let text = lines.join("\n");
+
+        assert_eq!(lines[1], "TZID:America/New_York");
+        assert!(text.contains(
+            "BEGIN:DAYLIGHT\nDTSTART:20250309T020000\nTZOFFSETFROM:-0500\nTZOFFSETTO:-0400\nTZNAME:EDT\nEND:DAYLIGHT"
+        ));
+        assert!(text.contains(
+            "BEGIN:DAYLIGHT\nDTSTART:20260308T020000\nRRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU\nTZOFFSETFROM:-0500\nTZOFFSETTO:-0400"
+        ));
+        assert!(text.contains(
+            "BEGIN:STANDARD\nDTSTART:20261101T020000\nRRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU\nTZOFFSETFROM:-0400\nTZOFFSETTO:-0500"
+        ));
+    }
+
+    #[test]
+    fn test_vtimezone_for_fixed_offset_zone() {
+        let lines = vtimezone(Tz::Asia__Kolkata, 2026, 2026);
+        assert_eq!(
+            lines,
+            vec![
+                "BEGIN:VTIMEZONE",
+                "TZID:Asia/Kolkata",
+                "BEGIN:STANDARD",
+                "DTSTART:20260101T000000",
+                "TZOFFSETFROM:+0530",
+                "TZOFFSETTO:+0530",
+                "TZNAME:IST",
+                "END:STANDARD",
+                "END:VTIMEZONE",
+            ]
+        );
+    }
+}
@@ -1,10 +1,12 @@
 use anyhow::Result;
-use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone, Utc};
 use chrono_tz::Tz;
 use std::str::FromStr;
 
-pub(super) fn format_datetime(dt: &DateTime<Local>) -> String {
-    dt.format("%Y%m%dT%H%M%S").to_string()
+/// Format as a UTC date-time with the `Z` suffix, as DTSTAMP, CREATED and
+/// LAST-MODIFIED require.
+pub(super) fn format_utc_datetime(dt: &DateTime<Local>) -> String {
+    dt.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ").to_string()
 }
 
 pub(super) fn format_date(dt: &DateTime<Local>) -> String {
@@ -13,16 +15,52 @@ pub(super) fn format_date(dt: &DateTime<Local>) -> String {
 
 pub(super) fn escape_text(text: &str) -> String {
     text.replace('\\', "\\\\")
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
         .replace('\n', "\\n")
         .replace(',', "\\,")
         .replace(';', "\\;")
 }
 
 pub(super) fn unescape_text(text: &str) -> String {
-    text.replace("\\n", "\n")
-        .replace("\\,", ",")
-        .replace("\\;", ";")
-        .replace("\\\\", "\\")
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(escaped @ (',' | ';' | ':' | '\\')) => unescaped.push(escaped),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Fold a content line at 75 octets (RFC 5545 section 3.1) without splitting
+/// a UTF-8 character, and end it with CRLF.
+pub(super) fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / 74 * 3 + 2);
+    let mut limit = 75;
+    let mut used = 0;
+    for c in line.chars() {
+        if used + c.len_utf8() > limit {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the continuation line
+            limit = 74;
+            used = 0;
+        }
+        folded.push(c);
+        used += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
 }
 
 pub(super) fn parse_datetime(s: &str) -> Result<DateTime<Local>> {
@@ -85,9 +123,52 @@ pub(super) fn parse_date(s: &str) -> Result<DateTime<Local>> {
         .ok_or_else(|| anyhow::anyhow!("Invalid date: {}", s))
 }
 
+/// Parse an RFC 5545 DURATION value such as `PT1H30M`, `P1D` or `-P2W`.
+pub(super) fn parse_duration(s: &str) -> Result<Duration> {
+    let invalid = || anyhow::anyhow!("Invalid duration: {}", s);
+    let (negative, rest) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' if number.is_empty() => in_time = true,
+            _ => {
+                let value: i64 = number.parse().map_err(|_| invalid())?;
+                number.clear();
+                let part = match (c, in_time) {
+                    ('W', false) => Duration::try_weeks(value),
+                    ('D', false) => Duration::try_days(value),
+                    ('H', true) => Duration::try_hours(value),
+                    ('M', true) => Duration::try_minutes(value),
+                    ('S', true) => Duration::try_seconds(value),
+                    _ => return Err(invalid()),
+                };
+                total = part
+                    .and_then(|part| total.checked_add(&part))
+                    .ok_or_else(invalid)?;
+            }
+        }
+    }
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(if negative { -total } else { total })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{parse_datetime, parse_datetime_with_tzid};
+    use super::{
+        escape_text, fold_line, parse_datetime, parse_datetime_with_tzid, parse_duration,
+        unescape_text,
+    };
     use chrono::Timelike;
 
     #[test]
@@ -101,4 +182,42 @@ mod tests {
         let parsed = parse_datetime_with_tzid("20260227T090000", Some("Australia/Sydney"));
         assert!(parsed.is_ok());
     }
+
+    #[test]
+    fn test_parse_duration() {
+        use chrono::Duration;
+
+        assert_eq!(parse_duration("PT1H30M").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration("P1DT2H").unwrap(), Duration::hours(26));
+        assert_eq!(parse_duration("-P2W").unwrap(), -Duration::weeks(2));
+        assert!(parse_duration("P1H").is_err());
+        assert!(parse_duration("1D").is_err());
+        // Out of range for a duration, rather than a panic
+        assert!(parse_duration("P99999999999999W").is_err());
+        assert!(parse_duration("P999999999999D").is_err());
+        assert!(parse_duration("P99999999999DT999999999999999S").is_err());
+        assert!(parse_duration("P99999999999999999999D").is_err());
+    }
+
+    #[test]
+    fn test_fold_line_limits_octets_and_keeps_characters_whole() {
+        let line = format!("SUMMARY:{}", "é".repeat(60));
+        let folded = fold_line(&line);
+
+        assert!(folded.ends_with("\r\n"));
+        for physical in folded.trim_end_matches("\r\n").split("\r\n") {
+            assert!(physical.len() <= 75, "{} octets", physical.len());
+        }
+        let unfolded = folded.trim_end_matches("\r\n").replace("\r\n ", "");
+        assert_eq!(unfolded, line);
+        assert_eq!(fold_line("VERSION:2.0"), "VERSION:2.0\r\n");
+    }
+
+    #[test]
+    fn test_unescape_text_reverses_escape_text() {
+        let text = "C:\\new\\folder; a, b\nnext";
+        assert_eq!(unescape_text(&escape_text(text)), text);
+        assert_eq!(escape_text("one\r\ntwo"), "one\\ntwo");
+        assert_eq!(unescape_text("Line\\Nbreak"), "Line\nbreak");
+    }
 }
@@ -2,17 +2,22 @@
 // This file will be populated as services are implemented
 
 pub mod backup;
+pub mod calendar_layout;
 pub mod calendar_sync;
 pub mod category;
 pub mod countdown;
+pub mod csv;
 pub mod database;
 pub mod event;
 pub mod google_account;
+pub mod html;
 pub mod icalendar;
 pub mod notification;
 pub mod outbound_sync;
 pub mod pdf;
 pub mod reminder;
+pub mod saved_search;
+pub mod secrets;
 pub mod settings;
 pub mod sync_conflict;
 pub mod template;
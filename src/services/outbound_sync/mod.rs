@@ -6,8 +6,9 @@ use rusqlite::{params, Connection};
 
 use crate::models::calendar_source::SYNC_CAPABILITY_READ_WRITE;
 use crate::models::outbound_sync_operation::{
-    OutboundSyncOperation, OUTBOUND_STATUS_COMPLETED, OUTBOUND_STATUS_FAILED,
-    OUTBOUND_STATUS_PENDING, OUTBOUND_STATUS_PROCESSING,
+    OutboundSyncOperation, OUTBOUND_OPERATION_CREATE, OUTBOUND_OPERATION_UPDATE,
+    OUTBOUND_STATUS_COMPLETED, OUTBOUND_STATUS_FAILED, OUTBOUND_STATUS_PENDING,
+    OUTBOUND_STATUS_PROCESSING,
 };
 use crate::services::calendar_sync::mapping::EventSyncMapService;
 
@@ -588,7 +589,14 @@ impl<'a> OutboundSyncService<'a> {
                  ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, NULL, NULL, ?7, ?8)
                  ON CONFLICT(source_id, external_uid) DO UPDATE SET
                     local_event_id = excluded.local_event_id,
-                    operation_type = excluded.operation_type,
+                    -- An edit before a create is pushed still needs the create
+                    operation_type = CASE
+                        WHEN outbound_sync_operations.operation_type = ?9
+                             AND outbound_sync_operations.status != ?10
+                             AND excluded.operation_type = ?11
+                        THEN outbound_sync_operations.operation_type
+                        ELSE excluded.operation_type
+                    END,
                     payload_json = excluded.payload_json,
                     status = excluded.status,
                     attempt_count = 0,
@@ -604,6 +612,9 @@ impl<'a> OutboundSyncService<'a> {
                     OUTBOUND_STATUS_PENDING,
                     now,
                     now,
+                    OUTBOUND_OPERATION_CREATE,
+                    OUTBOUND_STATUS_COMPLETED,
+                    OUTBOUND_OPERATION_UPDATE,
                 ],
             )
             .context("Failed to enqueue outbound sync operation")?;
@@ -0,0 +1,322 @@
+//! Fonts embedded in PDF exports.
+//!
+//! printpdf only writes the glyphs a font actually has, so text is split into
+//! runs, each drawn with the first font in the chain that covers it: the font
+//! chosen in settings, then the fonts the UI bundles (Ubuntu Light, Noto
+//! Emoji, the emoji icon font and the Noto Sans Symbols 2 subset). A font is
+//! embedded the first time something is drawn with it, so exports without
+//! emoji do not carry the emoji fonts.
+
+use anyhow::{anyhow, bail, Context, Result};
+use egui::Color32;
+use owned_ttf_parser::{AsFaceRef, OwnedFace};
+use printpdf::{
+    IndirectFontRef, Mm, PdfDocumentReference, PdfLayerReference, Rgb, TextRenderingMode,
+};
+use std::cell::OnceCell;
+use std::path::Path;
+
+const SYMBOLS_FONT: &[u8] =
+    include_bytes!("../../../assets/fonts/NotoSansSymbols2-Regular-Subset.ttf");
+
+/// Millimetres per PostScript point
+const MM_PER_PT: f32 = 25.4 / 72.0;
+
+/// Size, weight and colour of a piece of text
+#[derive(Debug, Clone, Copy)]
+pub struct TextStyle {
+    /// Font size in points
+    pub size: f32,
+    /// Drawn with a thin outline, as the fonts have no bold face
+    pub bold: bool,
+    pub color: Color32,
+}
+
+impl TextStyle {
+    pub fn regular(size: f32) -> Self {
+        Self {
+            size,
+            bold: false,
+            color: Color32::BLACK,
+        }
+    }
+
+    pub fn bold(size: f32) -> Self {
+        Self {
+            bold: true,
+            ..Self::regular(size)
+        }
+    }
+
+    pub fn color(self, color: Color32) -> Self {
+        Self { color, ..self }
+    }
+}
+
+/// The font chain for one document
+pub struct PdfFonts<'a> {
+    doc: &'a PdfDocumentReference,
+    faces: Vec<FontFace>,
+}
+
+struct FontFace {
+    face: OwnedFace,
+    embedded: OnceCell<Option<IndirectFontRef>>,
+}
+
+impl<'a> PdfFonts<'a> {
+    /// The chain for `doc`, starting with the TrueType font at `font_path`
+    /// when one is set
+    pub fn load(doc: &'a PdfDocumentReference, font_path: Option<&Path>) -> Result<Self> {
+        let mut fonts = Vec::new();
+        if let Some(path) = font_path {
+            let data = std::fs::read(path)
+                .with_context(|| format!("Failed to read PDF font {}", path.display()))?;
+            fonts.push((path.display().to_string(), data));
+        }
+        fonts.extend(Self::bundled());
+
+        let faces = fonts
+            .into_iter()
+            .map(|(name, data)| {
+                let face = OwnedFace::from_vec(data, 0)
+                    .map_err(|e| anyhow!("{} is not a usable TrueType font: {}", name, e))?;
+                // printpdf embeds glyphs from the glyf table only, so CFF
+                // outlines would export as blank text
+                if face.as_face_ref().tables().glyf.is_none() {
+                    bail!(
+                        "{} has OpenType (CFF) outlines, which PDF export cannot embed; \
+                         choose a TrueType (.ttf) font",
+                        name
+                    );
+                }
+                Ok(FontFace {
+                    face,
+                    embedded: OnceCell::new(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { doc, faces })
+    }
+
+    /// The UI's proportional fonts in fallback order, then the symbols subset
+    fn bundled() -> Vec<(String, Vec<u8>)> {
+        let mut definitions = egui::FontDefinitions::default();
+        let mut fonts: Vec<(String, Vec<u8>)> = definitions
+            .families
+            .remove(&egui::FontFamily::Proportional)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|name| {
+                let data = definitions.font_data.remove(&name)?.font.into_owned();
+                Some((name, data))
+            })
+            .collect();
+        fonts.push(("noto_symbols2".to_string(), SYMBOLS_FONT.to_vec()));
+        fonts
+    }
+
+    /// Split `text` into runs of characters drawn with the same font.
+    /// Characters no font covers are dropped.
+    fn runs(&self, text: &str) -> Vec<(usize, String)> {
+        let mut runs: Vec<(usize, String)> = Vec::new();
+        for ch in text.chars() {
+            let ch = if ch.is_control() { ' ' } else { ch };
+            let Some(index) = self.face_for(ch) else {
+                continue;
+            };
+            match runs.last_mut() {
+                // Spaces join the current run rather than switching font
+                Some((current, run)) if *current == index || ch == ' ' => run.push(ch),
+                _ => runs.push((index, ch.to_string())),
+            }
+        }
+        runs
+    }
+
+    fn face_for(&self, ch: char) -> Option<usize> {
+        self.faces
+            .iter()
+            .position(|face| face.face.as_face_ref().glyph_index(ch).is_some())
+    }
+
+    /// Width of `text` in millimetres at `size` points
+    pub fn width(&self, text: &str, size: f32) -> f32 {
+        self.runs(text)
+            .iter()
+            .map(|(index, run)| {
+                let face = self.faces[*index].face.as_face_ref();
+                let units: f32 = run
+                    .chars()
+                    .filter_map(|ch| face.glyph_index(ch))
+                    .filter_map(|glyph| face.glyph_hor_advance(glyph))
+                    .map(f32::from)
+                    .sum();
+                units / f32::from(face.units_per_em()) * size * MM_PER_PT
+            })
+            .sum()
+    }
+
+    /// `text` cut short with an ellipsis so it fits in `max_width` mm
+    pub fn fit(&self, text: &str, size: f32, max_width: f32) -> String {
+        if self.width(text, size) <= max_width {
+            return text.to_string();
+        }
+        let mut fitted: String = text.to_string();
+        while fitted.pop().is_some() {
+            let candidate = format!("{}…", fitted.trim_end());
+            if self.width(&candidate, size) <= max_width {
+                return candidate;
+            }
+        }
+        String::new()
+    }
+
+    /// Draw `text` with its baseline starting at (`x`, `y`)
+    pub fn draw(&self, layer: &PdfLayerReference, style: TextStyle, x: f32, y: f32, text: &str) {
+        let runs = self.runs(text);
+        if runs.is_empty() {
+            return;
+        }
+
+        let color = pdf_color(style.color);
+        layer.save_graphics_state();
+        layer.begin_text_section();
+        layer.set_fill_color(color.clone());
+        if style.bold {
+            layer.set_text_rendering_mode(TextRenderingMode::FillStroke);
+            layer.set_outline_color(color);
+            layer.set_outline_thickness(style.size * 0.04);
+        }
+        layer.set_text_cursor(Mm(x), Mm(y));
+        for (index, run) in runs {
+            // Each glyph advances the cursor, so runs follow on directly
+            if let Some(font) = self.embedded(index) {
+                layer.set_font(font, style.size);
+                layer.write_text(run, font);
+            }
+        }
+        layer.end_text_section();
+        layer.restore_graphics_state();
+    }
+
+    /// Draw `text` centred horizontally on `x`
+    pub fn draw_centered(
+        &self,
+        layer: &PdfLayerReference,
+        style: TextStyle,
+        x: f32,
+        y: f32,
+        text: &str,
+    ) {
+        let width = self.width(text, style.size);
+        self.draw(layer, style, x - width / 2.0, y, text);
+    }
+
+    fn embedded(&self, index: usize) -> Option<&IndirectFontRef> {
+        let face = &self.faces[index];
+        face.embedded
+            .get_or_init(|| {
+                self.doc
+                    .add_external_font(face.face.as_slice())
+                    .map_err(|e| log::warn!("Failed to embed PDF font: {}", e))
+                    .ok()
+            })
+            .as_ref()
+    }
+}
+
+/// `color` as a PDF RGB colour
+pub fn pdf_color(color: Color32) -> printpdf::Color {
+    printpdf::Color::Rgb(Rgb::new(
+        color.r() as f32 / 255.0,
+        color.g() as f32 / 255.0,
+        color.b() as f32 / 255.0,
+        None,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PdfFonts;
+    use printpdf::{Mm, PdfDocument};
+
+    #[test]
+    fn test_runs_fall_back_for_emoji() {
+        let (doc, _, _) = PdfDocument::new("Test", Mm(210.0), Mm(297.0), "Layer 1");
+        let fonts = PdfFonts::load(&doc, None).unwrap();
+
+        let runs = fonts.runs("Café 🎂 party");
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[0], (0, "Café ".to_string()));
+        assert_ne!(runs[1].0, 0);
+        assert_eq!(runs[1].1, "🎂 ");
+        assert_eq!(runs[2], (0, "party".to_string()));
+    }
+
+    #[test]
+    fn test_width_and_fit() {
+        let (doc, _, _) = PdfDocument::new("Test", Mm(210.0), Mm(297.0), "Layer 1");
+        let fonts = PdfFonts::load(&doc, None).unwrap();
+
+        let short = fonts.width("Hi", 10.0);
+        let long = fonts.width("Hi there", 10.0);
+        assert!(short > 0.0 && long > short);
+        // Twice the size is twice the width
+        assert!((fonts.width("Hi", 20.0) - short * 2.0).abs() < 0.001);
+
+        let fitted = fonts.fit("Quarterly planning review", 8.0, 15.0);
+        assert!(fitted.ends_with('…'));
+        assert!(fonts.width(&fitted, 8.0) <= 15.0);
+        assert_eq!(fonts.fit("Gym", 8.0, 15.0), "Gym");
+    }
+
+    #[test]
+    fn test_missing_font_file_is_an_error() {
+        let (doc, _, _) = PdfDocument::new("Test", Mm(210.0), Mm(297.0), "Layer 1");
+        let missing = std::path::Path::new("/nonexistent/font.ttf");
+        assert!(PdfFonts::load(&doc, Some(missing)).is_err());
+    }
+
+    /// The tables an OpenType font with CFF outlines needs to parse, without
+    /// the outlines themselves
+    fn cff_font() -> Vec<u8> {
+        let mut head = vec![0u8; 54];
+        head[0..2].copy_from_slice(&1u16.to_be_bytes());
+        head[12..16].copy_from_slice(&0x5F0F_3CF5u32.to_be_bytes());
+        head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+        let mut hhea = vec![0u8; 36];
+        hhea[0..2].copy_from_slice(&1u16.to_be_bytes());
+        hhea[34..36].copy_from_slice(&1u16.to_be_bytes());
+        let mut maxp = 0x0000_5000u32.to_be_bytes().to_vec();
+        maxp.extend_from_slice(&1u16.to_be_bytes());
+
+        let tables = [(b"head", head), (b"hhea", hhea), (b"maxp", maxp)];
+        let mut font = b"OTTO".to_vec();
+        font.extend_from_slice(&[0, 3, 0, 0, 0, 0, 0, 0]);
+        let mut offset = 12 + 16 * tables.len();
+        for (tag, data) in &tables {
+            font.extend_from_slice(*tag);
+            font.extend_from_slice(&[0; 4]);
+            font.extend_from_slice(&(offset as u32).to_be_bytes());
+            font.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            offset += data.len();
+        }
+        for (_, data) in tables {
+            font.extend(data);
+        }
+        font
+    }
+
+    #[test]
+    fn test_cff_font_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("NotoSansCJK-Regular.otf");
+        std::fs::write(&path, cff_font()).unwrap();
+
+        let (doc, _, _) = PdfDocument::new("Test", Mm(210.0), Mm(297.0), "Layer 1");
+        let err = PdfFonts::load(&doc, Some(&path)).err().unwrap();
+        assert!(err.to_string().contains("TrueType (.ttf)"), "{}", err);
+    }
+}
@@ -1,5 +1,6 @@
 //! PDF export service for calendar views
 
+pub mod fonts;
 pub mod service;
 
 pub use service::PdfExportService;
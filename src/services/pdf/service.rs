@@ -1,14 +1,28 @@
 //! PDF export service implementation
 
 use anyhow::{Context, Result};
-use chrono::{Datelike, Duration, Local, NaiveDate, TimeZone};
-use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfLayerReference, Point, Rgb};
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use egui::Color32;
+use printpdf::{Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point, Rgb};
 use std::fs::File;
 use std::io::BufWriter;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use super::fonts::{pdf_color, PdfFonts, TextStyle};
+use crate::models::category::Category;
 use crate::models::event::Event;
+use crate::services::calendar_layout::{self, MonthLayout};
 use crate::services::event::EventService;
+use crate::ui_egui::theme::CalendarTheme;
+
+/// Month grid geometry, in mm
+const MONTH_START_X: f32 = 20.0;
+const MONTH_START_Y: f32 = 255.0;
+const MONTH_COL_WIDTH: f32 = 25.0;
+const MONTH_ROW_HEIGHT: f32 = 35.0;
+const MONTH_EVENT_SPACING: f32 = 4.5;
+/// Events that fit under the day number; busier days continue on the next page
+const MONTH_EVENTS_PER_CELL: usize = 5;
 
 /// Service for exporting calendar data to PDF
 pub struct PdfExportService;
@@ -23,6 +37,8 @@ pub struct PdfExportOptions {
     pub include_descriptions: bool,
     /// Include event locations
     pub include_locations: bool,
+    /// TrueType font to embed ahead of the bundled fonts
+    pub font_path: Option<PathBuf>,
 }
 
 impl Default for PdfExportOptions {
@@ -32,14 +48,17 @@ impl Default for PdfExportOptions {
             page_size: (210.0, 297.0), // A4 Portrait
             include_descriptions: true,
             include_locations: true,
+            font_path: None,
         }
     }
 }
 
 impl PdfExportService {
-    /// Export a month view to PDF
+    /// Export a month view to PDF. Days with more events than fit in a cell
+    /// continue on extra pages of the same grid.
     pub fn export_month(
         event_service: &EventService,
+        categories: &[Category],
         date: NaiveDate,
         path: &Path,
         options: &PdfExportOptions,
@@ -51,56 +70,75 @@ impl PdfExportService {
             Mm(options.page_size.1),
             "Layer 1",
         );
+        let fonts = PdfFonts::load(&doc, options.font_path.as_deref())?;
+        // Printed pages always use the light palette
+        let theme = CalendarTheme::light();
 
-        let font = doc
-            .add_builtin_font(BuiltinFont::Helvetica)
-            .context("Failed to add font")?;
-        let font_bold = doc
-            .add_builtin_font(BuiltinFont::HelveticaBold)
-            .context("Failed to add bold font")?;
-
-        let layer = doc.get_page(page1).get_layer(layer1);
+        let layout = MonthLayout::new(date, first_day_of_week);
+        let (start, end) = calendar_layout::date_range(layout.first_day, layout.last_day);
+        let events = event_service
+            .expand_recurring_events(start, end)
+            .unwrap_or_default();
 
-        // Draw month header
+        let page_count = month_page_count(&layout, &events);
         let month_name = date.format("%B %Y").to_string();
-        Self::draw_text(&layer, &font_bold, 24.0, 105.0, 280.0, &month_name, true);
 
-        // Draw day headers
-        let day_names = Self::get_day_names(first_day_of_week);
-        let col_width = 25.0;
-        let start_x = 20.0;
-        let header_y = 265.0;
+        for page in 0..page_count {
+            let layer = if page == 0 {
+                doc.get_page(page1).get_layer(layer1)
+            } else {
+                let (new_page, new_layer) =
+                    doc.add_page(Mm(options.page_size.0), Mm(options.page_size.1), "Layer 1");
+                doc.get_page(new_page).get_layer(new_layer)
+            };
 
-        for (i, day_name) in day_names.iter().enumerate() {
-            let x = start_x + (i as f32 * col_width) + col_width / 2.0;
-            Self::draw_text(&layer, &font_bold, 10.0, x, header_y, day_name, true);
-        }
+            // Draw month header
+            let heading = if page == 0 {
+                month_name.clone()
+            } else {
+                format!("{} (continued)", month_name)
+            };
+            fonts.draw_centered(&layer, TextStyle::bold(24.0), 105.0, 280.0, &heading);
 
-        // Draw calendar grid
-        Self::draw_month_grid(
-            &layer,
-            event_service,
-            date,
-            start_x,
-            header_y - 10.0,
-            col_width,
-            35.0,
-            first_day_of_week,
-            &font,
-            &font_bold,
-        )?;
-
-        // Save to file
-        let file = File::create(path).context("Failed to create PDF file")?;
-        let mut writer = BufWriter::new(file);
-        doc.save(&mut writer).context("Failed to save PDF")?;
+            // Draw day headers
+            let header_y = MONTH_START_Y + 10.0;
+            Self::fill_rect(
+                &layer,
+                MONTH_START_X,
+                header_y - 2.5,
+                MONTH_COL_WIDTH * 7.0 - 1.0,
+                7.0,
+                theme.header_background,
+            );
+            let header_style = TextStyle::bold(10.0).color(theme.header_text);
+            for (i, day_name) in calendar_layout::day_names(first_day_of_week)
+                .iter()
+                .enumerate()
+            {
+                let x = MONTH_START_X + (i as f32 * MONTH_COL_WIDTH) + MONTH_COL_WIDTH / 2.0;
+                fonts.draw_centered(&layer, header_style, x, header_y, day_name);
+            }
 
-        Ok(())
+            Self::draw_month_grid(&layer, &fonts, &theme, categories, &layout, &events, page);
+
+            if page_count > 1 {
+                fonts.draw_centered(
+                    &layer,
+                    TextStyle::regular(8.0).color(theme.text_secondary),
+                    105.0,
+                    12.0,
+                    &format!("Page {} of {}", page + 1, page_count),
+                );
+            }
+        }
+
+        Self::save(doc, path)
     }
 
     /// Export a week view to PDF
     pub fn export_week(
         event_service: &EventService,
+        categories: &[Category],
         date: NaiveDate,
         path: &Path,
         options: &PdfExportOptions,
@@ -112,18 +150,13 @@ impl PdfExportService {
             Mm(options.page_size.0),
             "Layer 1",
         );
-
-        let font = doc
-            .add_builtin_font(BuiltinFont::Helvetica)
-            .context("Failed to add font")?;
-        let font_bold = doc
-            .add_builtin_font(BuiltinFont::HelveticaBold)
-            .context("Failed to add bold font")?;
+        let fonts = PdfFonts::load(&doc, options.font_path.as_deref())?;
+        let theme = CalendarTheme::light();
 
         let layer = doc.get_page(page1).get_layer(layer1);
 
         // Calculate week start
-        let week_start = Self::get_week_start(date, first_day_of_week);
+        let week_start = calendar_layout::week_start(date, first_day_of_week);
         let week_end = week_start + Duration::days(6);
 
         // Draw week header
@@ -132,23 +165,16 @@ impl PdfExportService {
             week_start.format("%B %d"),
             week_end.format("%B %d, %Y")
         );
-        Self::draw_text(&layer, &font_bold, 18.0, 148.5, 195.0, &week_title, true);
+        fonts.draw_centered(&layer, TextStyle::bold(18.0), 148.5, 195.0, &week_title);
 
         // Draw day columns
         let col_width = 38.0;
         let start_x = 15.0;
         let header_y = 180.0;
-        let day_names = Self::get_full_day_names(first_day_of_week);
+        let day_names = calendar_layout::full_day_names(first_day_of_week);
 
         // Get events for the week
-        let start = Local
-            .from_local_datetime(&week_start.and_hms_opt(0, 0, 0).unwrap())
-            .single()
-            .unwrap();
-        let end = Local
-            .from_local_datetime(&week_end.and_hms_opt(23, 59, 59).unwrap())
-            .single()
-            .unwrap();
+        let (start, end) = calendar_layout::date_range(week_start, week_end);
         let events = event_service
             .expand_recurring_events(start, end)
             .unwrap_or_default();
@@ -158,34 +184,26 @@ impl PdfExportService {
             let x = start_x + (i as f32 * col_width);
 
             // Day header
-            let _header_text = format!("{}\n{}", day_names[i as usize], day_date.format("%d"));
-            Self::draw_text(
+            fonts.draw_centered(
                 &layer,
-                &font_bold,
-                10.0,
+                TextStyle::bold(10.0),
                 x + col_width / 2.0,
                 header_y,
                 day_names[i as usize],
-                true,
             );
-            Self::draw_text(
+            fonts.draw_centered(
                 &layer,
-                &font,
-                9.0,
+                TextStyle::regular(9.0),
                 x + col_width / 2.0,
                 header_y - 6.0,
                 &day_date.format("%d").to_string(),
-                true,
             );
 
             // Draw border for day column
             Self::draw_rect(&layer, x, header_y - 165.0, col_width - 1.0, 155.0);
 
             // Events for this day
-            let day_events: Vec<&Event> = events
-                .iter()
-                .filter(|e| e.start.date_naive() == day_date)
-                .collect();
+            let day_events = calendar_layout::events_on_day(&events, day_date);
 
             let mut y_offset = header_y - 15.0;
             for event in day_events.iter().take(8) {
@@ -195,42 +213,46 @@ impl PdfExportService {
                     event.start.format("%H:%M").to_string()
                 };
 
-                // Truncate title to fit
-                let title = if event.title.len() > 15 {
-                    format!("{}...", &event.title[..12])
-                } else {
-                    event.title.clone()
-                };
-
-                Self::draw_text(&layer, &font, 7.0, x + 2.0, y_offset, &time_str, false);
-                Self::draw_text(&layer, &font, 7.0, x + 2.0, y_offset - 4.0, &title, false);
+                let background = Self::event_background(event, categories, &theme);
+                let text_style =
+                    TextStyle::regular(7.0).color(calendar_layout::readable_text(background));
+                Self::fill_rect(
+                    &layer,
+                    x + 1.0,
+                    y_offset - 6.0,
+                    col_width - 3.0,
+                    9.5,
+                    background,
+                );
+                fonts.draw(&layer, text_style, x + 2.0, y_offset, &time_str);
+                fonts.draw(
+                    &layer,
+                    text_style,
+                    x + 2.0,
+                    y_offset - 4.0,
+                    &fonts.fit(&event.title, 7.0, col_width - 5.0),
+                );
                 y_offset -= 12.0;
             }
 
             if day_events.len() > 8 {
-                Self::draw_text(
+                fonts.draw(
                     &layer,
-                    &font,
-                    6.0,
+                    TextStyle::regular(6.0),
                     x + 2.0,
                     y_offset,
                     &format!("+{} more", day_events.len() - 8),
-                    false,
                 );
             }
         }
 
-        // Save to file
-        let file = File::create(path).context("Failed to create PDF file")?;
-        let mut writer = BufWriter::new(file);
-        doc.save(&mut writer).context("Failed to save PDF")?;
-
-        Ok(())
+        Self::save(doc, path)
     }
 
     /// Export an event list to PDF
     pub fn export_event_list(
         events: &[Event],
+        categories: &[Category],
         path: &Path,
         options: &PdfExportOptions,
     ) -> Result<()> {
@@ -240,51 +262,49 @@ impl PdfExportService {
             Mm(options.page_size.1),
             "Layer 1",
         );
+        let fonts = PdfFonts::load(&doc, options.font_path.as_deref())?;
+        let theme = CalendarTheme::light();
 
-        let font = doc
-            .add_builtin_font(BuiltinFont::Helvetica)
-            .context("Failed to add font")?;
-        let font_bold = doc
-            .add_builtin_font(BuiltinFont::HelveticaBold)
-            .context("Failed to add bold font")?;
-
-        let mut _current_page = page1;
         let mut current_layer = doc.get_page(page1).get_layer(layer1);
 
         // Title
-        Self::draw_text(
+        fonts.draw_centered(
             &current_layer,
-            &font_bold,
-            18.0,
+            TextStyle::bold(18.0),
             105.0,
             280.0,
             &options.title,
-            true,
         );
 
         let mut y = 260.0;
         let margin_left = 20.0;
         let page_height = options.page_size.1;
+        let text_width = options.page_size.0 - margin_left * 2.0;
 
         for event in events {
             // Check if we need a new page
             if y < 30.0 {
                 let (new_page, new_layer) =
                     doc.add_page(Mm(options.page_size.0), Mm(options.page_size.1), "Layer 1");
-                _current_page = new_page;
                 current_layer = doc.get_page(new_page).get_layer(new_layer);
                 y = page_height - 20.0;
             }
 
-            // Event title
-            Self::draw_text(
+            // Colour marker and event title
+            Self::fill_rect(
                 &current_layer,
-                &font_bold,
-                11.0,
+                margin_left - 4.0,
+                y - 0.5,
+                2.5,
+                3.5,
+                Self::event_background(event, categories, &theme),
+            );
+            fonts.draw(
+                &current_layer,
+                TextStyle::bold(11.0),
                 margin_left,
                 y,
-                &event.title,
-                false,
+                &fonts.fit(&event.title, 11.0, text_width),
             );
             y -= 5.0;
 
@@ -294,14 +314,12 @@ impl PdfExportService {
             } else {
                 event.start.format("%B %d, %Y at %I:%M %p").to_string()
             };
-            Self::draw_text(
+            fonts.draw(
                 &current_layer,
-                &font,
-                9.0,
+                TextStyle::regular(9.0),
                 margin_left,
                 y,
                 &datetime_str,
-                false,
             );
             y -= 4.0;
 
@@ -309,14 +327,12 @@ impl PdfExportService {
             if options.include_locations {
                 if let Some(ref loc) = event.location {
                     if !loc.is_empty() {
-                        Self::draw_text(
+                        fonts.draw(
                             &current_layer,
-                            &font,
-                            8.0,
+                            TextStyle::regular(8.0),
                             margin_left,
                             y,
-                            &format!("📍 {}", loc),
-                            false,
+                            &fonts.fit(&format!("📍 {}", loc), 8.0, text_width),
                         );
                         y -= 4.0;
                     }
@@ -327,19 +343,12 @@ impl PdfExportService {
             if options.include_descriptions {
                 if let Some(ref desc) = event.description {
                     if !desc.is_empty() {
-                        let truncated = if desc.len() > 100 {
-                            format!("{}...", &desc[..97])
-                        } else {
-                            desc.clone()
-                        };
-                        Self::draw_text(
+                        fonts.draw(
                             &current_layer,
-                            &font,
-                            8.0,
+                            TextStyle::regular(8.0),
                             margin_left,
                             y,
-                            &truncated,
-                            false,
+                            &fonts.fit(desc, 8.0, text_width),
                         );
                         y -= 4.0;
                     }
@@ -349,191 +358,242 @@ impl PdfExportService {
             y -= 6.0; // Space between events
         }
 
-        // Save to file
+        Self::save(doc, path)
+    }
+
+    fn save(doc: PdfDocumentReference, path: &Path) -> Result<()> {
         let file = File::create(path).context("Failed to create PDF file")?;
         let mut writer = BufWriter::new(file);
         doc.save(&mut writer).context("Failed to save PDF")?;
-
         Ok(())
     }
 
-    fn draw_text(
+    /// The event's own or category colour, else the theme's default
+    fn event_background(event: &Event, categories: &[Category], theme: &CalendarTheme) -> Color32 {
+        calendar_layout::event_color(event, categories).unwrap_or(theme.event_colors.default)
+    }
+
+    fn draw_rect(layer: &PdfLayerReference, x: f32, y: f32, width: f32, height: f32) {
+        layer.set_outline_color(printpdf::Color::Rgb(Rgb::new(0.7, 0.7, 0.7, None)));
+        layer.set_outline_thickness(0.5);
+        layer.add_polygon(printpdf::Polygon {
+            rings: vec![Self::rect_points(x, y, width, height)],
+            mode: printpdf::path::PaintMode::Stroke,
+            winding_order: printpdf::path::WindingOrder::NonZero,
+        });
+    }
+
+    fn fill_rect(
         layer: &PdfLayerReference,
-        font: &IndirectFontRef,
-        size: f32,
         x: f32,
         y: f32,
-        text: &str,
-        centered: bool,
+        width: f32,
+        height: f32,
+        color: Color32,
     ) {
-        layer.begin_text_section();
-        layer.set_font(font, size);
-        layer.set_fill_color(printpdf::Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
-
-        let position = if centered {
-            // Approximate centering based on character count
-            let approx_width = text.len() as f32 * size * 0.4;
-            (Mm(x - approx_width / 2.0), Mm(y))
-        } else {
-            (Mm(x), Mm(y))
-        };
-
-        layer.set_text_cursor(position.0, position.1);
-        layer.write_text(text, font);
-        layer.end_text_section();
+        layer.set_fill_color(pdf_color(color));
+        layer.add_polygon(printpdf::Polygon {
+            rings: vec![Self::rect_points(x, y, width, height)],
+            mode: printpdf::path::PaintMode::Fill,
+            winding_order: printpdf::path::WindingOrder::NonZero,
+        });
     }
 
-    fn draw_rect(layer: &PdfLayerReference, x: f32, y: f32, width: f32, height: f32) {
-        let points = vec![
+    fn rect_points(x: f32, y: f32, width: f32, height: f32) -> Vec<(Point, bool)> {
+        vec![
             (Point::new(Mm(x), Mm(y)), false),
             (Point::new(Mm(x + width), Mm(y)), false),
             (Point::new(Mm(x + width), Mm(y + height)), false),
             (Point::new(Mm(x), Mm(y + height)), false),
-        ];
-        layer.set_outline_color(printpdf::Color::Rgb(Rgb::new(0.7, 0.7, 0.7, None)));
-        layer.set_outline_thickness(0.5);
-        layer.add_polygon(printpdf::Polygon {
-            rings: vec![points],
-            mode: printpdf::path::PaintMode::Stroke,
-            winding_order: printpdf::path::WindingOrder::NonZero,
-        });
+        ]
     }
 
-    #[allow(clippy::too_many_arguments)]
+    /// Draw one page of the month grid: the `page`th slice of each day's events
     fn draw_month_grid(
         layer: &PdfLayerReference,
-        event_service: &EventService,
-        date: NaiveDate,
-        start_x: f32,
-        start_y: f32,
-        col_width: f32,
-        row_height: f32,
-        first_day_of_week: u8,
-        font: &IndirectFontRef,
-        font_bold: &IndirectFontRef,
-    ) -> Result<()> {
-        let first_of_month = date.with_day(1).unwrap();
-        let first_weekday =
-            (first_of_month.weekday().num_days_from_sunday() as i32 - first_day_of_week as i32 + 7)
-                % 7;
-        let days_in_month = Self::get_days_in_month(date.year(), date.month());
-
-        // Get events for the month
-        let start = Local
-            .from_local_datetime(&first_of_month.and_hms_opt(0, 0, 0).unwrap())
-            .single()
-            .unwrap();
-        let end_of_month = date.with_day(days_in_month as u32).unwrap();
-        let end = Local
-            .from_local_datetime(&end_of_month.and_hms_opt(23, 59, 59).unwrap())
-            .single()
-            .unwrap();
-        let events = event_service
-            .expand_recurring_events(start, end)
-            .unwrap_or_default();
-
-        let mut day_counter = 1 - first_weekday;
+        fonts: &PdfFonts,
+        theme: &CalendarTheme,
+        categories: &[Category],
+        layout: &MonthLayout,
+        events: &[Event],
+        page: usize,
+    ) {
         let today = Local::now().date_naive();
-
-        for week in 0..6 {
-            let y = start_y - (week as f32 * row_height);
-
-            for day_of_week in 0..7 {
-                let x = start_x + (day_of_week as f32 * col_width);
+        let col_width = MONTH_COL_WIDTH;
+        let row_height = MONTH_ROW_HEIGHT;
+
+        for (week, days) in layout.weeks.iter().enumerate() {
+            let y = MONTH_START_Y - (week as f32 * row_height);
+
+            for (day_of_week, cell_date) in days.iter().enumerate() {
+                let x = MONTH_START_X + (day_of_week as f32 * col_width);
+
+                if let Some(cell_date) = *cell_date {
+                    let background = if cell_date == today {
+                        Some(theme.today_background)
+                    } else if matches!(cell_date.weekday(), Weekday::Sat | Weekday::Sun) {
+                        Some(theme.weekend_background)
+                    } else {
+                        None
+                    };
+                    if let Some(background) = background {
+                        Self::fill_rect(
+                            layer,
+                            x,
+                            y - row_height,
+                            col_width - 1.0,
+                            row_height - 1.0,
+                            background,
+                        );
+                    }
+                }
 
                 // Draw cell border
                 Self::draw_rect(layer, x, y - row_height, col_width - 1.0, row_height - 1.0);
 
-                if day_counter >= 1 && day_counter <= days_in_month {
-                    let cell_date =
-                        NaiveDate::from_ymd_opt(date.year(), date.month(), day_counter as u32)
-                            .unwrap();
-
-                    // Draw day number
-                    let day_str = format!("{}", day_counter);
-                    let is_today = cell_date == today;
-                    let day_font = if is_today { font_bold } else { font };
-                    Self::draw_text(layer, day_font, 9.0, x + 2.0, y - 5.0, &day_str, false);
-
-                    // Draw events (max 2)
-                    let day_events: Vec<&Event> = events
-                        .iter()
-                        .filter(|e| {
-                            if e.all_day {
-                                let start_date = e.start.date_naive();
-                                let end_date = e.end.date_naive();
-                                cell_date >= start_date && cell_date <= end_date
-                            } else {
-                                e.start.date_naive() == cell_date
-                            }
-                        })
-                        .collect();
-
-                    let mut event_y = y - 12.0;
-                    for event in day_events.iter().take(2) {
-                        let title = if event.title.len() > 10 {
-                            format!("{}...", &event.title[..7])
-                        } else {
-                            event.title.clone()
-                        };
-                        Self::draw_text(layer, font, 6.0, x + 2.0, event_y, &title, false);
-                        event_y -= 5.0;
-                    }
+                let Some(cell_date) = *cell_date else {
+                    continue;
+                };
 
-                    if day_events.len() > 2 {
-                        Self::draw_text(
-                            layer,
-                            font,
-                            5.0,
-                            x + 2.0,
-                            event_y,
-                            &format!("+{}", day_events.len() - 2),
-                            false,
-                        );
-                    }
+                // Draw day number
+                let day_str = cell_date.format("%-d").to_string();
+                let day_style = if cell_date == today {
+                    TextStyle::bold(9.0)
+                } else {
+                    TextStyle::regular(9.0)
+                };
+                fonts.draw(layer, day_style, x + 2.0, y - 5.0, &day_str);
+
+                // Draw this page's share of the day's events
+                let day_events = calendar_layout::events_on_day(events, cell_date);
+                let shown = day_events
+                    .iter()
+                    .skip(page * MONTH_EVENTS_PER_CELL)
+                    .take(MONTH_EVENTS_PER_CELL);
+
+                let mut event_y = y - 10.0;
+                for event in shown {
+                    let label = if event.all_day {
+                        event.title.clone()
+                    } else {
+                        format!("{} {}", event.start.format("%H:%M"), event.title)
+                    };
+                    let background = Self::event_background(event, categories, theme);
+                    Self::fill_rect(
+                        layer,
+                        x + 1.0,
+                        event_y - 1.0,
+                        col_width - 3.0,
+                        3.8,
+                        background,
+                    );
+                    fonts.draw(
+                        layer,
+                        TextStyle::regular(6.0).color(calendar_layout::readable_text(background)),
+                        x + 2.0,
+                        event_y,
+                        &fonts.fit(&label, 6.0, col_width - 5.0),
+                    );
+                    event_y -= MONTH_EVENT_SPACING;
                 }
 
-                day_counter += 1;
+                let remaining = day_events
+                    .len()
+                    .saturating_sub((page + 1) * MONTH_EVENTS_PER_CELL);
+                if remaining > 0 {
+                    let more = format!("+{} more", remaining);
+                    let style = TextStyle::regular(6.0).color(theme.text_secondary);
+                    let width = fonts.width(&more, style.size);
+                    fonts.draw(layer, style, x + col_width - 3.0 - width, y - 5.0, &more);
+                }
             }
         }
-
-        Ok(())
     }
+}
 
-    fn get_week_start(date: NaiveDate, first_day_of_week: u8) -> NaiveDate {
-        let weekday = date.weekday().num_days_from_sunday();
-        let days_to_subtract = (weekday as i64 - first_day_of_week as i64 + 7) % 7;
-        date - Duration::days(days_to_subtract)
-    }
+/// Pages needed for every event of the busiest day to appear in the grid
+fn month_page_count(layout: &MonthLayout, events: &[Event]) -> usize {
+    layout
+        .weeks
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|day| {
+            calendar_layout::events_on_day(events, *day)
+                .len()
+                .div_ceil(MONTH_EVENTS_PER_CELL)
+        })
+        .max()
+        .unwrap_or(0)
+        .max(1)
+}
 
-    fn get_days_in_month(year: i32, month: u32) -> i32 {
-        NaiveDate::from_ymd_opt(
-            if month == 12 { year + 1 } else { year },
-            if month == 12 { 1 } else { month + 1 },
-            1,
-        )
-        .unwrap()
-        .signed_duration_since(NaiveDate::from_ymd_opt(year, month, 1).unwrap())
-        .num_days() as i32
+#[cfg(test)]
+mod tests {
+    use super::{month_page_count, PdfExportOptions, PdfExportService, MONTH_EVENTS_PER_CELL};
+    use crate::models::category::Category;
+    use crate::models::event::Event;
+    use crate::services::calendar_layout::MonthLayout;
+    use chrono::{Duration, Local, NaiveDate, TimeZone};
+
+    fn events_on(day: u32, count: usize) -> Vec<Event> {
+        let start = Local.with_ymd_and_hms(2026, 3, day, 8, 0, 0).unwrap();
+        (0..count)
+            .map(|i| {
+                let start = start + Duration::minutes(30 * i as i64);
+                Event::builder()
+                    .title(format!("Slot {}", i + 1))
+                    .start(start)
+                    .end(start + Duration::minutes(30))
+                    .build()
+                    .unwrap()
+            })
+            .collect()
     }
 
-    fn get_day_names(first_day_of_week: u8) -> Vec<&'static str> {
-        let all_days = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
-        let start = first_day_of_week as usize;
-        (0..7).map(|i| all_days[(start + i) % 7]).collect()
+    #[test]
+    fn test_month_page_count_follows_busiest_day() {
+        let layout = MonthLayout::new(NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(), 0);
+
+        assert_eq!(month_page_count(&layout, &[]), 1);
+        assert_eq!(
+            month_page_count(&layout, &events_on(10, MONTH_EVENTS_PER_CELL)),
+            1
+        );
+
+        let mut busy = events_on(10, MONTH_EVENTS_PER_CELL + 1);
+        busy.extend(events_on(20, MONTH_EVENTS_PER_CELL * 2 + 1));
+        assert_eq!(month_page_count(&layout, &busy), 3);
     }
 
-    fn get_full_day_names(first_day_of_week: u8) -> Vec<&'static str> {
-        let all_days = [
-            "Sunday",
-            "Monday",
-            "Tuesday",
-            "Wednesday",
-            "Thursday",
-            "Friday",
-            "Saturday",
-        ];
-        let start = first_day_of_week as usize;
-        (0..7).map(|i| all_days[(start + i) % 7]).collect()
+    #[test]
+    fn test_event_list_embeds_fonts_for_unicode_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.pdf");
+        let start = Local.with_ymd_and_hms(2026, 3, 12, 9, 0, 0).unwrap();
+        let event = Event::builder()
+            .title("Réunion d'équipe 🎂")
+            .location("Zürich")
+            .description("Ελληνικά και Français")
+            .start(start)
+            .end(start + Duration::hours(1))
+            .category("Work")
+            .build()
+            .unwrap();
+        let categories = vec![Category::new("Work", "#3366CC")];
+
+        PdfExportService::export_event_list(
+            &[event],
+            &categories,
+            &path,
+            &PdfExportOptions::default(),
+        )
+        .unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+        let content = String::from_utf8_lossy(&bytes);
+        assert!(content.contains("FontFile2"));
+        assert!(!content.contains("Helvetica"));
     }
 }
@@ -0,0 +1,125 @@
+// Saved search service
+// Named event queries ("smart filters") for the sidebar and view filters
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+
+use crate::models::saved_search::SavedSearch;
+use crate::services::event::queries::EventQuery;
+
+pub struct SavedSearchService<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SavedSearchService<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Save a search, replacing the query of an existing search with the same name
+    pub fn save(&self, search: &SavedSearch) -> Result<SavedSearch> {
+        search.validate().map_err(|e| anyhow::anyhow!(e))?;
+        EventQuery::parse(&search.query)?;
+
+        let name = search.name.trim();
+        self.conn
+            .execute(
+                "INSERT INTO saved_searches (name, query) VALUES (?1, ?2)
+                 ON CONFLICT(name) DO UPDATE SET query = excluded.query",
+                params![name, search.query.trim()],
+            )
+            .context("Failed to save search")?;
+
+        self.conn
+            .query_row(
+                "SELECT id, name, query FROM saved_searches WHERE name = ?1",
+                params![name],
+                map_saved_search_row,
+            )
+            .context("Saved search not found")
+    }
+
+    /// Get all saved searches ordered by name
+    pub fn list_all(&self) -> Result<Vec<SavedSearch>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, query FROM saved_searches ORDER BY name COLLATE NOCASE ASC",
+        )?;
+
+        let searches = stmt.query_map([], map_saved_search_row)?;
+
+        searches
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to fetch saved searches")
+    }
+
+    /// Delete a saved search by ID
+    pub fn delete(&self, id: i64) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM saved_searches WHERE id = ?1", params![id])
+            .context("Failed to delete saved search")?;
+
+        Ok(())
+    }
+}
+
+fn map_saved_search_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SavedSearch> {
+    Ok(SavedSearch {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        query: row.get(2)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::database::Database;
+
+    #[test]
+    fn test_save_replaces_query_with_same_name() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let service = SavedSearchService::new(db.connection());
+
+        let first = service
+            .save(&SavedSearch::new("Work", "category:Work"))
+            .unwrap();
+        service
+            .save(&SavedSearch::new("Offsite", "location:\"Room 4\""))
+            .unwrap();
+        let updated = service
+            .save(&SavedSearch::new("Work", "category:Work is:recurring"))
+            .unwrap();
+
+        assert_eq!(updated.id, first.id);
+        let names: Vec<_> = service
+            .list_all()
+            .unwrap()
+            .into_iter()
+            .map(|search| (search.name, search.query))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Offsite".to_string(), "location:\"Room 4\"".to_string()),
+                ("Work".to_string(), "category:Work is:recurring".to_string()),
+            ]
+        );
+
+        service.delete(first.id.unwrap()).unwrap();
+        assert_eq!(service.list_all().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_save_rejects_invalid_query() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let service = SavedSearchService::new(db.connection());
+
+        assert!(service
+            .save(&SavedSearch::new("Bad", "after:tomorrow"))
+            .is_err());
+        assert!(service.save(&SavedSearch::new(" ", "lunch")).is_err());
+        assert!(service.list_all().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,69 @@
+//! Passphrase-based authenticated encryption shared by the file vault.
+//!
+//! Keys are derived with Argon2id and data is sealed with ChaCha20-Poly1305,
+//! so a wrong passphrase or a tampered file fails to decrypt rather than
+//! producing garbage.
+
+use anyhow::{anyhow, Result};
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+pub const SALT_LEN: usize = 16;
+pub const NONCE_LEN: usize = 12;
+
+/// A 256-bit key derived from a passphrase.
+#[derive(Clone)]
+pub struct DerivedKey([u8; 32]);
+
+pub fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+pub fn derive_key(passphrase: &str, salt: &[u8]) -> Result<DerivedKey> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| anyhow!("Failed to derive key from passphrase: {}", err))?;
+    Ok(DerivedKey(key))
+}
+
+/// Encrypts `plaintext`, returning `(nonce, ciphertext)`.
+pub fn seal(key: &DerivedKey, plaintext: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key.0));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| anyhow!("Failed to encrypt data"))?;
+    Ok((nonce.to_vec(), ciphertext))
+}
+
+pub fn open(key: &DerivedKey, nonce: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+    if nonce.len() != NONCE_LEN {
+        return Err(anyhow!("Encrypted data has an invalid nonce"));
+    }
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key.0));
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("Incorrect passphrase or corrupted data"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open_round_trip() {
+        let salt = random_bytes::<SALT_LEN>();
+        let key = derive_key("correct horse", &salt).unwrap();
+        let (nonce, ciphertext) = seal(&key, b"refresh-token").unwrap();
+
+        assert_eq!(open(&key, &nonce, &ciphertext).unwrap(), b"refresh-token");
+
+        let wrong = derive_key("battery staple", &salt).unwrap();
+        assert!(open(&wrong, &nonce, &ciphertext).is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! Passphrase-protected secret file used when no OS keyring is available.
+//!
+//! The whole entry map is encrypted as one blob and rewritten on every change.
+//! The vault starts locked; [`EncryptedFileSecretStore::unlock`] either opens
+//! the existing file or creates a new one protected by the given passphrase.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use super::crypto::{self, DerivedKey, SALT_LEN};
+use super::SecretStore;
+
+const VAULT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+struct UnlockedVault {
+    key: DerivedKey,
+    salt: Vec<u8>,
+    entries: BTreeMap<String, String>,
+}
+
+pub struct EncryptedFileSecretStore {
+    path: PathBuf,
+    unlocked: Mutex<Option<UnlockedVault>>,
+}
+
+impl EncryptedFileSecretStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            unlocked: Mutex::new(None),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// True once a vault file has been created, i.e. a passphrase was chosen.
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.unlocked.lock().map(|v| v.is_some()).unwrap_or(false)
+    }
+
+    /// Opens the vault with `passphrase`, creating it if it does not exist yet.
+    pub fn unlock(&self, passphrase: &str) -> Result<()> {
+        if passphrase.is_empty() {
+            return Err(anyhow!("Passphrase cannot be empty"));
+        }
+
+        let vault = if self.exists() {
+            let contents = fs::read_to_string(&self.path)
+                .with_context(|| format!("Failed to read secret vault {}", self.path.display()))?;
+            let file: VaultFile =
+                serde_json::from_str(&contents).context("Secret vault file is corrupted")?;
+            if file.version != VAULT_VERSION {
+                return Err(anyhow!("Unsupported secret vault version {}", file.version));
+            }
+
+            let salt = decode(&file.salt)?;
+            let key = crypto::derive_key(passphrase, &salt)?;
+            let plaintext = crypto::open(&key, &decode(&file.nonce)?, &decode(&file.ciphertext)?)?;
+            let entries =
+                serde_json::from_slice(&plaintext).context("Secret vault contents are invalid")?;
+            UnlockedVault { key, salt, entries }
+        } else {
+            let salt = crypto::random_bytes::<SALT_LEN>().to_vec();
+            let key = crypto::derive_key(passphrase, &salt)?;
+            let vault = UnlockedVault {
+                key,
+                salt,
+                entries: BTreeMap::new(),
+            };
+            self.write(&vault)?;
+            vault
+        };
+
+        *self
+            .unlocked
+            .lock()
+            .map_err(|_| anyhow!("Secret vault lock poisoned"))? = Some(vault);
+        Ok(())
+    }
+
+    fn write(&self, vault: &UnlockedVault) -> Result<()> {
+        let plaintext = serde_json::to_vec(&vault.entries).context("Failed to encode secrets")?;
+        let (nonce, ciphertext) = crypto::seal(&vault.key, &plaintext)?;
+        let file = VaultFile {
+            version: VAULT_VERSION,
+            salt: BASE64.encode(&vault.salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).with_context(|| {
+                format!(
+                    "Failed to create secret vault directory {}",
+                    parent.display()
+                )
+            })?;
+        }
+
+        // Write to a temporary file first so a crash never leaves a half-written vault.
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(&file)?)
+            .with_context(|| format!("Failed to write secret vault {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("Failed to replace secret vault {}", self.path.display()))?;
+        Ok(())
+    }
+
+    fn with_vault<T>(&self, f: impl FnOnce(&mut UnlockedVault) -> Result<T>) -> Result<T> {
+        let mut guard = self
+            .unlocked
+            .lock()
+            .map_err(|_| anyhow!("Secret vault lock poisoned"))?;
+        let vault = guard
+            .as_mut()
+            .ok_or_else(|| anyhow!("Secret vault is locked; enter its passphrase to unlock it"))?;
+        f(vault)
+    }
+}
+
+impl SecretStore for EncryptedFileSecretStore {
+    fn backend_name(&self) -> &'static str {
+        "encrypted file"
+    }
+
+    fn needs_passphrase(&self) -> bool {
+        !self.is_unlocked()
+    }
+
+    fn creates_on_unlock(&self) -> bool {
+        !self.exists()
+    }
+
+    fn unlock(&self, passphrase: &str) -> Result<()> {
+        EncryptedFileSecretStore::unlock(self, passphrase)
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        self.with_vault(|vault| Ok(vault.entries.get(key).cloned()))
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        self.with_vault(|vault| {
+            vault.entries.insert(key.to_string(), value.to_string());
+            self.write(vault)
+        })
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        self.with_vault(|vault| {
+            if vault.entries.remove(key).is_some() {
+                self.write(vault)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+fn decode(value: &str) -> Result<Vec<u8>> {
+    BASE64
+        .decode(value)
+        .context("Secret vault file is corrupted")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_persists_entries_across_unlocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.vault");
+
+        let store = EncryptedFileSecretStore::new(&path);
+        assert!(store.get("token").is_err(), "locked vault must not read");
+        store.unlock("passphrase").unwrap();
+        store.set("token", "refresh-secret").unwrap();
+
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("refresh-secret"));
+
+        let reopened = EncryptedFileSecretStore::new(&path);
+        assert!(reopened.unlock("wrong").is_err());
+        reopened.unlock("passphrase").unwrap();
+        assert_eq!(
+            reopened.get("token").unwrap().as_deref(),
+            Some("refresh-secret")
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! OS keyring backend (Secret Service on Linux, Keychain on macOS,
+//! Credential Manager on Windows).
+
+use anyhow::{Context, Result};
+
+use super::SecretStore;
+
+const KEYRING_SERVICE: &str = "rust-calendar";
+const PROBE_KEY: &str = "keyring-probe";
+
+pub struct KeyringSecretStore {
+    service: String,
+}
+
+impl KeyringSecretStore {
+    pub fn new() -> Self {
+        Self {
+            service: KEYRING_SERVICE.to_string(),
+        }
+    }
+
+    /// Checks that the keyring is reachable by writing and reading a probe entry.
+    pub fn probe(&self) -> Result<()> {
+        self.set(PROBE_KEY, "ok")?;
+        self.get(PROBE_KEY)?;
+        self.delete(PROBE_KEY)
+    }
+
+    fn entry(&self, key: &str) -> Result<keyring::Entry> {
+        keyring::Entry::new(&self.service, key).context("Failed to open keyring entry")
+    }
+}
+
+impl Default for KeyringSecretStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecretStore for KeyringSecretStore {
+    fn backend_name(&self) -> &'static str {
+        "system keyring"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        match self.entry(key)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(err) => Err(err).context("Failed to read secret from keyring"),
+        }
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        self.entry(key)?
+            .set_password(value)
+            .context("Failed to write secret to keyring")
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        match self.entry(key)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(err) => Err(err).context("Failed to delete secret from keyring"),
+        }
+    }
+}
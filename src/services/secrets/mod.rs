@@ -0,0 +1,358 @@
+//! Storage for credentials that must not live in the SQLite database.
+//!
+//! OAuth tokens and private ICS URLs are written to a [`SecretStore`] and the
+//! database keeps only an opaque reference (`secret-ref:<id>`) in their place,
+//! so a copied database file or backup grants no calendar access.
+//!
+//! The process-wide store is chosen at startup by [`open_default_store`]: the
+//! OS keyring when it is reachable, otherwise an [`EncryptedFileSecretStore`]
+//! that stays locked until the user enters its passphrase. Until a store is
+//! installed an in-memory store is used, which keeps tests self-contained.
+
+#![allow(dead_code)]
+
+pub mod crypto;
+mod file_vault;
+mod keyring_store;
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+use anyhow::{anyhow, Context, Result};
+use rusqlite::{params, Connection};
+
+pub use file_vault::EncryptedFileSecretStore;
+pub use keyring_store::KeyringSecretStore;
+
+/// Prefix marking a database value as a reference into the secret store.
+pub const SECRET_REF_PREFIX: &str = "secret-ref:";
+/// File name of the encrypted fallback vault, next to the database.
+pub const VAULT_FILE_NAME: &str = "secrets.vault";
+/// Set to `file` to skip the OS keyring and always use the encrypted file.
+pub const SECRET_STORE_ENV: &str = "RUST_CALENDAR_SECRET_STORE";
+
+pub trait SecretStore: Send + Sync {
+    /// Short description shown in settings, e.g. "system keyring".
+    fn backend_name(&self) -> &'static str;
+
+    /// True while the store cannot be used until [`SecretStore::unlock`] succeeds.
+    fn needs_passphrase(&self) -> bool {
+        false
+    }
+
+    /// True when unlocking will create the store, so a new passphrase is being chosen.
+    fn creates_on_unlock(&self) -> bool {
+        false
+    }
+
+    fn unlock(&self, _passphrase: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>>;
+    fn set(&self, key: &str, value: &str) -> Result<()>;
+    fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// Non-persistent store used in tests and before startup installs a real one.
+#[derive(Default)]
+pub struct MemorySecretStore {
+    entries: Mutex<HashMap<String, String>>,
+}
+
+impl SecretStore for MemorySecretStore {
+    fn backend_name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(self
+            .entries
+            .lock()
+            .map_err(|_| anyhow!("Secret store lock poisoned"))?
+            .get(key)
+            .cloned())
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        self.entries
+            .lock()
+            .map_err(|_| anyhow!("Secret store lock poisoned"))?
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        self.entries
+            .lock()
+            .map_err(|_| anyhow!("Secret store lock poisoned"))?
+            .remove(key);
+        Ok(())
+    }
+}
+
+struct SecretRegistry {
+    store: Arc<dyn SecretStore>,
+    /// Resolved values, so per-frame lookups don't hit the keyring.
+    cache: HashMap<String, String>,
+}
+
+fn registry() -> &'static RwLock<SecretRegistry> {
+    static REGISTRY: OnceLock<RwLock<SecretRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        RwLock::new(SecretRegistry {
+            store: Arc::new(MemorySecretStore::default()),
+            cache: HashMap::new(),
+        })
+    })
+}
+
+/// Replaces the process-wide secret store.
+pub fn install(store: Arc<dyn SecretStore>) {
+    if let Ok(mut registry) = registry().write() {
+        registry.store = store;
+        registry.cache.clear();
+    }
+}
+
+/// The process-wide secret store.
+pub fn store() -> Arc<dyn SecretStore> {
+    registry()
+        .read()
+        .map(|registry| registry.store.clone())
+        .unwrap_or_else(|_| Arc::new(MemorySecretStore::default()))
+}
+
+/// The installed store, cloned out so keyring and file I/O run without the
+/// registry lock held.
+fn installed_store() -> Result<Arc<dyn SecretStore>> {
+    registry()
+        .read()
+        .map(|registry| registry.store.clone())
+        .map_err(|_| anyhow!("Secret store lock poisoned"))
+}
+
+/// Caches a value read from or written to `store`, unless another store was
+/// installed in the meantime.
+fn cache_value(store: &Arc<dyn SecretStore>, reference: &str, value: &str) {
+    if let Ok(mut registry) = registry().write() {
+        if Arc::ptr_eq(&registry.store, store) {
+            registry
+                .cache
+                .insert(reference.to_string(), value.to_string());
+        }
+    }
+}
+
+/// Picks the OS keyring when it works, otherwise the encrypted file vault in `data_dir`.
+pub fn open_default_store(data_dir: &Path) -> Arc<dyn SecretStore> {
+    let force_file = std::env::var(SECRET_STORE_ENV)
+        .map(|value| value.eq_ignore_ascii_case("file"))
+        .unwrap_or(false);
+
+    if !force_file {
+        let keyring = KeyringSecretStore::new();
+        match keyring.probe() {
+            Ok(()) => return Arc::new(keyring),
+            Err(err) => log::warn!(
+                "System keyring unavailable ({:#}); using encrypted secret file",
+                err
+            ),
+        }
+    }
+
+    Arc::new(EncryptedFileSecretStore::new(
+        data_dir.join(VAULT_FILE_NAME),
+    ))
+}
+
+pub fn is_reference(value: &str) -> bool {
+    value.starts_with(SECRET_REF_PREFIX)
+}
+
+/// Stores `value` and returns the reference to save in the database instead.
+///
+/// When `existing` is already a reference its entry is overwritten in place.
+pub fn put(existing: Option<&str>, value: &str) -> Result<String> {
+    let reference = match existing.filter(|current| is_reference(current)) {
+        Some(reference) => reference.to_string(),
+        None => {
+            let id: String = crypto::random_bytes::<16>()
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect();
+            format!("{}{}", SECRET_REF_PREFIX, id)
+        }
+    };
+
+    let store = installed_store()?;
+    store.set(&reference[SECRET_REF_PREFIX.len()..], value)?;
+    cache_value(&store, &reference, value);
+    Ok(reference)
+}
+
+/// Returns the secret behind a database value. Values that are not references
+/// (rows written before secrets moved out of the database) are returned as-is.
+pub fn resolve(stored: &str) -> Result<String> {
+    if !is_reference(stored) {
+        return Ok(stored.to_string());
+    }
+
+    if let Some(value) = registry()
+        .read()
+        .ok()
+        .and_then(|registry| registry.cache.get(stored).cloned())
+    {
+        return Ok(value);
+    }
+
+    let store = installed_store()?;
+    let value = store
+        .get(&stored[SECRET_REF_PREFIX.len()..])?
+        .ok_or_else(|| {
+            anyhow!(
+                "Secret is missing from the {}; re-enter it",
+                store.backend_name()
+            )
+        })?;
+    cache_value(&store, stored, &value);
+    Ok(value)
+}
+
+pub fn resolve_optional(stored: Option<String>) -> Result<Option<String>> {
+    stored.map(|value| resolve(&value)).transpose()
+}
+
+/// Deletes the secret behind a reference. Failures are logged, not returned,
+/// because the database row is being cleared regardless.
+pub fn forget(stored: Option<&str>) {
+    let Some(reference) = stored.filter(|value| is_reference(value)) else {
+        return;
+    };
+    let store = match registry().write() {
+        Ok(mut registry) => {
+            registry.cache.remove(reference);
+            registry.store.clone()
+        }
+        Err(_) => return,
+    };
+    if let Err(err) = store.delete(&reference[SECRET_REF_PREFIX.len()..]) {
+        log::warn!("Failed to delete secret: {:#}", err);
+    }
+}
+
+/// Forgets `created` when the database write that was meant to reference it
+/// failed, so a failed save leaves no orphaned secret behind. References that
+/// were reused from `existing` are kept, since the row still points at them.
+pub fn discard_on_error<T>(
+    result: Result<T>,
+    existing: Option<&str>,
+    created: Option<&str>,
+) -> Result<T> {
+    if result.is_err() && created != existing {
+        forget(created);
+    }
+    result
+}
+
+/// Moves plaintext tokens and private ICS URLs left in the database into the
+/// secret store. Returns how many values were moved.
+pub fn migrate_plaintext_secrets(conn: &Connection) -> Result<usize> {
+    if store().needs_passphrase() {
+        return Ok(0);
+    }
+
+    let mut moved = 0;
+    for (table, column) in [
+        ("google_accounts", "access_token"),
+        ("google_accounts", "refresh_token"),
+        ("calendar_sources", "ics_url"),
+    ] {
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT id, {column} FROM {table}
+                 WHERE {column} IS NOT NULL AND {column} != '' AND {column} NOT LIKE 'secret-ref:%'"
+            ))
+            .with_context(|| format!("Failed to query plaintext {}.{}", table, column))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        for (id, value) in rows {
+            let reference = put(None, &value)?;
+            conn.execute(
+                &format!("UPDATE {table} SET {column} = ?1 WHERE id = ?2"),
+                params![reference, id],
+            )
+            .with_context(|| format!("Failed to replace plaintext {}.{}", table, column))?;
+            moved += 1;
+        }
+    }
+
+    Ok(moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::database::Database;
+
+    #[test]
+    fn test_put_resolve_and_forget() {
+        let reference = put(None, "private-value").unwrap();
+        assert!(is_reference(&reference));
+        assert_eq!(resolve(&reference).unwrap(), "private-value");
+
+        let same = put(Some(&reference), "rotated").unwrap();
+        assert_eq!(same, reference);
+        assert_eq!(resolve(&reference).unwrap(), "rotated");
+
+        forget(Some(&reference));
+        assert!(resolve(&reference).is_err());
+        assert_eq!(resolve("plain").unwrap(), "plain");
+    }
+
+    #[test]
+    fn test_discard_on_error_forgets_only_new_references() {
+        let existing = put(None, "kept").unwrap();
+        let failed: Result<()> = Err(anyhow::anyhow!("write failed"));
+        assert!(discard_on_error(failed, Some(&existing), Some(&existing)).is_err());
+        assert_eq!(resolve(&existing).unwrap(), "kept");
+
+        let created = put(None, "orphan").unwrap();
+        let failed: Result<()> = Err(anyhow::anyhow!("write failed"));
+        assert!(discard_on_error(failed, Some(&existing), Some(&created)).is_err());
+        assert!(resolve(&created).is_err());
+
+        let fresh = put(None, "saved").unwrap();
+        discard_on_error(Ok(()), None, Some(&fresh)).unwrap();
+        assert_eq!(resolve(&fresh).unwrap(), "saved");
+    }
+
+    #[test]
+    fn test_migrate_plaintext_secrets_replaces_columns() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        conn.execute(
+            "INSERT INTO google_accounts (account_email, refresh_token) VALUES ('a@example.com', 'plain-refresh')",
+            [],
+        )
+        .unwrap();
+
+        assert!(migrate_plaintext_secrets(conn).unwrap() >= 1);
+
+        let stored: String = conn
+            .query_row(
+                "SELECT refresh_token FROM google_accounts WHERE account_email = 'a@example.com'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(is_reference(&stored));
+        assert_eq!(resolve(&stored).unwrap(), "plain-refresh");
+    }
+}
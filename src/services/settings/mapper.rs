@@ -28,5 +28,19 @@ pub fn row_to_settings(row: &Row) -> Result<Settings, rusqlite::Error> {
         sync_startup_delay_minutes: row.get::<_, i64>(21).unwrap_or(15),
         minimize_to_tray: row.get::<_, i32>(22).unwrap_or(0) != 0,
         show_countdown_cards: row.get::<_, i32>(23).unwrap_or(1) != 0,
+        sync_mass_delete_percent: row.get::<_, i64>(24).unwrap_or(50),
+        sync_mass_delete_count: row.get::<_, i64>(25).unwrap_or(25),
+        backup_interval: row
+            .get::<_, String>(26)
+            .unwrap_or_else(|_| "daily".to_string()),
+        backup_directory: row.get::<_, Option<String>>(27).unwrap_or(None),
+        backup_keep_hourly: row.get::<_, i64>(28).unwrap_or(24),
+        backup_keep_daily: row.get::<_, i64>(29).unwrap_or(7),
+        backup_keep_weekly: row.get::<_, i64>(30).unwrap_or(4),
+        backup_keep_monthly: row.get::<_, i64>(31).unwrap_or(12),
+        backup_encrypt: row.get::<_, i32>(32).unwrap_or(0) != 0,
+        backup_passphrase_ref: row.get::<_, Option<String>>(33).unwrap_or(None),
+        trash_retention_days: row.get::<_, i64>(34).unwrap_or(30),
+        pdf_font_path: row.get::<_, Option<String>>(35).unwrap_or(None),
     })
 }
@@ -25,7 +25,12 @@ impl<'a> SettingsService<'a> {
                     current_view, default_event_duration, first_day_of_work_week, last_day_of_work_week,
                     default_event_start_time, default_card_width, default_card_height,
                     auto_create_countdown_on_import, edit_before_import, sidebar_width,
-                          sync_startup_delay_minutes, minimize_to_tray, show_countdown_cards
+                          sync_startup_delay_minutes, minimize_to_tray, show_countdown_cards,
+                          sync_mass_delete_percent, sync_mass_delete_count,
+                          backup_interval, backup_directory, backup_keep_hourly,
+                          backup_keep_daily, backup_keep_weekly, backup_keep_monthly,
+                          backup_encrypt, backup_passphrase_ref, trash_retention_days,
+                          pdf_font_path
              FROM settings WHERE id = 1",
                 [],
                 row_to_settings,
@@ -68,6 +73,18 @@ impl<'a> SettingsService<'a> {
                  sync_startup_delay_minutes = ?21, \
                  minimize_to_tray = ?22, \
                  show_countdown_cards = ?23, \
+                 sync_mass_delete_percent = ?24, \
+                 sync_mass_delete_count = ?25, \
+                 backup_interval = ?26, \
+                 backup_directory = ?27, \
+                 backup_keep_hourly = ?28, \
+                 backup_keep_daily = ?29, \
+                 backup_keep_weekly = ?30, \
+                 backup_keep_monthly = ?31, \
+                 backup_encrypt = ?32, \
+                 backup_passphrase_ref = ?33, \
+                 trash_retention_days = ?34, \
+                 pdf_font_path = ?35, \
                  updated_at = CURRENT_TIMESTAMP \
              WHERE id = 1",
             params![
@@ -94,6 +111,18 @@ impl<'a> SettingsService<'a> {
                 settings.sync_startup_delay_minutes,
                 settings.minimize_to_tray as i32,
                 settings.show_countdown_cards as i32,
+                settings.sync_mass_delete_percent,
+                settings.sync_mass_delete_count,
+                &settings.backup_interval,
+                &settings.backup_directory,
+                settings.backup_keep_hourly,
+                settings.backup_keep_daily,
+                settings.backup_keep_weekly,
+                settings.backup_keep_monthly,
+                settings.backup_encrypt as i32,
+                &settings.backup_passphrase_ref,
+                settings.trash_retention_days,
+                &settings.pdf_font_path,
             ],
         )
         .context("Failed to update settings")?;
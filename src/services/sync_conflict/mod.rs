@@ -5,9 +5,17 @@ use chrono::Local;
 use rusqlite::{params, Connection};
 
 use crate::models::sync_conflict::{
-    SyncConflict, SYNC_CONFLICT_RESOLUTION_REMOTE_WINS, SYNC_CONFLICT_RESOLUTION_RETRY_LOCAL,
+    ConflictEventSnapshot, SyncConflict, SYNC_CONFLICT_RESOLUTION_MERGED,
     SYNC_CONFLICT_STATUS_OPEN, SYNC_CONFLICT_STATUS_RESOLVED,
 };
+use crate::services::event::EventService;
+
+const SELECT_CONFLICT_COLUMNS: &str =
+    "SELECT id, source_id, local_event_id, external_uid, outbound_operation_id,
+        local_operation_type, remote_change_type, reason, resolution,
+        status, base_snapshot_json, local_snapshot_json, remote_snapshot_json,
+        created_at, resolved_at, updated_at
+ FROM sync_conflicts";
 
 pub struct SyncConflictService<'a> {
     conn: &'a Connection,
@@ -38,8 +46,10 @@ impl<'a> SyncConflictService<'a> {
                          remote_change_type = ?4,
                          reason = ?5,
                          resolution = ?6,
-                         updated_at = ?7
-                     WHERE id = ?8",
+                         local_snapshot_json = ?7,
+                         remote_snapshot_json = ?8,
+                         updated_at = ?9
+                     WHERE id = ?10",
                     params![
                         conflict.local_event_id,
                         conflict.outbound_operation_id,
@@ -47,6 +57,8 @@ impl<'a> SyncConflictService<'a> {
                         conflict.remote_change_type,
                         conflict.reason,
                         conflict.resolution,
+                        Self::snapshot_json(conflict.local_snapshot.as_ref()),
+                        Self::snapshot_json(conflict.remote_snapshot.as_ref()),
                         now,
                         id,
                     ],
@@ -64,8 +76,9 @@ impl<'a> SyncConflictService<'a> {
                 "INSERT INTO sync_conflicts (
                     source_id, local_event_id, external_uid, outbound_operation_id,
                     local_operation_type, remote_change_type, reason, resolution,
-                    status, created_at, resolved_at, updated_at
-                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, NULL, ?11)",
+                    status, base_snapshot_json, local_snapshot_json, remote_snapshot_json,
+                    created_at, resolved_at, updated_at
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, NULL, ?14)",
                 params![
                     stored.source_id,
                     stored.local_event_id,
@@ -76,6 +89,9 @@ impl<'a> SyncConflictService<'a> {
                     stored.reason,
                     stored.resolution,
                     SYNC_CONFLICT_STATUS_OPEN,
+                    Self::snapshot_json(stored.base_snapshot.as_ref()),
+                    Self::snapshot_json(stored.local_snapshot.as_ref()),
+                    Self::snapshot_json(stored.remote_snapshot.as_ref()),
                     now,
                     now,
                 ],
@@ -104,15 +120,13 @@ impl<'a> SyncConflictService<'a> {
         let safe_limit = limit.clamp(1, 1000);
         let mut stmt = self
             .conn
-            .prepare(
-                "SELECT id, source_id, local_event_id, external_uid, outbound_operation_id,
-                        local_operation_type, remote_change_type, reason, resolution,
-                        status, created_at, resolved_at, updated_at
-                 FROM sync_conflicts
+            .prepare(&format!(
+                "{}
                  WHERE source_id = ?1 AND status = ?2
                  ORDER BY updated_at DESC
                  LIMIT ?3",
-            )
+                SELECT_CONFLICT_COLUMNS
+            ))
             .context("Failed to prepare open sync conflicts query")?;
 
         let rows = stmt.query_map(
@@ -192,13 +206,13 @@ impl<'a> SyncConflictService<'a> {
         external_uid: &str,
     ) -> Result<Option<SyncConflict>> {
         let result = self.conn.query_row(
-            "SELECT id, source_id, local_event_id, external_uid, outbound_operation_id,
-                    local_operation_type, remote_change_type, reason, resolution,
-                    status, created_at, resolved_at, updated_at
-             FROM sync_conflicts
-             WHERE source_id = ?1 AND external_uid = ?2 AND status = ?3
-             ORDER BY updated_at DESC
-             LIMIT 1",
+            &format!(
+                "{}
+                 WHERE source_id = ?1 AND external_uid = ?2 AND status = ?3
+                 ORDER BY updated_at DESC
+                 LIMIT 1",
+                SELECT_CONFLICT_COLUMNS
+            ),
             params![source_id, external_uid, SYNC_CONFLICT_STATUS_OPEN],
             Self::row_to_conflict,
         );
@@ -210,13 +224,9 @@ impl<'a> SyncConflictService<'a> {
         }
     }
 
-    fn get_by_id(&self, conflict_id: i64) -> Result<Option<SyncConflict>> {
+    pub fn get_by_id(&self, conflict_id: i64) -> Result<Option<SyncConflict>> {
         let result = self.conn.query_row(
-            "SELECT id, source_id, local_event_id, external_uid, outbound_operation_id,
-                    local_operation_type, remote_change_type, reason, resolution,
-                    status, created_at, resolved_at, updated_at
-             FROM sync_conflicts
-             WHERE id = ?1",
+            &format!("{} WHERE id = ?1", SELECT_CONFLICT_COLUMNS),
             [conflict_id],
             Self::row_to_conflict,
         );
@@ -228,12 +238,43 @@ impl<'a> SyncConflictService<'a> {
         }
     }
 
+    /// Resolves a conflict by writing a merged version to the local event.
+    ///
+    /// The update goes through `EventService::update_local`, so the merged event
+    /// replaces the failed outbound operation and is pushed on the next sync.
+    pub fn resolve_with_merge(
+        &self,
+        conflict_id: i64,
+        merged: &ConflictEventSnapshot,
+    ) -> Result<()> {
+        let conflict = self
+            .get_by_id(conflict_id)?
+            .ok_or_else(|| anyhow!("Sync conflict with id {} not found", conflict_id))?;
+
+        if conflict.status != SYNC_CONFLICT_STATUS_OPEN {
+            return Err(anyhow!("Sync conflict {} is already resolved", conflict_id));
+        }
+
+        let local_event_id = conflict
+            .local_event_id
+            .ok_or_else(|| anyhow!("Sync conflict {} has no local event", conflict_id))?;
+        let event_service = EventService::new(self.conn);
+        let mut event = event_service
+            .get(local_event_id)?
+            .ok_or_else(|| anyhow!("Event with id {} not found", local_event_id))?;
+
+        merged.apply_to(&mut event);
+        event_service
+            .update_local(&event)
+            .context("Failed to save merged event")?;
+
+        self.mark_resolved(conflict_id, SYNC_CONFLICT_RESOLUTION_MERGED)
+    }
+
     fn validate_resolution(resolution: &str) -> Result<()> {
-        if resolution != SYNC_CONFLICT_RESOLUTION_REMOTE_WINS
-            && resolution != SYNC_CONFLICT_RESOLUTION_RETRY_LOCAL
-        {
+        if !SyncConflict::is_valid_resolution(resolution) {
             return Err(anyhow!(
-                "Invalid sync conflict resolution '{}': expected remote_wins or retry_local",
+                "Invalid sync conflict resolution '{}': expected remote_wins, retry_local or merged",
                 resolution
             ));
         }
@@ -241,6 +282,14 @@ impl<'a> SyncConflictService<'a> {
         Ok(())
     }
 
+    fn snapshot_json(snapshot: Option<&ConflictEventSnapshot>) -> Option<String> {
+        snapshot.and_then(ConflictEventSnapshot::to_json)
+    }
+
+    fn parse_snapshot(json: Option<String>) -> Option<ConflictEventSnapshot> {
+        json.as_deref().and_then(ConflictEventSnapshot::from_json)
+    }
+
     fn row_to_conflict(row: &rusqlite::Row<'_>) -> rusqlite::Result<SyncConflict> {
         Ok(SyncConflict {
             id: row.get(0)?,
@@ -253,9 +302,12 @@ impl<'a> SyncConflictService<'a> {
             reason: row.get(7)?,
             resolution: row.get(8)?,
             status: row.get(9)?,
-            created_at: row.get(10)?,
-            resolved_at: row.get(11)?,
-            updated_at: row.get(12)?,
+            base_snapshot: Self::parse_snapshot(row.get(10)?),
+            local_snapshot: Self::parse_snapshot(row.get(11)?),
+            remote_snapshot: Self::parse_snapshot(row.get(12)?),
+            created_at: row.get(13)?,
+            resolved_at: row.get(14)?,
+            updated_at: row.get(15)?,
         })
     }
 }
@@ -263,12 +315,17 @@ impl<'a> SyncConflictService<'a> {
 #[cfg(test)]
 mod tests {
     use super::SyncConflictService;
+    use crate::models::calendar_source::SYNC_CAPABILITY_READ_WRITE;
+    use crate::models::event::Event;
     use crate::models::sync_conflict::{
-        SyncConflict, SYNC_CONFLICT_REASON_LOCAL_UPDATE_PENDING,
+        merge_snapshots, ConflictEventSnapshot, ConflictField, FieldChoice, SyncConflict,
+        SYNC_CONFLICT_REASON_LOCAL_UPDATE_PENDING, SYNC_CONFLICT_RESOLUTION_MERGED,
         SYNC_CONFLICT_RESOLUTION_REMOTE_WINS, SYNC_CONFLICT_STATUS_OPEN,
         SYNC_CONFLICT_STATUS_RESOLVED,
     };
     use crate::services::database::Database;
+    use crate::services::event::EventService;
+    use chrono::{Duration, Local, TimeZone};
     use rusqlite::params;
 
     fn create_source(conn: &rusqlite::Connection) -> i64 {
@@ -296,6 +353,9 @@ mod tests {
             reason: SYNC_CONFLICT_REASON_LOCAL_UPDATE_PENDING.to_string(),
             resolution: Some(SYNC_CONFLICT_RESOLUTION_REMOTE_WINS.to_string()),
             status: SYNC_CONFLICT_STATUS_OPEN.to_string(),
+            base_snapshot: None,
+            local_snapshot: None,
+            remote_snapshot: None,
             created_at: None,
             resolved_at: None,
             updated_at: None,
@@ -343,4 +403,79 @@ mod tests {
             .unwrap();
         assert_eq!(status, SYNC_CONFLICT_STATUS_RESOLVED);
     }
+
+    #[test]
+    fn test_resolve_with_merge_updates_event_and_requeues_outbound() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let conn = db.connection();
+        let source_id = create_source(conn);
+        conn.execute(
+            "UPDATE calendar_sources SET sync_capability = ?1 WHERE id = ?2",
+            params![SYNC_CAPABILITY_READ_WRITE, source_id],
+        )
+        .unwrap();
+
+        let start = Local.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let remote_event = Event::new("Remote title", start, start + Duration::hours(1)).unwrap();
+        let event_service = EventService::new(conn);
+        let stored = event_service.create(remote_event.clone()).unwrap();
+        let event_id = stored.id.unwrap();
+        conn.execute(
+            "INSERT INTO event_sync_map (source_id, external_uid, local_event_id)
+             VALUES (?1, 'uid-1', ?2)",
+            params![source_id, event_id],
+        )
+        .unwrap();
+
+        let mut local_event = remote_event.clone();
+        local_event.title = "Local title".to_string();
+        local_event.location = Some("Room 4".to_string());
+
+        let service = SyncConflictService::new(conn);
+        let mut conflict = build_conflict(source_id);
+        conflict.local_event_id = Some(event_id);
+        conflict.local_snapshot = Some(ConflictEventSnapshot::from(&local_event));
+        conflict.remote_snapshot = Some(ConflictEventSnapshot::from(&remote_event));
+        let created = service.upsert_open(&conflict).unwrap();
+
+        let reloaded = service.get_by_id(created.id.unwrap()).unwrap().unwrap();
+        assert!(reloaded.supports_field_merge());
+
+        let merged = merge_snapshots(
+            reloaded.local_snapshot.as_ref().unwrap(),
+            reloaded.remote_snapshot.as_ref().unwrap(),
+            &[
+                (ConflictField::Title, FieldChoice::Remote),
+                (ConflictField::Location, FieldChoice::Local),
+            ],
+        );
+        service
+            .resolve_with_merge(created.id.unwrap(), &merged)
+            .unwrap();
+
+        let saved = event_service.get(event_id).unwrap().unwrap();
+        assert_eq!(saved.title, "Remote title");
+        assert_eq!(saved.location.as_deref(), Some("Room 4"));
+
+        let (status, resolution): (String, String) = conn
+            .query_row(
+                "SELECT status, resolution FROM sync_conflicts WHERE id = ?1",
+                [created.id.unwrap()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(status, SYNC_CONFLICT_STATUS_RESOLVED);
+        assert_eq!(resolution, SYNC_CONFLICT_RESOLUTION_MERGED);
+
+        let pending: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM outbound_sync_operations
+                 WHERE source_id = ?1 AND external_uid = 'uid-1' AND status = 'pending'",
+                [source_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(pending, 1);
+    }
 }
@@ -1,3 +1,4 @@
+mod backup_scheduler;
 mod confirm;
 #[path = "app/context.rs"]
 mod context;
@@ -24,7 +25,7 @@ mod views;
 use self::confirm::ConfirmDialogState;
 use self::context::AppContext;
 use self::countdown::CountdownUiState;
-use self::state::{AppState, ViewType};
+use self::state::{AppState, ViewFilter, ViewType};
 use self::toast::ToastManager;
 use crate::models::settings::Settings;
 use crate::services::calendar_sync::scheduler::CalendarSyncScheduler;
@@ -63,8 +64,8 @@ pub struct CalendarApp {
     toast_manager: ToastManager,
     /// Confirmation dialog state
     confirm_dialog: ConfirmDialogState,
-    /// Active category filter (None = show all categories)
-    active_category_filter: Option<String>,
+    /// Active view filter (None = show all events)
+    active_view_filter: Option<ViewFilter>,
     /// When true, only show events imported via calendar sync mappings
     show_synced_events_only: bool,
     /// Optional selected calendar source when synced-only filtering is enabled
@@ -85,6 +86,14 @@ pub struct CalendarApp {
     >,
     /// True while a background scheduled sync run is active
     calendar_sync_in_progress: bool,
+    /// Receiver for an in-flight scheduled backup
+    backup_result_rx: Option<
+        std::sync::mpsc::Receiver<
+            Result<Option<crate::services::backup::policy::BackupRunReport>, String>,
+        >,
+    >,
+    /// Wall-clock time of the next backup policy check
+    backup_check_due_at: Option<std::time::Instant>,
     /// Undo/Redo manager for event operations
     undo_manager: UndoManager,
     /// Background scheduler for periodic calendar source sync
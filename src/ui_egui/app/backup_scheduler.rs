@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+use std::time::Duration as StdDuration;
+use std::time::Instant;
+use std::{sync::mpsc, thread};
+
+use chrono::Local;
+
+use super::CalendarApp;
+use crate::services::backup::policy::{BackupPolicy, BackupRunReport};
+
+impl CalendarApp {
+    /// Runs the backup policy in the background while the app is open.
+    pub(super) fn run_backup_scheduler(&mut self, ctx: &egui::Context) {
+        if let Some(rx) = &self.backup_result_rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.backup_result_rx = None;
+                    self.apply_backup_result(result);
+                }
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.backup_result_rx = None;
+                    log::error!("Scheduled backup worker disconnected");
+                }
+            }
+        }
+
+        let now = Instant::now();
+        if let Some(due_at) = self.backup_check_due_at {
+            if now < due_at {
+                ctx.request_repaint_after(due_at.duration_since(now));
+                return;
+            }
+        }
+
+        let policy = BackupPolicy::from_settings(&self.settings);
+        let db_path = PathBuf::from(self.context.database().path());
+        let (tx, rx) = mpsc::channel();
+        self.backup_result_rx = Some(rx);
+        // Re-armed with the policy's own wait once this run reports back.
+        self.backup_check_due_at = Some(now + StdDuration::from_secs(60));
+
+        thread::spawn(move || {
+            let result = policy
+                .run_if_due(&db_path, Local::now())
+                .map_err(|err| format!("{:#}", err));
+            let _ = tx.send(result);
+        });
+    }
+
+    fn apply_backup_result(&mut self, result: Result<Option<BackupRunReport>, String>) {
+        let policy = BackupPolicy::from_settings(&self.settings);
+        match result {
+            Ok(Some(report)) => {
+                log::info!(
+                    "Scheduled backup created and verified: {:?} (pruned {})",
+                    report.backup_path,
+                    report.removed
+                );
+                self.state.backup_manager_state.refresh_backups();
+                self.backup_check_due_at = Some(Instant::now() + policy.next_check_in());
+            }
+            Ok(None) => {
+                self.backup_check_due_at = Some(Instant::now() + policy.next_check_in());
+            }
+            Err(err) => {
+                log::error!("Scheduled backup failed: {}", err);
+                self.toast_manager
+                    .error(format!("Scheduled backup failed: {}", err));
+                // Retry later rather than on every frame.
+                self.backup_check_due_at = Some(Instant::now() + StdDuration::from_secs(15 * 60));
+            }
+        }
+    }
+
+    /// Picks up changed backup folder, interval or encryption after settings are saved.
+    pub(super) fn apply_backup_policy_settings(&mut self) {
+        let policy = BackupPolicy::from_settings(&self.settings);
+        self.state.backup_manager_state.set_policy(policy);
+        self.backup_check_due_at = None;
+    }
+}
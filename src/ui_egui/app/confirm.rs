@@ -6,7 +6,10 @@
 // Allow unused variants/methods - these are API surface for future use
 #![allow(dead_code)]
 
-use crate::ui_egui::commands::DeleteEventCommand;
+use crate::ui_egui::commands::{
+    CommandContext, DeleteCountdownCardCommand, DeleteEventCommand, DeleteEventsCommand,
+    DeleteOccurrenceCommand,
+};
 use egui::{Context, RichText};
 
 /// Types of confirmation dialogs
@@ -14,6 +17,8 @@ use egui::{Context, RichText};
 pub enum ConfirmAction {
     /// Delete an event by ID
     DeleteEvent { event_id: i64, event_title: String },
+    /// Delete several events at once (e.g. all search results)
+    DeleteEvents { event_ids: Vec<i64> },
     /// Delete a single occurrence of a recurring event
     DeleteEventOccurrence {
         event_id: i64,
@@ -38,6 +43,7 @@ impl ConfirmAction {
     pub fn title(&self) -> &'static str {
         match self {
             ConfirmAction::DeleteEvent { .. } => "Delete Event",
+            ConfirmAction::DeleteEvents { .. } => "Delete Events",
             ConfirmAction::DeleteEventOccurrence { .. } => "Delete Occurrence",
             ConfirmAction::DeleteCountdownCard { .. } => "Delete Countdown",
             ConfirmAction::DiscardChanges => "Discard Changes",
@@ -51,10 +57,16 @@ impl ConfirmAction {
         match self {
             ConfirmAction::DeleteEvent { event_title, .. } => {
                 format!(
-                    "Are you sure you want to delete \"{}\"?\n\nThis action cannot be undone.",
+                    "Are you sure you want to delete \"{}\"?\n\nYou can undo this from the Edit menu.",
                     event_title
                 )
             }
+            ConfirmAction::DeleteEvents { event_ids } => {
+                format!(
+                    "Are you sure you want to delete {} event(s)?\n\nYou can undo this from the Edit menu.",
+                    event_ids.len()
+                )
+            }
             ConfirmAction::DeleteEventOccurrence {
                 event_title,
                 occurrence_date,
@@ -67,7 +79,7 @@ impl ConfirmAction {
                 )
             }
             ConfirmAction::DeleteCountdownCard { card_title, .. } => {
-                format!("Are you sure you want to delete the countdown \"{}\"?\n\nThis action cannot be undone.", card_title)
+                format!("Are you sure you want to delete the countdown \"{}\"?\n\nYou can undo this from the Edit menu.", card_title)
             }
             ConfirmAction::DiscardChanges => {
                 "You have unsaved changes.\n\nAre you sure you want to discard them?".to_string()
@@ -86,6 +98,7 @@ impl ConfirmAction {
     pub fn confirm_text(&self) -> &'static str {
         match self {
             ConfirmAction::DeleteEvent { .. } => "Delete",
+            ConfirmAction::DeleteEvents { .. } => "Delete All",
             ConfirmAction::DeleteEventOccurrence { .. } => "Delete",
             ConfirmAction::DeleteCountdownCard { .. } => "Delete",
             ConfirmAction::DiscardChanges => "Discard",
@@ -98,6 +111,7 @@ impl ConfirmAction {
     pub fn is_destructive(&self) -> bool {
         match self {
             ConfirmAction::DeleteEvent { .. } => true,
+            ConfirmAction::DeleteEvents { .. } => true,
             ConfirmAction::DeleteEventOccurrence { .. } => true,
             ConfirmAction::DeleteCountdownCard { .. } => true,
             ConfirmAction::DiscardChanges => false,
@@ -274,12 +288,18 @@ impl CalendarApp {
                     return;
                 }
 
-                let event_service = self.context.event_service();
-
-                // Fetch the full event before deleting (for undo)
-                let event_for_undo = event_service.get(event_id).ok().flatten();
-
-                if let Err(e) = event_service.delete_local(event_id) {
+                let Some(event) = self.context.event_service().get(event_id).ok().flatten() else {
+                    self.toast_manager
+                        .error(format!("\"{}\" no longer exists", event_title));
+                    return;
+                };
+
+                // The command also removes (and on undo restores) linked countdown cards
+                let conn = self.context.database().connection();
+                let mut command_ctx =
+                    CommandContext::new(conn, self.context.countdown_service_mut());
+                let cmd = DeleteEventCommand::new(event);
+                if let Err(e) = self.undo_manager.execute(Box::new(cmd), &mut command_ctx) {
                     log::error!("Failed to delete event: {}", e);
                     self.toast_manager
                         .error(format!("Failed to delete event: {}", e));
@@ -287,17 +307,33 @@ impl CalendarApp {
                     log::info!("Deleted event: {} (ID: {})", event_title, event_id);
                     self.toast_manager
                         .success(format!("Deleted \"{}\"", event_title));
+                }
+            }
+            ConfirmAction::DeleteEvents { event_ids } => {
+                let event_service = self.context.event_service();
+                let events: Vec<_> = event_ids
+                    .into_iter()
+                    .filter(|id| !self.is_read_only_synced_event_id(*id))
+                    .filter_map(|id| event_service.get(id).ok().flatten())
+                    .collect();
+                if events.is_empty() {
+                    self.toast_manager.info("No editable events to delete");
+                    return;
+                }
 
-                    // Push delete command for undo capability
-                    if let Some(event) = event_for_undo {
-                        let cmd = DeleteEventCommand::new(event);
-                        self.undo_manager.push(Box::new(cmd));
-                    }
-
-                    // Also remove any linked countdown card
-                    self.context
-                        .countdown_service_mut()
-                        .remove_cards_for_event(event_id);
+                let count = events.len();
+                let conn = self.context.database().connection();
+                let mut command_ctx =
+                    CommandContext::new(conn, self.context.countdown_service_mut());
+                let cmd = DeleteEventsCommand::new(events);
+                if let Err(e) = self.undo_manager.execute(Box::new(cmd), &mut command_ctx) {
+                    log::error!("Failed to delete events: {}", e);
+                    self.toast_manager
+                        .error(format!("Failed to delete events: {}", e));
+                } else {
+                    log::info!("Deleted {} events", count);
+                    self.toast_manager
+                        .success(format!("Deleted {} event(s)", count));
                 }
             }
             ConfirmAction::DeleteEventOccurrence {
@@ -310,8 +346,17 @@ impl CalendarApp {
                     return;
                 }
 
-                let event_service = self.context.event_service();
-                if let Err(e) = event_service.delete_occurrence_local(event_id, occurrence_date) {
+                let Some(parent) = self.context.event_service().get(event_id).ok().flatten() else {
+                    self.toast_manager
+                        .error(format!("\"{}\" no longer exists", event_title));
+                    return;
+                };
+
+                let conn = self.context.database().connection();
+                let mut command_ctx =
+                    CommandContext::new(conn, self.context.countdown_service_mut());
+                let cmd = DeleteOccurrenceCommand::new(parent, occurrence_date);
+                if let Err(e) = self.undo_manager.execute(Box::new(cmd), &mut command_ctx) {
                     log::error!("Failed to delete occurrence: {}", e);
                     self.toast_manager
                         .error(format!("Failed to delete occurrence: {}", e));
@@ -330,7 +375,24 @@ impl CalendarApp {
                 card_id,
                 card_title,
             } => {
-                self.context.countdown_service_mut().remove_card(card_id);
+                let Some(card) = self
+                    .context
+                    .countdown_service()
+                    .cards()
+                    .iter()
+                    .find(|card| card.id == card_id)
+                    .cloned()
+                else {
+                    return;
+                };
+                let conn = self.context.database().connection();
+                let mut command_ctx =
+                    CommandContext::new(conn, self.context.countdown_service_mut());
+                let cmd = DeleteCountdownCardCommand::new(card);
+                if let Err(e) = self.undo_manager.execute(Box::new(cmd), &mut command_ctx) {
+                    log::error!("Failed to delete countdown card: {}", e);
+                    return;
+                }
                 log::info!("Deleted countdown card: {} (ID: {:?})", card_title, card_id);
                 self.toast_manager
                     .success(format!("Deleted countdown \"{}\"", card_title));
@@ -8,6 +8,7 @@ use crate::services::countdown::{
     CountdownCardState, CountdownCardVisuals, CountdownCategoryId, CountdownDisplayMode,
     CountdownService, LayoutOrientation,
 };
+use crate::ui_egui::commands::CountdownCardSettingsCommand;
 use chrono::Local;
 use egui::{self, Context};
 use std::collections::{HashMap, HashSet};
@@ -53,6 +54,8 @@ pub(in super::super) struct CountdownUiState {
     category_drag_states: HashMap<CountdownCategoryId, DragState>,
     // Pending delete requests from settings dialogs
     pub(super) pending_delete_requests: Vec<DeleteCardRequest>,
+    // Undo records for settings applied in settings dialogs
+    pub(super) pending_undo: Vec<CountdownCardSettingsCommand>,
     // Skip geometry updates for this many frames (used after reset)
     skip_geometry_frames: u32,
 }
@@ -149,6 +152,11 @@ impl CountdownUiState {
         std::mem::take(&mut self.pending_delete_requests)
     }
 
+    /// Drain undo records for settings applied in settings dialogs
+    pub(in super::super) fn drain_undo_commands(&mut self) -> Vec<CountdownCardSettingsCommand> {
+        std::mem::take(&mut self.pending_undo)
+    }
+
     pub(in super::super) fn mark_card_pending(
         &mut self,
         card_id: CountdownCardId,
@@ -4,6 +4,7 @@ use super::render::{
 use super::settings::{render_countdown_settings_ui, CountdownSettingsCommand};
 use super::state::{CountdownUiState, DeleteCardRequest};
 use crate::services::countdown::{CountdownCardGeometry, CountdownCardState, CountdownService};
+use crate::ui_egui::commands::CountdownCardSettingsCommand;
 use egui::Context;
 
 use super::super::geometry::{geometry_from_viewport_info, viewport_info};
@@ -107,8 +108,20 @@ impl CountdownUiState {
                     }
                     dialogs_to_close.push(id);
                 } else if should_apply {
-                    // Apply: discard snapshot, keep changes
-                    self.settings_snapshots.remove(&id);
+                    // Apply: keep changes and record them for undo
+                    if let (Some(before), Some(after)) = (
+                        self.settings_snapshots.remove(&id),
+                        service.snapshot_card_settings(id),
+                    ) {
+                        if before != after {
+                            self.pending_undo.push(CountdownCardSettingsCommand::new(
+                                id,
+                                card.effective_title().to_string(),
+                                before,
+                                after,
+                            ));
+                        }
+                    }
                     dialogs_to_close.push(id);
                 }
             } else {
@@ -1,22 +1,39 @@
 use super::countdown::OpenEventDialogRequest;
+use super::state::ViewFilter;
 use super::CalendarApp;
 use crate::models::event::Event;
+use crate::models::saved_search::SavedSearch;
 use crate::services::countdown::RgbaColor;
+use crate::services::event::queries::EventQuery;
+use crate::services::event::trash::TrashRestoreSync;
 use crate::services::event::EventService;
-use crate::ui_egui::commands::{CreateEventCommand, UpdateEventCommand};
+use crate::services::saved_search::SavedSearchService;
+use crate::ui_egui::commands::{CreateEventCommand, DetachOccurrenceCommand, UpdateEventCommand};
 use crate::ui_egui::dialogs::backup_manager::render_backup_manager_dialog;
 use crate::ui_egui::dialogs::category_manager::render_category_manager_dialog;
 use crate::ui_egui::dialogs::countdown_category_manager::render_countdown_category_manager_dialog;
 use crate::ui_egui::dialogs::countdown_template_manager::render_countdown_template_manager_dialog;
-use crate::ui_egui::dialogs::export_dialog::{render_export_range_dialog, ExportDialogResult};
+use crate::ui_egui::dialogs::csv_import_dialog::{render_csv_import_dialog, CsvImportAction};
+use crate::ui_egui::dialogs::export_dialog::{
+    render_export_range_dialog, ExportDialogResult, ExportFormat,
+};
+use crate::ui_egui::dialogs::import_preview_dialog::{
+    render_import_preview_dialog, ImportPreviewAction,
+};
+use crate::ui_egui::dialogs::publish_dialog::{render_publish_dialog, PublishDialogAction};
 use crate::ui_egui::dialogs::search_dialog::{render_search_dialog, SearchDialogAction};
+use crate::ui_egui::dialogs::secret_vault_dialog::{
+    render_secret_vault_dialog, SecretVaultDialogAction,
+};
 use crate::ui_egui::dialogs::template_manager::render_template_manager_dialog;
+use crate::ui_egui::dialogs::trash_dialog::{render_trash_dialog, TrashDialogAction};
+use crate::ui_egui::dialogs::undo_history_dialog::{render_undo_history_dialog, UndoHistoryAction};
 use crate::ui_egui::event_dialog::{
     render_event_dialog, CountdownCardChanges, EventDialogResult, EventDialogState,
 };
 use crate::ui_egui::settings_dialog::render_settings_dialog;
 use crate::ui_egui::views::CountdownRequest;
-use chrono::{Local, NaiveDateTime, TimeZone};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
 
 mod theme_handling;
 
@@ -42,9 +59,15 @@ impl CalendarApp {
         self.render_about_dialog(ctx);
         self.render_export_range_dialog(ctx);
         self.render_template_manager_dialog(ctx);
+        self.render_trash_dialog(ctx);
+        self.render_undo_history_dialog(ctx);
+        self.render_import_preview_dialog(ctx);
+        self.render_csv_import_dialog(ctx);
+        self.render_publish_dialog(ctx);
         self.render_category_manager_dialog(ctx);
         self.render_countdown_category_manager_dialog(ctx);
         self.render_countdown_template_manager_dialog(ctx);
+        self.render_secret_vault_dialog(ctx);
 
         let should_reload_db =
             render_backup_manager_dialog(ctx, &mut self.state.backup_manager_state);
@@ -57,6 +80,27 @@ impl CalendarApp {
         }
     }
 
+    fn render_secret_vault_dialog(&mut self, ctx: &egui::Context) {
+        if !self.state.secret_vault_dialog_state.is_needed() {
+            return;
+        }
+
+        match render_secret_vault_dialog(
+            ctx,
+            &mut self.state.secret_vault_dialog_state,
+            self.context.database(),
+        ) {
+            SecretVaultDialogAction::None => {}
+            SecretVaultDialogAction::Unlocked(message) => {
+                self.toast_manager.success(message);
+            }
+            SecretVaultDialogAction::Dismissed => {
+                self.toast_manager
+                    .info("Saved credentials are locked; calendar sync is paused");
+            }
+        }
+    }
+
     fn ensure_event_dialog_state(&mut self) {
         if self.event_dialog_state.is_some() {
             return;
@@ -135,6 +179,28 @@ impl CalendarApp {
             }
         };
 
+        // Editing one occurrence detaches it from its series; keep the series
+        // as it was so undo can put the occurrence back
+        let occurrence_parent: Option<(Event, DateTime<Local>)> = {
+            let state = self
+                .event_dialog_state
+                .as_ref()
+                .expect("dialog state just checked");
+            match (
+                state.detached_occurrence_parent_id,
+                state.detached_occurrence_date,
+            ) {
+                (Some(parent_id), Some(date)) => self
+                    .context
+                    .event_service()
+                    .get(parent_id)
+                    .ok()
+                    .flatten()
+                    .map(|parent| (parent, date)),
+                _ => None,
+            }
+        };
+
         let (
             saved_event,
             card_changes,
@@ -212,7 +278,10 @@ impl CalendarApp {
 
         if let Some(ref event) = saved_event {
             // Push undo command for the saved event
-            if was_new_event {
+            if let Some((parent, date)) = occurrence_parent {
+                let cmd = DetachOccurrenceCommand::new(parent, date, event.clone());
+                self.undo_manager.push(Box::new(cmd));
+            } else if was_new_event {
                 // New event created - push CreateEventCommand
                 let cmd = CreateEventCommand::new(event.clone());
                 self.undo_manager.push(Box::new(cmd));
@@ -359,6 +428,7 @@ impl CalendarApp {
         if response.saved {
             self.apply_theme_from_db(ctx);
             self.sync_tray_to_settings(ctx);
+            self.apply_backup_policy_settings();
         }
     }
 
@@ -407,6 +477,36 @@ impl CalendarApp {
                 }
                 self.state.show_search_dialog = false;
             }
+            SearchDialogAction::SaveSearch { name, query } => {
+                let service = SavedSearchService::new(self.context.database().connection());
+                match service.save(&SavedSearch::new(name, query)) {
+                    Ok(saved) => {
+                        self.toast_manager
+                            .success(format!("Saved search '{}'", saved.name));
+                    }
+                    Err(err) => {
+                        self.toast_manager
+                            .error(format!("Failed to save search: {:#}", err));
+                    }
+                }
+            }
+            SearchDialogAction::FilterViews(query) => match EventQuery::parse(&query) {
+                Ok(parsed) => {
+                    self.active_view_filter = Some(ViewFilter {
+                        label: query,
+                        query: parsed,
+                        saved_search_id: None,
+                    });
+                    self.state.show_search_dialog = false;
+                }
+                Err(err) => self.toast_manager.error(format!("{:#}", err)),
+            },
+            SearchDialogAction::DeleteResults(event_ids) => {
+                use super::confirm::ConfirmAction;
+                self.state.show_search_dialog = false;
+                self.confirm_dialog
+                    .request(ConfirmAction::DeleteEvents { event_ids });
+            }
             SearchDialogAction::Close => {
                 self.state.show_search_dialog = false;
             }
@@ -426,21 +526,136 @@ impl CalendarApp {
                 self.state.show_export_range_dialog = false;
                 self.state.export_dialog_state.reset();
             }
-            ExportDialogResult::Export { start, end } => {
+            ExportDialogResult::Export { start, end, format } => {
                 self.state.show_export_range_dialog = false;
-                self.export_events_in_range(start, end);
+                match format {
+                    ExportFormat::Events(format) => self.export_events_in_range(start, end, format),
+                    ExportFormat::Csv => self.export_occurrences_in_range_csv(start, end),
+                }
                 self.state.export_dialog_state.reset();
             }
         }
     }
 
+    fn render_undo_history_dialog(&mut self, ctx: &egui::Context) {
+        if !self.state.undo_history_dialog_state.is_open {
+            return;
+        }
+
+        let undo = self.undo_manager.undo_history();
+        let redo = self.undo_manager.redo_history();
+        match render_undo_history_dialog(
+            ctx,
+            &mut self.state.undo_history_dialog_state,
+            &undo,
+            &redo,
+        ) {
+            UndoHistoryAction::None => {}
+            UndoHistoryAction::Undo(steps) => {
+                for _ in 0..steps {
+                    if !self.perform_undo() {
+                        break;
+                    }
+                }
+            }
+            UndoHistoryAction::Redo(steps) => {
+                for _ in 0..steps {
+                    if !self.perform_redo() {
+                        break;
+                    }
+                }
+            }
+            UndoHistoryAction::Clear => {
+                self.undo_manager.clear();
+                self.toast_manager.info("Undo history cleared");
+            }
+        }
+    }
+
+    fn render_import_preview_dialog(&mut self, ctx: &egui::Context) {
+        if let ImportPreviewAction::Import(selection) =
+            render_import_preview_dialog(ctx, &mut self.state.import_preview_dialog_state)
+        {
+            self.import_selection(selection);
+        }
+    }
+
+    fn render_csv_import_dialog(&mut self, ctx: &egui::Context) {
+        if let CsvImportAction::Import {
+            events,
+            skipped,
+            source_label,
+        } = render_csv_import_dialog(ctx, &mut self.state.csv_import_dialog_state)
+        {
+            for row in &skipped {
+                log::warn!("Skipped CSV line {}: {}", row.line, row.message);
+            }
+            if !skipped.is_empty() {
+                self.toast_manager.warning(format!(
+                    "{} CSV row(s) could not be read and were skipped",
+                    skipped.len()
+                ));
+            }
+            self.handle_ics_import(events, &source_label);
+        }
+    }
+
+    fn render_publish_dialog(&mut self, ctx: &egui::Context) {
+        if let PublishDialogAction::Publish =
+            render_publish_dialog(ctx, &mut self.state.publish_dialog_state)
+        {
+            self.publish_html_site();
+        }
+    }
+
+    fn render_trash_dialog(&mut self, ctx: &egui::Context) {
+        let action = render_trash_dialog(
+            ctx,
+            &mut self.state.trash_dialog_state,
+            self.context.database(),
+            self.settings.trash_retention_days,
+        );
+
+        match action {
+            TrashDialogAction::None => {}
+            TrashDialogAction::Restored(restored) => {
+                let message = match restored.sync {
+                    TrashRestoreSync::NotSynced => format!("Restored \"{}\"", restored.event.title),
+                    TrashRestoreSync::Requeued => format!(
+                        "Restored \"{}\"; it will be synced back to its calendar",
+                        restored.event.title
+                    ),
+                    TrashRestoreSync::LocalOnly => format!(
+                        "Restored \"{}\" as a local event; its calendar no longer accepts changes",
+                        restored.event.title
+                    ),
+                };
+                if restored.sync == TrashRestoreSync::LocalOnly {
+                    self.toast_manager.warning(message);
+                } else {
+                    self.toast_manager.success(message);
+                }
+            }
+            TrashDialogAction::Purged(title) => {
+                self.toast_manager
+                    .info(format!("Permanently deleted \"{}\"", title));
+            }
+            TrashDialogAction::Emptied(count) => {
+                self.toast_manager
+                    .info(format!("Permanently deleted {} event(s)", count));
+            }
+        }
+    }
+
     fn render_template_manager_dialog(&mut self, ctx: &egui::Context) {
-        render_template_manager_dialog(
+        if let Some(cmd) = render_template_manager_dialog(
             ctx,
             &mut self.state.template_manager_state,
             self.context.database(),
             &self.settings,
-        );
+        ) {
+            self.undo_manager.push(Box::new(cmd));
+        }
     }
 
     fn render_category_manager_dialog(&mut self, ctx: &egui::Context) {
@@ -454,6 +669,9 @@ impl CalendarApp {
             // Categories were modified - could refresh event display if needed
             log::info!("Categories changed");
         }
+        if let Some(cmd) = response.undo_command {
+            self.undo_manager.push(Box::new(cmd));
+        }
     }
 
     fn render_countdown_category_manager_dialog(&mut self, ctx: &egui::Context) {
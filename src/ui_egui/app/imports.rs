@@ -1,6 +1,13 @@
 use super::CalendarApp;
+use crate::models::category::Category;
 use crate::models::event::Event;
+use crate::services::category::CategoryService;
 use crate::services::countdown::{CountdownCardGeometry, RgbaColor};
+use crate::services::event::history::ChangeOrigin;
+use crate::services::event::ics_import::IcsImportOutcome;
+use crate::services::icalendar::import::ImportedIcsEvent;
+use crate::ui_egui::commands::{CategoryCommand, ImportEventsCommand};
+use crate::ui_egui::dialogs::import_preview_dialog::ImportSelection;
 use chrono::Local;
 
 impl CalendarApp {
@@ -18,30 +25,22 @@ impl CalendarApp {
             return;
         }
 
+        use crate::services::icalendar::ICalendarService;
+        let ics_service = ICalendarService::new();
+
         for path in dropped_files {
             match std::fs::read_to_string(&path) {
-                Ok(ics_content) => {
-                    if !(ics_content.contains("BEGIN:VCALENDAR")
-                        || ics_content.contains("BEGIN:VEVENT"))
-                    {
-                        log::warn!(
-                            "Dropped file {:?} does not look like an iCalendar file",
-                            path
-                        );
-                        continue;
+                Ok(content) => match ics_service.import_any_format(&content) {
+                    Some(Ok(events)) => {
+                        self.handle_ics_import(events, "drag-and-drop");
                     }
-
-                    use crate::services::icalendar::import;
-
-                    match import::from_str(&ics_content) {
-                        Ok(events) => {
-                            self.handle_ics_import(events, "drag-and-drop");
-                        }
-                        Err(e) => {
-                            log::error!("Failed to parse dropped ICS file {:?}: {}", path, e);
-                        }
+                    Some(Err(e)) => {
+                        log::error!("Failed to parse dropped calendar file {:?}: {:#}", path, e);
                     }
-                }
+                    None => {
+                        log::warn!("Dropped file {:?} does not look like a calendar file", path);
+                    }
+                },
                 Err(e) => {
                     log::error!("Failed to read dropped file {:?}: {}", path, e);
                 }
@@ -49,113 +48,161 @@ impl CalendarApp {
         }
     }
 
-    pub(super) fn handle_ics_import(&mut self, events: Vec<Event>, source_label: &str) {
+    pub(super) fn handle_ics_import(&mut self, events: Vec<ImportedIcsEvent>, source_label: &str) {
         if events.is_empty() {
             log::info!("No events found in {} import", source_label);
             return;
         }
 
-        let mut existing_events = self
-            .context
-            .event_service()
-            .list_all()
-            .unwrap_or_else(|err| {
-                log::error!(
-                    "Failed to list existing events before {} import: {}",
-                    source_label,
-                    err
-                );
-                Vec::new()
-            });
+        // Files with several events go through the preview so the user can
+        // pick events and map categories before anything is written.
+        if events.len() > 1 {
+            if self.state.import_preview_dialog_state.is_open {
+                self.toast_manager
+                    .warning("Finish the open import before importing another file");
+                return;
+            }
+            self.state.import_preview_dialog_state.open(
+                self.context.database(),
+                events,
+                source_label,
+            );
+            return;
+        }
 
         if self.settings.edit_before_import {
-            let first_event = events[0].clone();
-            let remaining = events.len().saturating_sub(1);
+            let first_event = &events[0];
 
-            if Self::is_duplicate_event(&existing_events, &first_event) {
-                log::info!(
-                    "Skipping duplicate event (edit mode) from {}: '{}'",
-                    source_label,
-                    first_event.title
-                );
-            } else {
-                match self
-                    .context
-                    .event_service()
-                    .create_local(first_event.clone())
-                {
-                    Ok(created_event) => {
-                        self.focus_on_event(&created_event);
-                        if let Some(event_id) = created_event.id {
-                            self.event_to_edit = Some(event_id);
-                            self.show_event_dialog = true;
-                            log::info!(
-                                "Opening event '{}' for editing from {}",
-                                created_event.title,
-                                source_label
-                            );
+            match self
+                .context
+                .event_service()
+                .with_origin(ChangeOrigin::Import)
+                .import_ics_event(first_event)
+            {
+                Ok(IcsImportOutcome::Created(created_event)) => {
+                    self.focus_on_event(&created_event);
+                    if let Some(event_id) = created_event.id {
+                        self.event_to_edit = Some(event_id);
+                        self.show_event_dialog = true;
+                        log::info!(
+                            "Opening event '{}' for editing from {}",
+                            created_event.title,
+                            source_label
+                        );
 
-                            // Also create countdown card if enabled and event is in the future
-                            log::info!(
-                                "[COUNTDOWN DEBUG] edit_before_import path: checking auto_create. setting={}, event_start={}, now={}, is_future={}",
-                                self.settings.auto_create_countdown_on_import,
-                                created_event.start,
-                                Local::now(),
-                                created_event.start > Local::now()
-                            );
-                            if self.settings.auto_create_countdown_on_import
-                                && created_event.start > Local::now()
-                            {
-                                log::info!("[COUNTDOWN DEBUG] edit_before_import path: CALLING create_countdown_card_for_event");
-                                self.create_countdown_card_for_event(&created_event);
-                            } else {
-                                log::info!("[COUNTDOWN DEBUG] edit_before_import path: NOT creating card (conditions not met)");
-                            }
+                        // Also create countdown card if enabled and event is in the future
+                        log::info!(
+                            "[COUNTDOWN DEBUG] edit_before_import path: checking auto_create. setting={}, event_start={}, now={}, is_future={}",
+                            self.settings.auto_create_countdown_on_import,
+                            created_event.start,
+                            Local::now(),
+                            created_event.start > Local::now()
+                        );
+                        if self.settings.auto_create_countdown_on_import
+                            && created_event.start > Local::now()
+                        {
+                            log::info!("[COUNTDOWN DEBUG] edit_before_import path: CALLING create_countdown_card_for_event");
+                            self.create_countdown_card_for_event(&created_event);
+                        } else {
+                            log::info!("[COUNTDOWN DEBUG] edit_before_import path: NOT creating card (conditions not met)");
                         }
-                        existing_events.push(created_event);
                     }
-                    Err(err) => {
-                        log::error!(
-                            "Failed to create event for editing from {}: {}",
-                            source_label,
-                            err
+                    self.undo_manager.push(Box::new(ImportEventsCommand::new(
+                        vec![created_event],
+                        source_label,
+                    )));
+                }
+                Ok(IcsImportOutcome::Updated { before, after }) => {
+                    self.focus_on_event(&after);
+                    if let Some(event_id) = after.id {
+                        self.event_to_edit = Some(event_id);
+                        self.show_event_dialog = true;
+                        log::info!(
+                            "Updated event '{}' from {}; opening it for editing",
+                            after.title,
+                            source_label
                         );
                     }
+                    self.undo_manager.push(Box::new(
+                        ImportEventsCommand::new(Vec::new(), source_label)
+                            .with_updates(vec![(*before, after)]),
+                    ));
+                }
+                Ok(IcsImportOutcome::Unchanged(existing)) => {
+                    log::info!(
+                        "Skipping unchanged event (edit mode) from {}: '{}'",
+                        source_label,
+                        existing.title
+                    );
+                    self.focus_on_event(&existing);
+                    self.toast_manager
+                        .info(format!("'{}' is already up to date", existing.title));
+                }
+                Err(err) => {
+                    log::error!(
+                        "Failed to import event for editing from {}: {}",
+                        source_label,
+                        err
+                    );
+                    self.toast_manager.error("Failed to import event");
                 }
-            }
-
-            if remaining > 0 {
-                log::info!(
-                    "Note: Only the first event was opened for editing from {}. {} other event(s) were not imported.",
-                    source_label,
-                    remaining
-                );
             }
 
             return;
         }
 
-        let mut imported_count = 0;
-        let mut failed_count = 0;
-        let mut duplicate_count = 0;
-
-        for event in events {
-            let event_title = event.title.clone();
+        self.import_events(&events, Vec::new(), source_label);
+    }
 
-            if Self::is_duplicate_event(&existing_events, &event) {
-                log::info!(
-                    "Skipping duplicate event from {}: '{}'",
-                    source_label,
-                    event_title
-                );
-                duplicate_count += 1;
-                continue;
+    /// Import the events confirmed in the preview dialog, creating the
+    /// categories the user chose to add first.
+    pub(super) fn import_selection(&mut self, mut selection: ImportSelection) {
+        let category_service = CategoryService::new(self.context.database().connection());
+        let mut created_categories = Vec::new();
+        for name in selection.categories_to_create() {
+            match category_service.get_by_name(&name) {
+                Ok(Some(_)) => {}
+                Ok(None) => match category_service.create(Category::new(&name, "#3B82F6")) {
+                    Ok(category) => created_categories.push(CategoryCommand::created(category)),
+                    Err(e) => {
+                        log::error!("Failed to create category '{}' on import: {}", name, e);
+                        self.toast_manager
+                            .error(format!("Failed to create category \"{}\"", name));
+                    }
+                },
+                Err(e) => log::error!("Failed to look up category '{}': {}", name, e),
             }
+        }
+
+        selection.apply_category_mappings();
+        self.import_events(
+            &selection.events,
+            created_categories,
+            &selection.source_label,
+        );
+    }
+
+    /// Import `events` as one undoable batch and report what changed.
+    fn import_events(
+        &mut self,
+        events: &[ImportedIcsEvent],
+        created_categories: Vec<CategoryCommand>,
+        source_label: &str,
+    ) {
+        let mut failed_count = 0;
+        let mut unchanged_count = 0;
+        let mut imported_events = Vec::new();
+        let mut updated_events = Vec::new();
 
-            match self.context.event_service().create_local(event) {
-                Ok(created_event) => {
+        for imported in events {
+            match self
+                .context
+                .event_service()
+                .with_origin(ChangeOrigin::Import)
+                .import_ics_event(imported)
+            {
+                Ok(IcsImportOutcome::Created(created_event)) => {
                     self.focus_on_event(&created_event);
-                    imported_count += 1;
 
                     // Auto-create countdown card if enabled and event is in the future
                     log::info!(
@@ -174,12 +221,29 @@ impl CalendarApp {
                         log::info!("[COUNTDOWN DEBUG] batch import path: NOT creating card (conditions not met)");
                     }
 
-                    existing_events.push(created_event.clone());
+                    imported_events.push(created_event);
+                }
+                Ok(IcsImportOutcome::Updated { before, after }) => {
+                    log::info!(
+                        "Updated event '{}' from {} (newer SEQUENCE or LAST-MODIFIED)",
+                        after.title,
+                        source_label
+                    );
+                    self.focus_on_event(&after);
+                    updated_events.push((*before, after));
+                }
+                Ok(IcsImportOutcome::Unchanged(existing)) => {
+                    log::info!(
+                        "Skipping unchanged event from {}: '{}'",
+                        source_label,
+                        existing.title
+                    );
+                    unchanged_count += 1;
                 }
                 Err(err) => {
                     log::error!(
                         "Failed to import event '{}' from {}: {}",
-                        event_title,
+                        imported.event.title,
                         source_label,
                         err
                     );
@@ -188,30 +252,35 @@ impl CalendarApp {
             }
         }
 
-        if duplicate_count > 0 {
-            log::info!(
-                "{} import complete: {} events imported, {} duplicates skipped, {} failed",
-                source_label,
-                imported_count,
-                duplicate_count,
-                failed_count
-            );
-        } else {
-            log::info!(
-                "{} import complete: {} events imported, {} failed",
-                source_label,
-                imported_count,
-                failed_count
-            );
+        let imported_count = imported_events.len();
+        let updated_count = updated_events.len();
+        if imported_count + updated_count > 0 || !created_categories.is_empty() {
+            self.undo_manager.push(Box::new(
+                ImportEventsCommand::new(imported_events, source_label)
+                    .with_updates(updated_events)
+                    .with_categories(created_categories),
+            ));
         }
-    }
 
-    fn is_duplicate_event(existing_events: &[Event], candidate: &Event) -> bool {
-        existing_events.iter().any(|event| {
-            event.title == candidate.title
-                && event.start == candidate.start
-                && event.end == candidate.end
-        })
+        log::info!(
+            "{} import complete: {} events imported, {} updated, {} unchanged, {} failed",
+            source_label,
+            imported_count,
+            updated_count,
+            unchanged_count,
+            failed_count
+        );
+
+        let summary = format!(
+            "Imported {} new, updated {}, skipped {} unchanged",
+            imported_count, updated_count, unchanged_count
+        );
+        if failed_count > 0 {
+            self.toast_manager
+                .warning(format!("{} ({} failed)", summary, failed_count));
+        } else {
+            self.toast_manager.success(summary);
+        }
     }
 
     /// Creates a countdown card for the given event and marks it for display.
@@ -5,10 +5,12 @@ use super::state::{AppState, ViewType};
 use super::toast::ToastManager;
 use super::CalendarApp;
 use crate::models::settings::Settings;
-use crate::services::backup::BackupService;
+use crate::services::backup::policy::BackupPolicy;
 use crate::services::countdown::CountdownService;
-use crate::services::database::Database;
+use crate::services::database::{Database, SchemaTooNewError};
+use crate::services::event::EventService;
 use crate::services::notification::NotificationService;
+use crate::services::secrets;
 use crate::services::settings::SettingsService;
 use crate::ui_egui::commands::UndoManager;
 use crate::ui_egui::dialogs::backup_manager::BackupManagerState;
@@ -34,6 +36,8 @@ impl CalendarApp {
             settings.default_card_height
         );
 
+        purge_expired_trash(database, settings.trash_retention_days);
+
         let current_view = Self::parse_view_type(&settings.current_view);
         let countdown_storage_path = Self::resolve_countdown_storage_path();
         cc.egui_ctx.set_embed_viewports(false);
@@ -52,7 +56,10 @@ impl CalendarApp {
             notification_service,
         );
 
-        let backup_manager_state = BackupManagerState::new(resolve_backup_db_path());
+        let backup_manager_state = BackupManagerState::new(
+            resolve_backup_db_path(),
+            BackupPolicy::from_settings(&settings),
+        );
         let show_ribbon = settings.show_ribbon;
         let sync_startup_delay = Duration::minutes(settings.sync_startup_delay_minutes.max(0));
 
@@ -75,7 +82,7 @@ impl CalendarApp {
             state: AppState::new(backup_manager_state, pending_root_geometry),
             toast_manager: ToastManager::new(),
             confirm_dialog: ConfirmDialogState::new(),
-            active_category_filter: None,
+            active_view_filter: None,
             show_synced_events_only: false,
             selected_synced_source_id: None,
             calendar_sync_status_message: None,
@@ -84,7 +91,9 @@ impl CalendarApp {
             calendar_sync_poll_due_at: None,
             calendar_sync_result_rx: None,
             calendar_sync_in_progress: false,
-            undo_manager: UndoManager::new(),
+            backup_result_rx: None,
+            backup_check_due_at: None,
+            undo_manager: UndoManager::load(database.connection()),
             calendar_sync_scheduler: Arc::new(Mutex::new(
                 crate::services::calendar_sync::scheduler::CalendarSyncScheduler::with_startup_delay(
                     sync_startup_delay,
@@ -230,6 +239,9 @@ impl CalendarApp {
             if self.settings.show_countdown_cards {
                 self.countdown_ui
                     .render_settings_dialogs(ctx, self.context.countdown_service_mut());
+                for cmd in self.countdown_ui.drain_undo_commands() {
+                    self.undo_manager.push(Box::new(cmd));
+                }
 
                 for request in self.countdown_ui.drain_delete_requests() {
                     self.restore_from_tray(ctx);
@@ -335,6 +347,9 @@ impl CalendarApp {
         if self.settings.show_countdown_cards {
             self.countdown_ui
                 .render_settings_dialogs(ctx, self.context.countdown_service_mut());
+            for cmd in self.countdown_ui.drain_undo_commands() {
+                self.undo_manager.push(Box::new(cmd));
+            }
 
             // Handle delete requests from settings dialogs
             for request in self.countdown_ui.drain_delete_requests() {
@@ -359,6 +374,7 @@ impl CalendarApp {
         // Refresh countdown timers and check for notifications
         self.refresh_countdowns(ctx);
         self.run_calendar_sync_scheduler(ctx);
+        self.run_backup_scheduler(ctx);
         self.check_and_show_countdown_notifications(ctx);
 
         self.persist_countdowns_if_needed();
@@ -392,13 +408,31 @@ fn initialize_database() -> &'static Database {
     };
 
     let db = Database::new(&db_path).expect("Failed to create database connection");
-    db.initialize_schema()
-        .expect("Failed to initialize database schema");
+    if let Err(err) = db.initialize_schema() {
+        if let Some(too_new) = err.downcast_ref::<SchemaTooNewError>() {
+            log::error!("{}", too_new);
+            rfd::MessageDialog::new()
+                .set_level(rfd::MessageLevel::Error)
+                .set_title("Rust Calendar needs an update")
+                .set_description(too_new.to_string())
+                .set_buttons(rfd::MessageButtons::Ok)
+                .show();
+            std::process::exit(1);
+        }
+        panic!("Failed to initialize database schema: {:#}", err);
+    }
 
-    if let Err(e) = BackupService::auto_backup_on_startup(std::path::Path::new(&db_path), Some(5)) {
-        log::warn!("Failed to create automatic backup on startup: {}", e);
-    } else {
-        log::info!("Automatic backup created successfully");
+    let data_dir = Path::new(&db_path)
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let secret_store = secrets::open_default_store(data_dir);
+    log::info!("Using {} for credentials", secret_store.backend_name());
+    secrets::install(secret_store);
+    match secrets::migrate_plaintext_secrets(db.connection()) {
+        Ok(0) => {}
+        Ok(moved) => log::info!("Moved {} stored credentials into the secret store", moved),
+        Err(e) => log::warn!("Failed to move credentials into the secret store: {}", e),
     }
 
     Box::leak(Box::new(db))
@@ -414,6 +448,15 @@ fn load_settings_or_default(settings_service: &SettingsService) -> Settings {
     }
 }
 
+fn purge_expired_trash(database: &Database, retention_days: i64) {
+    let service = EventService::new(database.connection());
+    match service.purge_expired_trash(retention_days, Local::now()) {
+        Ok(0) => {}
+        Ok(count) => log::info!("Purged {} expired event(s) from the trash", count),
+        Err(e) => log::warn!("Failed to purge expired trash: {}", e),
+    }
+}
+
 fn load_countdown_service(path: &Path, database: &Database) -> CountdownService {
     // First, try to migrate from JSON to database if JSON file exists
     if path.exists() {
@@ -1,8 +1,12 @@
+use super::state::ViewFilter;
 use super::CalendarApp;
+use crate::models::saved_search::SavedSearch;
 use crate::services::countdown::CountdownDisplayMode;
+use crate::services::saved_search::SavedSearchService;
 use crate::services::template::TemplateService;
+use crate::ui_egui::commands::CommandContext;
 use crate::ui_egui::event_dialog::EventDialogState;
-use egui::Context;
+use egui::{Context, RichText};
 
 impl CalendarApp {
     pub(super) fn render_menu_bar(&mut self, ctx: &Context) {
@@ -24,6 +28,10 @@ impl CalendarApp {
                 self.import_events_ics();
                 ui.close_menu();
             }
+            if ui.button("📥 Import Events from CSV...").clicked() {
+                self.import_events_csv();
+                ui.close_menu();
+            }
             if ui.button("📥 Import Countdown Layout...").clicked() {
                 self.import_countdown_layout();
                 ui.close_menu();
@@ -33,8 +41,8 @@ impl CalendarApp {
 
             // --- Export ---
             ui.menu_button("📤 Export Events", |ui| {
-                if let Some(category) = &self.active_category_filter.clone() {
-                    let label = format!("Export '{}' Events...", category);
+                if let Some(filter) = &self.active_view_filter.clone() {
+                    let label = format!("Export '{}' Events...", filter.label);
                     if ui.button(&label).clicked() {
                         self.export_filtered_events_ics();
                         ui.close_menu();
@@ -68,6 +76,11 @@ impl CalendarApp {
                 }
             });
 
+            if ui.button("🌐 Publish as Web Pages...").clicked() {
+                self.open_publish_dialog();
+                ui.close_menu();
+            }
+
             if ui.button("📤 Export Countdown Layout...").clicked() {
                 self.export_countdown_layout();
                 ui.close_menu();
@@ -126,6 +139,20 @@ impl CalendarApp {
                 }
             });
 
+            if ui.button("📜 Undo History...").clicked() {
+                self.state.undo_history_dialog_state.open();
+                ui.close_menu();
+            }
+
+            ui.separator();
+
+            if ui.button("🗑 Trash...").clicked() {
+                self.state
+                    .trash_dialog_state
+                    .open(self.context.database(), self.settings.trash_retention_days);
+                ui.close_menu();
+            }
+
             ui.separator();
 
             if ui.button("⚙ Settings    Ctrl+S").clicked() {
@@ -160,36 +187,44 @@ impl CalendarApp {
         });
     }
 
-    /// Perform undo operation
-    pub(super) fn perform_undo(&mut self) {
-        let event_service = self.context.event_service();
-        match self.undo_manager.undo(&event_service) {
+    /// Perform undo operation, returning whether a command was undone
+    pub(super) fn perform_undo(&mut self) -> bool {
+        let conn = self.context.database().connection();
+        let mut ctx = CommandContext::new(conn, self.context.countdown_service_mut());
+        match self.undo_manager.undo(&mut ctx) {
             Ok(Some(desc)) => {
                 self.toast_manager.info(format!("Undone: {}", desc));
+                true
             }
             Ok(None) => {
                 // Nothing to undo
+                false
             }
             Err(e) => {
                 log::error!("Undo failed: {}", e);
                 self.toast_manager.error(format!("Undo failed: {}", e));
+                false
             }
         }
     }
 
-    /// Perform redo operation
-    pub(super) fn perform_redo(&mut self) {
-        let event_service = self.context.event_service();
-        match self.undo_manager.redo(&event_service) {
+    /// Perform redo operation, returning whether a command was redone
+    pub(super) fn perform_redo(&mut self) -> bool {
+        let conn = self.context.database().connection();
+        let mut ctx = CommandContext::new(conn, self.context.countdown_service_mut());
+        match self.undo_manager.redo(&mut ctx) {
             Ok(Some(desc)) => {
                 self.toast_manager.info(format!("Redone: {}", desc));
+                true
             }
             Ok(None) => {
                 // Nothing to redo
+                false
             }
             Err(e) => {
                 log::error!("Redo failed: {}", e);
                 self.toast_manager.error(format!("Redo failed: {}", e));
+                false
             }
         }
     }
@@ -299,8 +334,8 @@ impl CalendarApp {
 
             ui.separator();
 
-            // Category filter submenu
-            self.render_category_filter_submenu(ui);
+            // View filter submenu
+            self.render_view_filter_submenu(ui);
 
             ui.separator();
 
@@ -376,27 +411,26 @@ impl CalendarApp {
         });
     }
 
-    fn render_category_filter_submenu(&mut self, ui: &mut egui::Ui) {
-        ui.menu_button("📂 Filter by Category", |ui| {
-            // Get all categories
+    fn render_view_filter_submenu(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button("📂 Filter Events", |ui| {
             let categories = self
                 .context
                 .category_service()
                 .list_all()
                 .unwrap_or_default();
+            let saved_searches = SavedSearchService::new(self.context.database().connection())
+                .list_all()
+                .unwrap_or_default();
 
-            // "All Categories" option
-            let is_all_selected = self.active_category_filter.is_none();
-            if ui
-                .selectable_label(is_all_selected, "All Categories")
-                .clicked()
-            {
-                self.active_category_filter = None;
+            let is_all_selected = self.active_view_filter.is_none();
+            if ui.selectable_label(is_all_selected, "All Events").clicked() {
+                self.active_view_filter = None;
                 ui.close_menu();
             }
 
             if !categories.is_empty() {
                 ui.separator();
+                ui.label(RichText::new("Categories").small().weak());
 
                 for category in &categories {
                     let label = if let Some(icon) = &category.icon {
@@ -405,9 +439,32 @@ impl CalendarApp {
                         category.name.clone()
                     };
 
-                    let is_selected = self.active_category_filter.as_ref() == Some(&category.name);
+                    let is_selected = self
+                        .active_view_filter
+                        .as_ref()
+                        .is_some_and(|filter| filter.is_category(&category.name));
                     if ui.selectable_label(is_selected, label).clicked() {
-                        self.active_category_filter = Some(category.name.clone());
+                        self.active_view_filter = Some(ViewFilter::for_category(&category.name));
+                        ui.close_menu();
+                    }
+                }
+            }
+
+            if !saved_searches.is_empty() {
+                ui.separator();
+                ui.label(RichText::new("Saved Searches").small().weak());
+
+                for search in &saved_searches {
+                    let is_selected = self
+                        .active_view_filter
+                        .as_ref()
+                        .is_some_and(|filter| filter.saved_search_id == search.id);
+                    if ui
+                        .selectable_label(is_selected, format!("🔎 {}", search.name))
+                        .on_hover_text(&search.query)
+                        .clicked()
+                    {
+                        self.apply_saved_search_filter(search);
                         ui.close_menu();
                     }
                 }
@@ -415,6 +472,17 @@ impl CalendarApp {
         });
     }
 
+    /// Filter every view by a saved search.
+    pub(super) fn apply_saved_search_filter(&mut self, search: &SavedSearch) {
+        match ViewFilter::from_saved_search(search) {
+            Ok(filter) => self.active_view_filter = Some(filter),
+            Err(err) => self.toast_manager.error(format!(
+                "Saved search '{}' is invalid: {}",
+                search.name, err
+            )),
+        }
+    }
+
     fn set_countdown_cards_visible(&mut self, ctx: &Context, visible: bool) {
         let previous = self.settings.show_countdown_cards;
         if previous == visible {
@@ -1,11 +1,15 @@
 use super::CalendarApp;
+use crate::models::category::Category;
 use crate::services::event::EventService;
+use crate::services::icalendar::EventFileFormat;
 use crate::services::pdf::{service::PdfExportOptions, PdfExportService};
 use chrono::Datelike;
+use std::path::{Path, PathBuf};
 
-/// Export and import menu functions (PDF, ICS, countdown layout).
+/// Export and import menu functions (PDF, ICS, jCal, JSON, CSV, HTML, countdown
+/// layout).
 impl CalendarApp {
-    pub(super) fn export_month_to_pdf(&self) {
+    pub(super) fn export_month_to_pdf(&mut self) {
         let date = self.current_date;
         let month_name = date.format("%B_%Y").to_string();
 
@@ -18,24 +22,22 @@ impl CalendarApp {
             let event_service = EventService::new(self.context.database().connection());
             let options = PdfExportOptions {
                 title: format!("Calendar - {}", date.format("%B %Y")),
-                ..Default::default()
+                ..self.pdf_export_options()
             };
 
-            if let Err(e) = PdfExportService::export_month(
+            let result = PdfExportService::export_month(
                 &event_service,
+                &self.pdf_categories(),
                 date,
                 &path,
                 &options,
                 self.settings.first_day_of_week,
-            ) {
-                log::error!("Failed to export PDF: {}", e);
-            } else {
-                log::info!("Successfully exported month view to {:?}", path);
-            }
+            );
+            self.report_pdf_export(result, &path);
         }
     }
 
-    pub(super) fn export_week_to_pdf(&self) {
+    pub(super) fn export_week_to_pdf(&mut self) {
         let date = self.current_date;
         let week_num = date.iso_week().week();
 
@@ -48,24 +50,22 @@ impl CalendarApp {
             let event_service = EventService::new(self.context.database().connection());
             let options = PdfExportOptions {
                 title: format!("Calendar - Week {}", week_num),
-                ..Default::default()
+                ..self.pdf_export_options()
             };
 
-            if let Err(e) = PdfExportService::export_week(
+            let result = PdfExportService::export_week(
                 &event_service,
+                &self.pdf_categories(),
                 date,
                 &path,
                 &options,
                 self.settings.first_day_of_week,
-            ) {
-                log::error!("Failed to export PDF: {}", e);
-            } else {
-                log::info!("Successfully exported week view to {:?}", path);
-            }
+            );
+            self.report_pdf_export(result, &path);
         }
     }
 
-    pub(super) fn export_events_to_pdf(&self) {
+    pub(super) fn export_events_to_pdf(&mut self) {
         if let Some(path) = rfd::FileDialog::new()
             .set_title("Export All Events to PDF")
             .set_file_name("calendar_events.pdf")
@@ -76,17 +76,45 @@ impl CalendarApp {
             let events = event_service.list_all().unwrap_or_default();
             let options = PdfExportOptions {
                 title: "Calendar Events".to_string(),
-                ..Default::default()
+                ..self.pdf_export_options()
             };
 
-            if let Err(e) = PdfExportService::export_event_list(&events, &path, &options) {
-                log::error!("Failed to export PDF: {}", e);
-            } else {
-                log::info!(
-                    "Successfully exported {} events to {:?}",
-                    events.len(),
-                    path
-                );
+            let result = PdfExportService::export_event_list(
+                &events,
+                &self.pdf_categories(),
+                &path,
+                &options,
+            );
+            self.report_pdf_export(result, &path);
+        }
+    }
+
+    /// PDF options carrying the font chosen in settings
+    fn pdf_export_options(&self) -> PdfExportOptions {
+        PdfExportOptions {
+            font_path: self.settings.pdf_font_path.as_ref().map(PathBuf::from),
+            ..Default::default()
+        }
+    }
+
+    fn pdf_categories(&self) -> Vec<Category> {
+        self.context
+            .category_service()
+            .list_all()
+            .unwrap_or_default()
+    }
+
+    fn report_pdf_export(&mut self, result: anyhow::Result<()>, path: &Path) {
+        match result {
+            Ok(()) => {
+                log::info!("Successfully exported PDF to {:?}", path);
+                self.toast_manager
+                    .success(format!("Exported PDF to {}", path.display()));
+            }
+            Err(e) => {
+                log::error!("Failed to export PDF: {:#}", e);
+                self.toast_manager
+                    .error(format!("Failed to export PDF: {}", e));
             }
         }
     }
@@ -131,19 +159,25 @@ impl CalendarApp {
         }
     }
 
-    /// Export filtered events (by category) to an .ics file
+    /// Export events matching the active view filter to an .ics file
     pub(super) fn export_filtered_events_ics(&mut self) {
-        let category = match &self.active_category_filter {
-            Some(cat) => cat.clone(),
-            None => {
-                // No filter active, fall back to export all
-                self.export_all_events_ics();
-                return;
-            }
+        let Some(filter) = self.active_view_filter.clone() else {
+            // No filter active, fall back to export all
+            self.export_all_events_ics();
+            return;
         };
+        let category = filter.label;
 
         let event_service = EventService::new(self.context.database().connection());
-        let all_events = match event_service.list_all() {
+        let events = match event_service
+            .matching_event_ids(&filter.query)
+            .and_then(|ids| {
+                Ok(event_service
+                    .list_all()?
+                    .into_iter()
+                    .filter(|event| event.id.is_some_and(|id| ids.contains(&id)))
+                    .collect::<Vec<_>>())
+            }) {
             Ok(events) => events,
             Err(e) => {
                 log::error!("Failed to load events for export: {}", e);
@@ -152,12 +186,6 @@ impl CalendarApp {
             }
         };
 
-        // Filter events by category
-        let events: Vec<_> = all_events
-            .into_iter()
-            .filter(|e| e.category.as_deref() == Some(&category))
-            .collect();
-
         if events.is_empty() {
             self.toast_manager
                 .warning(format!("No '{}' events to export", category));
@@ -196,11 +224,12 @@ impl CalendarApp {
         }
     }
 
-    /// Export events in a date range to an .ics file
+    /// Export events in a date range to an .ics, jCal or JSON file
     pub(super) fn export_events_in_range(
         &mut self,
         start: chrono::NaiveDate,
         end: chrono::NaiveDate,
+        format: EventFileFormat,
     ) {
         use chrono::{Local, NaiveTime, TimeZone};
 
@@ -228,21 +257,22 @@ impl CalendarApp {
         }
 
         let filename = format!(
-            "calendar_{}_{}.ics",
+            "calendar_{}_{}.{}",
             start.format("%Y%m%d"),
-            end.format("%Y%m%d")
+            end.format("%Y%m%d"),
+            format.extension()
         );
 
         if let Some(path) = rfd::FileDialog::new()
             .set_title("Export Events")
             .set_file_name(&filename)
-            .add_filter("iCalendar", &["ics"])
+            .add_filter(format.filter_name(), &[format.extension()])
             .save_file()
         {
             use crate::services::icalendar::ICalendarService;
             let ics_service = ICalendarService::new();
 
-            match ics_service.export_events_to_file(&events, &path) {
+            match ics_service.export_events_to_file_as(&events, format, &path) {
                 Ok(()) => {
                     log::info!("Exported {} events to {:?}", events.len(), path);
                     self.toast_manager
@@ -256,41 +286,98 @@ impl CalendarApp {
         }
     }
 
+    /// Export every occurrence in a date range to a .csv file, with dates in
+    /// the user's date format
+    pub(super) fn export_occurrences_in_range_csv(
+        &mut self,
+        start: chrono::NaiveDate,
+        end: chrono::NaiveDate,
+    ) {
+        use chrono::{Local, NaiveTime, TimeZone};
+
+        let start_dt = Local
+            .from_local_datetime(&start.and_time(NaiveTime::from_hms_opt(0, 0, 0).unwrap()))
+            .unwrap();
+        let end_dt = Local
+            .from_local_datetime(&end.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap()))
+            .unwrap();
+
+        let event_service = EventService::new(self.context.database().connection());
+        let occurrences = match event_service.expand_recurring_events(start_dt, end_dt) {
+            Ok(occurrences) => occurrences,
+            Err(e) => {
+                log::error!("Failed to load events for export: {}", e);
+                self.toast_manager.error("Failed to load events");
+                return;
+            }
+        };
+
+        if occurrences.is_empty() {
+            self.toast_manager.warning("No events in selected range");
+            return;
+        }
+
+        let filename = format!(
+            "calendar_{}_{}.csv",
+            start.format("%Y%m%d"),
+            end.format("%Y%m%d")
+        );
+
+        if let Some(path) = rfd::FileDialog::new()
+            .set_title("Export Events")
+            .set_file_name(&filename)
+            .add_filter("CSV", &["csv"])
+            .save_file()
+        {
+            use crate::services::csv::export;
+
+            match export::to_file(&occurrences, &self.settings.date_format, &path) {
+                Ok(()) => {
+                    log::info!("Exported {} occurrences to {:?}", occurrences.len(), path);
+                    self.toast_manager
+                        .success(format!("Exported {} events", occurrences.len()));
+                }
+                Err(e) => {
+                    log::error!("Failed to export events: {}", e);
+                    self.toast_manager.error("Failed to export events");
+                }
+            }
+        }
+    }
+
     // ========== ICS Import ==========
 
     /// Open a file dialog and import events from an .ics file.
     pub(super) fn import_events_ics(&mut self) {
         let paths = rfd::FileDialog::new()
             .set_title("Import Events")
+            .add_filter("Calendar files", &["ics", "jcal", "json"])
             .add_filter("iCalendar", &["ics"])
+            .add_filter("jCal or JSON", &["jcal", "json"])
             .pick_files();
 
         let Some(paths) = paths else { return };
 
+        use crate::services::icalendar::ICalendarService;
+        let ics_service = ICalendarService::new();
+
         for path in &paths {
             match std::fs::read_to_string(path) {
-                Ok(ics_content) => {
-                    if !(ics_content.contains("BEGIN:VCALENDAR")
-                        || ics_content.contains("BEGIN:VEVENT"))
-                    {
-                        log::warn!("File {:?} does not look like an iCalendar file", path);
+                Ok(content) => match ics_service.import_any_format(&content) {
+                    Some(Ok(events)) => {
+                        self.handle_ics_import(events, "file import");
+                    }
+                    Some(Err(e)) => {
+                        log::error!("Failed to parse calendar file {:?}: {:#}", path, e);
                         self.toast_manager
-                            .warning("Selected file is not a valid iCalendar file");
-                        continue;
+                            .error(format!("Failed to parse calendar file: {}", e));
                     }
-
-                    use crate::services::icalendar::import;
-
-                    match import::from_str(&ics_content) {
-                        Ok(events) => {
-                            self.handle_ics_import(events, "file import");
-                        }
-                        Err(e) => {
-                            log::error!("Failed to parse ICS file {:?}: {}", path, e);
-                            self.toast_manager.error("Failed to parse iCalendar file");
-                        }
+                    None => {
+                        log::warn!("File {:?} does not look like a calendar file", path);
+                        self.toast_manager
+                            .warning("Selected file is not an iCalendar, jCal or JSON file");
                     }
-                }
+                },
                 Err(e) => {
                     log::error!("Failed to read file {:?}: {}", path, e);
                     self.toast_manager.error("Failed to read selected file");
@@ -299,6 +386,122 @@ impl CalendarApp {
         }
     }
 
+    // ========== HTML Publishing ==========
+
+    /// Open the publish dialog with the current categories
+    pub(super) fn open_publish_dialog(&mut self) {
+        let categories = match self.context.category_service().list_all() {
+            Ok(categories) => categories,
+            Err(e) => {
+                log::error!("Failed to load categories for publishing: {}", e);
+                self.toast_manager.error("Failed to load categories");
+                return;
+            }
+        };
+        self.state.publish_dialog_state.open(&categories);
+    }
+
+    /// Publish the range chosen in the publish dialog as a static HTML site
+    pub(super) fn publish_html_site(&mut self) {
+        use crate::services::html::service::HtmlPublishOptions;
+        use crate::services::html::HtmlPublishService;
+
+        let dialog = &self.state.publish_dialog_state;
+        let options = HtmlPublishOptions {
+            title: Some(dialog.title.trim())
+                .filter(|title| !title.is_empty())
+                .unwrap_or("Calendar")
+                .to_string(),
+            start: dialog.start,
+            end: dialog.end,
+            categories: dialog.selected_categories(),
+            include_uncategorized: dialog.include_uncategorized,
+            first_day_of_week: self.settings.first_day_of_week,
+            use_24_hour: self.settings.time_format == "24h",
+            include_descriptions: dialog.include_descriptions,
+            include_locations: dialog.include_locations,
+        };
+        let folder = dialog.folder_path();
+        let site_folder = HtmlPublishService::site_folder(&folder);
+
+        let event_service = EventService::new(self.context.database().connection());
+        let categories = self
+            .context
+            .category_service()
+            .list_all()
+            .unwrap_or_default();
+
+        match HtmlPublishService::publish(
+            &event_service,
+            &categories,
+            &self.active_theme,
+            &options,
+            &folder,
+        ) {
+            Ok(count) => {
+                log::info!("Published {} occurrences to {:?}", count, site_folder);
+                self.toast_manager.success(format!(
+                    "Published {} events to {}",
+                    count,
+                    site_folder.display()
+                ));
+                self.state.publish_dialog_state.close();
+            }
+            Err(e) => {
+                log::error!("Failed to publish calendar: {:#}", e);
+                self.toast_manager
+                    .error(format!("Failed to publish calendar: {}", e));
+            }
+        }
+    }
+
+    // ========== CSV Import ==========
+
+    /// Open a file dialog and map the columns of a .csv file to event fields.
+    pub(super) fn import_events_csv(&mut self) {
+        if self.state.csv_import_dialog_state.is_open {
+            self.toast_manager
+                .warning("Finish the open import before importing another file");
+            return;
+        }
+
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Import Events from CSV")
+            .add_filter("CSV", &["csv", "txt"])
+            .pick_file()
+        else {
+            return;
+        };
+
+        use crate::services::csv::import::CsvTable;
+
+        let table = match std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| CsvTable::parse(&content))
+        {
+            Ok(table) => table,
+            Err(e) => {
+                log::error!("Failed to read CSV file {:?}: {}", path, e);
+                self.toast_manager.error("Failed to read CSV file");
+                return;
+            }
+        };
+
+        if table.rows.is_empty() {
+            self.toast_manager
+                .warning("The CSV file has no rows to import");
+            return;
+        }
+
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "CSV file".to_string());
+        self.state
+            .csv_import_dialog_state
+            .open(table, &file_name, &self.settings.date_format);
+    }
+
     // ========== Countdown Layout Export / Import ==========
 
     /// Export the current countdown layout configuration to a JSON file.
@@ -26,6 +26,16 @@ impl CalendarApp {
                     self.state.date_picker_state.close();
                 } else if self.state.template_manager_state.is_open {
                     self.state.template_manager_state.close();
+                } else if self.state.trash_dialog_state.is_open {
+                    self.state.trash_dialog_state.close();
+                } else if self.state.undo_history_dialog_state.is_open {
+                    self.state.undo_history_dialog_state.close();
+                } else if self.state.import_preview_dialog_state.is_open {
+                    self.state.import_preview_dialog_state.close();
+                } else if self.state.csv_import_dialog_state.is_open {
+                    self.state.csv_import_dialog_state.close();
+                } else if self.state.publish_dialog_state.is_open {
+                    self.state.publish_dialog_state.close();
                 }
             }
 
@@ -84,6 +94,11 @@ impl CalendarApp {
                 || self.state.theme_dialog_state.is_open
                 || self.state.date_picker_state.is_open
                 || self.state.template_manager_state.is_open
+                || self.state.trash_dialog_state.is_open
+                || self.state.undo_history_dialog_state.is_open
+                || self.state.import_preview_dialog_state.is_open
+                || self.state.csv_import_dialog_state.is_open
+                || self.state.publish_dialog_state.is_open
                 || self.state.show_export_range_dialog;
 
             if !any_dialog_open {
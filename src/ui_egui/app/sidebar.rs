@@ -2,6 +2,7 @@
 
 use super::CalendarApp;
 use crate::models::event::Event;
+use crate::services::saved_search::SavedSearchService;
 use chrono::{Datelike, Duration, Local, NaiveDate, TimeZone};
 use egui::{Color32, RichText};
 
@@ -156,6 +157,10 @@ impl CalendarApp {
                 ui.separator();
                 ui.add_space(8.0);
                 self.render_sidebar_upcoming_events(ui);
+                ui.add_space(8.0);
+                ui.separator();
+                ui.add_space(8.0);
+                self.render_sidebar_saved_searches(ui);
             });
     }
 
@@ -324,6 +329,71 @@ impl CalendarApp {
         }
     }
 
+    /// Render saved searches, each usable as a view filter
+    fn render_sidebar_saved_searches(&mut self, ui: &mut egui::Ui) {
+        let service = SavedSearchService::new(self.context.database().connection());
+        let searches = self
+            .state
+            .saved_search_list
+            .refresh(self.context.database())
+            .to_vec();
+
+        ui.label(RichText::new("🔎 Saved Searches").strong());
+        ui.add_space(4.0);
+
+        if searches.is_empty() {
+            ui.label(
+                RichText::new("Save a search from Ctrl+F to filter by it")
+                    .weak()
+                    .italics(),
+            );
+            return;
+        }
+
+        for search in &searches {
+            let is_active = self
+                .active_view_filter
+                .as_ref()
+                .is_some_and(|filter| filter.saved_search_id == search.id);
+
+            let response = ui
+                .selectable_label(is_active, &search.name)
+                .on_hover_text(&search.query);
+            if response.clicked() {
+                if is_active {
+                    self.active_view_filter = None;
+                } else {
+                    self.apply_saved_search_filter(search);
+                }
+            }
+
+            response.context_menu(|ui| {
+                if ui.button("🔍 Open in Search").clicked() {
+                    self.state
+                        .search_dialog_state
+                        .open_saved_search(&search.name, &search.query);
+                    self.state.show_search_dialog = true;
+                    ui.close_menu();
+                }
+                if ui.button("🗑 Delete").clicked() {
+                    if let Some(id) = search.id {
+                        match service.delete(id) {
+                            Ok(()) => {
+                                if is_active {
+                                    self.active_view_filter = None;
+                                }
+                            }
+                            Err(err) => self
+                                .toast_manager
+                                .error(format!("Failed to delete saved search: {}", err)),
+                        }
+                    }
+                    ui.close_menu();
+                }
+            });
+        }
+    }
+
     /// Render a single event item in the sidebar
     fn render_sidebar_event_item(&mut self, ui: &mut egui::Ui, event: &Event, show_date: bool) {
         let event_color = event
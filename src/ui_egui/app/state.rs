@@ -1,14 +1,25 @@
+use crate::models::saved_search::SavedSearch;
 use crate::services::countdown::CountdownCardGeometry;
+use crate::services::database::Database;
+use crate::services::event::queries::EventQuery;
+use crate::services::saved_search::SavedSearchService;
 use crate::ui_egui::dialogs::backup_manager::BackupManagerState;
 use crate::ui_egui::dialogs::category_manager::CategoryManagerState;
 use crate::ui_egui::dialogs::countdown_category_manager::CountdownCategoryManagerState;
 use crate::ui_egui::dialogs::countdown_template_manager::CountdownTemplateManagerState;
+use crate::ui_egui::dialogs::csv_import_dialog::CsvImportDialogState;
 use crate::ui_egui::dialogs::export_dialog::ExportDialogState;
+use crate::ui_egui::dialogs::import_preview_dialog::ImportPreviewDialogState;
+use crate::ui_egui::dialogs::publish_dialog::PublishDialogState;
 use crate::ui_egui::dialogs::search_dialog::SearchDialogState;
+use crate::ui_egui::dialogs::secret_vault_dialog::SecretVaultDialogState;
 use crate::ui_egui::dialogs::template_manager::TemplateManagerState;
 use crate::ui_egui::dialogs::theme_creator::ThemeCreatorState;
 use crate::ui_egui::dialogs::theme_dialog::ThemeDialogState;
+use crate::ui_egui::dialogs::trash_dialog::TrashDialogState;
+use crate::ui_egui::dialogs::undo_history_dialog::UndoHistoryDialogState;
 use crate::ui_egui::settings_dialog::SettingsDialogState;
+use crate::ui_egui::views::ViewFilterMatches;
 use chrono::NaiveDate;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,6 +30,40 @@ pub enum ViewType {
     Month,
 }
 
+/// Filter applied to every calendar view (a category or a saved search)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewFilter {
+    pub label: String,
+    pub query: EventQuery,
+    /// Set when the filter came from a saved search
+    pub saved_search_id: Option<i64>,
+}
+
+impl ViewFilter {
+    pub fn for_category(name: &str) -> Self {
+        Self {
+            label: name.to_string(),
+            query: EventQuery {
+                categories: vec![name.to_string()],
+                ..EventQuery::default()
+            },
+            saved_search_id: None,
+        }
+    }
+
+    pub fn from_saved_search(search: &SavedSearch) -> anyhow::Result<Self> {
+        Ok(Self {
+            label: search.name.clone(),
+            query: EventQuery::parse(&search.query)?,
+            saved_search_id: search.id,
+        })
+    }
+
+    pub fn is_category(&self, name: &str) -> bool {
+        self.saved_search_id.is_none() && self.query == Self::for_category(name).query
+    }
+}
+
 /// State for the date picker popup used in navigation
 #[derive(Default)]
 pub struct DatePickerState {
@@ -39,6 +84,32 @@ impl DatePickerState {
     }
 }
 
+/// The saved searches, reloaded only when the database changed
+#[derive(Default)]
+pub struct SavedSearchList {
+    stamp: Option<(i64, i64)>,
+    searches: Vec<SavedSearch>,
+}
+
+impl SavedSearchList {
+    pub fn refresh(&mut self, database: &Database) -> &[SavedSearch] {
+        let stamp = database.change_stamp().ok();
+        if stamp.is_none() || stamp != self.stamp {
+            match SavedSearchService::new(database.connection()).list_all() {
+                Ok(searches) => {
+                    self.searches = searches;
+                    self.stamp = stamp;
+                }
+                Err(err) => {
+                    log::warn!("Failed to load saved searches: {:#}", err);
+                    self.stamp = None;
+                }
+            }
+        }
+        &self.searches
+    }
+}
+
 pub struct AppState {
     pub backup_manager_state: BackupManagerState,
     pub category_manager_state: CategoryManagerState,
@@ -47,14 +118,24 @@ pub struct AppState {
     pub theme_dialog_state: ThemeDialogState,
     pub theme_creator_state: ThemeCreatorState,
     pub search_dialog_state: SearchDialogState,
+    pub secret_vault_dialog_state: SecretVaultDialogState,
     pub settings_dialog_state: SettingsDialogState,
     pub template_manager_state: TemplateManagerState,
+    pub trash_dialog_state: TrashDialogState,
+    pub undo_history_dialog_state: UndoHistoryDialogState,
+    pub import_preview_dialog_state: ImportPreviewDialogState,
+    pub csv_import_dialog_state: CsvImportDialogState,
+    pub publish_dialog_state: PublishDialogState,
     pub show_search_dialog: bool,
     pub show_about_dialog: bool,
     pub show_export_range_dialog: bool,
     pub export_dialog_state: ExportDialogState,
     pub pending_root_geometry: Option<CountdownCardGeometry>,
     pub date_picker_state: DatePickerState,
+    /// Events matching the active view filter, kept between frames
+    pub view_filter_matches: ViewFilterMatches,
+    /// Saved searches shown in the sidebar, kept between frames
+    pub saved_search_list: SavedSearchList,
     /// Whether we've done the initial geometry sanitization on first frame
     pub geometry_sanitized: bool,
     /// Flag to apply theme on next frame (set when theme changed from menu)
@@ -74,14 +155,22 @@ impl AppState {
             theme_dialog_state: ThemeDialogState::new(),
             theme_creator_state: ThemeCreatorState::new(),
             search_dialog_state: SearchDialogState::default(),
+            secret_vault_dialog_state: SecretVaultDialogState::default(),
             settings_dialog_state: SettingsDialogState::new(),
             template_manager_state: TemplateManagerState::default(),
+            trash_dialog_state: TrashDialogState::default(),
+            undo_history_dialog_state: UndoHistoryDialogState::default(),
+            import_preview_dialog_state: ImportPreviewDialogState::default(),
+            csv_import_dialog_state: CsvImportDialogState::default(),
+            publish_dialog_state: PublishDialogState::default(),
             show_search_dialog: false,
             show_about_dialog: false,
             show_export_range_dialog: false,
             export_dialog_state: ExportDialogState::default(),
             pending_root_geometry,
             date_picker_state: DatePickerState::default(),
+            view_filter_matches: ViewFilterMatches::default(),
+            saved_search_list: SavedSearchList::default(),
             geometry_sanitized: false,
             pending_theme_apply: false,
         }
@@ -5,8 +5,9 @@
 //! `handle_timed_view_result` helper, and `handle_delete_confirm_request`.
 
 use super::super::confirm::ConfirmAction;
-use super::super::state::ViewType;
+use super::super::state::{ViewFilter, ViewType};
 use super::super::CalendarApp;
+use crate::services::database::Database;
 use crate::ui_egui::commands::UpdateEventCommand;
 use crate::ui_egui::views::day_view::DayView;
 use crate::ui_egui::views::month_view::{MonthView, MonthViewAction};
@@ -14,12 +15,22 @@ use crate::ui_egui::views::week_shared::{DeleteConfirmRequest, EventInteractionR
 use crate::ui_egui::views::week_view::WeekView;
 use crate::ui_egui::views::workweek_view::WorkWeekView;
 use crate::ui_egui::views::{
-    filter_events_by_category, is_ribbon_event, is_synced_event, load_synced_event_ids,
-    AutoFocusRequest, CountdownRequest,
+    filter_events_by_query, is_ribbon_event, is_synced_event, load_synced_event_ids,
+    AutoFocusRequest, CountdownRequest, ViewFilterMatches,
 };
 use chrono::Datelike;
 use std::collections::HashSet;
 
+/// The matches for the active view filter, re-queried only when the filter
+/// or the database changed
+fn view_filter_matches<'a>(
+    cache: &'a mut ViewFilterMatches,
+    database: &Database,
+    filter: Option<&ViewFilter>,
+) -> Option<&'a ViewFilterMatches> {
+    filter.map(|filter| cache.refresh(database, &filter.query))
+}
+
 impl CalendarApp {
     /// Handle a delete confirmation request from a view
     pub(in crate::ui_egui::app) fn handle_delete_confirm_request(
@@ -129,7 +140,11 @@ impl CalendarApp {
             countdown_requests,
             active_countdown_events,
             focus_request,
-            self.active_category_filter.as_deref(),
+            view_filter_matches(
+                &mut self.state.view_filter_matches,
+                self.context.database(),
+                self.active_view_filter.as_ref(),
+            ),
             self.show_synced_events_only,
             synced_source_id,
         );
@@ -171,8 +186,14 @@ impl CalendarApp {
                 .filter(is_ribbon_event)
                 .collect::<Vec<_>>();
 
-            let all_events =
-                filter_events_by_category(all_events, self.active_category_filter.as_deref());
+            let all_events = filter_events_by_query(
+                all_events,
+                view_filter_matches(
+                    &mut self.state.view_filter_matches,
+                    self.context.database(),
+                    self.active_view_filter.as_ref(),
+                ),
+            );
             if self.show_synced_events_only {
                 let synced_event_ids =
                     load_synced_event_ids(self.context.database(), synced_source_id);
@@ -202,7 +223,11 @@ impl CalendarApp {
             show_ribbon,
             &all_day_events,
             focus_request,
-            self.active_category_filter.as_deref(),
+            view_filter_matches(
+                &mut self.state.view_filter_matches,
+                self.context.database(),
+                self.active_view_filter.as_ref(),
+            ),
             self.show_synced_events_only,
             synced_source_id,
         );
@@ -245,8 +270,14 @@ impl CalendarApp {
                     .filter(is_ribbon_event)
                     .collect::<Vec<_>>();
 
-                let all_events =
-                    filter_events_by_category(all_events, self.active_category_filter.as_deref());
+                let all_events = filter_events_by_query(
+                    all_events,
+                    view_filter_matches(
+                        &mut self.state.view_filter_matches,
+                        self.context.database(),
+                        self.active_view_filter.as_ref(),
+                    ),
+                );
                 if self.show_synced_events_only {
                     let synced_event_ids =
                         load_synced_event_ids(self.context.database(), synced_source_id);
@@ -279,7 +310,11 @@ impl CalendarApp {
             self.show_ribbon,
             &all_day_events,
             focus_request,
-            self.active_category_filter.as_deref(),
+            view_filter_matches(
+                &mut self.state.view_filter_matches,
+                self.context.database(),
+                self.active_view_filter.as_ref(),
+            ),
             self.show_synced_events_only,
             synced_source_id,
         );
@@ -306,7 +341,11 @@ impl CalendarApp {
             &mut self.event_to_edit,
             countdown_requests,
             active_countdown_events,
-            self.active_category_filter.as_deref(),
+            view_filter_matches(
+                &mut self.state.view_filter_matches,
+                self.context.database(),
+                self.active_view_filter.as_ref(),
+            ),
             self.show_synced_events_only,
             synced_source_id,
         );
@@ -0,0 +1,159 @@
+//! Commands that create or delete many events at once.
+
+use super::{
+    delete_event_with_cards, persisted_command, restore_event_with_cards, CategoryCommand, Command,
+    CommandContext,
+};
+use crate::models::event::Event;
+use crate::services::countdown::CountdownCardState;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Command for a file import; undo removes every imported event, puts back
+/// the previous version of every event the import updated and deletes the
+/// categories it created
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportEventsCommand {
+    /// The imported events, with IDs
+    events: Vec<Event>,
+    /// Events updated in place, as (before, after)
+    #[serde(default)]
+    updates: Vec<(Event, Event)>,
+    /// Categories created for the imported events
+    #[serde(default)]
+    categories: Vec<CategoryCommand>,
+    /// Countdown cards removed on undo, restored on redo
+    #[serde(default)]
+    cards: Vec<CountdownCardState>,
+    source_label: String,
+}
+
+impl ImportEventsCommand {
+    pub fn new(events: Vec<Event>, source_label: &str) -> Self {
+        Self {
+            events,
+            updates: Vec::new(),
+            categories: Vec::new(),
+            cards: Vec::new(),
+            source_label: source_label.to_string(),
+        }
+    }
+
+    pub fn with_updates(mut self, updates: Vec<(Event, Event)>) -> Self {
+        self.updates = updates;
+        self
+    }
+
+    pub fn with_categories(mut self, categories: Vec<CategoryCommand>) -> Self {
+        self.categories = categories;
+        self
+    }
+}
+
+impl Command for ImportEventsCommand {
+    fn execute(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        for category in &mut self.categories {
+            category.execute(ctx)?;
+        }
+        restore_events(ctx, &mut self.events, &mut self.cards)?;
+        for (_, after) in &self.updates {
+            ctx.event_service().update_local(after)?;
+        }
+        Ok(())
+    }
+
+    fn undo(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        for (before, _) in &self.updates {
+            ctx.event_service().update_local(before)?;
+            // Let a later import of the same file apply the update again.
+            if let Some(id) = before.id {
+                ctx.event_service().forget_import_revision(id)?;
+            }
+        }
+        self.cards = delete_events(ctx, &self.events)?;
+        for category in self.categories.iter_mut().rev() {
+            category.undo(ctx)?;
+        }
+        Ok(())
+    }
+
+    fn description(&self) -> String {
+        if self.updates.is_empty() {
+            format!(
+                "Import {} event(s) from {}",
+                self.events.len(),
+                self.source_label
+            )
+        } else {
+            format!(
+                "Import {} new and {} updated event(s) from {}",
+                self.events.len(),
+                self.updates.len(),
+                self.source_label
+            )
+        }
+    }
+
+    persisted_command!("import_events");
+}
+
+/// Command for deleting several events at once
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteEventsCommand {
+    /// The events to delete (stored for undo)
+    events: Vec<Event>,
+    /// Countdown cards removed with the events
+    #[serde(default)]
+    cards: Vec<CountdownCardState>,
+}
+
+impl DeleteEventsCommand {
+    pub fn new(events: Vec<Event>) -> Self {
+        Self {
+            events,
+            cards: Vec::new(),
+        }
+    }
+}
+
+impl Command for DeleteEventsCommand {
+    fn execute(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        self.cards = delete_events(ctx, &self.events)?;
+        Ok(())
+    }
+
+    fn undo(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        restore_events(ctx, &mut self.events, &mut self.cards)
+    }
+
+    fn description(&self) -> String {
+        format!("Delete {} event(s)", self.events.len())
+    }
+
+    persisted_command!("delete_events");
+}
+
+fn delete_events(ctx: &mut CommandContext, events: &[Event]) -> Result<Vec<CountdownCardState>> {
+    let mut cards = Vec::new();
+    for id in events.iter().filter_map(|event| event.id) {
+        cards.extend(delete_event_with_cards(ctx, id)?);
+    }
+    Ok(cards)
+}
+
+fn restore_events(
+    ctx: &mut CommandContext,
+    events: &mut [Event],
+    cards: &mut Vec<CountdownCardState>,
+) -> Result<()> {
+    for event in events.iter_mut() {
+        let event_cards: Vec<_> = cards
+            .iter()
+            .filter(|card| card.event_id.is_some() && card.event_id == event.id)
+            .cloned()
+            .collect();
+        restore_event_with_cards(ctx, event, &event_cards)?;
+    }
+    cards.clear();
+    Ok(())
+}
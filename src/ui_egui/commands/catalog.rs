@@ -0,0 +1,196 @@
+//! Commands for category and template management.
+//!
+//! Each command stores the record before and after the change; `None` on one
+//! side means the record was created or deleted. Re-creating a record gives
+//! it a new ID, which is written back into both snapshots.
+
+use super::{persisted_command, Command, CommandContext};
+use crate::models::category::Category;
+use crate::models::template::EventTemplate;
+use crate::services::category::CategoryService;
+use crate::services::template::TemplateService;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Command for creating, editing or deleting a category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryCommand {
+    before: Option<Category>,
+    after: Option<Category>,
+    /// Events that used the category when it was deleted
+    #[serde(default)]
+    affected_event_ids: Vec<i64>,
+}
+
+impl CategoryCommand {
+    pub fn created(category: Category) -> Self {
+        Self {
+            before: None,
+            after: Some(category),
+            affected_event_ids: Vec::new(),
+        }
+    }
+
+    pub fn updated(before: Category, after: Category) -> Self {
+        Self {
+            before: Some(before),
+            after: Some(after),
+            affected_event_ids: Vec::new(),
+        }
+    }
+
+    pub fn deleted(category: Category, affected_event_ids: Vec<i64>) -> Self {
+        Self {
+            before: Some(category),
+            after: None,
+            affected_event_ids,
+        }
+    }
+
+    fn apply(&mut self, ctx: &mut CommandContext, forward: bool) -> Result<()> {
+        let service = CategoryService::new(ctx.conn);
+        let (from, to) = if forward {
+            (&self.before, &self.after)
+        } else {
+            (&self.after, &self.before)
+        };
+
+        match (from, to) {
+            (None, Some(category)) => {
+                let mut fresh = category.clone();
+                fresh.id = None;
+                let created = service.create(fresh)?;
+                service.assign_to_events(&created.name, &self.affected_event_ids)?;
+                for snapshot in [&mut self.before, &mut self.after].into_iter().flatten() {
+                    snapshot.id = created.id;
+                }
+                Ok(())
+            }
+            (Some(category), None) => {
+                let id = category
+                    .id
+                    .ok_or_else(|| anyhow!("Category \"{}\" has no id", category.name))?;
+                service.delete(id)
+            }
+            (Some(_), Some(category)) => service.update(category),
+            (None, None) => Ok(()),
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.after
+            .as_ref()
+            .or(self.before.as_ref())
+            .map(|category| category.name.as_str())
+            .unwrap_or_default()
+    }
+}
+
+impl Command for CategoryCommand {
+    fn execute(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        self.apply(ctx, true)
+    }
+
+    fn undo(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        self.apply(ctx, false)
+    }
+
+    fn description(&self) -> String {
+        let verb = match (&self.before, &self.after) {
+            (None, _) => "Create",
+            (_, None) => "Delete",
+            _ => "Edit",
+        };
+        format!("{} category \"{}\"", verb, self.name())
+    }
+
+    persisted_command!("category");
+}
+
+/// Command for creating, editing or deleting an event template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateCommand {
+    before: Option<EventTemplate>,
+    after: Option<EventTemplate>,
+}
+
+impl TemplateCommand {
+    pub fn created(template: EventTemplate) -> Self {
+        Self {
+            before: None,
+            after: Some(template),
+        }
+    }
+
+    pub fn updated(before: EventTemplate, after: EventTemplate) -> Self {
+        Self {
+            before: Some(before),
+            after: Some(after),
+        }
+    }
+
+    pub fn deleted(template: EventTemplate) -> Self {
+        Self {
+            before: Some(template),
+            after: None,
+        }
+    }
+
+    fn apply(&mut self, ctx: &mut CommandContext, forward: bool) -> Result<()> {
+        let service = TemplateService::new(ctx.conn);
+        let (from, to) = if forward {
+            (&self.before, &self.after)
+        } else {
+            (&self.after, &self.before)
+        };
+
+        match (from, to) {
+            (None, Some(template)) => {
+                let mut fresh = template.clone();
+                fresh.id = None;
+                let created = service.create(fresh)?;
+                for snapshot in [&mut self.before, &mut self.after].into_iter().flatten() {
+                    snapshot.id = created.id;
+                }
+                Ok(())
+            }
+            (Some(template), None) => {
+                let id = template
+                    .id
+                    .ok_or_else(|| anyhow!("Template \"{}\" has no id", template.name))?;
+                service.delete(id)
+            }
+            (Some(_), Some(template)) => service.update(template),
+            (None, None) => Ok(()),
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.after
+            .as_ref()
+            .or(self.before.as_ref())
+            .map(|template| template.name.as_str())
+            .unwrap_or_default()
+    }
+}
+
+impl Command for TemplateCommand {
+    fn execute(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        self.apply(ctx, true)
+    }
+
+    fn undo(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        self.apply(ctx, false)
+    }
+
+    fn description(&self) -> String {
+        let verb = match (&self.before, &self.after) {
+            (None, _) => "Create",
+            (_, None) => "Delete",
+            _ => "Edit",
+        };
+        format!("{} template \"{}\"", verb, self.name())
+    }
+
+    persisted_command!("template");
+}
@@ -0,0 +1,85 @@
+//! Commands for countdown card changes.
+
+use super::{persisted_command, Command, CommandContext};
+use crate::services::countdown::{CardSettingsSnapshot, CountdownCardId, CountdownCardState};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Command for changes applied in a card's settings dialog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountdownCardSettingsCommand {
+    card_id: CountdownCardId,
+    title: String,
+    before: CardSettingsSnapshot,
+    after: CardSettingsSnapshot,
+}
+
+impl CountdownCardSettingsCommand {
+    pub fn new(
+        card_id: CountdownCardId,
+        title: String,
+        before: CardSettingsSnapshot,
+        after: CardSettingsSnapshot,
+    ) -> Self {
+        Self {
+            card_id,
+            title,
+            before,
+            after,
+        }
+    }
+
+    fn apply(&self, ctx: &mut CommandContext, snapshot: &CardSettingsSnapshot) -> Result<()> {
+        if ctx.countdowns.restore_card_settings(self.card_id, snapshot) {
+            Ok(())
+        } else {
+            Err(anyhow!("Countdown \"{}\" no longer exists", self.title))
+        }
+    }
+}
+
+impl Command for CountdownCardSettingsCommand {
+    fn execute(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        self.apply(ctx, &self.after)
+    }
+
+    fn undo(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        self.apply(ctx, &self.before)
+    }
+
+    fn description(&self) -> String {
+        format!("Change countdown \"{}\"", self.title)
+    }
+
+    persisted_command!("countdown_card_settings");
+}
+
+/// Command for deleting a countdown card
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteCountdownCardCommand {
+    card: CountdownCardState,
+}
+
+impl DeleteCountdownCardCommand {
+    pub fn new(card: CountdownCardState) -> Self {
+        Self { card }
+    }
+}
+
+impl Command for DeleteCountdownCardCommand {
+    fn execute(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        ctx.countdowns.remove_card(self.card.id);
+        Ok(())
+    }
+
+    fn undo(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        ctx.countdowns.restore_card(self.card.clone());
+        Ok(())
+    }
+
+    fn description(&self) -> String {
+        format!("Delete countdown \"{}\"", self.card.effective_title())
+    }
+
+    persisted_command!("delete_countdown_card");
+}
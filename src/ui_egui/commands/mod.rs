@@ -1,71 +1,111 @@
 // Undo/Redo Command System
 //
-// Implements the Command pattern for event operations,
-// enabling undo and redo functionality.
+// Implements the Command pattern for mutating operations,
+// enabling undo and redo functionality. Commands serialize to JSON so the
+// stacks survive restarts (see `store`).
+
+mod batch;
+mod catalog;
+mod countdown;
+mod occurrence;
+mod store;
+
+pub use batch::{DeleteEventsCommand, ImportEventsCommand};
+pub use catalog::{CategoryCommand, TemplateCommand};
+pub use countdown::{CountdownCardSettingsCommand, DeleteCountdownCardCommand};
+pub use occurrence::{DeleteOccurrenceCommand, DetachOccurrenceCommand};
 
 use crate::models::event::Event;
+use crate::services::countdown::{CountdownCardState, CountdownService};
 use crate::services::event::EventService;
-use anyhow::Result;
-use std::sync::Mutex;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// What a command needs to run: the database and the in-memory countdown cards
+pub struct CommandContext<'a> {
+    pub conn: &'a Connection,
+    pub countdowns: &'a mut CountdownService,
+}
+
+impl<'a> CommandContext<'a> {
+    pub fn new(conn: &'a Connection, countdowns: &'a mut CountdownService) -> Self {
+        Self { conn, countdowns }
+    }
+
+    pub fn event_service(&self) -> EventService<'a> {
+        EventService::new(self.conn)
+    }
+}
 
 /// Trait for undoable commands
 pub trait Command: std::fmt::Debug {
     /// Execute the command (do/redo)
-    fn execute(&self, event_service: &EventService) -> Result<()>;
+    fn execute(&mut self, ctx: &mut CommandContext) -> Result<()>;
 
     /// Undo the command
-    fn undo(&self, event_service: &EventService) -> Result<()>;
+    fn undo(&mut self, ctx: &mut CommandContext) -> Result<()>;
 
     /// Get a human-readable description of the command
     fn description(&self) -> String;
+
+    /// Type tag stored with the persisted command
+    fn kind(&self) -> &'static str;
+
+    /// Serialize the command's current state (ids change on undo/redo)
+    fn to_json(&self) -> Result<String>;
 }
 
+/// Implements `kind` and `to_json` for a `Serialize` command type
+macro_rules! persisted_command {
+    ($kind:literal) => {
+        fn kind(&self) -> &'static str {
+            $kind
+        }
+
+        fn to_json(&self) -> anyhow::Result<String> {
+            serde_json::to_string(self).map_err(Into::into)
+        }
+    };
+}
+pub(crate) use persisted_command;
+
 /// Command for creating an event
-/// Uses Mutex to allow updating the event ID after redo (recreation)
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CreateEventCommand {
     /// The event that was created (with ID set after execution)
-    /// Wrapped in Mutex so we can update the ID after recreation
-    event: Mutex<Event>,
+    event: Event,
 }
 
 impl CreateEventCommand {
     pub fn new(event: Event) -> Self {
-        Self {
-            event: Mutex::new(event),
-        }
+        Self { event }
     }
 }
 
 impl Command for CreateEventCommand {
-    fn execute(&self, event_service: &EventService) -> Result<()> {
-        // Re-create the event (for redo) and update stored ID
-        let mut event = self.event.lock().unwrap();
-        let mut new_event = event.clone();
-        new_event.id = None; // Clear ID so it creates a new record
-        let created = event_service.create_local(new_event)?;
-        // Update our stored event with the new ID so undo works
-        event.id = created.id;
-        Ok(())
+    fn execute(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        // Bring the event back from the trash, or re-create it
+        restore_or_recreate(&ctx.event_service(), &mut self.event)
     }
 
-    fn undo(&self, event_service: &EventService) -> Result<()> {
-        // Delete the event
-        let event = self.event.lock().unwrap();
-        if let Some(id) = event.id {
-            event_service.delete_local(id)?;
+    fn undo(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        if let Some(id) = self.event.id {
+            ctx.event_service().delete_local(id)?;
         }
         Ok(())
     }
 
     fn description(&self) -> String {
-        let event = self.event.lock().unwrap();
-        format!("Create event \"{}\"", event.title)
+        format!("Create event \"{}\"", self.event.title)
     }
+
+    persisted_command!("create_event");
 }
 
 /// Command for updating an event
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateEventCommand {
     /// The event state before the update
     pub old_event: Event,
@@ -83,73 +123,155 @@ impl UpdateEventCommand {
 }
 
 impl Command for UpdateEventCommand {
-    fn execute(&self, event_service: &EventService) -> Result<()> {
-        event_service.update_local(&self.new_event)?;
+    fn execute(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        ctx.event_service().update_local(&self.new_event)?;
         Ok(())
     }
 
-    fn undo(&self, event_service: &EventService) -> Result<()> {
-        event_service.update_local(&self.old_event)?;
+    fn undo(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        ctx.event_service().update_local(&self.old_event)?;
         Ok(())
     }
 
     fn description(&self) -> String {
         format!("Update event \"{}\"", self.new_event.title)
     }
+
+    persisted_command!("update_event");
 }
 
-/// Command for deleting an event
-/// Uses Mutex to allow updating the event ID after undo (recreation)
-#[derive(Debug)]
+/// Command for deleting an event together with its countdown cards
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DeleteEventCommand {
     /// The event that was deleted (stored for undo)
-    /// Wrapped in Mutex so we can update the ID after recreation
-    event: Mutex<Event>,
+    event: Event,
+    /// Countdown cards removed with the event
+    #[serde(default)]
+    cards: Vec<CountdownCardState>,
 }
 
 impl DeleteEventCommand {
     pub fn new(event: Event) -> Self {
         Self {
-            event: Mutex::new(event),
+            event,
+            cards: Vec::new(),
         }
     }
 }
 
 impl Command for DeleteEventCommand {
-    fn execute(&self, event_service: &EventService) -> Result<()> {
-        let event = self.event.lock().unwrap();
-        if let Some(id) = event.id {
-            event_service.delete_local(id)?;
+    fn execute(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        if let Some(id) = self.event.id {
+            self.cards = delete_event_with_cards(ctx, id)?;
         }
         Ok(())
     }
 
-    fn undo(&self, event_service: &EventService) -> Result<()> {
-        // Re-create the event and update stored ID
-        let mut event = self.event.lock().unwrap();
-        let mut new_event = event.clone();
-        new_event.id = None; // Clear ID so it creates a new record
-        let created = event_service.create_local(new_event)?;
-        // Update our stored event with the new ID so redo works
-        event.id = created.id;
-        Ok(())
+    fn undo(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        restore_event_with_cards(ctx, &mut self.event, &self.cards)
     }
 
     fn description(&self) -> String {
-        let event = self.event.lock().unwrap();
-        format!("Delete event \"{}\"", event.title)
+        format!("Delete event \"{}\"", self.event.title)
     }
+
+    persisted_command!("delete_event");
+}
+
+/// Delete an event (to the trash) and remove its countdown cards, returning
+/// the cards so an undo can put them back.
+fn delete_event_with_cards(
+    ctx: &mut CommandContext,
+    event_id: i64,
+) -> Result<Vec<CountdownCardState>> {
+    ctx.event_service().delete_local(event_id)?;
+    let cards: Vec<_> = ctx
+        .countdowns
+        .cards()
+        .iter()
+        .filter(|card| card.event_id == Some(event_id))
+        .cloned()
+        .collect();
+    ctx.countdowns.remove_cards_for_event(event_id);
+    Ok(cards)
+}
+
+/// Undo [`delete_event_with_cards`], re-linking the cards if the event came
+/// back under a new id.
+fn restore_event_with_cards(
+    ctx: &mut CommandContext,
+    event: &mut Event,
+    cards: &[CountdownCardState],
+) -> Result<()> {
+    let original_id = event.id;
+    restore_or_recreate(&ctx.event_service(), event)?;
+    for card in cards {
+        let mut card = card.clone();
+        if card.event_id == original_id {
+            card.event_id = event.id;
+        }
+        ctx.countdowns.restore_card(card);
+    }
+    Ok(())
+}
+
+/// Prefer the trash copy, which keeps the ID and calendar link; otherwise
+/// re-create the event and store its new ID.
+fn restore_or_recreate(event_service: &EventService, event: &mut Event) -> Result<()> {
+    if let Some(id) = event.id {
+        if let Some(restored) = event_service.restore_latest_trashed(id)? {
+            event.id = restored.event.id;
+            return Ok(());
+        }
+        if event_service.get(id)?.is_some() {
+            return Ok(());
+        }
+    }
+
+    let mut new_event = event.clone();
+    new_event.id = None; // Clear ID so it creates a new record
+    let created = event_service.create_local(new_event)?;
+    event.id = created.id;
+    Ok(())
+}
+
+/// An entry on the undo or redo stack
+#[derive(Debug)]
+struct UndoEntry {
+    /// Row in `undo_history`, when persisted
+    row_id: Option<i64>,
+    command: Box<dyn Command + Send + Sync>,
+    recorded_at: DateTime<Local>,
+}
+
+impl UndoEntry {
+    fn new(command: Box<dyn Command + Send + Sync>) -> Self {
+        Self {
+            row_id: None,
+            command,
+            recorded_at: Local::now(),
+        }
+    }
+}
+
+/// A stack entry as shown in the history panel
+#[derive(Debug, Clone)]
+pub struct UndoHistoryItem {
+    pub description: String,
+    pub recorded_at: DateTime<Local>,
 }
 
 /// Manager for undo/redo stacks
 #[derive(Debug, Default)]
 pub struct UndoManager {
     /// Stack of commands that can be undone
-    undo_stack: Vec<Box<dyn Command + Send + Sync>>,
+    undo_stack: Vec<UndoEntry>,
     /// Stack of commands that can be redone
-    redo_stack: Vec<Box<dyn Command + Send + Sync>>,
+    redo_stack: Vec<UndoEntry>,
     /// Maximum number of commands to keep in history
     max_history: usize,
+    /// Database the stacks are mirrored to; `None` keeps them in memory
+    store: Option<&'static Connection>,
 }
 
 impl UndoManager {
@@ -158,45 +280,75 @@ impl UndoManager {
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
             max_history: 50,
+            store: None,
+        }
+    }
+
+    /// Load the stacks saved by a previous session and keep them in sync
+    /// with the database from now on.
+    pub fn load(conn: &'static Connection) -> Self {
+        let mut manager = Self::new();
+        manager.store = Some(conn);
+        match store::load(conn) {
+            Ok((undo_stack, redo_stack)) => {
+                manager.undo_stack = undo_stack;
+                manager.redo_stack = redo_stack;
+                manager.trim_history();
+            }
+            Err(e) => log::error!("Failed to load undo history: {}", e),
         }
+        manager
+    }
+
+    /// Execute a command and push it onto the undo stack
+    pub fn execute(
+        &mut self,
+        mut command: Box<dyn Command + Send + Sync>,
+        ctx: &mut CommandContext,
+    ) -> Result<()> {
+        command.execute(ctx)?;
+        self.push(command);
+        Ok(())
     }
 
     /// Push a command onto the undo stack after it has been executed
     pub fn push(&mut self, command: Box<dyn Command + Send + Sync>) {
         // Clear redo stack when a new command is executed
         self.redo_stack.clear();
+        self.persist(|conn| store::clear_stack(conn, store::REDO));
 
         // Add to undo stack
-        self.undo_stack.push(command);
+        let mut entry = UndoEntry::new(command);
+        self.persist_entry(&mut entry, store::UNDO);
+        self.undo_stack.push(entry);
 
-        // Trim undo stack if it exceeds max history
-        while self.undo_stack.len() > self.max_history {
-            self.undo_stack.remove(0);
-        }
+        self.trim_history();
     }
 
     /// Undo the last command
-    pub fn undo(&mut self, event_service: &EventService) -> Result<Option<String>> {
-        if let Some(command) = self.undo_stack.pop() {
-            let description = command.description();
-            command.undo(event_service)?;
-            self.redo_stack.push(command);
-            Ok(Some(description))
-        } else {
-            Ok(None)
-        }
+    pub fn undo(&mut self, ctx: &mut CommandContext) -> Result<Option<String>> {
+        let Some(mut entry) = self.undo_stack.pop() else {
+            return Ok(None);
+        };
+        let description = entry.command.description();
+        self.forget_entry(&entry);
+        entry.command.undo(ctx)?;
+        self.persist_entry(&mut entry, store::REDO);
+        self.redo_stack.push(entry);
+        Ok(Some(description))
     }
 
     /// Redo the last undone command
-    pub fn redo(&mut self, event_service: &EventService) -> Result<Option<String>> {
-        if let Some(command) = self.redo_stack.pop() {
-            let description = command.description();
-            command.execute(event_service)?;
-            self.undo_stack.push(command);
-            Ok(Some(description))
-        } else {
-            Ok(None)
-        }
+    pub fn redo(&mut self, ctx: &mut CommandContext) -> Result<Option<String>> {
+        let Some(mut entry) = self.redo_stack.pop() else {
+            return Ok(None);
+        };
+        let description = entry.command.description();
+        self.forget_entry(&entry);
+        entry.command.execute(ctx)?;
+        self.persist_entry(&mut entry, store::UNDO);
+        self.undo_stack.push(entry);
+        Ok(Some(description))
     }
 
     /// Check if there are commands to undo
@@ -211,25 +363,83 @@ impl UndoManager {
 
     /// Get the description of the next command to undo (owned string for menu display)
     pub fn undo_description(&self) -> Option<String> {
-        self.undo_stack.last().map(|cmd| cmd.description())
+        self.undo_stack
+            .last()
+            .map(|entry| entry.command.description())
     }
 
     /// Get the description of the next command to redo (owned string for menu display)
     pub fn redo_description(&self) -> Option<String> {
-        self.redo_stack.last().map(|cmd| cmd.description())
+        self.redo_stack
+            .last()
+            .map(|entry| entry.command.description())
+    }
+
+    /// Undoable commands, most recent first
+    pub fn undo_history(&self) -> Vec<UndoHistoryItem> {
+        Self::history_items(&self.undo_stack)
+    }
+
+    /// Redoable commands, next redo first
+    pub fn redo_history(&self) -> Vec<UndoHistoryItem> {
+        Self::history_items(&self.redo_stack)
     }
 
     /// Clear all history
-    #[allow(dead_code)]
     pub fn clear(&mut self) {
         self.undo_stack.clear();
         self.redo_stack.clear();
+        self.persist(store::clear_all);
+    }
+
+    fn history_items(stack: &[UndoEntry]) -> Vec<UndoHistoryItem> {
+        stack
+            .iter()
+            .rev()
+            .map(|entry| UndoHistoryItem {
+                description: entry.command.description(),
+                recorded_at: entry.recorded_at,
+            })
+            .collect()
+    }
+
+    fn trim_history(&mut self) {
+        // Trim undo stack if it exceeds max history
+        while self.undo_stack.len() > self.max_history {
+            let entry = self.undo_stack.remove(0);
+            self.forget_entry(&entry);
+        }
+    }
+
+    fn persist_entry(&self, entry: &mut UndoEntry, stack: &str) {
+        let Some(conn) = self.store else {
+            return;
+        };
+        match store::insert(conn, stack, entry) {
+            Ok(row_id) => entry.row_id = Some(row_id),
+            Err(e) => log::error!("Failed to save undo history: {}", e),
+        }
+    }
+
+    fn forget_entry(&self, entry: &UndoEntry) {
+        if let Some(row_id) = entry.row_id {
+            self.persist(|conn| store::delete(conn, row_id));
+        }
+    }
+
+    fn persist(&self, op: impl FnOnce(&Connection) -> Result<()>) {
+        if let Some(conn) = self.store {
+            if let Err(e) = op(conn).context("Failed to update undo history") {
+                log::error!("{:#}", e);
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::database::Database;
     use chrono::Local;
 
     fn create_test_event(title: &str) -> Event {
@@ -247,9 +457,16 @@ mod tests {
             recurrence_exceptions: None,
             created_at: None,
             updated_at: None,
+            ics_raw_properties: None,
         }
     }
 
+    fn leaked_test_db() -> &'static Database {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        Box::leak(Box::new(db))
+    }
+
     #[test]
     fn test_create_event_command_description() {
         let event = create_test_event("Team Meeting");
@@ -323,4 +540,192 @@ mod tests {
 
         assert!(!manager.can_redo());
     }
+
+    #[test]
+    fn test_undo_history_survives_reload_and_keeps_ids() {
+        let db = leaked_test_db();
+        let mut countdowns = CountdownService::new();
+        let mut ctx = CommandContext::new(db.connection(), &mut countdowns);
+
+        let created = ctx
+            .event_service()
+            .create(create_test_event("Planning"))
+            .unwrap();
+        let id = created.id.unwrap();
+
+        let mut manager = UndoManager::load(db.connection());
+        manager
+            .execute(Box::new(DeleteEventCommand::new(created)), &mut ctx)
+            .unwrap();
+        assert!(ctx.event_service().get(id).unwrap().is_none());
+
+        // A new session picks up the stack and can undo the delete.
+        let mut manager = UndoManager::load(db.connection());
+        assert_eq!(
+            manager.undo_description().as_deref(),
+            Some("Delete event \"Planning\"")
+        );
+        manager.undo(&mut ctx).unwrap();
+        assert_eq!(
+            ctx.event_service().get(id).unwrap().unwrap().title,
+            "Planning"
+        );
+
+        let mut manager = UndoManager::load(db.connection());
+        assert!(!manager.can_undo());
+        assert_eq!(manager.redo_history().len(), 1);
+        manager.redo(&mut ctx).unwrap();
+        assert!(ctx.event_service().get(id).unwrap().is_none());
+
+        manager.clear();
+        assert!(!UndoManager::load(db.connection()).can_undo());
+    }
+
+    #[test]
+    fn test_undoing_import_removes_events_and_their_cards() {
+        let db = leaked_test_db();
+        let mut countdowns = CountdownService::new();
+        let mut ctx = CommandContext::new(db.connection(), &mut countdowns);
+
+        let imported = ctx
+            .event_service()
+            .create(create_test_event("Conference"))
+            .unwrap();
+        let id = imported.id.unwrap();
+        ctx.countdowns.create_card(
+            Some(id),
+            "Conference",
+            imported.start,
+            Some(imported.start),
+            Some(imported.end),
+            None,
+            None,
+            200.0,
+            100.0,
+        );
+
+        let mut manager = UndoManager::new();
+        manager.push(Box::new(ImportEventsCommand::new(vec![imported], "file")));
+        manager.undo(&mut ctx).unwrap();
+        assert!(ctx.event_service().get(id).unwrap().is_none());
+        assert!(ctx.countdowns.cards().is_empty());
+
+        manager.redo(&mut ctx).unwrap();
+        assert!(ctx.event_service().get(id).unwrap().is_some());
+        assert_eq!(ctx.countdowns.cards().len(), 1);
+        assert_eq!(ctx.countdowns.cards()[0].event_id, Some(id));
+    }
+
+    #[test]
+    fn test_undoing_import_reverts_updated_events() {
+        use crate::services::event::ics_import::IcsImportOutcome;
+        use crate::services::icalendar::import::from_str_with_metadata;
+
+        let db = leaked_test_db();
+        let mut countdowns = CountdownService::new();
+        let mut ctx = CommandContext::new(db.connection(), &mut countdowns);
+        let ics = |sequence: i64, summary: &str| {
+            from_str_with_metadata(&format!(
+                "BEGIN:VEVENT\nUID:retro@example.com\nSEQUENCE:{}\n\
+                 DTSTART:20260410T150000\nDTEND:20260410T160000\nSUMMARY:{}\nEND:VEVENT\n",
+                sequence, summary
+            ))
+            .unwrap()
+            .remove(0)
+        };
+
+        let service = ctx.event_service();
+        service.import_ics_event(&ics(0, "Retro")).unwrap();
+        let IcsImportOutcome::Updated { before, after } =
+            service.import_ics_event(&ics(1, "Retro (moved)")).unwrap()
+        else {
+            panic!("expected the newer copy to update the event");
+        };
+        let id = after.id.unwrap();
+
+        let mut manager = UndoManager::new();
+        manager.push(Box::new(
+            ImportEventsCommand::new(Vec::new(), "file").with_updates(vec![(*before, after)]),
+        ));
+        manager.undo(&mut ctx).unwrap();
+        assert_eq!(ctx.event_service().get(id).unwrap().unwrap().title, "Retro");
+
+        // Importing the same file again re-applies the update.
+        assert!(matches!(
+            ctx.event_service()
+                .import_ics_event(&ics(1, "Retro (moved)"))
+                .unwrap(),
+            IcsImportOutcome::Updated { .. }
+        ));
+    }
+
+    #[test]
+    fn test_undoing_import_deletes_the_categories_it_created() {
+        use crate::models::category::Category;
+        use crate::services::category::CategoryService;
+
+        let db = leaked_test_db();
+        let mut countdowns = CountdownService::new();
+        let mut ctx = CommandContext::new(db.connection(), &mut countdowns);
+        let categories = CategoryService::new(db.connection());
+
+        let category = categories
+            .create(Category::new("Conferences", "#3B82F6"))
+            .unwrap();
+        let mut event = create_test_event("RustConf");
+        event.category = Some("Conferences".to_string());
+        let imported = ctx.event_service().create(event).unwrap();
+        let id = imported.id.unwrap();
+
+        let mut manager = UndoManager::new();
+        manager.push(Box::new(
+            ImportEventsCommand::new(vec![imported], "file")
+                .with_categories(vec![CategoryCommand::created(category)]),
+        ));
+        manager.undo(&mut ctx).unwrap();
+        assert!(ctx.event_service().get(id).unwrap().is_none());
+        assert!(categories.get_by_name("Conferences").unwrap().is_none());
+
+        manager.redo(&mut ctx).unwrap();
+        assert!(categories.get_by_name("Conferences").unwrap().is_some());
+        let restored = ctx.event_service().get(id).unwrap().unwrap();
+        assert_eq!(restored.category.as_deref(), Some("Conferences"));
+    }
+
+    #[test]
+    fn test_undoing_category_delete_restores_it_on_its_events() {
+        use crate::models::category::Category;
+        use crate::services::category::CategoryService;
+
+        let db = leaked_test_db();
+        let mut countdowns = CountdownService::new();
+        let mut ctx = CommandContext::new(db.connection(), &mut countdowns);
+        let categories = CategoryService::new(db.connection());
+
+        let category = categories
+            .create(Category::new("Hobby", "#22AA44"))
+            .unwrap();
+        let mut event = create_test_event("Climbing");
+        event.category = Some("Hobby".to_string());
+        let event_id = ctx.event_service().create(event).unwrap().id.unwrap();
+
+        let affected = categories.event_ids_using("Hobby").unwrap();
+        let mut manager = UndoManager::new();
+        manager
+            .execute(
+                Box::new(CategoryCommand::deleted(category, affected)),
+                &mut ctx,
+            )
+            .unwrap();
+        assert!(categories.get_by_name("Hobby").unwrap().is_none());
+
+        manager.undo(&mut ctx).unwrap();
+        assert!(categories.get_by_name("Hobby").unwrap().is_some());
+        let restored = ctx.event_service().get(event_id).unwrap().unwrap();
+        assert_eq!(restored.category.as_deref(), Some("Hobby"));
+
+        // Redo deletes the re-created row, which has a new ID.
+        manager.redo(&mut ctx).unwrap();
+        assert!(categories.get_by_name("Hobby").unwrap().is_none());
+    }
 }
@@ -0,0 +1,103 @@
+//! Commands for editing single occurrences of a recurring event.
+
+use super::{persisted_command, Command, CommandContext};
+use crate::models::event::Event;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+/// Command for deleting one occurrence of a series (adds an exception date)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteOccurrenceCommand {
+    /// The series before the occurrence was removed
+    parent_before: Event,
+    occurrence_date: DateTime<Local>,
+}
+
+impl DeleteOccurrenceCommand {
+    pub fn new(parent_before: Event, occurrence_date: DateTime<Local>) -> Self {
+        Self {
+            parent_before,
+            occurrence_date,
+        }
+    }
+}
+
+impl Command for DeleteOccurrenceCommand {
+    fn execute(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        let id = self
+            .parent_before
+            .id
+            .ok_or_else(|| anyhow!("Series has no id"))?;
+        ctx.event_service()
+            .delete_occurrence_local(id, self.occurrence_date)
+    }
+
+    fn undo(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        ctx.event_service().update_local(&self.parent_before)
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "Delete occurrence of \"{}\" on {}",
+            self.parent_before.title,
+            self.occurrence_date.format("%b %d")
+        )
+    }
+
+    persisted_command!("delete_occurrence");
+}
+
+/// Command for editing one occurrence, which detaches it into its own event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetachOccurrenceCommand {
+    /// The series before the occurrence was detached
+    parent_before: Event,
+    occurrence_date: DateTime<Local>,
+    /// The standalone event (with ID set after execution)
+    detached: Event,
+}
+
+impl DetachOccurrenceCommand {
+    pub fn new(parent_before: Event, occurrence_date: DateTime<Local>, detached: Event) -> Self {
+        Self {
+            parent_before,
+            occurrence_date,
+            detached,
+        }
+    }
+}
+
+impl Command for DetachOccurrenceCommand {
+    fn execute(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        let parent_id = self
+            .parent_before
+            .id
+            .ok_or_else(|| anyhow!("Series has no id"))?;
+        let created = ctx.event_service().detach_occurrence_local(
+            parent_id,
+            self.occurrence_date,
+            self.detached.clone(),
+        )?;
+        self.detached.id = created.id;
+        Ok(())
+    }
+
+    fn undo(&mut self, ctx: &mut CommandContext) -> Result<()> {
+        let event_service = ctx.event_service();
+        if let Some(id) = self.detached.id {
+            event_service.delete_local(id)?;
+        }
+        event_service.update_local(&self.parent_before)
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "Edit occurrence of \"{}\" on {}",
+            self.parent_before.title,
+            self.occurrence_date.format("%b %d")
+        )
+    }
+
+    persisted_command!("detach_occurrence");
+}
@@ -0,0 +1,123 @@
+//! Persistence of the undo/redo stacks in the `undo_history` table.
+//!
+//! Each row holds one command as its type tag plus JSON state. Moving a
+//! command between stacks deletes its row and inserts a new one, so the top
+//! of each stack is always its highest id.
+
+use super::{
+    CategoryCommand, Command, CountdownCardSettingsCommand, CreateEventCommand,
+    DeleteCountdownCardCommand, DeleteEventCommand, DeleteEventsCommand, DeleteOccurrenceCommand,
+    DetachOccurrenceCommand, ImportEventsCommand, TemplateCommand, UndoEntry, UpdateEventCommand,
+};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Local};
+use rusqlite::{params, Connection};
+use serde::de::DeserializeOwned;
+
+pub(super) const UNDO: &str = "undo";
+pub(super) const REDO: &str = "redo";
+
+pub(super) fn insert(conn: &Connection, stack: &str, entry: &UndoEntry) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO undo_history (stack, command_type, description, payload_json, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            stack,
+            entry.command.kind(),
+            entry.command.description(),
+            entry.command.to_json()?,
+            entry.recorded_at.to_rfc3339(),
+        ],
+    )
+    .context("Failed to save undo entry")?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub(super) fn delete(conn: &Connection, row_id: i64) -> Result<()> {
+    conn.execute("DELETE FROM undo_history WHERE id = ?1", [row_id])
+        .context("Failed to delete undo entry")?;
+    Ok(())
+}
+
+pub(super) fn clear_stack(conn: &Connection, stack: &str) -> Result<()> {
+    conn.execute("DELETE FROM undo_history WHERE stack = ?1", [stack])
+        .context("Failed to clear undo stack")?;
+    Ok(())
+}
+
+pub(super) fn clear_all(conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM undo_history", [])
+        .context("Failed to clear undo history")?;
+    Ok(())
+}
+
+/// Load both stacks, bottom first. Rows that no longer decode (for example
+/// after a command type was removed) are dropped.
+pub(super) fn load(conn: &Connection) -> Result<(Vec<UndoEntry>, Vec<UndoEntry>)> {
+    let mut stmt = conn.prepare(
+        "SELECT id, stack, command_type, payload_json, created_at
+         FROM undo_history ORDER BY id",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to load undo history")?;
+
+    let mut undo_stack = Vec::new();
+    let mut redo_stack = Vec::new();
+    for (row_id, stack, kind, payload, created_at) in rows {
+        let command = match decode_command(&kind, &payload) {
+            Ok(command) => command,
+            Err(e) => {
+                log::warn!("Dropping undo entry {}: {}", row_id, e);
+                delete(conn, row_id)?;
+                continue;
+            }
+        };
+        let entry = UndoEntry {
+            row_id: Some(row_id),
+            command,
+            recorded_at: DateTime::parse_from_rfc3339(&created_at)
+                .map(|dt| dt.with_timezone(&Local))
+                .unwrap_or_else(|_| Local::now()),
+        };
+        if stack == REDO {
+            redo_stack.push(entry);
+        } else {
+            undo_stack.push(entry);
+        }
+    }
+
+    Ok((undo_stack, redo_stack))
+}
+
+fn decode_command(kind: &str, payload: &str) -> Result<Box<dyn Command + Send + Sync>> {
+    fn decode<T: Command + Send + Sync + DeserializeOwned + 'static>(
+        payload: &str,
+    ) -> Result<Box<dyn Command + Send + Sync>> {
+        Ok(Box::new(serde_json::from_str::<T>(payload)?))
+    }
+
+    match kind {
+        "create_event" => decode::<CreateEventCommand>(payload),
+        "update_event" => decode::<UpdateEventCommand>(payload),
+        "delete_event" => decode::<DeleteEventCommand>(payload),
+        "delete_occurrence" => decode::<DeleteOccurrenceCommand>(payload),
+        "detach_occurrence" => decode::<DetachOccurrenceCommand>(payload),
+        "import_events" => decode::<ImportEventsCommand>(payload),
+        "delete_events" => decode::<DeleteEventsCommand>(payload),
+        "category" => decode::<CategoryCommand>(payload),
+        "template" => decode::<TemplateCommand>(payload),
+        "countdown_card_settings" => decode::<CountdownCardSettingsCommand>(payload),
+        "delete_countdown_card" => decode::<DeleteCountdownCardCommand>(payload),
+        other => Err(anyhow!("unknown command type '{}'", other)),
+    }
+}
@@ -1,3 +1,4 @@
+use crate::services::backup::policy::BackupPolicy;
 use crate::services::backup::{BackupInfo, BackupService};
 use anyhow::Result;
 use egui::{Color32, RichText};
@@ -11,11 +12,15 @@ pub struct BackupManagerState {
     confirm_restore_index: Option<usize>,
     confirm_delete_index: Option<usize>,
     db_path: PathBuf,
+    /// Folder and encryption come from the backup policy in settings.
+    policy: BackupPolicy,
+    /// Passphrase typed in while confirming restore of an encrypted backup
+    restore_passphrase: String,
 }
 
 impl BackupManagerState {
-    pub fn new(db_path: PathBuf) -> Self {
-        let backups = BackupService::list_backups(None).unwrap_or_default();
+    pub fn new(db_path: PathBuf, policy: BackupPolicy) -> Self {
+        let backups = BackupService::list_backups(policy.directory.as_deref()).unwrap_or_default();
         Self {
             show_dialog: false,
             backups,
@@ -24,6 +29,16 @@ impl BackupManagerState {
             confirm_restore_index: None,
             confirm_delete_index: None,
             db_path,
+            policy,
+            restore_passphrase: String::new(),
+        }
+    }
+
+    pub fn set_policy(&mut self, policy: BackupPolicy) {
+        let dir_changed = self.policy.directory != policy.directory;
+        self.policy = policy;
+        if dir_changed {
+            self.refresh_backups();
         }
     }
 
@@ -38,10 +53,11 @@ impl BackupManagerState {
         self.clear_messages();
         self.confirm_restore_index = None;
         self.confirm_delete_index = None;
+        self.restore_passphrase.clear();
     }
 
     pub fn refresh_backups(&mut self) {
-        match BackupService::list_backups(None) {
+        match BackupService::list_backups(self.policy.directory.as_deref()) {
             Ok(backups) => {
                 self.backups = backups;
                 self.error_message = None;
@@ -58,7 +74,7 @@ impl BackupManagerState {
     }
 
     pub fn create_backup(&mut self) -> Result<()> {
-        let backup_path = BackupService::create_backup(&self.db_path, None)?;
+        let backup_path = self.policy.create_backup(&self.db_path)?;
         self.success_message = Some(format!(
             "Backup created successfully: {}",
             backup_path.file_name().unwrap().to_string_lossy()
@@ -73,7 +89,11 @@ impl BackupManagerState {
         }
 
         let backup_info = &self.backups[index];
-        BackupService::restore_backup(&backup_info.path, &self.db_path)?;
+        let passphrase = backup_info
+            .encrypted
+            .then_some(self.restore_passphrase.as_str());
+        BackupService::restore_backup(&backup_info.path, &self.db_path, passphrase)?;
+        self.restore_passphrase.clear();
 
         self.success_message = Some(format!(
             "Database restored from backup: {}",
@@ -153,7 +173,7 @@ pub fn render_backup_manager_dialog(ctx: &egui::Context, state: &mut BackupManag
                 ui.add_space(4.0);
 
                 // Backup location info
-                if let Ok(backup_dir) = BackupService::default_backup_dir() {
+                if let Ok(backup_dir) = state.policy.backup_dir() {
                     ui.horizontal(|ui| {
                         ui.label(RichText::new("Backup Location:").strong());
                         ui.label(backup_dir.to_string_lossy().to_string());
@@ -245,6 +265,9 @@ fn render_backup_item(
                 ui.vertical(|ui| {
                     ui.horizontal(|ui| {
                         ui.label(RichText::new(&backup.filename).strong());
+                        if backup.encrypted {
+                            ui.label(RichText::new("🔒 Encrypted").weak());
+                        }
                     });
 
                     ui.horizontal(|ui| {
@@ -264,6 +287,7 @@ fn render_backup_item(
                         // Confirmation buttons for restore
                         if ui.button("❌ Cancel").clicked() {
                             state.confirm_restore_index = None;
+                            state.restore_passphrase.clear();
                             state.clear_messages();
                         }
                         if ui
@@ -284,6 +308,14 @@ fn render_backup_item(
                                 }
                             }
                         }
+                        if backup.encrypted {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut state.restore_passphrase)
+                                    .password(true)
+                                    .hint_text("Backup passphrase")
+                                    .desired_width(140.0),
+                            );
+                        }
                         ui.label(
                             RichText::new("⚠ This will overwrite the current database!")
                                 .color(Color32::from_rgb(255, 165, 0)),
@@ -3,6 +3,7 @@
 use crate::models::category::Category;
 use crate::services::category::CategoryService;
 use crate::services::database::Database;
+use crate::ui_egui::commands::CategoryCommand;
 use egui::{Color32, RichText};
 
 /// State for the category management dialog.
@@ -83,6 +84,8 @@ impl CategoryManagerState {
 pub struct CategoryManagerResponse {
     /// Whether categories were modified
     pub categories_changed: bool,
+    /// Undo record for the change made this frame
+    pub undo_command: Option<CategoryCommand>,
 }
 
 /// Render the category management dialog.
@@ -349,8 +352,13 @@ pub fn render_category_manager_dialog(
                                 .button(RichText::new("Yes, Delete").color(Color32::RED))
                                 .clicked()
                             {
+                                let before = service.get_by_id(id);
+                                let affected = service.event_ids_using(&name).unwrap_or_default();
                                 match service.delete(id) {
                                     Ok(_) => {
+                                        response.undo_command = before
+                                            .ok()
+                                            .map(|c| CategoryCommand::deleted(c, affected));
                                         state.success_message = Some(format!("Deleted '{}'", name));
                                         response.categories_changed = true;
                                         state.needs_refresh = true;
@@ -431,8 +439,11 @@ fn handle_save(
         editing.color = state.color_input.clone();
         editing.icon = icon;
 
+        let before = editing.id.and_then(|id| service.get_by_id(id).ok());
         match service.update(editing) {
             Ok(_) => {
+                response.undo_command =
+                    before.map(|before| CategoryCommand::updated(before, editing.clone()));
                 state.success_message = Some("Category updated".to_string());
                 response.categories_changed = true;
                 state.needs_refresh = true;
@@ -448,6 +459,7 @@ fn handle_save(
 
         match service.create(new_cat) {
             Ok(created) => {
+                response.undo_command = Some(CategoryCommand::created(created.clone()));
                 state.success_message = Some(format!("Created '{}'", created.name));
                 response.categories_changed = true;
                 state.needs_refresh = true;
@@ -0,0 +1,295 @@
+// CSV Import Dialog
+// UI for mapping the columns of a spreadsheet export to event fields
+
+use egui::{Color32, RichText};
+
+use crate::services::csv::import::{
+    to_events, CsvColumnMapping, CsvField, CsvImport, CsvRowError, CsvTable,
+};
+use crate::services::icalendar::import::ImportedIcsEvent;
+
+/// Date formats offered for the date columns, as in the settings dialog
+const DATE_FORMATS: [&str; 3] = ["DD/MM/YYYY", "MM/DD/YYYY", "YYYY-MM-DD"];
+
+/// Rows shown in the preview grid
+const PREVIEW_ROWS: usize = 5;
+
+/// State for the CSV import dialog
+pub struct CsvImportDialogState {
+    pub is_open: bool,
+    pub file_name: String,
+    table: CsvTable,
+    mapping: CsvColumnMapping,
+    date_format: String,
+    /// The rows converted with the current mapping, or why they can't be
+    preview: Result<CsvImport, String>,
+}
+
+impl Default for CsvImportDialogState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            file_name: String::new(),
+            table: CsvTable::default(),
+            mapping: CsvColumnMapping::default(),
+            date_format: DATE_FORMATS[0].to_string(),
+            preview: Ok(CsvImport::default()),
+        }
+    }
+}
+
+/// Action result from the CSV import dialog
+pub enum CsvImportAction {
+    /// No action
+    None,
+    /// Import the converted events; `skipped` lists rows that failed
+    Import {
+        events: Vec<ImportedIcsEvent>,
+        skipped: Vec<CsvRowError>,
+        source_label: String,
+    },
+}
+
+impl CsvImportDialogState {
+    /// Open the dialog for a parsed file, guessing the mapping from its
+    /// headers and starting with the user's date format.
+    pub fn open(&mut self, table: CsvTable, file_name: &str, date_format: &str) {
+        self.mapping = CsvColumnMapping::guess(&table.headers);
+        self.table = table;
+        self.file_name = file_name.to_string();
+        self.date_format = DATE_FORMATS
+            .iter()
+            .find(|format| format.eq_ignore_ascii_case(date_format))
+            .unwrap_or(&DATE_FORMATS[0])
+            .to_string();
+        self.refresh_preview();
+        self.is_open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.table = CsvTable::default();
+        self.preview = Ok(CsvImport::default());
+    }
+
+    fn refresh_preview(&mut self) {
+        self.preview =
+            to_events(&self.table, &self.mapping, &self.date_format).map_err(|e| e.to_string());
+    }
+
+    /// Hand over the converted events and close the dialog.
+    fn take_import(&mut self) -> CsvImportAction {
+        let preview = std::mem::replace(&mut self.preview, Ok(CsvImport::default()));
+        let action = match preview {
+            Ok(import) => CsvImportAction::Import {
+                events: import.events,
+                skipped: import.errors,
+                source_label: format!("CSV import ({})", self.file_name),
+            },
+            Err(_) => CsvImportAction::None,
+        };
+        self.close();
+        action
+    }
+}
+
+/// Render the CSV import dialog
+pub fn render_csv_import_dialog(
+    ctx: &egui::Context,
+    state: &mut CsvImportDialogState,
+) -> CsvImportAction {
+    if !state.is_open {
+        return CsvImportAction::None;
+    }
+
+    let mut action = CsvImportAction::None;
+    let mut dialog_open = state.is_open;
+    let mut cancelled = false;
+    let mapping_before = state.mapping.clone();
+    let date_format_before = state.date_format.clone();
+
+    egui::Window::new("📥 Import CSV")
+        .open(&mut dialog_open)
+        .collapsible(false)
+        .resizable(true)
+        .default_width(620.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(
+                RichText::new(format!(
+                    "{} row(s) in {}. Choose the column for each field.",
+                    state.table.rows.len(),
+                    state.file_name
+                ))
+                .italics()
+                .color(Color32::GRAY),
+            );
+            ui.add_space(4.0);
+
+            egui::Grid::new("csv_import_mapping_grid")
+                .num_columns(2)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    for field in CsvField::ALL {
+                        render_field_mapping(ui, state, field);
+                        ui.end_row();
+                    }
+
+                    ui.label("Date format:");
+                    egui::ComboBox::from_id_source("csv_import_date_format")
+                        .selected_text(&state.date_format)
+                        .show_ui(ui, |ui| {
+                            for format in DATE_FORMATS {
+                                ui.selectable_value(
+                                    &mut state.date_format,
+                                    format.to_string(),
+                                    format,
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text("Dates in YYYY-MM-DD form are always accepted too");
+                    ui.end_row();
+                });
+
+            ui.add_space(8.0);
+            ui.separator();
+            render_preview(ui, &state.preview);
+
+            ui.add_space(8.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                let ready = state
+                    .preview
+                    .as_ref()
+                    .map_or(0, |import| import.events.len());
+                if ui
+                    .add_enabled(
+                        ready > 0,
+                        egui::Button::new(format!("Import {} Event(s)...", ready)),
+                    )
+                    .on_hover_text("Review the events before they are added")
+                    .clicked()
+                {
+                    action = state.take_import();
+                }
+                if ui.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if state.mapping != mapping_before || state.date_format != date_format_before {
+        state.refresh_preview();
+    }
+    if !dialog_open || cancelled {
+        state.close();
+    }
+
+    action
+}
+
+fn render_field_mapping(ui: &mut egui::Ui, state: &mut CsvImportDialogState, field: CsvField) {
+    let label = if field.is_required() {
+        format!("{} *", field.label())
+    } else {
+        field.label().to_string()
+    };
+    ui.label(label);
+
+    let headers = &state.table.headers;
+    let mut column = state.mapping.column(field);
+    let column_name = |index: usize| match headers.get(index) {
+        Some(header) if !header.is_empty() => header.clone(),
+        _ => format!("Column {}", index + 1),
+    };
+    let selected_text = column.map_or_else(|| "(not imported)".to_string(), column_name);
+
+    egui::ComboBox::from_id_source(("csv_import_field", field.label()))
+        .selected_text(selected_text)
+        .width(220.0)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut column, None, "(not imported)");
+            for index in 0..headers.len() {
+                let sample = state
+                    .table
+                    .rows
+                    .first()
+                    .and_then(|row| row.get(index))
+                    .map(|cell| cell.trim())
+                    .filter(|cell| !cell.is_empty());
+                let text = match sample {
+                    Some(sample) => format!("{}  —  e.g. {}", column_name(index), sample),
+                    None => column_name(index),
+                };
+                ui.selectable_value(&mut column, Some(index), text);
+            }
+        });
+    state.mapping.set_column(field, column);
+}
+
+fn render_preview(ui: &mut egui::Ui, preview: &Result<CsvImport, String>) {
+    let import = match preview {
+        Ok(import) => import,
+        Err(message) => {
+            ui.colored_label(Color32::from_rgb(230, 160, 40), message);
+            return;
+        }
+    };
+
+    ui.label(RichText::new("Preview").strong());
+    if import.events.is_empty() {
+        ui.label(RichText::new("No rows could be read as events").color(Color32::GRAY));
+    } else {
+        egui::Grid::new("csv_import_preview_grid")
+            .num_columns(4)
+            .striped(true)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                ui.label(RichText::new("Event").strong());
+                ui.label(RichText::new("Start").strong());
+                ui.label(RichText::new("End").strong());
+                ui.label(RichText::new("Category").strong());
+                ui.end_row();
+
+                for imported in import.events.iter().take(PREVIEW_ROWS) {
+                    let event = &imported.event;
+                    ui.label(&event.title);
+                    if event.all_day {
+                        let last_day = event.end - chrono::Duration::days(1);
+                        ui.label(event.start.format("%b %d, %Y").to_string());
+                        ui.label(last_day.format("%b %d, %Y").to_string());
+                    } else {
+                        ui.label(event.start.format("%b %d, %Y %H:%M").to_string());
+                        ui.label(event.end.format("%b %d, %Y %H:%M").to_string());
+                    }
+                    ui.label(event.category.as_deref().unwrap_or("—"));
+                    ui.end_row();
+                }
+            });
+        if import.events.len() > PREVIEW_ROWS {
+            ui.label(
+                RichText::new(format!("…and {} more", import.events.len() - PREVIEW_ROWS))
+                    .color(Color32::GRAY),
+            );
+        }
+    }
+
+    if !import.errors.is_empty() {
+        ui.add_space(4.0);
+        egui::CollapsingHeader::new(
+            RichText::new(format!("⚠ {} row(s) will be skipped", import.errors.len()))
+                .color(Color32::from_rgb(230, 160, 40)),
+        )
+        .id_source("csv_import_errors")
+        .show(ui, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(120.0)
+                .show(ui, |ui| {
+                    for error in &import.errors {
+                        ui.label(format!("Line {}: {}", error.line, error.message));
+                    }
+                });
+        });
+    }
+}
@@ -1,5 +1,7 @@
-//! Export date range dialog for exporting events to .ics files.
+//! Export date range dialog for exporting events to .ics, jCal, JSON or .csv
+//! files.
 
+use crate::services::icalendar::EventFileFormat;
 use chrono::{Datelike, Local, NaiveDate};
 use egui::Context;
 
@@ -10,7 +12,26 @@ pub enum ExportDialogResult {
     /// User cancelled the dialog
     Cancelled,
     /// User confirmed export with the given date range
-    Export { start: NaiveDate, end: NaiveDate },
+    Export {
+        start: NaiveDate,
+        end: NaiveDate,
+        format: ExportFormat,
+    },
+}
+
+/// File format of a date range export
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One of the event file formats, with recurring events as series
+    Events(EventFileFormat),
+    /// Spreadsheet rows, one per occurrence
+    Csv,
+}
+
+impl Default for ExportFormat {
+    fn default() -> Self {
+        ExportFormat::Events(EventFileFormat::default())
+    }
 }
 
 /// Which date picker is currently active
@@ -24,6 +45,7 @@ enum ActiveDatePicker {
 pub struct ExportDialogState {
     pub start_date: NaiveDate,
     pub end_date: NaiveDate,
+    pub format: ExportFormat,
     active_picker: Option<ActiveDatePicker>,
     viewing_date: NaiveDate,
 }
@@ -36,6 +58,7 @@ impl Default for ExportDialogState {
         Self {
             start_date: start,
             end_date: end,
+            format: ExportFormat::default(),
             active_picker: None,
             viewing_date: today,
         }
@@ -197,6 +220,33 @@ pub fn render_export_range_dialog(
                 }
             });
 
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                ui.label("Format:");
+                ui.radio_value(
+                    &mut state.format,
+                    ExportFormat::Events(EventFileFormat::Ics),
+                    "iCalendar (.ics)",
+                );
+                ui.radio_value(&mut state.format, ExportFormat::Csv, "CSV (.csv)")
+                    .on_hover_text("One row per occurrence, for spreadsheets");
+            });
+            ui.horizontal(|ui| {
+                ui.add_space(52.0);
+                ui.radio_value(
+                    &mut state.format,
+                    ExportFormat::Events(EventFileFormat::Jcal),
+                    "jCal (.jcal)",
+                )
+                .on_hover_text("iCalendar as JSON (RFC 7265)");
+                ui.radio_value(
+                    &mut state.format,
+                    ExportFormat::Events(EventFileFormat::Json),
+                    "JSON (.json)",
+                )
+                .on_hover_text("Simple JSON with the full event, for scripts");
+            });
+
             // Validation
             let validation_error = if state.end_date < state.start_date {
                 Some("End date must be after start date")
@@ -222,6 +272,7 @@ pub fn render_export_range_dialog(
                         result = ExportDialogResult::Export {
                             start: state.start_date,
                             end: state.end_date,
+                            format: state.format,
                         };
                     }
                 });
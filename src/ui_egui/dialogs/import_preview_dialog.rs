@@ -0,0 +1,406 @@
+// Import Preview Dialog
+// UI for choosing which events of an .ics file to import and how its categories map
+
+use egui::{Color32, RichText};
+
+use crate::services::category::CategoryService;
+use crate::services::database::Database;
+use crate::services::event::ics_import::{IcsImportPreview, IcsImportStatus};
+use crate::services::event::EventService;
+use crate::services::icalendar::import::ImportedIcsEvent;
+
+/// One parsed event with its checkbox and conflict flags
+pub struct ImportPreviewRow {
+    pub imported: ImportedIcsEvent,
+    pub preview: IcsImportPreview,
+    pub selected: bool,
+}
+
+/// Where an ICS category goes on import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CategoryMapping {
+    /// Use this existing category
+    Existing(String),
+    /// Create a category with the ICS name
+    Create,
+    /// Import the events without a category
+    Drop,
+}
+
+/// An ICS category found in the file and the user's choice for it
+pub struct CategoryMappingRow {
+    pub ics_name: String,
+    pub mapping: CategoryMapping,
+}
+
+/// State for the import preview dialog
+#[derive(Default)]
+pub struct ImportPreviewDialogState {
+    pub is_open: bool,
+    pub source_label: String,
+    pub rows: Vec<ImportPreviewRow>,
+    pub category_mappings: Vec<CategoryMappingRow>,
+    /// Names of the categories already in the database
+    pub existing_categories: Vec<String>,
+}
+
+/// The events and category choices confirmed in the dialog
+pub struct ImportSelection {
+    pub events: Vec<ImportedIcsEvent>,
+    pub category_mappings: Vec<CategoryMappingRow>,
+    pub source_label: String,
+}
+
+impl ImportSelection {
+    /// ICS categories mapped to a new category and used by a selected event
+    pub fn categories_to_create(&self) -> Vec<String> {
+        self.category_mappings
+            .iter()
+            .filter(|row| row.mapping == CategoryMapping::Create)
+            .filter(|row| {
+                self.events
+                    .iter()
+                    .any(|e| e.event.category.as_deref().map(str::trim) == Some(&row.ics_name))
+            })
+            .map(|row| row.ics_name.clone())
+            .collect()
+    }
+
+    /// Rewrite each event's category according to the chosen mappings
+    pub fn apply_category_mappings(&mut self) {
+        for imported in &mut self.events {
+            let Some(name) = imported.event.category.as_deref().map(str::trim) else {
+                continue;
+            };
+            let Some(row) = self.category_mappings.iter().find(|m| m.ics_name == name) else {
+                continue;
+            };
+            imported.event.category = match &row.mapping {
+                CategoryMapping::Existing(existing) => Some(existing.clone()),
+                CategoryMapping::Create => Some(row.ics_name.clone()),
+                CategoryMapping::Drop => None,
+            };
+        }
+    }
+}
+
+/// Action result from the import preview dialog
+pub enum ImportPreviewAction {
+    /// No action
+    None,
+    /// Import the selected events
+    Import(ImportSelection),
+}
+
+impl ImportPreviewDialogState {
+    /// Open the dialog for `events`, working out what importing each would do.
+    /// New and updated events start selected; up-to-date ones do not.
+    pub fn open(&mut self, database: &Database, events: Vec<ImportedIcsEvent>, source_label: &str) {
+        let event_service = EventService::new(database.connection());
+        self.rows = events
+            .into_iter()
+            .map(|imported| {
+                let preview = event_service
+                    .preview_ics_event(&imported)
+                    .unwrap_or_else(|e| {
+                        log::error!(
+                            "Failed to preview imported event '{}': {}",
+                            imported.event.title,
+                            e
+                        );
+                        IcsImportPreview {
+                            status: IcsImportStatus::New,
+                            existing: None,
+                            overlaps: Vec::new(),
+                        }
+                    });
+                ImportPreviewRow {
+                    selected: preview.status != IcsImportStatus::Unchanged,
+                    imported,
+                    preview,
+                }
+            })
+            .collect();
+
+        self.existing_categories = CategoryService::new(database.connection())
+            .list_all()
+            .map(|categories| categories.into_iter().map(|c| c.name).collect())
+            .unwrap_or_else(|e| {
+                log::error!("Failed to load categories for import preview: {}", e);
+                Vec::new()
+            });
+
+        self.category_mappings.clear();
+        for row in &self.rows {
+            let Some(name) = row.imported.event.category.as_deref().map(str::trim) else {
+                continue;
+            };
+            if name.is_empty() || self.category_mappings.iter().any(|m| m.ics_name == name) {
+                continue;
+            }
+            let mapping = match self
+                .existing_categories
+                .iter()
+                .find(|existing| existing.eq_ignore_ascii_case(name))
+            {
+                Some(existing) => CategoryMapping::Existing(existing.clone()),
+                None => CategoryMapping::Create,
+            };
+            self.category_mappings.push(CategoryMappingRow {
+                ics_name: name.to_string(),
+                mapping,
+            });
+        }
+
+        self.source_label = source_label.to_string();
+        self.is_open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.rows.clear();
+        self.category_mappings.clear();
+    }
+
+    fn selected_count(&self) -> usize {
+        self.rows.iter().filter(|row| row.selected).count()
+    }
+
+    fn select_where(&mut self, predicate: impl Fn(&ImportPreviewRow) -> bool) {
+        for row in &mut self.rows {
+            row.selected = predicate(row);
+        }
+    }
+
+    /// Hand over the selected events and close the dialog.
+    fn take_selection(&mut self) -> ImportSelection {
+        let rows = std::mem::take(&mut self.rows);
+        let selection = ImportSelection {
+            events: rows
+                .into_iter()
+                .filter(|row| row.selected)
+                .map(|row| row.imported)
+                .collect(),
+            category_mappings: std::mem::take(&mut self.category_mappings),
+            source_label: std::mem::take(&mut self.source_label),
+        };
+        self.close();
+        selection
+    }
+}
+
+/// Render the import preview dialog
+pub fn render_import_preview_dialog(
+    ctx: &egui::Context,
+    state: &mut ImportPreviewDialogState,
+) -> ImportPreviewAction {
+    if !state.is_open {
+        return ImportPreviewAction::None;
+    }
+
+    let mut action = ImportPreviewAction::None;
+    let mut dialog_open = state.is_open;
+    let mut cancelled = false;
+
+    egui::Window::new("📥 Import Preview")
+        .open(&mut dialog_open)
+        .collapsible(false)
+        .resizable(true)
+        .default_width(680.0)
+        .default_height(520.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(
+                RichText::new(format!(
+                    "{} event(s) found in {}. Choose which to import.",
+                    state.rows.len(),
+                    state.source_label
+                ))
+                .italics()
+                .color(Color32::GRAY),
+            );
+
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Select All").clicked() {
+                    state.select_where(|_| true);
+                }
+                if ui.button("Select None").clicked() {
+                    state.select_where(|_| false);
+                }
+                if ui
+                    .button("Only New & Updated")
+                    .on_hover_text("Skip events that are already up to date")
+                    .clicked()
+                {
+                    state.select_where(|row| row.preview.status != IcsImportStatus::Unchanged);
+                }
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .id_source("import_preview_events")
+                .max_height(280.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    egui::Grid::new("import_preview_grid")
+                        .num_columns(5)
+                        .striped(true)
+                        .spacing([12.0, 4.0])
+                        .show(ui, |ui| {
+                            ui.label("");
+                            ui.label(RichText::new("Event").strong());
+                            ui.label(RichText::new("When").strong());
+                            ui.label(RichText::new("Category").strong());
+                            ui.label(RichText::new("Status").strong());
+                            ui.end_row();
+
+                            for row in &mut state.rows {
+                                render_event_row(ui, row);
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            if !state.category_mappings.is_empty() {
+                ui.add_space(8.0);
+                ui.label(RichText::new("Categories").strong());
+                egui::ScrollArea::vertical()
+                    .id_source("import_preview_categories")
+                    .max_height(120.0)
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        egui::Grid::new("import_preview_category_grid")
+                            .num_columns(2)
+                            .spacing([12.0, 4.0])
+                            .show(ui, |ui| {
+                                for mapping in &mut state.category_mappings {
+                                    render_category_mapping(
+                                        ui,
+                                        mapping,
+                                        &state.existing_categories,
+                                    );
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+
+            ui.add_space(8.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                let selected = state.selected_count();
+                if ui
+                    .add_enabled(
+                        selected > 0,
+                        egui::Button::new(format!("Import {} Event(s)", selected)),
+                    )
+                    .clicked()
+                {
+                    action = ImportPreviewAction::Import(state.take_selection());
+                }
+                if ui.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if !dialog_open || cancelled {
+        state.close();
+    }
+
+    action
+}
+
+fn render_event_row(ui: &mut egui::Ui, row: &mut ImportPreviewRow) {
+    let event = &row.imported.event;
+    ui.checkbox(&mut row.selected, "");
+    ui.label(&event.title);
+    let when = if event.all_day {
+        event.start.format("%b %d, %Y").to_string()
+    } else {
+        event.start.format("%b %d, %Y %H:%M").to_string()
+    };
+    if event.recurrence_rule.is_some() {
+        ui.label(format!("{} 🔁", when));
+    } else {
+        ui.label(when);
+    }
+    ui.label(event.category.as_deref().unwrap_or("—"));
+
+    let matched = row
+        .preview
+        .existing
+        .as_ref()
+        .map(|e| {
+            format!(
+                "\nMatches \"{}\" on {}",
+                e.title,
+                e.start.format("%b %d, %Y")
+            )
+        })
+        .unwrap_or_default();
+    ui.horizontal(|ui| {
+        match row.preview.status {
+            IcsImportStatus::New => {
+                ui.colored_label(Color32::from_rgb(80, 170, 90), "New");
+            }
+            IcsImportStatus::Update => {
+                ui.colored_label(Color32::from_rgb(70, 130, 220), "Update")
+                    .on_hover_text(format!(
+                        "A newer copy of an event imported earlier{}",
+                        matched
+                    ));
+            }
+            IcsImportStatus::Unchanged => {
+                ui.colored_label(Color32::GRAY, "Duplicate")
+                    .on_hover_text(format!("Already in the calendar and up to date{}", matched));
+            }
+        }
+        let overlaps = &row.preview.overlaps;
+        if !overlaps.is_empty() {
+            let titles = overlaps
+                .iter()
+                .map(|e| format!("{} ({})", e.title, e.start.format("%H:%M")))
+                .collect::<Vec<_>>()
+                .join("\n");
+            ui.colored_label(
+                Color32::from_rgb(230, 160, 40),
+                format!("⚠ Overlaps {}", overlaps.len()),
+            )
+            .on_hover_text(titles);
+        }
+    });
+}
+
+fn render_category_mapping(
+    ui: &mut egui::Ui,
+    row: &mut CategoryMappingRow,
+    existing_categories: &[String],
+) {
+    ui.label(format!("“{}” →", row.ics_name));
+    let selected_text = match &row.mapping {
+        CategoryMapping::Existing(name) => name.clone(),
+        CategoryMapping::Create => format!("New category “{}”", row.ics_name),
+        CategoryMapping::Drop => "No category".to_string(),
+    };
+    egui::ComboBox::from_id_source(("import_category_mapping", &row.ics_name))
+        .selected_text(selected_text)
+        .width(220.0)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(
+                &mut row.mapping,
+                CategoryMapping::Create,
+                format!("New category “{}”", row.ics_name),
+            );
+            ui.selectable_value(&mut row.mapping, CategoryMapping::Drop, "No category");
+            ui.separator();
+            for name in existing_categories {
+                ui.selectable_value(
+                    &mut row.mapping,
+                    CategoryMapping::Existing(name.clone()),
+                    name,
+                );
+            }
+        });
+}
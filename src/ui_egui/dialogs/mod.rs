@@ -2,8 +2,15 @@ pub mod backup_manager;
 pub mod category_manager;
 pub mod countdown_category_manager;
 pub mod countdown_template_manager;
+pub mod csv_import_dialog;
 pub mod export_dialog;
+pub mod import_preview_dialog;
+pub mod publish_dialog;
 pub mod search_dialog;
+pub mod secret_vault_dialog;
+pub mod sync_conflict_dialog;
 pub mod template_manager;
 pub mod theme_creator;
 pub mod theme_dialog;
+pub mod trash_dialog;
+pub mod undo_history_dialog;
@@ -0,0 +1,223 @@
+// Publish Dialog
+// UI for publishing a date range as a static HTML site
+
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
+use egui::{Color32, RichText};
+use egui_extras::DatePickerButton;
+use std::path::PathBuf;
+
+use crate::models::category::Category;
+
+/// State for the publish dialog
+pub struct PublishDialogState {
+    pub is_open: bool,
+    pub title: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Every category with whether it is published
+    pub categories: Vec<(String, bool)>,
+    pub include_uncategorized: bool,
+    pub include_locations: bool,
+    pub include_descriptions: bool,
+    pub folder: String,
+}
+
+impl Default for PublishDialogState {
+    fn default() -> Self {
+        let today = Local::now().date_naive();
+        let start = today.with_day(1).unwrap_or(today);
+        Self {
+            is_open: false,
+            title: "Calendar".to_string(),
+            start,
+            end: (start + Months::new(3)) - Duration::days(1),
+            categories: Vec::new(),
+            include_uncategorized: true,
+            include_locations: true,
+            include_descriptions: true,
+            folder: String::new(),
+        }
+    }
+}
+
+/// Action result from the publish dialog
+pub enum PublishDialogAction {
+    /// No action
+    None,
+    /// Publish with the dialog's current settings
+    Publish,
+}
+
+impl PublishDialogState {
+    /// Open the dialog listing `categories`, keeping earlier choices for
+    /// categories that still exist and selecting new ones.
+    pub fn open(&mut self, categories: &[Category]) {
+        self.categories = categories
+            .iter()
+            .map(|category| {
+                let selected = self
+                    .categories
+                    .iter()
+                    .find(|(name, _)| *name == category.name)
+                    .is_none_or(|(_, selected)| *selected);
+                (category.name.clone(), selected)
+            })
+            .collect();
+        self.is_open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+    }
+
+    /// The selected category names, or `None` when every category is
+    /// selected so nothing is filtered out
+    pub fn selected_categories(&self) -> Option<Vec<String>> {
+        if self.include_uncategorized && self.categories.iter().all(|(_, selected)| *selected) {
+            return None;
+        }
+        Some(
+            self.categories
+                .iter()
+                .filter(|(_, selected)| *selected)
+                .map(|(name, _)| name.clone())
+                .collect(),
+        )
+    }
+
+    fn validation_error(&self) -> Option<&'static str> {
+        if self.end < self.start {
+            Some("End date must be after start date")
+        } else if self.folder.trim().is_empty() {
+            Some("Choose a folder to publish to")
+        } else if !self.include_uncategorized
+            && self.categories.iter().all(|(_, selected)| !selected)
+        {
+            Some("Select at least one category")
+        } else {
+            None
+        }
+    }
+
+    pub fn folder_path(&self) -> PathBuf {
+        PathBuf::from(self.folder.trim())
+    }
+}
+
+/// Render the publish dialog
+pub fn render_publish_dialog(
+    ctx: &egui::Context,
+    state: &mut PublishDialogState,
+) -> PublishDialogAction {
+    if !state.is_open {
+        return PublishDialogAction::None;
+    }
+
+    let mut action = PublishDialogAction::None;
+    let mut dialog_open = state.is_open;
+    let mut cancelled = false;
+
+    egui::Window::new("🌐 Publish as Web Pages")
+        .open(&mut dialog_open)
+        .collapsible(false)
+        .resizable(false)
+        .default_width(420.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(
+                RichText::new(
+                    "Writes a month grid and an agenda page for each month, styled \
+                     with the current theme, to a calendar-site folder inside the \
+                     folder you choose, ready to copy to a web server.",
+                )
+                .italics()
+                .color(Color32::GRAY),
+            );
+            ui.add_space(6.0);
+
+            egui::Grid::new("publish_dialog_grid")
+                .num_columns(2)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label("Title:");
+                    ui.add(egui::TextEdit::singleline(&mut state.title).desired_width(240.0));
+                    ui.end_row();
+
+                    ui.label("From:");
+                    ui.add(DatePickerButton::new(&mut state.start).id_source("publish_start"));
+                    ui.end_row();
+
+                    ui.label("To:");
+                    ui.add(DatePickerButton::new(&mut state.end).id_source("publish_end"));
+                    ui.end_row();
+
+                    ui.label("Folder:");
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut state.folder).desired_width(200.0));
+                        if ui.button("Browse…").clicked() {
+                            if let Some(folder) = rfd::FileDialog::new()
+                                .set_title("Publish to Folder")
+                                .pick_folder()
+                            {
+                                state.folder = folder.to_string_lossy().to_string();
+                            }
+                        }
+                    });
+                    ui.end_row();
+                });
+
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Categories").strong());
+                if ui.small_button("All").clicked() {
+                    state.categories.iter_mut().for_each(|(_, s)| *s = true);
+                    state.include_uncategorized = true;
+                }
+                if ui.small_button("None").clicked() {
+                    state.categories.iter_mut().for_each(|(_, s)| *s = false);
+                    state.include_uncategorized = false;
+                }
+            });
+            egui::ScrollArea::vertical()
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    for (name, selected) in &mut state.categories {
+                        ui.checkbox(selected, name.as_str());
+                    }
+                    ui.checkbox(
+                        &mut state.include_uncategorized,
+                        "Events without a category",
+                    );
+                });
+
+            ui.add_space(6.0);
+            ui.checkbox(&mut state.include_locations, "Include locations");
+            ui.checkbox(&mut state.include_descriptions, "Include descriptions");
+
+            let validation_error = state.validation_error();
+            if let Some(error) = validation_error {
+                ui.add_space(4.0);
+                ui.colored_label(Color32::from_rgb(230, 160, 40), error);
+            }
+
+            ui.add_space(8.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(validation_error.is_none(), egui::Button::new("Publish"))
+                    .clicked()
+                {
+                    action = PublishDialogAction::Publish;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if !dialog_open || cancelled {
+        state.close();
+    }
+
+    action
+}
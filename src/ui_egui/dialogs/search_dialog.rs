@@ -1,12 +1,13 @@
 //! Search dialog for finding events
 
 use chrono::NaiveDate;
-use egui::{Color32, Margin, RichText, Stroke, Vec2};
+use egui::text::LayoutJob;
+use egui::{Color32, FontId, Margin, RichText, Stroke, TextFormat, Vec2};
 use std::collections::HashMap;
 
-use crate::models::event::Event;
 use crate::services::calendar_sync::mapping::EventSyncMapService;
 use crate::services::database::Database;
+use crate::services::event::queries::{highlight_spans, SearchHit};
 use crate::services::event::EventService;
 use crate::ui_egui::theme::CalendarTheme;
 use crate::ui_egui::views::is_synced_event;
@@ -15,8 +16,23 @@ use crate::ui_egui::views::is_synced_event;
 #[derive(Default)]
 pub struct SearchDialogState {
     pub query: String,
-    pub results: Vec<Event>,
+    /// Query the current `results` were fetched for
+    pub searched_query: String,
+    pub results: Vec<SearchHit>,
+    /// Why the current query could not be run
+    pub error_message: Option<String>,
     pub selected_event: Option<i64>,
+    /// Name to save the current query under
+    pub save_name: String,
+}
+
+impl SearchDialogState {
+    /// Load a saved search into the dialog for running or editing.
+    pub fn open_saved_search(&mut self, name: &str, query: &str) {
+        self.query = query.to_string();
+        self.save_name = name.to_string();
+        self.selected_event = None;
+    }
 }
 
 /// Action result from the search dialog
@@ -29,6 +45,12 @@ pub enum SearchDialogAction {
     EditEvent(i64),
     /// Create a countdown card from the selected event
     CreateCountdown(i64),
+    /// Save the query as a named smart filter
+    SaveSearch { name: String, query: String },
+    /// Filter all calendar views by the query
+    FilterViews(String),
+    /// Delete every event in the results
+    DeleteResults(Vec<i64>),
     /// Close the dialog
     Close,
 }
@@ -49,7 +71,8 @@ pub fn render_search_dialog(
         .unwrap_or_default();
     let mut synced_source_names: HashMap<i64, String> = HashMap::new();
 
-    for event in &state.results {
+    for hit in &state.results {
+        let event = &hit.event;
         if let Some(event_id) = event.id {
             if synced_event_ids.contains(&event_id) {
                 if let Ok(Some(source_name)) =
@@ -77,7 +100,7 @@ pub fn render_search_dialog(
                 let response = ui.add(
                     egui::TextEdit::singleline(&mut state.query)
                         .desired_width(ui.available_width() - 80.0)
-                        .hint_text("Type to search events..."),
+                        .hint_text("Words, \"phrase\", category:Work, after:2026-01-01..."),
                 );
 
                 // Auto-focus on open
@@ -87,31 +110,92 @@ pub fn render_search_dialog(
 
                 if ui.button("Clear").clicked() {
                     state.query.clear();
-                    state.results.clear();
                 }
             });
 
             ui.add_space(8.0);
 
             // Perform search when query changes
-            if !state.query.is_empty() {
+            if state.query != state.searched_query {
+                state.searched_query = state.query.clone();
                 let event_service = EventService::new(database.connection());
-                if let Ok(results) = event_service.search(&state.query) {
-                    state.results = results;
+                match event_service.search(&state.query) {
+                    Ok(results) => {
+                        state.results = results;
+                        state.error_message = None;
+                    }
+                    Err(err) => {
+                        state.results.clear();
+                        state.error_message = Some(format!("{:#}", err));
+                    }
                 }
-            } else {
-                state.results.clear();
             }
 
             // Results count
             if state.query.is_empty() {
                 ui.label(RichText::new("Enter a search term to find events").italics());
+                ui.label(
+                    RichText::new(
+                        "Filters: category:  location:\"…\"  source:  after:YYYY-MM-DD  \
+                         before:YYYY-MM-DD  is:recurring  is:single",
+                    )
+                    .size(10.0)
+                    .color(theme.text_secondary),
+                );
+            } else if let Some(error) = &state.error_message {
+                ui.colored_label(Color32::LIGHT_RED, error);
             } else if state.results.is_empty() {
                 ui.label(RichText::new("No events found").italics());
             } else {
                 ui.label(format!("{} event(s) found", state.results.len()));
             }
 
+            if !state.query.trim().is_empty() && state.error_message.is_none() {
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut state.save_name)
+                            .desired_width(160.0)
+                            .hint_text("Name"),
+                    );
+                    if ui
+                        .add_enabled(
+                            !state.save_name.trim().is_empty(),
+                            egui::Button::new("💾 Save Search"),
+                        )
+                        .on_hover_text("Show this search in the sidebar as a smart filter")
+                        .clicked()
+                    {
+                        action = SearchDialogAction::SaveSearch {
+                            name: state.save_name.trim().to_string(),
+                            query: state.query.trim().to_string(),
+                        };
+                    }
+                    if ui
+                        .button("🔎 Filter Views")
+                        .on_hover_text("Only show matching events in the calendar")
+                        .clicked()
+                    {
+                        action = SearchDialogAction::FilterViews(state.query.trim().to_string());
+                    }
+                    if ui
+                        .add_enabled(
+                            !state.results.is_empty(),
+                            egui::Button::new("🗑 Delete All Results"),
+                        )
+                        .on_hover_text("Move every matching event to the trash")
+                        .clicked()
+                    {
+                        action = SearchDialogAction::DeleteResults(
+                            state
+                                .results
+                                .iter()
+                                .filter_map(|hit| hit.event.id)
+                                .collect(),
+                        );
+                    }
+                });
+            }
+
             ui.separator();
 
             // Results list
@@ -119,7 +203,8 @@ pub fn render_search_dialog(
                 .auto_shrink([false, false])
                 .max_height(350.0)
                 .show(ui, |ui| {
-                    for event in &state.results {
+                    for hit in &state.results {
+                        let event = &hit.event;
                         let is_selected = state.selected_event == event.id;
                         let event_is_synced = is_synced_event(event.id, &synced_event_ids);
                         let source_name = event
@@ -156,11 +241,11 @@ pub fn render_search_dialog(
 
                                     ui.vertical(|ui| {
                                         // Title
-                                        ui.label(
-                                            RichText::new(&event.title)
-                                                .strong()
-                                                .color(theme.text_primary),
-                                        );
+                                        ui.label(highlighted_text(
+                                            &hit.title,
+                                            14.0,
+                                            theme.text_primary,
+                                        ));
 
                                         // Date/time
                                         let date_str = if event.all_day {
@@ -175,16 +260,25 @@ pub fn render_search_dialog(
                                         );
 
                                         // Location if present
-                                        if let Some(ref loc) = event.location {
+                                        if let Some(ref loc) = hit.location {
                                             if !loc.is_empty() {
-                                                ui.label(
-                                                    RichText::new(format!("📍 {}", loc))
-                                                        .size(10.0)
-                                                        .color(theme.text_secondary),
-                                                );
+                                                ui.label(highlighted_text(
+                                                    &format!("📍 {}", loc),
+                                                    10.0,
+                                                    theme.text_secondary,
+                                                ));
                                             }
                                         }
 
+                                        // Matching part of the description
+                                        if let Some(ref snippet) = hit.snippet {
+                                            ui.label(highlighted_text(
+                                                snippet,
+                                                10.0,
+                                                theme.text_secondary,
+                                            ));
+                                        }
+
                                         if event_is_synced {
                                             let label = source_name
                                                 .as_deref()
@@ -269,10 +363,13 @@ pub fn render_search_dialog(
                     let selected_source_name = synced_source_names.get(&event_id).cloned();
 
                     if ui.button("📅 Go to date").clicked() {
-                        if let Some(event) =
-                            state.results.iter().find(|e| e.id == state.selected_event)
+                        if let Some(event) = state
+                            .results
+                            .iter()
+                            .find(|hit| hit.event.id == state.selected_event)
                         {
-                            action = SearchDialogAction::NavigateToDate(event.start.date_naive());
+                            action =
+                                SearchDialogAction::NavigateToDate(event.event.start.date_naive());
                         }
                     }
 
@@ -329,9 +426,33 @@ pub fn render_search_dialog(
         *open = false;
     }
 
+    // The event may change through the action; search again next frame.
+    if !matches!(action, SearchDialogAction::None) {
+        state.searched_query.clear();
+    }
+
     action
 }
 
+/// Lays out search result text with matched terms highlighted.
+fn highlighted_text(text: &str, size: f32, color: Color32) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    for (span, is_match) in highlight_spans(text) {
+        let format = if is_match {
+            TextFormat {
+                font_id: FontId::proportional(size),
+                color,
+                background: Color32::from_rgba_unmultiplied(255, 200, 0, 90),
+                ..Default::default()
+            }
+        } else {
+            TextFormat::simple(FontId::proportional(size), color)
+        };
+        job.append(span, 0.0, format);
+    }
+    job
+}
+
 fn parse_color(hex: &str) -> Option<Color32> {
     if hex.is_empty() {
         return None;
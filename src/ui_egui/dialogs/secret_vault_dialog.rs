@@ -0,0 +1,155 @@
+//! Passphrase prompt for the encrypted secret file.
+//!
+//! Shown at startup when no OS keyring is available. Until the vault is
+//! unlocked, Google sync and private ICS feeds cannot read their credentials.
+
+use egui::{Color32, RichText};
+
+use crate::services::database::Database;
+use crate::services::secrets;
+
+/// State for the secret vault unlock dialog
+#[derive(Default)]
+pub struct SecretVaultDialogState {
+    pub passphrase: String,
+    pub confirm_passphrase: String,
+    pub error_message: Option<String>,
+    /// Set when the user chose to continue without unlocking
+    pub dismissed: bool,
+}
+
+/// Action result from the secret vault dialog
+pub enum SecretVaultDialogAction {
+    /// No action
+    None,
+    /// The vault was unlocked (or created)
+    Unlocked(String),
+    /// Continue without credentials for this session
+    Dismissed,
+}
+
+impl SecretVaultDialogState {
+    /// Whether the dialog should be shown this frame.
+    pub fn is_needed(&self) -> bool {
+        !self.dismissed && secrets::store().needs_passphrase()
+    }
+
+    fn unlock(&mut self, database: &Database, creating: bool) -> anyhow::Result<String> {
+        if creating && self.passphrase != self.confirm_passphrase {
+            return Err(anyhow::anyhow!("Passphrases do not match"));
+        }
+
+        secrets::store().unlock(&self.passphrase)?;
+        self.passphrase.clear();
+        self.confirm_passphrase.clear();
+
+        let moved = secrets::migrate_plaintext_secrets(database.connection())?;
+        Ok(if moved > 0 {
+            format!(
+                "Secret vault unlocked; moved {} stored credentials into it",
+                moved
+            )
+        } else {
+            "Secret vault unlocked".to_string()
+        })
+    }
+}
+
+/// Render the secret vault unlock dialog
+pub fn render_secret_vault_dialog(
+    ctx: &egui::Context,
+    state: &mut SecretVaultDialogState,
+    database: &Database,
+) -> SecretVaultDialogAction {
+    let mut action = SecretVaultDialogAction::None;
+    let mut dialog_open = true;
+    let creating = secrets::store().creates_on_unlock();
+
+    egui::Window::new("Unlock Saved Credentials")
+        .open(&mut dialog_open)
+        .collapsible(false)
+        .resizable(false)
+        .default_width(420.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            if creating {
+                ui.label(
+                    "No system keyring is available, so Google tokens and private calendar \
+                     URLs are kept in an encrypted file. Choose a passphrase to protect it.",
+                );
+            } else {
+                ui.label("Enter the passphrase for your saved Google tokens and calendar URLs.");
+            }
+            ui.add_space(8.0);
+
+            let mut submitted = false;
+            egui::Grid::new("secret_vault_grid")
+                .num_columns(2)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label("Passphrase:");
+                    let response =
+                        ui.add(egui::TextEdit::singleline(&mut state.passphrase).password(true));
+                    submitted |= !creating
+                        && response.lost_focus()
+                        && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    ui.end_row();
+
+                    if creating {
+                        ui.label("Confirm:");
+                        let response = ui.add(
+                            egui::TextEdit::singleline(&mut state.confirm_passphrase)
+                                .password(true),
+                        );
+                        submitted |=
+                            response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        ui.end_row();
+                    }
+                });
+
+            if let Some(error) = &state.error_message {
+                ui.add_space(4.0);
+                ui.colored_label(Color32::LIGHT_RED, error);
+            }
+
+            ui.add_space(4.0);
+            ui.label(
+                RichText::new("Without it, calendar sync is paused for this session.")
+                    .italics()
+                    .color(Color32::GRAY),
+            );
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Skip").clicked() {
+                        action = SecretVaultDialogAction::Dismissed;
+                    }
+                    let label = if creating { "Create" } else { "Unlock" };
+                    if ui.button(label).clicked() || submitted {
+                        match state.unlock(database, creating) {
+                            Ok(message) => {
+                                state.error_message = None;
+                                action = SecretVaultDialogAction::Unlocked(message);
+                            }
+                            Err(err) => {
+                                state.error_message = Some(format!("{:#}", err));
+                            }
+                        }
+                    }
+                });
+            });
+        });
+
+    if !dialog_open {
+        action = SecretVaultDialogAction::Dismissed;
+    }
+
+    if matches!(action, SecretVaultDialogAction::Dismissed) {
+        state.dismissed = true;
+        state.passphrase.clear();
+        state.confirm_passphrase.clear();
+    }
+
+    action
+}
@@ -0,0 +1,201 @@
+//! Three-way merge dialog for resolving a sync conflict field by field.
+
+use egui::{Color32, RichText};
+
+use crate::models::sync_conflict::{
+    compare_fields, merge_snapshots, ConflictField, ConflictFieldState, FieldChoice, SyncConflict,
+};
+use crate::services::database::Database;
+use crate::services::sync_conflict::SyncConflictService;
+
+/// State for an open conflict merge dialog
+pub struct SyncConflictDialogState {
+    pub conflict: SyncConflict,
+    pub fields: Vec<ConflictFieldState>,
+    pub choices: Vec<FieldChoice>,
+    pub error_message: Option<String>,
+}
+
+/// Action result from the conflict merge dialog
+pub enum SyncConflictDialogAction {
+    /// No action
+    None,
+    /// The merged version was saved and the conflict resolved
+    Merged(String),
+    /// Close the dialog without changes
+    Close,
+}
+
+impl SyncConflictDialogState {
+    /// Builds dialog state for a conflict, or `None` if it lacks snapshots.
+    pub fn new(conflict: SyncConflict) -> Option<Self> {
+        let local = conflict.local_snapshot.as_ref()?;
+        let remote = conflict.remote_snapshot.as_ref()?;
+        let fields = compare_fields(conflict.base_snapshot.as_ref(), local, remote);
+        let choices = fields.iter().map(|f| f.suggested_choice()).collect();
+
+        Some(Self {
+            conflict,
+            fields,
+            choices,
+            error_message: None,
+        })
+    }
+
+    fn set_all(&mut self, choice: FieldChoice) {
+        self.choices.fill(choice);
+    }
+
+    fn apply(&self, database: &Database) -> anyhow::Result<()> {
+        let conflict_id = self
+            .conflict
+            .id
+            .ok_or_else(|| anyhow::anyhow!("Conflict is missing an ID"))?;
+        let (Some(local), Some(remote)) = (
+            self.conflict.local_snapshot.as_ref(),
+            self.conflict.remote_snapshot.as_ref(),
+        ) else {
+            return Err(anyhow::anyhow!("Conflict has no snapshots to merge"));
+        };
+
+        let choices: Vec<(ConflictField, FieldChoice)> = self
+            .fields
+            .iter()
+            .zip(self.choices.iter())
+            .map(|(state, choice)| (state.field, *choice))
+            .collect();
+        let merged = merge_snapshots(local, remote, &choices);
+
+        SyncConflictService::new(database.connection()).resolve_with_merge(conflict_id, &merged)
+    }
+}
+
+/// Render the conflict merge dialog
+pub fn render_sync_conflict_dialog(
+    ctx: &egui::Context,
+    state: &mut SyncConflictDialogState,
+    database: &Database,
+) -> SyncConflictDialogAction {
+    let mut action = SyncConflictDialogAction::None;
+    let mut dialog_open = true;
+    let has_base = state.conflict.base_snapshot.is_some();
+
+    egui::Window::new("Resolve Sync Conflict")
+        .open(&mut dialog_open)
+        .collapsible(false)
+        .resizable(true)
+        .default_width(720.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(format!("Event UID: {}", state.conflict.external_uid));
+            if !has_base {
+                ui.label(
+                    RichText::new(
+                        "No last-synced version was recorded, so every difference is shown as a conflict.",
+                    )
+                    .italics()
+                    .color(Color32::GRAY),
+                );
+            }
+            ui.add_space(6.0);
+
+            egui::ScrollArea::vertical()
+                .max_height(360.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("sync_conflict_merge_grid")
+                        .num_columns(5)
+                        .striped(true)
+                        .spacing([12.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label(RichText::new("Field").strong());
+                            ui.label(RichText::new("Last synced").strong());
+                            ui.label(RichText::new("Local").strong());
+                            ui.label(RichText::new("Remote").strong());
+                            ui.label(RichText::new("Keep").strong());
+                            ui.end_row();
+
+                            for (field_state, choice) in
+                                state.fields.iter().zip(state.choices.iter_mut())
+                            {
+                                let label = if field_state.is_conflicting() {
+                                    RichText::new(field_state.field.label())
+                                        .color(Color32::LIGHT_RED)
+                                        .strong()
+                                } else {
+                                    RichText::new(field_state.field.label())
+                                };
+                                ui.label(label);
+                                ui.label(display_cell(field_state.base.as_deref(), false));
+                                ui.label(display_cell(
+                                    Some(&field_state.local),
+                                    field_state.local_changed,
+                                ));
+                                ui.label(display_cell(
+                                    Some(&field_state.remote),
+                                    field_state.remote_changed,
+                                ));
+                                ui.horizontal(|ui| {
+                                    ui.radio_value(choice, FieldChoice::Local, "Local");
+                                    ui.radio_value(choice, FieldChoice::Remote, "Remote");
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            if let Some(error) = &state.error_message {
+                ui.add_space(4.0);
+                ui.colored_label(Color32::LIGHT_RED, error);
+            }
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button("Use All Local").clicked() {
+                    state.set_all(FieldChoice::Local);
+                }
+                if ui.button("Use All Remote").clicked() {
+                    state.set_all(FieldChoice::Remote);
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Cancel").clicked() {
+                        action = SyncConflictDialogAction::Close;
+                    }
+                    if ui.button("Apply Merge").clicked() {
+                        match state.apply(database) {
+                            Ok(()) => {
+                                action = SyncConflictDialogAction::Merged(format!(
+                                    "Merged conflict for '{}'; the result will be pushed on the next sync",
+                                    state.conflict.external_uid
+                                ));
+                            }
+                            Err(err) => {
+                                state.error_message =
+                                    Some(format!("Failed to apply merge: {}", err));
+                            }
+                        }
+                    }
+                });
+            });
+        });
+
+    if !dialog_open {
+        action = SyncConflictDialogAction::Close;
+    }
+
+    action
+}
+
+fn display_cell(value: Option<&str>, changed: bool) -> RichText {
+    let text = match value {
+        Some("") => "(empty)".to_string(),
+        Some(value) => value.to_string(),
+        None => "—".to_string(),
+    };
+
+    if changed {
+        RichText::new(text).strong()
+    } else {
+        RichText::new(text).color(Color32::GRAY)
+    }
+}
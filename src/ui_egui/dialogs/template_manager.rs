@@ -8,6 +8,7 @@ use crate::models::template::EventTemplate;
 use crate::services::category::CategoryService;
 use crate::services::database::Database;
 use crate::services::template::TemplateService;
+use crate::ui_egui::commands::TemplateCommand;
 
 /// State for the template manager dialog
 #[derive(Default)]
@@ -190,16 +191,20 @@ impl TemplateManagerState {
 
 const FORM_LABEL_WIDTH: f32 = 120.0;
 
+/// Render the template manager. Returns an undo record when a template was
+/// created, edited or deleted this frame.
 pub fn render_template_manager_dialog(
     ctx: &egui::Context,
     state: &mut TemplateManagerState,
     database: &Database,
     settings: &Settings,
-) {
+) -> Option<TemplateCommand> {
     if !state.is_open {
-        return;
+        return None;
     }
 
+    let mut undo_command = None;
+
     if state.needs_refresh {
         state.refresh_templates(database);
     }
@@ -215,16 +220,18 @@ pub fn render_template_manager_dialog(
         .default_height(500.0)
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
-            if state.editing_template.is_some() {
-                render_edit_form(ui, state, database);
+            undo_command = if state.editing_template.is_some() {
+                render_edit_form(ui, state, database)
             } else {
-                render_template_list(ui, state, database, default_duration);
-            }
+                render_template_list(ui, state, database, default_duration)
+            };
         });
 
     if !dialog_open {
         state.close();
     }
+
+    undo_command
 }
 
 fn render_template_list(
@@ -232,7 +239,9 @@ fn render_template_list(
     state: &mut TemplateManagerState,
     database: &Database,
     default_duration: u32,
-) {
+) -> Option<TemplateCommand> {
+    let mut undo_command = None;
+
     // Error message
     if let Some(ref error) = state.error_message {
         ui.colored_label(Color32::RED, error);
@@ -262,7 +271,7 @@ fn render_template_list(
             }
             ui.add_space(40.0);
         });
-        return;
+        return None;
     }
 
     // Template list
@@ -345,6 +354,9 @@ fn render_template_list(
                                                 state.error_message =
                                                     Some(format!("Delete failed: {}", e));
                                             } else {
+                                                undo_command = Some(TemplateCommand::deleted(
+                                                    template.clone(),
+                                                ));
                                                 state.needs_refresh = true;
                                             }
                                         }
@@ -369,12 +381,18 @@ fn render_template_list(
                 }
             }
         });
+
+    undo_command
 }
 
-fn render_edit_form(ui: &mut egui::Ui, state: &mut TemplateManagerState, database: &Database) {
-    let Some(editing) = state.editing_template.as_mut() else {
-        return;
-    };
+fn render_edit_form(
+    ui: &mut egui::Ui,
+    state: &mut TemplateManagerState,
+    database: &Database,
+) -> Option<TemplateCommand> {
+    let editing = state.editing_template.as_mut()?;
+
+    let mut undo_command = None;
 
     let is_new = editing.id.is_none();
 
@@ -569,6 +587,9 @@ fn render_edit_form(ui: &mut egui::Ui, state: &mut TemplateManagerState, databas
             {
                 state.error_message = Some("A template with this name already exists".to_string());
             } else {
+                let before = template_to_save
+                    .id
+                    .and_then(|id| service.get_by_id(id).ok());
                 let result = if is_new {
                     service.create(template_to_save)
                 } else {
@@ -576,7 +597,11 @@ fn render_edit_form(ui: &mut egui::Ui, state: &mut TemplateManagerState, databas
                 };
 
                 match result {
-                    Ok(_) => {
+                    Ok(saved) => {
+                        undo_command = Some(match before {
+                            Some(before) if !is_new => TemplateCommand::updated(before, saved),
+                            _ => TemplateCommand::created(saved),
+                        });
                         state.editing_template = None;
                         state.needs_refresh = true;
                         state.error_message = None;
@@ -593,6 +618,8 @@ fn render_edit_form(ui: &mut egui::Ui, state: &mut TemplateManagerState, databas
         state.editing_template = None;
         state.error_message = None;
     }
+
+    undo_command
 }
 
 fn labeled_row<F>(ui: &mut egui::Ui, label: &str, add_contents: F)
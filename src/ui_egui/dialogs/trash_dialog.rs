@@ -0,0 +1,222 @@
+// Trash Dialog
+// UI for restoring or permanently deleting trashed events
+
+use chrono::Local;
+use egui::{Color32, RichText};
+
+use crate::services::database::Database;
+use crate::services::event::trash::{RestoredEvent, TrashedEvent};
+use crate::services::event::EventService;
+
+/// State for the trash dialog
+#[derive(Default)]
+pub struct TrashDialogState {
+    pub is_open: bool,
+    pub entries: Vec<TrashedEvent>,
+    pub error_message: Option<String>,
+    /// Waiting for the user to confirm "Empty Trash"
+    pub confirm_empty: bool,
+}
+
+/// Action result from the trash dialog
+pub enum TrashDialogAction {
+    /// No action
+    None,
+    /// An event was restored
+    Restored(Box<RestoredEvent>),
+    /// One event was permanently deleted (title)
+    Purged(String),
+    /// The trash was emptied (count)
+    Emptied(usize),
+}
+
+impl TrashDialogState {
+    /// Open the dialog, first purging entries past the retention period
+    pub fn open(&mut self, database: &Database, retention_days: i64) {
+        let service = EventService::new(database.connection());
+        if let Err(e) = service.purge_expired_trash(retention_days, Local::now()) {
+            log::error!("Failed to purge expired trash: {}", e);
+        }
+        self.is_open = true;
+        self.confirm_empty = false;
+        self.error_message = None;
+        self.refresh(database);
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.confirm_empty = false;
+        self.entries.clear();
+    }
+
+    pub fn refresh(&mut self, database: &Database) {
+        match EventService::new(database.connection()).list_trash() {
+            Ok(entries) => self.entries = entries,
+            Err(e) => {
+                log::error!("Failed to load trash: {}", e);
+                self.error_message = Some(format!("Failed to load trash: {}", e));
+            }
+        }
+    }
+}
+
+/// Render the trash dialog
+pub fn render_trash_dialog(
+    ctx: &egui::Context,
+    state: &mut TrashDialogState,
+    database: &Database,
+    retention_days: i64,
+) -> TrashDialogAction {
+    if !state.is_open {
+        return TrashDialogAction::None;
+    }
+
+    let mut action = TrashDialogAction::None;
+    let mut dialog_open = state.is_open;
+    let service = EventService::new(database.connection());
+    let now = Local::now();
+
+    egui::Window::new("🗑 Trash")
+        .open(&mut dialog_open)
+        .collapsible(false)
+        .resizable(true)
+        .default_width(560.0)
+        .default_height(420.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(
+                RichText::new(format!(
+                    "Deleted events are kept for {} days, then removed permanently.",
+                    retention_days
+                ))
+                .italics()
+                .color(Color32::GRAY),
+            );
+
+            if let Some(ref error) = state.error_message {
+                ui.add_space(4.0);
+                ui.colored_label(Color32::RED, error);
+            }
+
+            ui.add_space(8.0);
+            ui.separator();
+
+            if state.entries.is_empty() {
+                ui.add_space(16.0);
+                ui.vertical_centered(|ui| {
+                    ui.label(RichText::new("The trash is empty").italics());
+                });
+                ui.add_space(16.0);
+            }
+
+            egui::ScrollArea::vertical()
+                .max_height(320.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for entry in &state.entries {
+                        let event = &entry.event;
+                        let days_left =
+                            (retention_days - (now - entry.deleted_at).num_days()).max(0);
+
+                        ui.horizontal(|ui| {
+                            ui.vertical(|ui| {
+                                ui.label(RichText::new(&event.title).strong());
+                                let when = if event.all_day {
+                                    event.start.format("%B %d, %Y").to_string()
+                                } else {
+                                    event.start.format("%B %d, %Y at %I:%M %p").to_string()
+                                };
+                                let mut details = format!(
+                                    "{} · deleted {} · {} day(s) left",
+                                    when,
+                                    entry.deleted_at.format("%b %d %H:%M"),
+                                    days_left
+                                );
+                                if entry.was_synced {
+                                    details.push_str(" · synced");
+                                }
+                                ui.label(RichText::new(details).size(11.0).color(Color32::GRAY));
+                            });
+
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    if ui
+                                        .button("Delete Forever")
+                                        .on_hover_text("Remove permanently; this cannot be undone")
+                                        .clicked()
+                                    {
+                                        match service.purge_from_trash(entry.trash_id) {
+                                            Ok(()) => {
+                                                action =
+                                                    TrashDialogAction::Purged(event.title.clone())
+                                            }
+                                            Err(e) => {
+                                                state.error_message =
+                                                    Some(format!("Failed to delete: {}", e))
+                                            }
+                                        }
+                                    }
+                                    if ui.button("↩ Restore").clicked() {
+                                        match service.restore_from_trash(entry.trash_id) {
+                                            Ok(restored) => {
+                                                action =
+                                                    TrashDialogAction::Restored(Box::new(restored))
+                                            }
+                                            Err(e) => {
+                                                state.error_message =
+                                                    Some(format!("Failed to restore: {:#}", e))
+                                            }
+                                        }
+                                    }
+                                },
+                            );
+                        });
+                        ui.separator();
+                    }
+                });
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if state.confirm_empty {
+                    ui.label(
+                        RichText::new(format!(
+                            "Permanently delete {} event(s)?",
+                            state.entries.len()
+                        ))
+                        .color(Color32::LIGHT_RED),
+                    );
+                    if ui.button("Empty Trash").clicked() {
+                        match service.empty_trash() {
+                            Ok(count) => action = TrashDialogAction::Emptied(count),
+                            Err(e) => {
+                                state.error_message = Some(format!("Failed to empty trash: {}", e))
+                            }
+                        }
+                        state.confirm_empty = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        state.confirm_empty = false;
+                    }
+                } else if ui
+                    .add_enabled(
+                        !state.entries.is_empty(),
+                        egui::Button::new("🗑 Empty Trash"),
+                    )
+                    .clicked()
+                {
+                    state.confirm_empty = true;
+                }
+            });
+        });
+
+    if !matches!(action, TrashDialogAction::None) {
+        state.refresh(database);
+    }
+
+    if !dialog_open {
+        state.close();
+    }
+
+    action
+}
@@ -0,0 +1,150 @@
+// Undo History Dialog
+// UI for browsing the undo/redo stacks and jumping to an earlier state
+
+use egui::{Color32, RichText};
+
+use crate::ui_egui::commands::UndoHistoryItem;
+
+/// State for the undo history dialog
+#[derive(Default)]
+pub struct UndoHistoryDialogState {
+    pub is_open: bool,
+    /// Waiting for the user to confirm "Clear History"
+    pub confirm_clear: bool,
+}
+
+/// Action result from the undo history dialog
+pub enum UndoHistoryAction {
+    /// No action
+    None,
+    /// Undo this many steps
+    Undo(usize),
+    /// Redo this many steps
+    Redo(usize),
+    /// Forget all undo and redo entries
+    Clear,
+}
+
+impl UndoHistoryDialogState {
+    pub fn open(&mut self) {
+        self.is_open = true;
+        self.confirm_clear = false;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.confirm_clear = false;
+    }
+}
+
+/// Render the undo history dialog. `undo` is most recent first and `redo` is
+/// next redo first, as returned by the undo manager.
+pub fn render_undo_history_dialog(
+    ctx: &egui::Context,
+    state: &mut UndoHistoryDialogState,
+    undo: &[UndoHistoryItem],
+    redo: &[UndoHistoryItem],
+) -> UndoHistoryAction {
+    if !state.is_open {
+        return UndoHistoryAction::None;
+    }
+
+    let mut action = UndoHistoryAction::None;
+    let mut dialog_open = state.is_open;
+
+    egui::Window::new("↶ Undo History")
+        .open(&mut dialog_open)
+        .collapsible(false)
+        .resizable(true)
+        .default_width(420.0)
+        .default_height(400.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(
+                RichText::new("Click an entry to undo or redo up to that point.")
+                    .italics()
+                    .color(Color32::GRAY),
+            );
+            ui.add_space(8.0);
+            ui.separator();
+
+            if undo.is_empty() && redo.is_empty() {
+                ui.add_space(16.0);
+                ui.vertical_centered(|ui| {
+                    ui.label(RichText::new("Nothing to undo").italics());
+                });
+                ui.add_space(16.0);
+            }
+
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    // Furthest redo at the top, so the list reads oldest last
+                    for (index, item) in redo.iter().enumerate().rev() {
+                        let text = RichText::new(format!(
+                            "↷ {}  ·  {}",
+                            item.description,
+                            item.recorded_at.format("%b %d %H:%M")
+                        ))
+                        .color(Color32::GRAY);
+                        if ui
+                            .selectable_label(false, text)
+                            .on_hover_text("Redo up to here")
+                            .clicked()
+                        {
+                            action = UndoHistoryAction::Redo(index + 1);
+                        }
+                    }
+
+                    if !undo.is_empty() || !redo.is_empty() {
+                        ui.label(RichText::new("● Current state").strong());
+                    }
+
+                    for (index, item) in undo.iter().enumerate() {
+                        let text = format!(
+                            "↶ {}  ·  {}",
+                            item.description,
+                            item.recorded_at.format("%b %d %H:%M")
+                        );
+                        if ui
+                            .selectable_label(false, text)
+                            .on_hover_text("Undo back to before this change")
+                            .clicked()
+                        {
+                            action = UndoHistoryAction::Undo(index + 1);
+                        }
+                    }
+                });
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if state.confirm_clear {
+                    ui.label(
+                        RichText::new("Forget all undo and redo steps?").color(Color32::LIGHT_RED),
+                    );
+                    if ui.button("Clear History").clicked() {
+                        action = UndoHistoryAction::Clear;
+                        state.confirm_clear = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        state.confirm_clear = false;
+                    }
+                } else if ui
+                    .add_enabled(
+                        !undo.is_empty() || !redo.is_empty(),
+                        egui::Button::new("🗑 Clear History"),
+                    )
+                    .clicked()
+                {
+                    state.confirm_clear = true;
+                }
+            });
+        });
+
+    if !dialog_open {
+        state.close();
+    }
+
+    action
+}
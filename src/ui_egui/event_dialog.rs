@@ -1,6 +1,7 @@
 pub mod recurrence;
 mod render;
 mod render_date_time;
+mod render_history;
 mod render_recurrence;
 pub mod state;
 mod state_persistence;
@@ -9,3 +10,4 @@ mod widgets;
 pub use recurrence::RecurrenceFrequency;
 pub use render::{render_event_dialog, CountdownCardChanges, EventDialogResult};
 pub use state::EventDialogState;
+pub use widgets::parse_hex_color;
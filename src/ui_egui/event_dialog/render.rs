@@ -7,9 +7,9 @@ use crate::services::category::CategoryService;
 use crate::services::countdown::{CountdownCardId, CountdownCategoryId};
 use crate::services::database::Database;
 
-use super::state::EventDialogState;
+use super::state::{EventDialogState, EventDialogTab};
 use super::widgets::{indented_row, labeled_row, parse_hex_color};
-use super::{render_date_time, render_recurrence};
+use super::{render_date_time, render_history, render_recurrence};
 
 /// Changes to apply to a linked countdown card
 #[derive(Debug, Clone)]
@@ -70,8 +70,20 @@ pub fn render_event_dialog(
     .min_height(720.0)
     .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
     .show(ctx, |ui| {
+        if state.event_id.is_some() && !state.is_occurrence_edit() {
+            render_tab_bar(ui, state);
+        }
+
+        if state.active_tab == EventDialogTab::History {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                render_history::render_history_tab(ui, state, database, settings);
+            });
+            return;
+        }
+
         egui::ScrollArea::vertical().show(ui, |ui| {
             render_error_banner(ui, state);
+            render_revert_notice(ui, state);
             render_warning_banner(ui, state);
             render_basic_information_section(ui, state, database, countdown_categories);
             render_date_time::render_date_time_section(ui, state);
@@ -99,6 +111,28 @@ fn render_error_banner(ui: &mut egui::Ui, state: &EventDialogState) {
     }
 }
 
+fn render_tab_bar(ui: &mut egui::Ui, state: &mut EventDialogState) {
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut state.active_tab, EventDialogTab::Details, "Details");
+        if ui
+            .selectable_value(&mut state.active_tab, EventDialogTab::History, "History")
+            .clicked()
+        {
+            // Reload so changes saved elsewhere since the last visit show up.
+            state.history = None;
+        }
+    });
+    ui.separator();
+    ui.add_space(4.0);
+}
+
+fn render_revert_notice(ui: &mut egui::Ui, state: &EventDialogState) {
+    if let Some(ref notice) = state.revert_notice {
+        ui.colored_label(Color32::LIGHT_BLUE, notice);
+        ui.add_space(8.0);
+    }
+}
+
 fn render_warning_banner(ui: &mut egui::Ui, state: &EventDialogState) {
     if state.warning_messages.is_empty() {
         return;
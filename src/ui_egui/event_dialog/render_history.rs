@@ -0,0 +1,157 @@
+//! History tab of the event dialog: recorded changes with field diffs and
+//! the option to load an earlier version back into the form.
+
+use chrono::{DateTime, Local};
+use egui::{Color32, RichText};
+
+use crate::models::settings::Settings;
+use crate::services::database::Database;
+use crate::services::event::history::{ChangeKind, ChangeOrigin, FieldChange};
+use crate::services::event::EventService;
+
+use super::state::{EventDialogState, EventDialogTab};
+
+const MAX_VALUE_CHARS: usize = 60;
+
+pub fn render_history_tab(
+    ui: &mut egui::Ui,
+    state: &mut EventDialogState,
+    database: &Database,
+    settings: &Settings,
+) {
+    let Some(event_id) = state.event_id else {
+        return;
+    };
+
+    if state.history.is_none() {
+        let service = EventService::new(database.connection());
+        state.history = Some(service.history(event_id).unwrap_or_else(|err| {
+            log::error!("Failed to load history for event {}: {}", event_id, err);
+            Vec::new()
+        }));
+    }
+
+    let mut revert_to = None;
+    let history = state.history.as_deref().unwrap_or_default();
+
+    if history.is_empty() {
+        ui.add_space(16.0);
+        ui.vertical_centered(|ui| {
+            ui.label(RichText::new("No changes recorded for this event yet").italics());
+        });
+        return;
+    }
+
+    for (index, change) in history.iter().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(change.kind.label()).strong());
+            ui.label(
+                RichText::new(format!("by {}", change.origin.label()))
+                    .color(origin_color(change.origin)),
+            );
+            ui.label(
+                RichText::new(change.changed_at.format("%b %d, %Y %H:%M").to_string())
+                    .color(Color32::GRAY),
+            );
+
+            // The newest entry is the current version unless it was a deletion.
+            let is_current = index == 0 && change.kind != ChangeKind::Deleted;
+            if change.snapshot.is_some() && !is_current {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .small_button("↩ Revert to this version")
+                        .on_hover_text("Load this version into the form; save to apply it")
+                        .clicked()
+                    {
+                        revert_to = Some(change.clone());
+                    }
+                });
+            }
+        });
+
+        if change.kind == ChangeKind::Updated {
+            for field_change in &change.changes {
+                render_field_change(ui, field_change);
+            }
+        }
+
+        ui.add_space(4.0);
+        ui.separator();
+    }
+
+    if let Some(change) = revert_to {
+        state.revert_to(&change, settings);
+        state.active_tab = EventDialogTab::Details;
+    }
+}
+
+fn render_field_change(ui: &mut egui::Ui, change: &FieldChange) {
+    ui.horizontal_wrapped(|ui| {
+        ui.add_space(12.0);
+        ui.label(RichText::new(format!("{}:", field_label(&change.field))).size(12.0));
+        ui.label(
+            RichText::new(display_value(&change.field, change.old.as_deref()))
+                .size(12.0)
+                .strikethrough()
+                .color(Color32::GRAY),
+        );
+        ui.label(RichText::new("→").size(12.0));
+        ui.label(RichText::new(display_value(&change.field, change.new.as_deref())).size(12.0));
+    });
+}
+
+fn origin_color(origin: ChangeOrigin) -> Color32 {
+    match origin {
+        ChangeOrigin::User => Color32::from_rgb(100, 150, 230),
+        ChangeOrigin::Import => Color32::from_rgb(160, 120, 220),
+        ChangeOrigin::Sync => Color32::from_rgb(80, 180, 120),
+    }
+}
+
+fn field_label(field: &str) -> &str {
+    match field {
+        "title" => "Title",
+        "description" => "Description",
+        "location" => "Location",
+        "start" => "Start",
+        "end" => "End",
+        "all_day" => "All day",
+        "category" => "Category",
+        "color" => "Color",
+        "recurrence_rule" => "Repeats",
+        "recurrence_exceptions" => "Skipped dates",
+        other => other,
+    }
+}
+
+fn display_value(field: &str, value: Option<&str>) -> String {
+    let Some(value) = value else {
+        return "(none)".to_string();
+    };
+
+    let formatted = match field {
+        "start" | "end" => format_datetime(value).unwrap_or_else(|| value.to_string()),
+        "all_day" => if value == "true" { "Yes" } else { "No" }.to_string(),
+        "recurrence_exceptions" => value
+            .split(", ")
+            .map(|date| format_datetime(date).unwrap_or_else(|| date.to_string()))
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => value.replace('\n', " "),
+    };
+
+    if formatted.chars().count() > MAX_VALUE_CHARS {
+        let truncated: String = formatted.chars().take(MAX_VALUE_CHARS).collect();
+        format!("{}…", truncated)
+    } else {
+        formatted
+    }
+}
+
+fn format_datetime(value: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(value).ok().map(|dt| {
+        dt.with_timezone(&Local)
+            .format("%b %d, %Y %H:%M")
+            .to_string()
+    })
+}
@@ -3,6 +3,7 @@ use crate::models::settings::Settings;
 use crate::services::countdown::{
     CountdownCardId, CountdownCardVisuals, CountdownCategoryId, DEFAULT_CATEGORY_ID,
 };
+use crate::services::event::history::EventChange;
 use chrono::{self, DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime};
 
 use super::recurrence::{ParsedRRule, RecurrenceFrequency, RecurrencePattern};
@@ -14,6 +15,14 @@ pub enum DatePickerTarget {
     EndDate,
 }
 
+/// Which tab of the event dialog is shown
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum EventDialogTab {
+    #[default]
+    Details,
+    History,
+}
+
 /// Optional countdown card state linked to the event
 #[derive(Clone)]
 pub struct LinkedCountdownCard {
@@ -69,6 +78,13 @@ pub struct EventDialogState {
     pub date_picker_viewing: NaiveDate,
     /// Whether this is a past event (end time < now) - date/time fields are read-only
     pub is_past_event: bool,
+    pub active_tab: EventDialogTab,
+    /// Change history, loaded when the History tab is first shown
+    pub history: Option<Vec<EventChange>>,
+    /// Shown after loading an earlier version into the form
+    pub revert_notice: Option<String>,
+    /// iCalendar properties the form does not edit, written back on save
+    pub ics_raw_properties: Option<String>,
 }
 
 impl EventDialogState {
@@ -139,6 +155,10 @@ impl EventDialogState {
             active_date_picker: None,
             date_picker_viewing: date,
             is_past_event: false,
+            active_tab: EventDialogTab::Details,
+            history: None,
+            revert_notice: None,
+            ics_raw_properties: None,
         }
     }
 
@@ -206,6 +226,10 @@ impl EventDialogState {
             active_date_picker: None,
             date_picker_viewing: date,
             is_past_event: is_past,
+            active_tab: EventDialogTab::Details,
+            history: None,
+            revert_notice: None,
+            ics_raw_properties: event.ics_raw_properties.clone(),
         }
     }
 
@@ -219,6 +243,8 @@ impl EventDialogState {
         state.event_id = None;
         state.detached_occurrence_parent_id = Some(parent_event_id);
         state.detached_occurrence_date = Some(occurrence_date);
+        // The detached copy is a new event, not the series it came from
+        state.ics_raw_properties = None;
         state.is_recurring = false;
         state.frequency = RecurrenceFrequency::Daily;
         state.interval = 1;
@@ -236,6 +262,26 @@ impl EventDialogState {
         state
     }
 
+    /// Load an earlier version of the event into the form. Nothing is written
+    /// until the user saves, so the revert goes through the normal save path.
+    pub fn revert_to(&mut self, change: &EventChange, settings: &Settings) {
+        let Some(snapshot) = change.snapshot.as_ref() else {
+            return;
+        };
+
+        let mut reverted = Self::from_event(snapshot, settings);
+        reverted.event_id = self.event_id;
+        reverted.linked_card = self.linked_card.take();
+        reverted.show_card_settings = self.show_card_settings;
+        reverted.history = self.history.take();
+        reverted.ics_raw_properties = self.ics_raw_properties.take();
+        reverted.revert_notice = Some(format!(
+            "Loaded the version from {}. Save to apply it.",
+            change.changed_at.format("%b %d, %Y %H:%M")
+        ));
+        *self = reverted;
+    }
+
     pub fn is_occurrence_edit(&self) -> bool {
         self.detached_occurrence_parent_id.is_some() && self.detached_occurrence_date.is_some()
     }
@@ -405,6 +451,56 @@ mod tests {
         assert!(state.validate().is_ok());
     }
 
+    #[test]
+    fn to_event_keeps_raw_ics_properties_except_for_occurrences() {
+        let settings = Settings::default();
+        let start = Local
+            .with_ymd_and_hms(2025, 1, 15, 9, 0, 0)
+            .single()
+            .unwrap();
+        let mut event = Event::new("Imported", start, start + chrono::Duration::hours(1)).unwrap();
+        event.id = Some(3);
+        event.ics_raw_properties = Some("UID:imported@example.com".to_string());
+
+        let state = EventDialogState::from_event(&event, &settings);
+        assert_eq!(
+            state.to_event().unwrap().ics_raw_properties,
+            event.ics_raw_properties
+        );
+
+        let occurrence = EventDialogState::from_occurrence(&event, &settings, 3, start);
+        assert!(occurrence.to_event().unwrap().ics_raw_properties.is_none());
+    }
+
+    #[test]
+    fn revert_to_loads_snapshot_and_keeps_event_id() {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        let service = EventService::new(db.connection());
+        let settings = Settings::default();
+
+        let start = Local
+            .with_ymd_and_hms(2025, 1, 15, 9, 0, 0)
+            .single()
+            .unwrap();
+        let created = service
+            .create(Event::new("Original", start, start + chrono::Duration::hours(1)).unwrap())
+            .unwrap();
+        let mut edited = created.clone();
+        edited.title = "Edited".to_string();
+        edited.location = Some("Room 2".to_string());
+        service.update(&edited).unwrap();
+
+        let mut state = EventDialogState::from_event(&edited, &settings);
+        let history = service.history(created.id.unwrap()).unwrap();
+        state.revert_to(history.last().unwrap(), &settings);
+
+        assert_eq!(state.event_id, created.id);
+        assert_eq!(state.title, "Original");
+        assert!(state.location.is_empty());
+        assert!(state.revert_notice.is_some());
+    }
+
     #[test]
     fn to_event_propagates_recurrence_rule() {
         let mut state = base_state();
@@ -282,6 +282,8 @@ impl EventDialogState {
             event = event.recurrence_rule(rrule);
         }
 
-        event.build()
+        let mut event = event.build()?;
+        event.ics_raw_properties = self.ics_raw_properties.clone();
+        Ok(event)
     }
 }
@@ -7,6 +7,8 @@ pub mod resize;
 mod resize_drawing;
 mod settings_calendar_sync;
 mod settings_dialog;
+mod settings_sync_history;
+mod settings_sync_preview;
 pub mod theme;
 mod theme_presets;
 mod views;
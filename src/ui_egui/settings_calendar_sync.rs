@@ -3,21 +3,34 @@
 //! Handles Google Calendar ICS source management: adding, editing,
 //! deleting sources and triggering manual sync operations.
 
-use crate::models::calendar_source::{CalendarSource, GOOGLE_ICS_SOURCE_TYPE};
-use crate::models::calendar_source::{SYNC_CAPABILITY_READ_ONLY, SYNC_CAPABILITY_READ_WRITE};
+use super::event_dialog::parse_hex_color;
+use super::settings_sync_history;
+use super::settings_sync_preview::{self, SyncPreviewAction, SyncPreviewState};
+use crate::models::calendar_source::{
+    CalendarSource, GOOGLE_API_SOURCE_TYPE, GOOGLE_ICS_SOURCE_TYPE,
+};
+use crate::models::calendar_source::{
+    SYNC_CAPABILITY_READ_ONLY, SYNC_CAPABILITY_READ_WRITE, SYNC_STATUS_NEEDS_CONFIRMATION,
+};
 use crate::models::google_account::GoogleAccount;
 use crate::models::settings::Settings;
 use crate::models::sync_conflict::{
     SYNC_CONFLICT_REASON_LOCAL_CREATE_PENDING, SYNC_CONFLICT_REASON_LOCAL_DELETE_PENDING,
-    SYNC_CONFLICT_REASON_LOCAL_UPDATE_PENDING, SYNC_CONFLICT_RESOLUTION_REMOTE_WINS,
-    SYNC_CONFLICT_RESOLUTION_RETRY_LOCAL,
+    SYNC_CONFLICT_REASON_LOCAL_UPDATE_PENDING, SYNC_CONFLICT_RESOLUTION_MERGED,
+    SYNC_CONFLICT_RESOLUTION_REMOTE_WINS, SYNC_CONFLICT_RESOLUTION_RETRY_LOCAL,
 };
-use crate::services::calendar_sync::engine::{CalendarSyncEngine, SyncRunResult};
+use crate::services::calendar_sync::discovery::{GoogleCalendarDiscovery, GoogleCalendarListEntry};
+use crate::services::calendar_sync::engine::{CalendarSyncEngine, SyncApplyOptions, SyncRunResult};
+use crate::services::calendar_sync::mapping::EventSyncMapService;
 use crate::services::calendar_sync::CalendarSourceService;
 use crate::services::database::Database;
 use crate::services::google_account::GoogleAccountService;
 use crate::services::outbound_sync::OutboundSyncService;
+use crate::services::secrets;
 use crate::services::sync_conflict::SyncConflictService;
+use crate::ui_egui::dialogs::sync_conflict_dialog::{
+    render_sync_conflict_dialog, SyncConflictDialogAction, SyncConflictDialogState,
+};
 use egui::{Color32, RichText};
 use std::collections::BTreeMap;
 use std::sync::mpsc::{self, Receiver, TryRecvError};
@@ -33,6 +46,7 @@ struct CalendarSourceDraft {
     sync_past_days: i64,
     sync_future_days: i64,
     sync_capability: String,
+    google_account_id: Option<i64>,
     enabled: bool,
 }
 
@@ -45,11 +59,12 @@ enum SyncJobKind {
 #[derive(Clone, Copy)]
 enum OAuthJobKind {
     Connect,
-    Refresh,
+    Refresh(i64),
 }
 
 type SyncWorkerMessage = Result<(String, SyncJobKind, SyncRunResult), String>;
 type OAuthWorkerMessage = Result<(OAuthJobKind, GoogleAccount), String>;
+type CalendarListWorkerMessage = Result<(i64, Vec<GoogleCalendarListEntry>), String>;
 
 /// Mutable state for the calendar sync section of the settings dialog.
 #[derive(Default)]
@@ -61,6 +76,7 @@ pub struct CalendarSyncState {
     new_source_sync_past_days: i64,
     new_source_sync_future_days: i64,
     new_source_sync_capability: String,
+    new_source_google_account_id: Option<i64>,
     source_status_message: Option<String>,
     source_error_message: Option<String>,
     source_sync_in_progress_id: Option<i64>,
@@ -71,6 +87,15 @@ pub struct CalendarSyncState {
     oauth_error_message: Option<String>,
     oauth_job_in_progress: Option<OAuthJobKind>,
     oauth_result_rx: Option<Receiver<OAuthWorkerMessage>>,
+    /// Account whose "Remove" button was clicked and awaits confirmation
+    account_pending_removal: Option<i64>,
+    calendar_lists: BTreeMap<i64, Vec<GoogleCalendarListEntry>>,
+    calendar_list_in_progress_id: Option<i64>,
+    calendar_list_rx: Option<Receiver<CalendarListWorkerMessage>>,
+    /// Subscribed source whose calendar was unticked and awaits confirmation
+    source_pending_unsubscribe: Option<i64>,
+    conflict_merge_dialog: Option<SyncConflictDialogState>,
+    sync_preview: Option<SyncPreviewState>,
 }
 
 impl CalendarSyncState {
@@ -101,6 +126,13 @@ pub fn poll_sync_result(ctx: &egui::Context, state: &mut CalendarSyncState) {
                     SyncJobKind::Apply => "Sync",
                 };
 
+                state.sync_preview = match kind {
+                    SyncJobKind::Preview if !summary.changes.is_empty() => Some(
+                        SyncPreviewState::new(summary.source_id, summary.changes.clone()),
+                    ),
+                    _ => None,
+                };
+
                 state.source_status_message = Some(format!(
                     "{} complete for '{}': +{} ~{} -{} ={} conflicts:{} skipped:{} errors:{} ({} ms)",
                     action,
@@ -112,7 +144,8 @@ pub fn poll_sync_result(ctx: &egui::Context, state: &mut CalendarSyncState) {
                     summary.conflicts,
                     summary.skipped_missing_uid
                         + summary.skipped_duplicate_uid
-                        + summary.skipped_filtered,
+                        + summary.skipped_filtered
+                        + summary.skipped_unapproved,
                     summary.error_count,
                     summary.duration_ms,
                 ));
@@ -150,7 +183,7 @@ pub fn poll_sync_result(ctx: &egui::Context, state: &mut CalendarSyncState) {
                             .unwrap_or("unknown account");
                         format!("Connected Google account: {}", email)
                     }
-                    OAuthJobKind::Refresh => {
+                    OAuthJobKind::Refresh(_) => {
                         let email = account
                             .account_email
                             .as_deref()
@@ -177,6 +210,36 @@ pub fn poll_sync_result(ctx: &egui::Context, state: &mut CalendarSyncState) {
             }
         }
     }
+
+    if let Some(rx) = &state.calendar_list_rx {
+        match rx.try_recv() {
+            Ok(Ok((account_id, entries))) => {
+                state.calendar_list_rx = None;
+                state.calendar_list_in_progress_id = None;
+                state.oauth_error_message = None;
+                state.oauth_status_message = Some(format!("Found {} calendar(s)", entries.len()));
+                state.calendar_lists.insert(account_id, entries);
+                // Colours and capabilities may have been refreshed from the list.
+                state.source_drafts.clear();
+            }
+            Ok(Err(err)) => {
+                state.calendar_list_rx = None;
+                state.calendar_list_in_progress_id = None;
+                state.oauth_status_message = None;
+                state.oauth_error_message = Some(format!("Failed to list calendars: {}", err));
+            }
+            Err(TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(200));
+            }
+            Err(TryRecvError::Disconnected) => {
+                state.calendar_list_rx = None;
+                state.calendar_list_in_progress_id = None;
+                state.oauth_status_message = None;
+                state.oauth_error_message =
+                    Some("Calendar list worker disconnected unexpectedly".to_string());
+            }
+        }
+    }
 }
 
 /// Render the Google Calendar Sync section of the settings dialog.
@@ -187,9 +250,26 @@ pub fn render_calendar_sync_section(
     database: &Database,
     state: &mut CalendarSyncState,
 ) {
-    ui.heading("Google Account (Read/Write Preview)");
+    ui.heading("Google Accounts (Read/Write Preview)");
     ui.add_space(4.0);
 
+    let secret_store = secrets::store();
+    if secret_store.needs_passphrase() {
+        ui.colored_label(
+            Color32::LIGHT_RED,
+            "Saved credentials are locked. Restart the app and enter the vault passphrase to sync.",
+        );
+    } else {
+        ui.label(
+            egui::RichText::new(format!(
+                "Tokens and private URLs are stored in the {}.",
+                secret_store.backend_name()
+            ))
+            .small()
+            .color(Color32::GRAY),
+        );
+    }
+
     let account_service = match GoogleAccountService::new(database.connection()) {
         Ok(service) => Some(service),
         Err(err) => {
@@ -203,10 +283,10 @@ pub fn render_calendar_sync_section(
 
     if let Some(service) = &account_service {
         if !state.oauth_client_id_loaded {
-            match service.load() {
-                Ok(account) => {
+            match service.client_id() {
+                Ok(client_id) => {
                     if state.oauth_client_id.trim().is_empty() {
-                        state.oauth_client_id = account.oauth_client_id.unwrap_or_default();
+                        state.oauth_client_id = client_id.unwrap_or_default();
                     }
                 }
                 Err(err) => {
@@ -225,9 +305,19 @@ pub fn render_calendar_sync_section(
         ui.colored_label(Color32::LIGHT_RED, message);
     }
 
-    let account_snapshot = account_service
-        .as_ref()
-        .and_then(|service| service.load().ok());
+    let accounts: Vec<GoogleAccount> = match &account_service {
+        Some(service) => match service.list_accounts() {
+            Ok(accounts) => accounts,
+            Err(err) => {
+                ui.colored_label(
+                    Color32::LIGHT_RED,
+                    format!("Failed to load Google accounts: {}", err),
+                );
+                Vec::new()
+            }
+        },
+        None => Vec::new(),
+    };
 
     ui.horizontal(|ui| {
         ui.allocate_ui_with_layout(
@@ -252,11 +342,12 @@ pub fn render_calendar_sync_section(
         let connect_label = if matches!(state.oauth_job_in_progress, Some(OAuthJobKind::Connect)) {
             "Connecting..."
         } else {
-            "Connect / Reconnect"
+            "Add / Reconnect Account"
         };
 
         if ui
             .add_enabled(!any_oauth_in_progress, egui::Button::new(connect_label))
+            .on_hover_text("Sign in with another Google identity, or reconnect an existing one")
             .clicked()
         {
             state.oauth_status_message = Some(
@@ -288,80 +379,186 @@ pub fn render_calendar_sync_section(
                 let _ = tx.send(result);
             });
         }
+    });
 
-        let refresh_label = if matches!(state.oauth_job_in_progress, Some(OAuthJobKind::Refresh)) {
-            "Refreshing..."
-        } else {
-            "Refresh Token"
-        };
+    if accounts.is_empty() {
+        ui.label("No Google accounts linked");
+    }
 
-        if ui
-            .add_enabled(!any_oauth_in_progress, egui::Button::new(refresh_label))
-            .clicked()
-        {
-            state.oauth_status_message = Some("Refreshing Google access token...".to_string());
-            state.oauth_error_message = None;
-            state.oauth_job_in_progress = Some(OAuthJobKind::Refresh);
+    for account in &accounts {
+        let account_id = account.id;
+        let email = account
+            .account_email
+            .as_deref()
+            .unwrap_or("unknown account");
 
-            let db_path = database.path().to_string();
-            let (tx, rx) = mpsc::channel();
-            state.oauth_result_rx = Some(rx);
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(email).strong());
+                if account.is_connected() {
+                    ui.colored_label(Color32::LIGHT_GREEN, "connected");
+                } else {
+                    ui.colored_label(Color32::LIGHT_RED, "disconnected");
+                }
+                if let Some(expires_at) = account.expires_at.as_deref() {
+                    ui.small(format!("token expiry: {}", expires_at));
+                }
+            });
 
-            thread::spawn(move || {
-                let result = (|| -> OAuthWorkerMessage {
-                    let db = Database::new(&db_path).map_err(|err| err.to_string())?;
-                    let service = GoogleAccountService::new(db.connection())
-                        .map_err(|err| err.to_string())?;
-                    let account = service
-                        .refresh_access_token()
-                        .map_err(|err| err.to_string())?;
-                    Ok((OAuthJobKind::Refresh, account))
-                })();
+            if let Some(last_error) = account.last_error.as_deref() {
+                ui.colored_label(
+                    Color32::LIGHT_RED,
+                    format!("Last auth error: {}", last_error),
+                );
+            }
 
-                let _ = tx.send(result);
-            });
-        }
+            ui.horizontal(|ui| {
+                let refresh_label = if matches!(
+                    state.oauth_job_in_progress,
+                    Some(OAuthJobKind::Refresh(id)) if id == account_id
+                ) {
+                    "Refreshing..."
+                } else {
+                    "Refresh Token"
+                };
 
-        if ui
-            .add_enabled(!any_oauth_in_progress, egui::Button::new("Disconnect"))
-            .clicked()
-        {
-            state.oauth_status_message = None;
-            state.oauth_error_message = None;
+                if ui
+                    .add_enabled(
+                        !any_oauth_in_progress && account.is_connected(),
+                        egui::Button::new(refresh_label),
+                    )
+                    .clicked()
+                {
+                    state.oauth_status_message =
+                        Some(format!("Refreshing Google access token for {}...", email));
+                    state.oauth_error_message = None;
+                    state.oauth_job_in_progress = Some(OAuthJobKind::Refresh(account_id));
 
-            if let Some(service) = &account_service {
-                match service.disconnect() {
-                    Ok(_) => {
-                        state.oauth_status_message =
-                            Some("Disconnected Google account".to_string());
-                    }
-                    Err(err) => {
-                        state.oauth_error_message =
-                            Some(format!("Failed to disconnect account: {}", err));
+                    let db_path = database.path().to_string();
+                    let (tx, rx) = mpsc::channel();
+                    state.oauth_result_rx = Some(rx);
+
+                    thread::spawn(move || {
+                        let result = (|| -> OAuthWorkerMessage {
+                            let db = Database::new(&db_path).map_err(|err| err.to_string())?;
+                            let service = GoogleAccountService::new(db.connection())
+                                .map_err(|err| err.to_string())?;
+                            let account = service
+                                .refresh_access_token(account_id)
+                                .map_err(|err| err.to_string())?;
+                            Ok((OAuthJobKind::Refresh(account_id), account))
+                        })();
+
+                        let _ = tx.send(result);
+                    });
+                }
+
+                if ui
+                    .add_enabled(
+                        !any_oauth_in_progress && account.is_connected(),
+                        egui::Button::new("Disconnect"),
+                    )
+                    .on_hover_text("Forget this account's tokens but keep its linked sources")
+                    .clicked()
+                {
+                    state.oauth_status_message = None;
+                    state.oauth_error_message = None;
+
+                    if let Some(service) = &account_service {
+                        match service.disconnect(account_id) {
+                            Ok(_) => {
+                                state.oauth_status_message =
+                                    Some(format!("Disconnected Google account {}", email));
+                            }
+                            Err(err) => {
+                                state.oauth_error_message =
+                                    Some(format!("Failed to disconnect account: {}", err));
+                            }
+                        }
                     }
                 }
-            }
-        }
-    });
 
-    if let Some(account) = account_snapshot {
-        if account.is_connected() {
-            if let Some(email) = account.account_email.as_deref() {
-                ui.label(format!("Linked account: {}", email));
-            }
-            if let Some(expires_at) = account.expires_at.as_deref() {
-                ui.label(format!("Token expiry: {}", expires_at));
+                if ui
+                    .add_enabled(!any_oauth_in_progress, egui::Button::new("Remove"))
+                    .on_hover_text("Delete this account; sources using it are unlinked")
+                    .clicked()
+                {
+                    state.account_pending_removal = Some(account_id);
+                }
+
+                let browse_label = if state.calendar_list_in_progress_id == Some(account_id) {
+                    "Loading..."
+                } else {
+                    "Browse Calendars"
+                };
+                if ui
+                    .add_enabled(
+                        state.calendar_list_in_progress_id.is_none() && account.is_connected(),
+                        egui::Button::new(browse_label),
+                    )
+                    .clicked()
+                {
+                    state.oauth_error_message = None;
+                    state.calendar_list_in_progress_id = Some(account_id);
+
+                    let db_path = database.path().to_string();
+                    let (tx, rx) = mpsc::channel();
+                    state.calendar_list_rx = Some(rx);
+
+                    thread::spawn(move || {
+                        let result = (|| -> CalendarListWorkerMessage {
+                            let db = Database::new(&db_path).map_err(|err| err.to_string())?;
+                            let entries = GoogleCalendarDiscovery::new(db.connection())
+                                .fetch_calendar_list(account_id)
+                                .map_err(|err| err.to_string())?;
+                            Ok((account_id, entries))
+                        })();
+
+                        let _ = tx.send(result);
+                    });
+                }
+            });
+
+            if state.account_pending_removal == Some(account_id) {
+                ui.horizontal(|ui| {
+                    ui.colored_label(
+                        Color32::LIGHT_RED,
+                        format!(
+                            "Remove {}? Its tokens are deleted and the sources using it \
+                             stop syncing until linked to another account.",
+                            email
+                        ),
+                    );
+                    if ui.button("Remove Account").clicked() {
+                        state.account_pending_removal = None;
+                        state.oauth_status_message = None;
+                        state.oauth_error_message = None;
+
+                        if let Some(service) = &account_service {
+                            match service.remove_account(account_id) {
+                                Ok(_) => {
+                                    state.source_drafts.clear();
+                                    state.oauth_status_message =
+                                        Some(format!("Removed Google account {}", email));
+                                }
+                                Err(err) => {
+                                    state.oauth_error_message =
+                                        Some(format!("Failed to remove account: {}", err));
+                                }
+                            }
+                        }
+                    }
+                    if ui.button("Cancel").clicked() {
+                        state.account_pending_removal = None;
+                    }
+                });
             }
-        } else {
-            ui.label("No Google account linked");
-        }
 
-        if let Some(last_error) = account.last_error.as_deref() {
-            ui.colored_label(
-                Color32::LIGHT_RED,
-                format!("Last auth error: {}", last_error),
-            );
-        }
+            if let Some(entries) = state.calendar_lists.get(&account_id) {
+                let entries = entries.clone();
+                render_calendar_list(ui, database, account_id, &entries, state);
+            }
+        });
     }
 
     ui.add_space(12.0);
@@ -388,6 +585,32 @@ pub fn render_calendar_sync_section(
         ui.label("(applies on app launch)");
     });
 
+    ui.horizontal(|ui| {
+        ui.allocate_ui_with_layout(
+            egui::Vec2::new(label_width, 20.0),
+            egui::Layout::right_to_left(egui::Align::Center),
+            |ui| {
+                ui.label("Pause deletions at:");
+            },
+        );
+        ui.add(
+            egui::DragValue::new(&mut settings.sync_mass_delete_percent)
+                .range(0..=100)
+                .speed(1)
+                .suffix(" %"),
+        );
+        ui.label("or");
+        ui.add(
+            egui::DragValue::new(&mut settings.sync_mass_delete_count)
+                .range(0..=100_000)
+                .speed(1)
+                .suffix(" events"),
+        )
+        .on_hover_text(
+            "A sync that would delete this many events waits for confirmation (0 = off)",
+        );
+    });
+
     ui.add_space(6.0);
 
     if let Some(message) = &state.source_status_message {
@@ -447,6 +670,15 @@ pub fn render_calendar_sync_section(
                 SYNC_CAPABILITY_READ_ONLY.to_string()
             };
         }
+
+        if write_back_enabled {
+            render_account_picker(
+                ui,
+                "new_source_google_account",
+                &mut state.new_source_google_account_id,
+                &accounts,
+            );
+        }
     });
 
     ui.horizontal(|ui| {
@@ -521,6 +753,9 @@ pub fn render_calendar_sync_section(
                 last_sync_at: None,
                 last_sync_status: None,
                 last_error: None,
+                google_account_id: state.new_source_google_account_id,
+                remote_calendar_id: None,
+                color: None,
             };
 
             match source_service.create(new_source) {
@@ -531,6 +766,7 @@ pub fn render_calendar_sync_section(
                     state.new_source_sync_past_days = 90;
                     state.new_source_sync_future_days = 365;
                     state.new_source_sync_capability = SYNC_CAPABILITY_READ_ONLY.to_string();
+                    state.new_source_google_account_id = None;
                     state.source_status_message = Some(format!("Added source '{}'", created.name));
                 }
                 Err(err) => {
@@ -561,23 +797,44 @@ pub fn render_calendar_sync_section(
                 sync_past_days: source.sync_past_days,
                 sync_future_days: source.sync_future_days,
                 sync_capability: source.sync_capability.clone(),
+                google_account_id: source.google_account_id,
                 enabled: source.enabled,
             });
 
+        let mut pending_job: Option<(SyncJobKind, SyncApplyOptions)> = None;
+
+        let subscribed = source.source_type == GOOGLE_API_SOURCE_TYPE;
+
         ui.group(|ui| {
             ui.horizontal(|ui| {
                 ui.checkbox(&mut draft.enabled, "Enabled");
+                if let Some(color) = source.color.as_deref().and_then(parse_hex_color) {
+                    ui.colored_label(color, "●");
+                }
                 ui.label("Name:");
                 ui.add_sized([140.0, 20.0], egui::TextEdit::singleline(&mut draft.name));
             });
 
-            ui.horizontal(|ui| {
-                ui.label("ICS URL:");
-                ui.add_sized(
-                    [390.0, 20.0],
-                    egui::TextEdit::singleline(&mut draft.ics_url),
-                );
-            });
+            if subscribed {
+                ui.horizontal(|ui| {
+                    ui.label("Calendar:");
+                    ui.label(source.remote_calendar_id.as_deref().unwrap_or_default());
+                    let email = accounts
+                        .iter()
+                        .find(|account| Some(account.id) == source.google_account_id)
+                        .and_then(|account| account.account_email.as_deref())
+                        .unwrap_or("no account");
+                    ui.small(format!("via {}", email));
+                });
+            } else {
+                ui.horizontal(|ui| {
+                    ui.label("ICS URL:");
+                    ui.add_sized(
+                        [390.0, 20.0],
+                        egui::TextEdit::singleline(&mut draft.ics_url),
+                    );
+                });
+            }
 
             ui.horizontal(|ui| {
                 ui.label("Poll:");
@@ -605,13 +862,28 @@ pub fn render_calendar_sync_section(
                 );
 
                 let mut write_back_enabled = draft.sync_capability == SYNC_CAPABILITY_READ_WRITE;
-                if ui.checkbox(&mut write_back_enabled, "Read/write").changed() {
+                if subscribed {
+                    // Capability follows the calendar's access role.
+                    ui.label(if write_back_enabled {
+                        "Read/write"
+                    } else {
+                        "Read-only"
+                    });
+                } else if ui.checkbox(&mut write_back_enabled, "Read/write").changed() {
                     draft.sync_capability = if write_back_enabled {
                         SYNC_CAPABILITY_READ_WRITE.to_string()
                     } else {
                         SYNC_CAPABILITY_READ_ONLY.to_string()
                     };
                 }
+                if write_back_enabled && !subscribed {
+                    render_account_picker(
+                        ui,
+                        ("source_google_account", source_id),
+                        &mut draft.google_account_id,
+                        &accounts,
+                    );
+                }
 
                 if ui.button("Update").clicked() {
                     state.source_status_message = None;
@@ -620,7 +892,7 @@ pub fn render_calendar_sync_section(
                     let updated = CalendarSource {
                         id: Some(source_id),
                         name: draft.name.trim().to_string(),
-                        source_type: GOOGLE_ICS_SOURCE_TYPE.to_string(),
+                        source_type: source.source_type.clone(),
                         ics_url: draft.ics_url.trim().to_string(),
                         enabled: draft.enabled,
                         poll_interval_minutes: draft.poll_interval_minutes,
@@ -632,6 +904,9 @@ pub fn render_calendar_sync_section(
                         last_sync_at: source.last_sync_at.clone(),
                         last_sync_status: source.last_sync_status.clone(),
                         last_error: source.last_error.clone(),
+                        google_account_id: draft.google_account_id,
+                        remote_calendar_id: source.remote_calendar_id.clone(),
+                        color: source.color.clone(),
                     };
 
                     match source_service.update(&updated) {
@@ -658,58 +933,14 @@ pub fn render_calendar_sync_section(
                     .add_enabled(!any_sync_in_progress, egui::Button::new("Preview Sync"))
                     .clicked()
                 {
-                    state.source_error_message = None;
-                    state.source_sync_in_progress_id = Some(source_id);
-
-                    let source_name = draft.name.clone();
-                    state.source_status_message = Some(format!("Previewing '{}'...", source_name));
-
-                    let db_path = database.path().to_string();
-                    let (tx, rx) = mpsc::channel();
-                    state.source_sync_result_rx = Some(rx);
-
-                    thread::spawn(move || {
-                        let result = (|| -> SyncWorkerMessage {
-                            let db = Database::new(&db_path).map_err(|err| err.to_string())?;
-                            let engine = CalendarSyncEngine::new(db.connection())
-                                .map_err(|err| err.to_string())?;
-                            let summary = engine
-                                .preview_source(source_id)
-                                .map_err(|err| err.to_string())?;
-                            Ok((source_name, SyncJobKind::Preview, summary))
-                        })();
-
-                        let _ = tx.send(result);
-                    });
+                    pending_job = Some((SyncJobKind::Preview, SyncApplyOptions::default()));
                 }
 
                 if ui
                     .add_enabled(!any_sync_in_progress, egui::Button::new(sync_button_text))
                     .clicked()
                 {
-                    state.source_error_message = None;
-                    state.source_sync_in_progress_id = Some(source_id);
-
-                    let source_name = draft.name.clone();
-                    state.source_status_message = Some(format!("Syncing '{}'...", source_name));
-
-                    let db_path = database.path().to_string();
-                    let (tx, rx) = mpsc::channel();
-                    state.source_sync_result_rx = Some(rx);
-
-                    thread::spawn(move || {
-                        let result = (|| -> SyncWorkerMessage {
-                            let db = Database::new(&db_path).map_err(|err| err.to_string())?;
-                            let engine = CalendarSyncEngine::new(db.connection())
-                                .map_err(|err| err.to_string())?;
-                            let summary = engine
-                                .sync_source(source_id)
-                                .map_err(|err| err.to_string())?;
-                            Ok((source_name, SyncJobKind::Apply, summary))
-                        })();
-
-                        let _ = tx.send(result);
-                    });
+                    pending_job = Some((SyncJobKind::Apply, SyncApplyOptions::default()));
                 }
 
                 if ui.button("Delete").clicked() {
@@ -729,40 +960,72 @@ pub fn render_calendar_sync_section(
                 }
             });
 
-            if let Some(status) = &source.last_sync_status {
+            if source.last_sync_status.as_deref() == Some(SYNC_STATUS_NEEDS_CONFIRMATION) {
+                ui.colored_label(
+                    Color32::YELLOW,
+                    "⚠ Sync paused: the last run would have deleted many events. \
+                     They are kept until you confirm.",
+                );
+                ui.horizontal(|ui| {
+                    let idle = state.source_sync_in_progress_id.is_none();
+                    if ui
+                        .add_enabled(idle, egui::Button::new("Review Changes"))
+                        .clicked()
+                    {
+                        pending_job = Some((SyncJobKind::Preview, SyncApplyOptions::default()));
+                    }
+                    if ui
+                        .add_enabled(idle, egui::Button::new("Confirm Deletions"))
+                        .clicked()
+                    {
+                        pending_job = Some((
+                            SyncJobKind::Apply,
+                            SyncApplyOptions {
+                                confirm_mass_deletion: true,
+                                ..SyncApplyOptions::default()
+                            },
+                        ));
+                    }
+                });
+            } else if let Some(status) = &source.last_sync_status {
                 ui.label(format!("Last status: {}", status));
             }
-            match source_service.list_recent_sync_runs(source_id, 3) {
-                Ok(sync_runs) if !sync_runs.is_empty() => {
-                    ui.label("Recent sync runs:");
-                    for run in sync_runs {
-                        let mut summary = format!(
-                            "{} | {} ms | +{} ~{} -{} ={} skip {} err {}",
-                            run.status,
-                            run.duration_ms,
-                            run.created_count,
-                            run.updated_count,
-                            run.deleted_count,
-                            run.unchanged_count,
-                            run.skipped_count,
-                            run.error_count,
-                        );
-
-                        if let Some(error_message) = run.error_message.as_deref() {
-                            summary.push_str(&format!(" | {}", error_message));
-                        }
 
-                        ui.small(summary);
-                    }
+            let preview_action = match state.sync_preview.as_mut() {
+                Some(preview) if preview.source_id == source_id => {
+                    settings_sync_preview::render_sync_preview(
+                        ui,
+                        preview,
+                        state.source_sync_in_progress_id.is_none(),
+                    )
                 }
-                Ok(_) => {}
-                Err(err) => {
-                    ui.colored_label(
-                        Color32::LIGHT_RED,
-                        format!("Failed to load sync history: {}", err),
-                    );
+                _ => SyncPreviewAction::None,
+            };
+            match preview_action {
+                SyncPreviewAction::None => {}
+                SyncPreviewAction::Discard => {
+                    state.sync_preview = None;
+                }
+                SyncPreviewAction::ApplySelected(approved) => {
+                    state.sync_preview = None;
+                    // The user reviewed every deletion in the checklist.
+                    pending_job = Some((
+                        SyncJobKind::Apply,
+                        SyncApplyOptions {
+                            approved: Some(approved),
+                            confirm_mass_deletion: true,
+                        },
+                    ));
                 }
             }
+
+            settings_sync_history::render_sync_history(
+                ui,
+                database,
+                source,
+                &mut state.source_status_message,
+                &mut state.source_error_message,
+            );
             ui.label(format!("Capability: {}", source.sync_capability));
             if source.sync_capability == SYNC_CAPABILITY_READ_WRITE {
                 match outbound_service.queue_stats_for_source(source_id) {
@@ -871,6 +1134,13 @@ pub fn render_calendar_sync_section(
                                             }
 
                                             ui.horizontal(|ui| {
+                                                if conflict.supports_field_merge()
+                                                    && ui.button("Compare & Merge…").clicked()
+                                                {
+                                                    state.conflict_merge_dialog =
+                                                        SyncConflictDialogState::new(conflict.clone());
+                                                }
+
                                                 if ui.button("Keep Remote").clicked() {
                                                     match conflict.id {
                                                         Some(conflict_id) => {
@@ -992,6 +1262,17 @@ pub fn render_calendar_sync_section(
             }
         });
 
+        if let Some((kind, options)) = pending_job {
+            start_sync_job(
+                state,
+                database,
+                source_id,
+                source.name.clone(),
+                kind,
+                options,
+            );
+        }
+
         ui.add_space(6.0);
     }
 
@@ -1001,6 +1282,67 @@ pub fn render_calendar_sync_section(
     }
 }
 
+/// Run a preview or sync for one source on a background thread.
+///
+/// The result is picked up by [`poll_sync_result`].
+fn start_sync_job(
+    state: &mut CalendarSyncState,
+    database: &Database,
+    source_id: i64,
+    source_name: String,
+    kind: SyncJobKind,
+    options: SyncApplyOptions,
+) {
+    state.source_error_message = None;
+    state.source_sync_in_progress_id = Some(source_id);
+    state.source_status_message = Some(match kind {
+        SyncJobKind::Preview => format!("Previewing '{}'...", source_name),
+        SyncJobKind::Apply => format!("Syncing '{}'...", source_name),
+    });
+
+    let db_path = database.path().to_string();
+    let (tx, rx) = mpsc::channel();
+    state.source_sync_result_rx = Some(rx);
+
+    thread::spawn(move || {
+        let result = (|| -> SyncWorkerMessage {
+            let db = Database::new(&db_path).map_err(|err| err.to_string())?;
+            let engine = CalendarSyncEngine::new(db.connection()).map_err(|err| err.to_string())?;
+            let summary = match kind {
+                SyncJobKind::Preview => engine.preview_source(source_id),
+                SyncJobKind::Apply => engine.sync_source_selected(source_id, &options),
+            }
+            .map_err(|err| err.to_string())?;
+            Ok((source_name, kind, summary))
+        })();
+
+        let _ = tx.send(result);
+    });
+}
+
+/// Render the three-way merge window for a conflict, if one is open.
+pub fn render_conflict_merge_dialog(
+    ctx: &egui::Context,
+    database: &Database,
+    state: &mut CalendarSyncState,
+) {
+    let Some(dialog_state) = state.conflict_merge_dialog.as_mut() else {
+        return;
+    };
+
+    match render_sync_conflict_dialog(ctx, dialog_state, database) {
+        SyncConflictDialogAction::None => {}
+        SyncConflictDialogAction::Merged(message) => {
+            state.source_status_message = Some(message);
+            state.source_error_message = None;
+            state.conflict_merge_dialog = None;
+        }
+        SyncConflictDialogAction::Close => {
+            state.conflict_merge_dialog = None;
+        }
+    }
+}
+
 fn describe_sync_conflict(reason: &str, remote_change_type: &str) -> String {
     let local_change = match reason {
         SYNC_CONFLICT_REASON_LOCAL_CREATE_PENDING => "a pending local create",
@@ -1021,6 +1363,154 @@ fn describe_conflict_resolution(resolution: &str) -> &'static str {
     match resolution {
         SYNC_CONFLICT_RESOLUTION_REMOTE_WINS => "remote version applied",
         SYNC_CONFLICT_RESOLUTION_RETRY_LOCAL => "local retry requested",
+        SYNC_CONFLICT_RESOLUTION_MERGED => "merged field by field",
         _ => "unknown",
     }
 }
+
+/// Combo box choosing which Google account a read/write source syncs through.
+fn render_account_picker(
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    selected: &mut Option<i64>,
+    accounts: &[GoogleAccount],
+) {
+    let selected_text = selected
+        .and_then(|id| accounts.iter().find(|account| account.id == id))
+        .and_then(|account| account.account_email.clone())
+        .unwrap_or_else(|| "No account".to_string());
+
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, "No account");
+            for account in accounts {
+                let email = account
+                    .account_email
+                    .as_deref()
+                    .unwrap_or("unknown account");
+                ui.selectable_value(selected, Some(account.id), email);
+            }
+        });
+}
+
+/// Checklist of an account's calendars; ticking one subscribes it as a source.
+fn render_calendar_list(
+    ui: &mut egui::Ui,
+    database: &Database,
+    account_id: i64,
+    entries: &[GoogleCalendarListEntry],
+    state: &mut CalendarSyncState,
+) {
+    let discovery = GoogleCalendarDiscovery::new(database.connection());
+    let subscribed = match discovery.subscribed_sources(account_id) {
+        Ok(subscribed) => subscribed,
+        Err(err) => {
+            ui.colored_label(
+                Color32::LIGHT_RED,
+                format!("Failed to load subscriptions: {}", err),
+            );
+            return;
+        }
+    };
+
+    egui::ScrollArea::vertical()
+        .id_source(("google_calendar_list", account_id))
+        .max_height(180.0)
+        .show(ui, |ui| {
+            for entry in entries {
+                let existing_source = subscribed.get(&entry.id).copied();
+                let mut checked = existing_source.is_some();
+
+                ui.horizontal(|ui| {
+                    let changed = ui.checkbox(&mut checked, "").changed();
+                    if let Some(color) = entry.background_color.as_deref().and_then(parse_hex_color)
+                    {
+                        ui.colored_label(color, "●");
+                    }
+                    ui.label(entry.display_name());
+                    if entry.primary {
+                        ui.small("(primary)");
+                    }
+                    ui.small(access_role_label(&entry.access_role));
+
+                    if !changed {
+                        return;
+                    }
+                    state.source_status_message = None;
+                    state.source_error_message = None;
+
+                    match existing_source {
+                        None => match discovery.subscribe(account_id, entry) {
+                            Ok(source) => {
+                                state.source_status_message =
+                                    Some(format!("Subscribed to '{}'", source.name));
+                            }
+                            Err(err) => {
+                                state.source_error_message =
+                                    Some(format!("Failed to subscribe: {}", err));
+                            }
+                        },
+                        // Unsubscribing deletes the synced events, so ask first.
+                        Some(source_id) => state.source_pending_unsubscribe = Some(source_id),
+                    }
+                });
+
+                if let Some(source_id) = existing_source
+                    .filter(|source_id| state.source_pending_unsubscribe == Some(*source_id))
+                {
+                    render_unsubscribe_confirmation(ui, database, source_id, entry, state);
+                }
+            }
+        });
+}
+
+fn render_unsubscribe_confirmation(
+    ui: &mut egui::Ui,
+    database: &Database,
+    source_id: i64,
+    entry: &GoogleCalendarListEntry,
+    state: &mut CalendarSyncState,
+) {
+    let event_count = EventSyncMapService::new(database.connection())
+        .list_by_source_id(source_id)
+        .map(|mappings| mappings.len())
+        .unwrap_or(0);
+
+    ui.horizontal_wrapped(|ui| {
+        ui.colored_label(
+            Color32::LIGHT_RED,
+            format!(
+                "Unsubscribe from '{}'? Its {} synced event(s) are removed from this calendar.",
+                entry.display_name(),
+                event_count
+            ),
+        );
+        if ui.button("Unsubscribe").clicked() {
+            state.source_pending_unsubscribe = None;
+            match CalendarSourceService::new(database.connection()).delete(source_id) {
+                Ok(_) => {
+                    state.source_drafts.remove(&source_id);
+                    state.source_status_message =
+                        Some(format!("Unsubscribed from '{}'", entry.display_name()));
+                }
+                Err(err) => {
+                    state.source_error_message = Some(format!("Failed to unsubscribe: {}", err));
+                }
+            }
+        }
+        if ui.button("Cancel").clicked() {
+            state.source_pending_unsubscribe = None;
+        }
+    });
+}
+
+fn access_role_label(access_role: &str) -> &'static str {
+    match access_role {
+        "owner" => "owner · read/write",
+        "writer" => "writer · read/write",
+        "reader" => "reader · read-only",
+        "freeBusyReader" => "free/busy only · read-only",
+        _ => "read-only",
+    }
+}
@@ -1,6 +1,10 @@
 use crate::models::settings::Settings;
+use crate::services::backup::policy::BackupInterval;
+use crate::services::backup::BackupService;
 use crate::services::database::Database;
+use crate::services::secrets;
 use crate::services::settings::SettingsService;
+use anyhow::Context;
 use egui::{Color32, RichText};
 
 use super::settings_calendar_sync;
@@ -13,8 +17,30 @@ pub struct SettingsDialogResponse {
     pub show_ribbon_changed: bool,
 }
 
-/// Re-export calendar sync state as the settings dialog state.
-pub type SettingsDialogState = settings_calendar_sync::CalendarSyncState;
+/// State the settings dialog keeps between frames
+pub struct SettingsDialogState {
+    calendar_sync: settings_calendar_sync::CalendarSyncState,
+    backup_passphrase: BackupPassphraseEntry,
+}
+
+impl SettingsDialogState {
+    pub fn new() -> Self {
+        Self {
+            calendar_sync: settings_calendar_sync::CalendarSyncState::new(),
+            backup_passphrase: BackupPassphraseEntry::default(),
+        }
+    }
+}
+
+/// The backup passphrase fields. A passphrase that was set is held here and
+/// only written to the secret store when the settings are saved.
+#[derive(Default)]
+struct BackupPassphraseEntry {
+    passphrase: String,
+    confirm: String,
+    pending: Option<String>,
+    feedback: Option<(bool, String)>,
+}
 
 impl SettingsDialogResponse {
     fn new(saved: bool, show_ribbon_changed: bool) -> Self {
@@ -37,7 +63,7 @@ pub fn render_settings_dialog(
     let mut error_message: Option<String> = None;
     let mut show_ribbon_changed = false;
 
-    settings_calendar_sync::poll_sync_result(ctx, dialog_state);
+    settings_calendar_sync::poll_sync_result(ctx, &mut dialog_state.calendar_sync);
 
     let mut dialog_open = *show_dialog;
 
@@ -458,12 +484,51 @@ pub fn render_settings_dialog(
                 ui.separator();
                 ui.add_space(8.0);
 
+                ui.heading("Trash");
+                ui.add_space(4.0);
+
+                ui.horizontal(|ui| {
+                    ui.allocate_ui_with_layout(
+                        egui::Vec2::new(label_width, 20.0),
+                        egui::Layout::right_to_left(egui::Align::Center),
+                        |ui| {
+                            ui.label("Keep deleted events:");
+                        },
+                    );
+                    ui.add(
+                        egui::DragValue::new(&mut settings.trash_retention_days)
+                            .range(1..=365)
+                            .suffix(" days"),
+                    );
+                });
+
+                ui.add_space(16.0);
+                ui.separator();
+                ui.add_space(8.0);
+
+                render_pdf_section(ui, label_width, settings);
+
+                ui.add_space(16.0);
+                ui.separator();
+                ui.add_space(8.0);
+
+                render_backup_section(
+                    ui,
+                    label_width,
+                    settings,
+                    &mut dialog_state.backup_passphrase,
+                );
+
+                ui.add_space(16.0);
+                ui.separator();
+                ui.add_space(8.0);
+
                 settings_calendar_sync::render_calendar_sync_section(
                     ui,
                     label_width,
                     settings,
                     database,
-                    dialog_state,
+                    &mut dialog_state.calendar_sync,
                 );
 
                 ui.add_space(16.0);
@@ -490,16 +555,28 @@ pub fn render_settings_dialog(
                                 "Default card height must be between {:.0} and {:.0} px",
                                 MIN_CARD_DIMENSION, MAX_CARD_DIMENSION
                             ));
+                        } else if backup_keeps_nothing(settings) {
+                            error_message =
+                                Some("Backup retention must keep at least one backup".to_string());
+                        } else if settings.backup_encrypt
+                            && settings.backup_passphrase_ref.is_none()
+                            && dialog_state.backup_passphrase.pending.is_none()
+                        {
+                            error_message =
+                                Some("Set a backup passphrase or turn off encryption".to_string());
                         } else {
-                            // Save settings
-                            let service = SettingsService::new(database);
-                            match service.update(settings) {
+                            match save_settings(
+                                database,
+                                settings,
+                                &mut dialog_state.backup_passphrase,
+                            ) {
                                 Ok(_) => {
                                     *show_dialog = false;
                                     saved = true;
                                 }
                                 Err(e) => {
-                                    error_message = Some(format!("Failed to save settings: {}", e));
+                                    error_message =
+                                        Some(format!("Failed to save settings: {:#}", e));
                                 }
                             }
                         }
@@ -525,13 +602,285 @@ pub fn render_settings_dialog(
             });
         });
 
+    settings_calendar_sync::render_conflict_merge_dialog(
+        ctx,
+        database,
+        &mut dialog_state.calendar_sync,
+    );
+
     if !dialog_open {
         *show_dialog = false;
     }
+    if !*show_dialog {
+        dialog_state.backup_passphrase = BackupPassphraseEntry::default();
+    }
 
     SettingsDialogResponse::new(saved, show_ribbon_changed)
 }
 
+fn render_pdf_section(ui: &mut egui::Ui, label_width: f32, settings: &mut Settings) {
+    ui.heading("PDF Export");
+    ui.add_space(4.0);
+
+    ui.horizontal(|ui| {
+        ui.allocate_ui_with_layout(
+            egui::Vec2::new(label_width, 20.0),
+            egui::Layout::right_to_left(egui::Align::Center),
+            |ui| {
+                ui.label("Font:");
+            },
+        );
+        let mut font_path = settings.pdf_font_path.clone().unwrap_or_default();
+        ui.add(
+            egui::TextEdit::singleline(&mut font_path)
+                .hint_text("Built-in font")
+                .desired_width(220.0),
+        );
+        if ui.button("Browse…").clicked() {
+            if let Some(file) = rfd::FileDialog::new()
+                .add_filter("TrueType fonts", &["ttf"])
+                .pick_file()
+            {
+                font_path = file.to_string_lossy().to_string();
+            }
+        }
+        if !font_path.is_empty() && ui.button("Default").clicked() {
+            font_path.clear();
+        }
+        settings.pdf_font_path = Some(font_path).filter(|path| !path.trim().is_empty());
+    });
+
+    ui.horizontal(|ui| {
+        ui.add_space(label_width);
+        ui.label(
+            RichText::new(
+                "Embedded in exported PDFs. Pick a TrueType (.ttf) font covering your \
+                 scripts (e.g. Noto Sans SC); .otf fonts with CFF outlines are not \
+                 supported. Emoji and symbols fall back to the built-in fonts.",
+            )
+            .small()
+            .color(Color32::GRAY),
+        );
+    });
+}
+
+fn render_backup_section(
+    ui: &mut egui::Ui,
+    label_width: f32,
+    settings: &mut Settings,
+    passphrase_entry: &mut BackupPassphraseEntry,
+) {
+    ui.heading("Backups");
+    ui.add_space(4.0);
+
+    let label = |ui: &mut egui::Ui, text: &str| {
+        ui.allocate_ui_with_layout(
+            egui::Vec2::new(label_width, 20.0),
+            egui::Layout::right_to_left(egui::Align::Center),
+            |ui| {
+                ui.label(text);
+            },
+        );
+    };
+
+    ui.horizontal(|ui| {
+        label(ui, "Automatic backup:");
+        let mut interval = BackupInterval::from_setting(&settings.backup_interval);
+        egui::ComboBox::from_id_source("backup_interval")
+            .selected_text(interval.label())
+            .show_ui(ui, |ui| {
+                for option in BackupInterval::ALL {
+                    ui.selectable_value(&mut interval, option, option.label());
+                }
+            });
+        settings.backup_interval = interval.as_setting().to_string();
+    });
+
+    ui.horizontal(|ui| {
+        label(ui, "Backup folder:");
+        let mut directory = settings.backup_directory.clone().unwrap_or_default();
+        let default_hint = BackupService::default_backup_dir()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_else(|_| "Default folder".to_string());
+        ui.add(
+            egui::TextEdit::singleline(&mut directory)
+                .hint_text(default_hint)
+                .desired_width(220.0),
+        );
+        if ui.button("Browse…").clicked() {
+            if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+                directory = folder.to_string_lossy().to_string();
+            }
+        }
+        if !directory.is_empty() && ui.button("Default").clicked() {
+            directory.clear();
+        }
+        settings.backup_directory = Some(directory).filter(|dir| !dir.trim().is_empty());
+    });
+
+    ui.horizontal(|ui| {
+        label(ui, "Keep:");
+        for (value, suffix) in [
+            (&mut settings.backup_keep_hourly, " hourly"),
+            (&mut settings.backup_keep_daily, " daily"),
+            (&mut settings.backup_keep_weekly, " weekly"),
+            (&mut settings.backup_keep_monthly, " monthly"),
+        ] {
+            ui.add(egui::DragValue::new(value).range(0..=1000).suffix(suffix));
+        }
+    });
+
+    if backup_keeps_nothing(settings) {
+        ui.horizontal(|ui| {
+            ui.add_space(label_width);
+            ui.colored_label(Color32::LIGHT_RED, "⚠ Keep at least one backup");
+        });
+    }
+
+    ui.horizontal(|ui| {
+        ui.add_space(label_width);
+        ui.checkbox(
+            &mut settings.backup_encrypt,
+            "Encrypt backups with a passphrase",
+        );
+    });
+
+    if settings.backup_encrypt {
+        render_backup_passphrase(ui, label_width, settings, passphrase_entry);
+    }
+
+    ui.horizontal(|ui| {
+        ui.add_space(label_width);
+        ui.label(
+            RichText::new(
+                "Backups run in the background while the app is open. Each one is \
+                 re-opened and integrity-checked before older backups are pruned.",
+            )
+            .small()
+            .color(Color32::GRAY),
+        );
+    });
+}
+
+/// Saves the settings, storing a newly set backup passphrase first. The new
+/// passphrase gets its own reference, so a failed save keeps the old one.
+fn save_settings(
+    database: &Database,
+    settings: &mut Settings,
+    passphrase_entry: &mut BackupPassphraseEntry,
+) -> anyhow::Result<()> {
+    let previous_ref = settings.backup_passphrase_ref.clone();
+    if let Some(passphrase) = &passphrase_entry.pending {
+        let reference =
+            secrets::put(None, passphrase).context("Failed to store the backup passphrase")?;
+        settings.backup_passphrase_ref = Some(reference);
+    }
+
+    let saved = SettingsService::new(database).update(settings);
+    if let Err(err) = secrets::discard_on_error(
+        saved,
+        previous_ref.as_deref(),
+        settings.backup_passphrase_ref.as_deref(),
+    ) {
+        settings.backup_passphrase_ref = previous_ref;
+        return Err(err);
+    }
+
+    if passphrase_entry.pending.take().is_some() {
+        secrets::forget(previous_ref.as_deref());
+    }
+    Ok(())
+}
+
+/// Passphrase entry for encrypted backups. A new passphrase waits in
+/// `entry` until Save; settings keep only its secret-store reference.
+fn render_backup_passphrase(
+    ui: &mut egui::Ui,
+    label_width: f32,
+    settings: &Settings,
+    entry: &mut BackupPassphraseEntry,
+) {
+    let has_passphrase = settings.backup_passphrase_ref.is_some();
+    ui.horizontal(|ui| {
+        ui.allocate_ui_with_layout(
+            egui::Vec2::new(label_width, 20.0),
+            egui::Layout::right_to_left(egui::Align::Center),
+            |ui| {
+                ui.label(if has_passphrase {
+                    "New passphrase:"
+                } else {
+                    "Passphrase:"
+                });
+            },
+        );
+        ui.add(
+            egui::TextEdit::singleline(&mut entry.passphrase)
+                .password(true)
+                .desired_width(140.0),
+        );
+        ui.add(
+            egui::TextEdit::singleline(&mut entry.confirm)
+                .password(true)
+                .hint_text("Confirm")
+                .desired_width(140.0),
+        );
+        if ui.button("Set").clicked() {
+            entry.feedback = Some(if entry.passphrase.is_empty() {
+                (false, "Passphrase cannot be empty".to_string())
+            } else if entry.passphrase != entry.confirm {
+                (false, "Passphrases do not match".to_string())
+            } else {
+                entry.pending = Some(std::mem::take(&mut entry.passphrase));
+                entry.confirm.clear();
+                (
+                    true,
+                    "Passphrase set; it is stored when you click Save. Older backups still need the passphrase they were made with."
+                        .to_string(),
+                )
+            });
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.add_space(label_width);
+        match &entry.feedback {
+            Some((true, message)) => {
+                ui.colored_label(Color32::LIGHT_GREEN, message);
+            }
+            Some((false, message)) => {
+                ui.colored_label(Color32::LIGHT_RED, message);
+            }
+            None if has_passphrase => {
+                ui.label(
+                    RichText::new(format!(
+                        "A passphrase is set and kept in the {}. Restoring asks for it.",
+                        secrets::store().backend_name()
+                    ))
+                    .small()
+                    .color(Color32::GRAY),
+                );
+            }
+            None => {
+                ui.colored_label(
+                    Color32::LIGHT_RED,
+                    "⚠ Set a passphrase, or backups will fail",
+                );
+            }
+        }
+    });
+}
+
+fn backup_keeps_nothing(settings: &Settings) -> bool {
+    [
+        settings.backup_keep_hourly,
+        settings.backup_keep_daily,
+        settings.backup_keep_weekly,
+        settings.backup_keep_monthly,
+    ]
+    .iter()
+    .all(|count| *count <= 0)
+}
+
 /// Convert weekday number to name
 fn weekday_name(day: u8) -> &'static str {
     match day {
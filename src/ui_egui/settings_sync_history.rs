@@ -0,0 +1,204 @@
+//! Sync run history and diagnostics export for a calendar source.
+//!
+//! Rendered inside each source's block in the calendar sync settings.
+
+use crate::models::calendar_source::CalendarSource;
+use crate::services::calendar_sync::diagnostics::{redact_error_message, SyncDiagnosticsExporter};
+use crate::services::calendar_sync::{
+    CalendarSourceService, SyncRunDiagnostics, SYNC_RUN_HISTORY_LIMIT,
+};
+use crate::services::database::Database;
+use egui::{Color32, RichText, Sense, Stroke, Vec2};
+
+const CHART_HEIGHT: f32 = 48.0;
+const CHART_BAR_WIDTH: f32 = 8.0;
+const CHART_BAR_GAP: f32 = 2.0;
+
+/// Render the collapsible history table, charts and diagnostics export button.
+pub(super) fn render_sync_history(
+    ui: &mut egui::Ui,
+    database: &Database,
+    source: &CalendarSource,
+    status_message: &mut Option<String>,
+    error_message: &mut Option<String>,
+) {
+    let Some(source_id) = source.id else {
+        return;
+    };
+
+    let source_service = CalendarSourceService::new(database.connection());
+    let runs = match source_service.list_recent_sync_runs(source_id, SYNC_RUN_HISTORY_LIMIT) {
+        Ok(runs) => runs,
+        Err(err) => {
+            ui.colored_label(
+                Color32::LIGHT_RED,
+                format!("Failed to load sync history: {}", err),
+            );
+            return;
+        }
+    };
+
+    egui::CollapsingHeader::new(format!("Sync history ({} runs)", runs.len()))
+        .id_source(("sync_history", source_id))
+        .show(ui, |ui| {
+            if runs.is_empty() {
+                ui.label(RichText::new("No sync runs recorded yet").italics());
+            } else {
+                // Oldest first so the charts read left to right.
+                let chronological: Vec<&SyncRunDiagnostics> = runs.iter().rev().collect();
+
+                ui.label("Duration (ms)");
+                render_bar_chart(ui, &chronological, |run| run.duration_ms);
+                ui.label("Errors");
+                render_bar_chart(ui, &chronological, |run| run.error_count);
+
+                ui.add_space(4.0);
+                render_history_table(ui, source, source_id, &runs);
+            }
+
+            ui.add_space(4.0);
+            if ui
+                .button("Export Diagnostics…")
+                .on_hover_text(
+                    "Save recent runs, queue stats and conflicts as a zip for bug reports. \
+                     URLs and tokens are redacted.",
+                )
+                .clicked()
+            {
+                export_diagnostics(database, source, status_message, error_message);
+            }
+        });
+}
+
+fn render_bar_chart(
+    ui: &mut egui::Ui,
+    runs: &[&SyncRunDiagnostics],
+    value: impl Fn(&SyncRunDiagnostics) -> i64,
+) {
+    let max_value = runs.iter().map(|run| value(run)).max().unwrap_or(0).max(1) as f32;
+    let width = runs.len() as f32 * (CHART_BAR_WIDTH + CHART_BAR_GAP);
+    let (rect, response) = ui.allocate_exact_size(Vec2::new(width, CHART_HEIGHT), Sense::hover());
+    let painter = ui.painter_at(rect);
+    let visuals = ui.visuals();
+
+    painter.rect_stroke(
+        rect,
+        0.0,
+        Stroke::new(1.0_f32, visuals.widgets.noninteractive.bg_stroke.color),
+    );
+
+    let mut hovered: Option<&SyncRunDiagnostics> = None;
+    for (index, run) in runs.iter().enumerate() {
+        let bar_value = value(run);
+        let height = (bar_value as f32 / max_value) * (CHART_HEIGHT - 2.0);
+        let left = rect.left() + index as f32 * (CHART_BAR_WIDTH + CHART_BAR_GAP);
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(left, rect.bottom() - height),
+            egui::pos2(left + CHART_BAR_WIDTH, rect.bottom()),
+        );
+        let color = if run.error_count > 0 {
+            Color32::LIGHT_RED
+        } else {
+            visuals.selection.bg_fill
+        };
+        painter.rect_filled(bar, 1.0, color);
+
+        if let Some(pointer) = response.hover_pos() {
+            if pointer.x >= left && pointer.x < left + CHART_BAR_WIDTH + CHART_BAR_GAP {
+                hovered = Some(run);
+            }
+        }
+    }
+
+    if let Some(run) = hovered {
+        response.on_hover_text(format!(
+            "{}\n{} | {} ms | errors {}",
+            run.started_at, run.status, run.duration_ms, run.error_count
+        ));
+    }
+}
+
+fn render_history_table(
+    ui: &mut egui::Ui,
+    source: &CalendarSource,
+    source_id: i64,
+    runs: &[SyncRunDiagnostics],
+) {
+    egui::ScrollArea::vertical()
+        .id_source(("sync_history_table", source_id))
+        .max_height(180.0)
+        .show(ui, |ui| {
+            egui::Grid::new(("sync_history_grid", source_id))
+                .num_columns(9)
+                .striped(true)
+                .spacing([8.0, 2.0])
+                .show(ui, |ui| {
+                    for header in ["Started", "Status", "ms", "+", "~", "-", "=", "Skip", "Err"] {
+                        ui.small(RichText::new(header).strong());
+                    }
+                    ui.end_row();
+
+                    for run in runs {
+                        ui.small(&run.started_at);
+                        let status = RichText::new(&run.status).small();
+                        if run.error_count > 0 {
+                            let error = run
+                                .error_message
+                                .as_deref()
+                                .map(|message| redact_error_message(source, message))
+                                .unwrap_or_default();
+                            ui.label(status.color(Color32::LIGHT_RED))
+                                .on_hover_text(error);
+                        } else {
+                            ui.label(status);
+                        }
+                        ui.small(run.duration_ms.to_string());
+                        ui.small(run.created_count.to_string());
+                        ui.small(run.updated_count.to_string());
+                        ui.small(run.deleted_count.to_string());
+                        ui.small(run.unchanged_count.to_string());
+                        ui.small(run.skipped_count.to_string());
+                        ui.small(run.error_count.to_string());
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn export_diagnostics(
+    database: &Database,
+    source: &CalendarSource,
+    status_message: &mut Option<String>,
+    error_message: &mut Option<String>,
+) {
+    let Some(source_id) = source.id else {
+        return;
+    };
+
+    let file_name = format!(
+        "sync_diagnostics_{}_{}.zip",
+        source_id,
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    );
+
+    if let Some(path) = rfd::FileDialog::new()
+        .set_title("Export Sync Diagnostics")
+        .set_file_name(file_name)
+        .add_filter("Zip archives", &["zip"])
+        .save_file()
+    {
+        match SyncDiagnosticsExporter::new(database.connection()).export_zip(source_id, &path) {
+            Ok(()) => {
+                *status_message = Some(format!(
+                    "Exported diagnostics for '{}' to {}",
+                    source.name,
+                    path.display()
+                ));
+                *error_message = None;
+            }
+            Err(err) => {
+                *error_message = Some(format!("Failed to export diagnostics: {}", err));
+            }
+        }
+    }
+}
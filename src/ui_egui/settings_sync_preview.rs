@@ -0,0 +1,135 @@
+//! Checklist of previewed sync changes for a calendar source.
+//!
+//! After "Preview Sync" the individual creates, updates and deletes are
+//! listed with a checkbox each; only the checked ones are applied.
+
+use crate::services::calendar_sync::engine::{SyncChangeKind, SyncPreviewChange};
+use egui::{Color32, RichText};
+use std::collections::HashSet;
+
+/// A completed preview awaiting the user's selection.
+pub(super) struct SyncPreviewState {
+    pub source_id: i64,
+    pub changes: Vec<SyncPreviewChange>,
+    pub selected: Vec<bool>,
+}
+
+/// Action requested from the preview checklist
+pub(super) enum SyncPreviewAction {
+    None,
+    /// Apply only the changes with the given ids
+    ApplySelected(HashSet<String>),
+    Discard,
+}
+
+impl SyncPreviewState {
+    pub fn new(source_id: i64, changes: Vec<SyncPreviewChange>) -> Self {
+        let selected = vec![true; changes.len()];
+        Self {
+            source_id,
+            changes,
+            selected,
+        }
+    }
+
+    fn approved_ids(&self) -> HashSet<String> {
+        self.changes
+            .iter()
+            .zip(self.selected.iter())
+            .filter(|(_, selected)| **selected)
+            .map(|(change, _)| change.id.clone())
+            .collect()
+    }
+}
+
+/// Render the checklist for a source's pending preview.
+pub(super) fn render_sync_preview(
+    ui: &mut egui::Ui,
+    state: &mut SyncPreviewState,
+    apply_enabled: bool,
+) -> SyncPreviewAction {
+    let mut action = SyncPreviewAction::None;
+    let selected_count = state.selected.iter().filter(|selected| **selected).count();
+
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Previewed changes").strong());
+            ui.label(format!(
+                "{} of {} selected",
+                selected_count,
+                state.changes.len()
+            ));
+        });
+
+        ui.horizontal(|ui| {
+            for (kind, label) in [
+                (SyncChangeKind::Create, "creates"),
+                (SyncChangeKind::Update, "updates"),
+                (SyncChangeKind::Delete, "deletes"),
+            ] {
+                let count = state.changes.iter().filter(|c| c.kind == kind).count();
+                if count == 0 {
+                    continue;
+                }
+                let all_selected = state
+                    .changes
+                    .iter()
+                    .zip(state.selected.iter())
+                    .filter(|(change, _)| change.kind == kind)
+                    .all(|(_, selected)| *selected);
+                let mut toggle = all_selected;
+                if ui
+                    .checkbox(&mut toggle, format!("All {} ({})", label, count))
+                    .changed()
+                {
+                    for (change, selected) in state.changes.iter().zip(state.selected.iter_mut()) {
+                        if change.kind == kind {
+                            *selected = toggle;
+                        }
+                    }
+                }
+            }
+        });
+
+        egui::ScrollArea::vertical()
+            .id_source(("sync_preview_changes", state.source_id))
+            .max_height(220.0)
+            .show(ui, |ui| {
+                for (change, selected) in state.changes.iter().zip(state.selected.iter_mut()) {
+                    let (marker, color) = match change.kind {
+                        SyncChangeKind::Create => ("+", Color32::LIGHT_GREEN),
+                        SyncChangeKind::Update => ("~", Color32::LIGHT_BLUE),
+                        SyncChangeKind::Delete => ("−", Color32::LIGHT_RED),
+                    };
+                    let when = change
+                        .start
+                        .map(|start| start.format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_default();
+
+                    ui.horizontal(|ui| {
+                        ui.checkbox(selected, "");
+                        ui.label(RichText::new(marker).color(color).strong());
+                        ui.label(&change.title);
+                        if !when.is_empty() {
+                            ui.small(when);
+                        }
+                    });
+                }
+            });
+
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(apply_enabled, egui::Button::new("Apply Selected"))
+                .on_hover_text("Unchecked changes are skipped and offered again next time")
+                .clicked()
+            {
+                action = SyncPreviewAction::ApplySelected(state.approved_ids());
+            }
+            if ui.button("Discard Preview").clicked() {
+                action = SyncPreviewAction::Discard;
+            }
+        });
+    });
+
+    action
+}
@@ -12,7 +12,8 @@ use crate::services::event::EventService;
 use crate::ui_egui::theme::CalendarTheme;
 
 use super::{
-    filter_events_by_category, filter_events_by_sync_scope, load_read_only_synced_event_ids,
+    filter_events_by_query, filter_events_by_sync_scope, load_read_only_synced_event_ids,
+    ViewFilterMatches,
 };
 
 pub struct DayView;
@@ -32,7 +33,7 @@ impl DayView {
         countdown_requests: &mut Vec<CountdownRequest>,
         active_countdown_events: &HashSet<i64>,
         focus_request: &mut Option<AutoFocusRequest>,
-        category_filter: Option<&str>,
+        view_filter: Option<&ViewFilterMatches>,
         synced_only: bool,
         synced_source_id: Option<i64>,
     ) -> EventInteractionResult {
@@ -45,7 +46,7 @@ impl DayView {
         // Get events for this day
         let event_service = EventService::new(database.connection());
         let events = Self::get_events_for_day(&event_service, *current_date);
-        let events = filter_events_by_category(events, category_filter);
+        let events = filter_events_by_query(events, view_filter);
         let events = filter_events_by_sync_scope(events, database, synced_only, synced_source_id);
         let synced_event_ids = load_read_only_synced_event_ids(database, None);
 
@@ -9,6 +9,8 @@ use std::collections::HashSet;
 use crate::models::event::Event;
 use crate::services::calendar_sync::mapping::EventSyncMapService;
 use crate::services::database::Database;
+use crate::services::event::queries::EventQuery;
+use crate::services::event::EventService;
 
 use super::{CountdownMenuState, CountdownRequest};
 
@@ -102,19 +104,65 @@ pub fn countdown_menu_state(
     }
 }
 
-/// Filter events by category if a filter is active.
-/// Returns only events whose category matches the filter.
-/// If filter is None, all events pass through.
-pub fn filter_events_by_category(events: Vec<Event>, filter: Option<&str>) -> Vec<Event> {
-    match filter {
-        None => events,
-        Some(category) => events
-            .into_iter()
-            .filter(|e| e.category.as_deref() == Some(category))
-            .collect(),
+/// Ids of the events a view filter matches. They come from an FTS query, so
+/// the app keeps them between frames and only re-runs the query when the
+/// filter or the database changes.
+#[derive(Debug, Default)]
+pub struct ViewFilterMatches {
+    query: EventQuery,
+    stamp: Option<(i64, i64)>,
+    ids: HashSet<i64>,
+}
+
+impl ViewFilterMatches {
+    /// Runs `query` against the database.
+    pub fn load(database: &Database, query: &EventQuery) -> Self {
+        let stamp = database
+            .change_stamp()
+            .map_err(|err| log::warn!("Failed to check for database changes: {:#}", err))
+            .ok();
+        let ids = EventService::new(database.connection())
+            .matching_event_ids(query)
+            .unwrap_or_else(|err| {
+                log::warn!("Failed to apply view filter: {:#}", err);
+                HashSet::new()
+            });
+        Self {
+            query: query.clone(),
+            stamp,
+            ids,
+        }
+    }
+
+    /// The matches for `query`, re-queried if it or the database changed.
+    pub fn refresh(&mut self, database: &Database, query: &EventQuery) -> &Self {
+        let stale = self.query != *query
+            || self.stamp.is_none()
+            || database.change_stamp().ok() != self.stamp;
+        if stale {
+            *self = Self::load(database, query);
+        }
+        self
     }
 }
 
+/// Filter events through the active view filter, if any.
+/// Recurring occurrences are also checked against the filter's date bounds.
+pub fn filter_events_by_query(
+    events: Vec<Event>,
+    filter: Option<&ViewFilterMatches>,
+) -> Vec<Event> {
+    let Some(filter) = filter else {
+        return events;
+    };
+
+    events
+        .into_iter()
+        .filter(|event| event.id.is_some_and(|id| filter.ids.contains(&id)))
+        .filter(|event| filter.query.includes_occurrence(event))
+        .collect()
+}
+
 pub fn is_ribbon_event(event: &Event) -> bool {
     event.all_day
 }
@@ -275,54 +323,123 @@ mod tests {
             recurrence_exceptions: None,
             created_at: None,
             updated_at: None,
+            ics_raw_properties: None,
         }
     }
 
+    fn create_events(db: &Database, events: Vec<Event>) -> Vec<Event> {
+        let service = EventService::new(db.connection());
+        events
+            .into_iter()
+            .map(|event| service.create(event).unwrap())
+            .collect()
+    }
+
+    fn filter_db() -> Database {
+        let db = Database::new(":memory:").unwrap();
+        db.initialize_schema().unwrap();
+        db
+    }
+
     #[test]
     fn test_filter_events_no_filter_passes_all() {
-        let events = vec![
-            make_event("Work Event", Some("Work")),
-            make_event("Personal Event", Some("Personal")),
-            make_event("No Category", None),
-        ];
+        let db = filter_db();
+        let events = create_events(
+            &db,
+            vec![
+                make_event("Work Event", Some("Work")),
+                make_event("Personal Event", Some("Personal")),
+                make_event("No Category", None),
+            ],
+        );
 
-        let result = filter_events_by_category(events, None);
+        let result = filter_events_by_query(events, None);
         assert_eq!(result.len(), 3);
     }
 
     #[test]
     fn test_filter_events_with_category_filter() {
-        let events = vec![
-            make_event("Work Event 1", Some("Work")),
-            make_event("Personal Event", Some("Personal")),
-            make_event("Work Event 2", Some("Work")),
-            make_event("No Category", None),
-        ];
-
-        let result = filter_events_by_category(events, Some("Work"));
+        let db = filter_db();
+        let events = create_events(
+            &db,
+            vec![
+                make_event("Work Event 1", Some("Work")),
+                make_event("Personal Event", Some("Personal")),
+                make_event("Work Event 2", Some("Work")),
+                make_event("No Category", None),
+            ],
+        );
+
+        let query = EventQuery::parse("category:Work").unwrap();
+        let result = filter_events_by_query(events, Some(&ViewFilterMatches::load(&db, &query)));
         assert_eq!(result.len(), 2);
         assert!(result.iter().all(|e| e.category.as_deref() == Some("Work")));
     }
 
     #[test]
     fn test_filter_events_no_matches() {
-        let events = vec![
-            make_event("Work Event", Some("Work")),
-            make_event("Personal Event", Some("Personal")),
-        ];
+        let db = filter_db();
+        let events = create_events(
+            &db,
+            vec![
+                make_event("Work Event", Some("Work")),
+                make_event("Personal Event", Some("Personal")),
+            ],
+        );
 
-        let result = filter_events_by_category(events, Some("Birthday"));
+        let query = EventQuery::parse("category:Birthday").unwrap();
+        let result = filter_events_by_query(events, Some(&ViewFilterMatches::load(&db, &query)));
         assert_eq!(result.len(), 0);
     }
 
     #[test]
     fn test_filter_events_empty_list() {
+        let db = filter_db();
         let events: Vec<Event> = vec![];
 
-        let result = filter_events_by_category(events, Some("Work"));
+        let query = EventQuery::parse("category:Work").unwrap();
+        let result = filter_events_by_query(events, Some(&ViewFilterMatches::load(&db, &query)));
         assert_eq!(result.len(), 0);
     }
 
+    #[test]
+    fn test_filter_events_checks_occurrence_dates() {
+        let db = filter_db();
+        let mut weekly = make_event("Standup", Some("Work"));
+        weekly.recurrence_rule = Some("FREQ=WEEKLY".to_string());
+        let master = create_events(&db, vec![weekly]).remove(0);
+
+        let mut later = master.clone();
+        later.start = master.start + Duration::weeks(3);
+        later.end = master.end + Duration::weeks(3);
+
+        let query = EventQuery::parse("is:recurring after:2025-02-01").unwrap();
+        let result = filter_events_by_query(
+            vec![master, later.clone()],
+            Some(&ViewFilterMatches::load(&db, &query)),
+        );
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].start, later.start);
+    }
+
+    #[test]
+    fn test_view_filter_matches_refresh_after_changes() {
+        let db = filter_db();
+        create_events(&db, vec![make_event("Work Event", Some("Work"))]);
+        let query = EventQuery::parse("category:Work").unwrap();
+
+        let mut matches = ViewFilterMatches::default();
+        assert_eq!(matches.refresh(&db, &query).ids.len(), 1);
+        assert_eq!(matches.refresh(&db, &query).ids.len(), 1);
+
+        let added = create_events(&db, vec![make_event("Work Event 2", Some("Work"))]);
+        let result = filter_events_by_query(added, Some(matches.refresh(&db, &query)));
+        assert_eq!(result.len(), 1);
+
+        let other = EventQuery::parse("category:Personal").unwrap();
+        assert!(matches.refresh(&db, &other).ids.is_empty());
+    }
+
     #[test]
     fn test_is_synced_event_detects_membership() {
         let mut synced = HashSet::new();
@@ -542,6 +659,7 @@ mod tests {
             recurrence_exceptions: None,
             created_at: None,
             updated_at: None,
+            ics_raw_properties: None,
         };
 
         assert!(!is_ribbon_event(&event));
@@ -565,6 +683,7 @@ mod tests {
             recurrence_exceptions: None,
             created_at: None,
             updated_at: None,
+            ics_raw_properties: None,
         };
 
         assert!(!is_ribbon_event(&event));
@@ -595,6 +714,7 @@ mod tests {
             recurrence_exceptions: None,
             created_at: None,
             updated_at: None,
+            ics_raw_properties: None,
         };
 
         assert_eq!(event_display_end_date(&event), start.date_naive());
@@ -618,6 +738,7 @@ mod tests {
             recurrence_exceptions: None,
             created_at: None,
             updated_at: None,
+            ics_raw_properties: None,
         };
 
         assert_eq!(event_display_end_date(&event), end.date_naive());
@@ -643,6 +764,7 @@ mod tests {
             recurrence_exceptions: None,
             created_at: None,
             updated_at: None,
+            ics_raw_properties: None,
         };
 
         let event_b = Event {
@@ -659,6 +781,7 @@ mod tests {
             recurrence_exceptions: None,
             created_at: None,
             updated_at: None,
+            ics_raw_properties: None,
         };
 
         let events = vec![event_a, event_b];
@@ -5,7 +5,9 @@ use std::collections::HashSet;
 use super::palette::{CalendarCellPalette, DayStripPalette};
 use super::utils::{days_in_month, get_short_day_names};
 use super::week_shared::DeleteConfirmRequest;
-use super::{filter_events_by_category, filter_events_by_sync_scope, CountdownRequest};
+use super::{
+    filter_events_by_query, filter_events_by_sync_scope, CountdownRequest, ViewFilterMatches,
+};
 use crate::models::event::Event;
 use crate::models::settings::Settings;
 use crate::services::database::Database;
@@ -73,7 +75,7 @@ impl MonthView {
         event_to_edit: &mut Option<i64>,
         countdown_requests: &mut Vec<CountdownRequest>,
         active_countdown_events: &HashSet<i64>,
-        category_filter: Option<&str>,
+        view_filter: Option<&ViewFilterMatches>,
         synced_only: bool,
         synced_source_id: Option<i64>,
     ) -> MonthViewResult {
@@ -83,7 +85,7 @@ impl MonthView {
         // Get events for the month
         let event_service = EventService::new(database.connection());
         let events = Self::get_events_for_month(&event_service, *current_date);
-        let events = filter_events_by_category(events, category_filter);
+        let events = filter_events_by_query(events, view_filter);
         let events = filter_events_by_sync_scope(events, database, synced_only, synced_source_id);
         let synced_event_ids = super::load_read_only_synced_event_ids(database, synced_source_id);
 
@@ -9,8 +9,8 @@ use super::week_shared::{
     render_time_grid, EventInteractionResult, TimeCellConfig, COLUMN_SPACING, TIME_LABEL_WIDTH,
 };
 use super::{
-    filter_events_by_category, filter_events_by_sync_scope, load_read_only_synced_event_ids,
-    AutoFocusRequest, CountdownRequest,
+    filter_events_by_query, filter_events_by_sync_scope, load_read_only_synced_event_ids,
+    AutoFocusRequest, CountdownRequest, ViewFilterMatches,
 };
 use crate::models::event::Event;
 use crate::models::settings::Settings;
@@ -49,7 +49,7 @@ impl WeekView {
         show_ribbon: bool,
         all_day_events: &[Event],
         focus_request: &mut Option<AutoFocusRequest>,
-        category_filter: Option<&str>,
+        view_filter: Option<&ViewFilterMatches>,
         synced_only: bool,
         synced_source_id: Option<i64>,
     ) -> EventInteractionResult {
@@ -65,7 +65,7 @@ impl WeekView {
         // Get events for the week
         let event_service = EventService::new(database.connection());
         let events = Self::get_events_for_week(&event_service, week_start);
-        let events = filter_events_by_category(events, category_filter);
+        let events = filter_events_by_query(events, view_filter);
         let events = filter_events_by_sync_scope(events, database, synced_only, synced_source_id);
         let synced_event_ids = load_read_only_synced_event_ids(database, None);
 